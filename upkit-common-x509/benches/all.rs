@@ -0,0 +1,161 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Benchmarks comparing the full decode, re-encoding of certificate parts and
+//! per call decoding with the lazy parsing, DER slices and decoded cache kept
+//! by [CertificateParser].
+//!
+//! Run with `cargo bench -p upkit_common_x509`.
+
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+use rasn::types::SequenceOf;
+use upkit_common_x509::cert::extensions::AlternativeName;
+use upkit_common_x509::cert::parse::CertificateParser;
+use upkit_common_x509::cert::types::DistinguishedName;
+use upkit_common_x509::cert::types::Validity;
+
+/// Number of calls of each getter in the repeated getter calls benchmark.
+const REPEATED_CALLS: usize = 10;
+
+fn load_b64_file(filename: &str) -> Vec<u8> {
+    let b64 = std::fs::read_to_string(format!(
+        "{}/resources/test/{filename}",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .unwrap();
+    tyst::encdec::base64::decode(&b64).unwrap()
+}
+
+/// Previous implementation: Re-encode the decoded rasn structure.
+fn old_encoded_tbs_certificate(certificate: &rasn_pkix::Certificate) -> Vec<u8> {
+    rasn::der::encode(&certificate.tbs_certificate).unwrap()
+}
+
+/// Previous implementation: Clone matching extensions and decode on every call.
+fn old_subject_alternative_name(
+    certificate: &rasn_pkix::Certificate,
+) -> Option<SequenceOf<rasn_pkix::GeneralName>> {
+    certificate
+        .tbs_certificate
+        .extensions
+        .iter()
+        .flatten()
+        .filter(|extension| extension.extn_id.to_vec() == AlternativeName::OID_SUBJECT_AN)
+        .cloned()
+        .collect::<Vec<_>>()
+        .first()
+        .map(|extension| rasn::der::decode(&extension.extn_value).unwrap())
+}
+
+/// Previous implementation: Fingerprint and decode the full certificate.
+fn old_from_bytes(der: &[u8]) -> (String, rasn_pkix::Certificate) {
+    (
+        upkit_common_x509::fingerprint_data(der),
+        rasn::der::decode::<rasn_pkix::Certificate>(der).unwrap(),
+    )
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let der = load_b64_file("h1_leaf.b64");
+    let mut group = c.benchmark_group("from_bytes");
+    group.bench_function("old_full_decode", |b| {
+        b.iter(|| old_from_bytes(black_box(&der)))
+    });
+    group.bench_function("new_lazy", |b| {
+        b.iter(|| CertificateParser::from_bytes(black_box(&der)).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_encoded_parts(c: &mut Criterion) {
+    let der = load_b64_file("h1_leaf.b64");
+    let certificate = rasn::der::decode::<rasn_pkix::Certificate>(&der).unwrap();
+    let cp = CertificateParser::from_bytes(&der).unwrap();
+    let mut group = c.benchmark_group("tbs_certificate");
+    group.bench_function("old_reencode", |b| {
+        b.iter(|| old_encoded_tbs_certificate(black_box(&certificate)))
+    });
+    group.bench_function("new_slice", |b| {
+        b.iter(|| black_box(&cp).as_encoded_tbs_certificate().len())
+    });
+    group.finish();
+    let mut group = c.benchmark_group("subject");
+    group.bench_function("old_reencode", |b| {
+        b.iter(|| rasn::der::encode(&black_box(&certificate).tbs_certificate.subject).unwrap())
+    });
+    group.bench_function("new_slice", |b| {
+        b.iter(|| black_box(&cp).as_encoded_subject().len())
+    });
+    group.finish();
+}
+
+fn bench_extensions(c: &mut Criterion) {
+    let der = load_b64_file("h1_leaf.b64");
+    let certificate = rasn::der::decode::<rasn_pkix::Certificate>(&der).unwrap();
+    let cp = CertificateParser::from_bytes(&der).unwrap();
+    let mut group = c.benchmark_group("subject_alternative_name");
+    group.bench_function("old_decode_per_call", |b| {
+        b.iter(|| old_subject_alternative_name(black_box(&certificate)))
+    });
+    group.bench_function("new_cached", |b| {
+        b.iter(|| black_box(&cp).get_subject_alternative_name())
+    });
+    group.finish();
+}
+
+fn bench_repeated_getters(c: &mut Criterion) {
+    let der = load_b64_file("h1_leaf.b64");
+    let certificate = rasn::der::decode::<rasn_pkix::Certificate>(&der).unwrap();
+    let cp = CertificateParser::from_bytes(&der).unwrap();
+    let mut group = c.benchmark_group("repeated_getters");
+    group.bench_function("old_decode_per_call", |b| {
+        b.iter(|| {
+            let certificate = black_box(&certificate);
+            for _ in 0..REPEATED_CALLS {
+                let tbs_certificate = &certificate.tbs_certificate;
+                DistinguishedName::from_rasn_type(&tbs_certificate.subject).unwrap();
+                DistinguishedName::from_rasn_type(&tbs_certificate.issuer).unwrap();
+                Validity::try_from_rasn_type(&tbs_certificate.validity).unwrap();
+                old_subject_alternative_name(certificate);
+            }
+        })
+    });
+    group.bench_function("new_cached", |b| {
+        b.iter(|| {
+            let cp = black_box(&cp);
+            for _ in 0..REPEATED_CALLS {
+                cp.get_subject().unwrap();
+                cp.get_issuer().unwrap();
+                cp.get_validity().unwrap();
+                cp.get_subject_alternative_name().unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_parse,
+    bench_encoded_parts,
+    bench_extensions,
+    bench_repeated_getters
+);
+criterion_main!(benches);
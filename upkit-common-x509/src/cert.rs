@@ -24,15 +24,23 @@ pub mod validate;
 pub mod types {
     //! X.509 Certificate types.
 
+    mod algorithm;
     mod distinguished_name;
     mod general_name;
     mod identity_fragment;
+    mod issuer_and_serial_number;
     mod serial_number;
     mod validity;
 
+    pub use self::algorithm::WellKnownAlgorithm;
+    pub use self::algorithm::WellKnownNamedCurve;
     pub use self::distinguished_name::*;
     pub use self::general_name::WellKnownGeneralName;
     pub use self::identity_fragment::*;
+    pub use self::issuer_and_serial_number::IssuerAndSerialNumber;
+    pub use self::serial_number::RandomSerialNumberStrategy;
     pub use self::serial_number::SerialNumber;
+    pub use self::serial_number::SerialNumberStrategy;
+    pub use self::serial_number::StructuredSerialNumberStrategy;
     pub use self::validity::Validity;
 }
@@ -17,10 +17,19 @@
 
 //! X.509 Certificate build.
 
+use super::extensions::AuthorityKeyIdentifier;
+use super::extensions::ExtendedKeyUsage;
 use super::extensions::Extensions;
+use super::extensions::SignedCertificateTimestamp;
+use super::parse::CertificateParser;
+use super::parse::CertificateParsingError;
 use super::types::DistinguishedName;
 use super::types::SerialNumber;
 use super::types::Validity;
+use crate::ct::CtError;
+use crate::ct::CtErrorKind;
+use crate::lint::LintFinding;
+use crate::lint::Linter;
 use rasn::prelude::Any;
 use rasn::types::BitString;
 use rasn::types::ObjectIdentifier;
@@ -102,6 +111,17 @@ impl NoSignatureTbsCertificate {
         Self::from_rasn_type(&tbs_certificate)
     }
 
+    /** Return a copy with the serial number replaced.
+
+    Use this with a [SerialNumberStrategy](crate::cert::types::SerialNumberStrategy)
+    when the default random serial number isn't desired.
+    */
+    pub fn with_serial_number(&self, serial_number: &SerialNumber) -> Self {
+        let mut tbs_certificate = self.to_rasn_type();
+        tbs_certificate.serial_number = serial_number.to_rasn_type();
+        Self::from_rasn_type(&tbs_certificate)
+    }
+
     /// See [Self::OID_NO_SIGNATURE].
     fn no_signature_algorithm_identifier() -> AlgorithmIdentifier {
         AlgorithmIdentifier {
@@ -138,6 +158,124 @@ impl NoSignatureTbsCertificate {
         }
     }
 
+    /** Return the findings of the `linter` for the certificate this will become.
+
+    Run this before signing to avoid issuing a non-compliant certificate. No
+    rule depends on the signature, so an empty placeholder is used.
+    */
+    pub fn lint(&self, linter: &Linter) -> Result<Vec<LintFinding>, CertificateParsingError> {
+        let signature_algorithm =
+            rasn::der::encode(&Self::no_signature_algorithm_identifier()).unwrap();
+        let certificate = self.to_certificate(&signature_algorithm, vec![]);
+        let cp = CertificateParser::from_bytes(&rasn::der::encode(&certificate).unwrap())?;
+        Ok(linter.lint(&cp))
+    }
+
+    /** Return the precertificate for Certificate Transparency logs.
+
+    The critical precertificate poison extension is added and any
+    SignedCertificateTimestampList extension is removed. See
+    [RFC6962 3.1](https://www.rfc-editor.org/rfc/rfc6962#section-3.1).
+
+    When the precertificate is signed by a dedicated Precertificate Signing
+    Certificate (issued by the CA and with the
+    [ExtendedKeyUsage::CtPrecertificateSigning] EKU), the issuer and the
+    AuthorityKeyIdentifier are replaced with the ones of that certificate.
+    */
+    pub fn to_precertificate(
+        &self,
+        precertificate_signing_certificate: Option<&CertificateParser>,
+    ) -> Result<Self, CtError> {
+        let mut tbs_certificate = self.to_rasn_type();
+        let extensions = tbs_certificate.extensions.get_or_insert_with(Vec::default);
+        remove_extension(extensions, SignedCertificateTimestamp::OID);
+        if extension_position(
+            extensions,
+            SignedCertificateTimestamp::OID_PRECERTIFICATE_POISON,
+        )
+        .is_none()
+        {
+            let mut poison = Extensions::default();
+            poison.add_precertificate_poison();
+            extensions.extend(poison.to_rasn_type().unwrap_or_default());
+        }
+        if let Some(signing_certificate) = precertificate_signing_certificate {
+            let decoding_error = |e: CertificateParsingError| {
+                CtErrorKind::DecodingError.error_with_msg(&format!(
+                    "Failed to decode Precertificate Signing Certificate: {e}"
+                ))
+            };
+            if !signing_certificate
+                .get_extended_key_usage()
+                .map_err(decoding_error)?
+                .contains(&ExtendedKeyUsage::CtPrecertificateSigning)
+            {
+                return Err(CtErrorKind::InvalidPrecertificateSigningCertificate
+                    .error_with_msg("Missing Precertificate Signing Certificate EKU."));
+            }
+            let kid = signing_certificate
+                .get_subject_key_identifier_kid()
+                .map_err(decoding_error)?
+                .ok_or_else(|| {
+                    CtErrorKind::InvalidPrecertificateSigningCertificate
+                        .error_with_msg("Missing SubjectKeyIdentifier.")
+                })?;
+            if let Some(position) = extension_position(extensions, AuthorityKeyIdentifier::OID) {
+                let mut aki = Extensions::default();
+                aki.add_authority_key_identifier(
+                    &AuthorityKeyIdentifier::from_issuers_subject_key_identifier(&kid),
+                );
+                extensions[position] = aki.to_rasn_type().unwrap_or_default().remove(0);
+            }
+            tbs_certificate.issuer = rasn::der::decode(&signing_certificate.get_encoded_subject())
+                .map_err(|e| {
+                    CtErrorKind::DecodingError
+                        .error_with_msg(&format!("Failed to decode subject: {e:?}"))
+                })?;
+        }
+        Ok(Self::from_rasn_type(&tbs_certificate))
+    }
+
+    /** Return the final certificate with the embedded `scts`.
+
+    The precertificate poison extension is replaced with the
+    SignedCertificateTimestampList extension in the same position, so that
+    everything else is byte-identical to the precertificate. This must be
+    invoked on the instance that the precertificate was derived from when a
+    dedicated Precertificate Signing Certificate was used.
+    */
+    pub fn with_signed_certificate_timestamps(
+        &self,
+        scts: &[SignedCertificateTimestamp],
+    ) -> Result<Self, CtError> {
+        let mut tbs_certificate = self.to_rasn_type();
+        let extensions = tbs_certificate.extensions.get_or_insert_with(Vec::default);
+        let mut sct_list = Extensions::default();
+        sct_list
+            .add_signed_certificate_timestamps(scts)
+            .map_err(|e| {
+                CtErrorKind::DecodingError
+                    .error_with_msg(&format!("Failed to encode SCT list: {e}"))
+            })?;
+        let mut sct_list = sct_list.to_rasn_type().unwrap_or_default();
+        remove_extension(extensions, SignedCertificateTimestamp::OID);
+        if let Some(position) = extension_position(
+            extensions,
+            SignedCertificateTimestamp::OID_PRECERTIFICATE_POISON,
+        ) {
+            extensions.remove(position);
+            if !sct_list.is_empty() {
+                extensions.insert(position, sct_list.remove(0));
+            }
+        } else {
+            extensions.append(&mut sct_list);
+        }
+        if extensions.is_empty() {
+            tbs_certificate.extensions = None;
+        }
+        Ok(Self::from_rasn_type(&tbs_certificate))
+    }
+
     /// Return a new instance from the `rasn` ASN.1 library type.
     pub fn from_rasn_type(tbs_certificate: &TbsCertificate) -> Self {
         if !Self::no_signature_algorithm_identifier().eq(&tbs_certificate.signature) {
@@ -153,3 +291,15 @@ impl NoSignatureTbsCertificate {
         rasn::der::decode(&self.tbs_certificate_b64).unwrap()
     }
 }
+
+fn extension_position(extensions: &[rasn_pkix::Extension], oid: &[u32]) -> Option<usize> {
+    let oid = ObjectIdentifier::new_unchecked(oid.to_vec().into());
+    extensions
+        .iter()
+        .position(|extension| extension.extn_id.eq(&oid))
+}
+
+fn remove_extension(extensions: &mut Vec<rasn_pkix::Extension>, oid: &[u32]) {
+    let oid = ObjectIdentifier::new_unchecked(oid.to_vec().into());
+    extensions.retain(|extension| !extension.extn_id.eq(&oid));
+}
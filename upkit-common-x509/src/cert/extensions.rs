@@ -21,8 +21,10 @@ mod alternative_name;
 mod authority_information_access;
 mod basic_constraints;
 mod certificate_policies;
+mod certificate_transparency;
 mod crl_distribution_points;
 mod extended_key_usage;
+mod extensions_error;
 mod key_identifier;
 mod key_usage;
 
@@ -31,12 +33,19 @@ pub use self::authority_information_access::AuthorityInfoAccessDescription;
 pub use self::basic_constraints::BasicConstraints;
 pub use self::certificate_policies::CertificatePolicy;
 pub use self::certificate_policies::WellKnownCertificatePolicy;
+pub use self::certificate_transparency::SignedCertificateTimestamp;
 pub use self::crl_distribution_points::CrlDistributionPoint;
 pub use self::extended_key_usage::ExtendedKeyUsage;
+pub use self::extensions_error::ExtensionsError;
+pub use self::extensions_error::ExtensionsErrorKind;
 pub use self::key_identifier::AuthorityKeyIdentifier;
+pub use self::key_identifier::KeyIdentifierMethod;
 pub use self::key_identifier::SubjectKeyIdentifier;
 pub use self::key_usage::KeyUsage;
+use super::parse::CertificateParser;
 use super::types::WellKnownGeneralName;
+use crate::encdec::tlv::Tlv;
+use crate::encdec::DecodingError;
 use rasn::error::EncodeError;
 use rasn::types::ObjectIdentifier;
 use rasn::types::OctetString;
@@ -47,11 +56,20 @@ use rasn::types::OctetString;
 // Certificate template v2            1.3.6.1.4.1.311.21.7
 // MS app policies extension          1.3.6.1.4.1.311.21.10
 // MS objectSid OtherName             1.3.6.1.4.1.311.25.2.1 szOID_NTDS_OBJECTSID
-// CT (Certificate Transparency) specific extensions   1.3.6.1.4.1.11129.2.4.6
-// CT (Certificate Transparency) precert poisoning ext 1.3.6.1.4.1.11129.2.4.3
 
-/// X.509 Certificate extensions.
-#[derive(Default)]
+/** X.509 Certificate extensions.
+
+Extensions are encoded in the order they were added. An instance never holds
+more than one extension with the same OID
+([RFC5280 4.2](https://www.rfc-editor.org/rfc/rfc5280#section-4.2)):
+[Self::add_extension] fails on duplicates, while the typed `add_*` methods
+replace an existing extension in place.
+
+The typed `add_*` methods mark extensions as critical according to best
+practices. Use [Self::set_critical] to override this, e.g. when a legacy
+profile requires a critical Extended Key Usage.
+*/
+#[derive(Default, Debug, Clone)]
 pub struct Extensions {
     extensions: Vec<rasn_pkix::Extension>,
 }
@@ -67,24 +85,200 @@ impl Extensions {
         }
     }
 
-    fn add_extension<T: rasn::Encode>(
+    /// Return a new instance from the `rasn` ASN.1 library type.
+    ///
+    /// Duplicate extensions are kept as is. Use [Self::try_from_rasn_type] to
+    /// reject them.
+    pub fn from_rasn_type(extensions: &rasn_pkix::Extensions) -> Self {
+        Self {
+            extensions: extensions.to_vec(),
+        }
+    }
+
+    /// Return a new instance from the `rasn` ASN.1 library type or an error
+    /// if an extension OID is present more than once.
+    pub fn try_from_rasn_type(extensions: &rasn_pkix::Extensions) -> Result<Self, ExtensionsError> {
+        let mut ret = Self::default();
+        for extension in extensions.iter() {
+            ret.add_extension(
+                &extension.extn_id.to_vec(),
+                extension.critical,
+                &extension.extn_value,
+            )?;
+        }
+        Ok(ret)
+    }
+
+    /** Return a new instance with the extensions of a parsed certificate or
+    an error if an extension OID is present more than once.
+
+    This is useful when re-issuing a certificate. Extensions that are bound to
+    the issuer or the issuance, like the [AuthorityKeyIdentifier] or the
+    [SignedCertificateTimestamp] list, should be removed or replaced.
+    */
+    pub fn from_certificate(cp: &CertificateParser) -> Result<Self, ExtensionsError> {
+        cp.as_encoded_extensions()
+            .map(|encoded| {
+                rasn::der::decode::<rasn_pkix::Extensions>(encoded)
+                    .map_err(|e| {
+                        ExtensionsErrorKind::InvalidEncoding.error_with_msg(&format!(
+                            "Failed to decode extensions of certificate with fingerprint '{}': {e:?}",
+                            cp.fingerprint()
+                        ))
+                    })
+                    .and_then(|extensions| Self::try_from_rasn_type(&extensions))
+            })
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Return the number of extensions.
+    pub fn len(&self) -> usize {
+        self.extensions.len()
+    }
+
+    /// Return `true` if there are no extensions.
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+    }
+
+    /// Return the OIDs of all extensions in order.
+    pub fn get_oids(&self) -> Vec<Vec<u32>> {
+        self.extensions
+            .iter()
+            .map(|extension| extension.extn_id.to_vec())
+            .collect()
+    }
+
+    /// Return `true` if an extension with the `oid` is present.
+    pub fn contains(&self, oid: &[u32]) -> bool {
+        self.position(oid).is_some()
+    }
+
+    /// Return the criticality of the extension with the `oid` if present.
+    pub fn is_critical(&self, oid: &[u32]) -> Option<bool> {
+        self.position(oid)
+            .map(|position| self.extensions[position].critical)
+    }
+
+    /// Return the DER encoded value (the content of `extnValue`) of the
+    /// extension with the `oid` if present.
+    pub fn as_encoded_extension_value(&self, oid: &[u32]) -> Option<&[u8]> {
+        self.position(oid)
+            .map(|position| self.extensions[position].extn_value.as_ref())
+    }
+
+    /** Add an extension with a DER encoded `extn_value`.
+
+    The `extn_value` is the content of the `extnValue` OCTET STRING. This
+    allows extensions without a typed `add_*` method, like enterprise
+    specific extensions.
+    */
+    pub fn add_extension(
         &mut self,
-        oid: &'static [u32],
+        oid: &[u32],
+        critical: bool,
+        extn_value: &[u8],
+    ) -> Result<(), ExtensionsError> {
+        if self.contains(oid) {
+            return Err(ExtensionsErrorKind::DuplicateExtension
+                .error_with_msg(&format!("Extension '{oid:?}' is already present.")));
+        }
+        Self::check_encoding(oid, extn_value)?;
+        self.set_encoded_extension(oid, critical, extn_value);
+        Ok(())
+    }
+
+    /// Replace the extension with the `oid` in place.
+    pub fn replace_extension(
+        &mut self,
+        oid: &[u32],
+        critical: bool,
+        extn_value: &[u8],
+    ) -> Result<(), ExtensionsError> {
+        if !self.contains(oid) {
+            return Err(Self::missing_extension(oid));
+        }
+        Self::check_encoding(oid, extn_value)?;
+        self.set_encoded_extension(oid, critical, extn_value);
+        Ok(())
+    }
+
+    /// Remove the extension with the `oid`. Return `true` if it was present.
+    pub fn remove_extension(&mut self, oid: &[u32]) -> bool {
+        let len = self.extensions.len();
+        self.extensions
+            .retain(|extension| extension.extn_id.to_vec() != oid);
+        len != self.extensions.len()
+    }
+
+    /// Override the criticality of the extension with the `oid`.
+    pub fn set_critical(&mut self, oid: &[u32], critical: bool) -> Result<(), ExtensionsError> {
+        let position = self
+            .position(oid)
+            .ok_or_else(|| Self::missing_extension(oid))?;
+        self.extensions[position].critical = critical;
+        Ok(())
+    }
+
+    /// Sort the extensions by OID for an order that is independent of how
+    /// the instance was assembled.
+    pub fn sort_by_oid(&mut self) {
+        self.extensions
+            .sort_by_key(|extension| extension.extn_id.to_vec());
+    }
+
+    fn position(&self, oid: &[u32]) -> Option<usize> {
+        self.extensions
+            .iter()
+            .position(|extension| extension.extn_id.to_vec() == oid)
+    }
+
+    fn missing_extension(oid: &[u32]) -> ExtensionsError {
+        ExtensionsErrorKind::MissingExtension
+            .error_with_msg(&format!("Extension '{oid:?}' is not present."))
+    }
+
+    fn check_encoding(oid: &[u32], extn_value: &[u8]) -> Result<(), ExtensionsError> {
+        Tlv::read(extn_value, 0)
+            .ok()
+            .filter(|tlv| tlv.range.end == extn_value.len())
+            .map(|_| ())
+            .ok_or_else(|| {
+                ExtensionsErrorKind::InvalidEncoding.error_with_msg(&format!(
+                    "Value of extension '{oid:?}' is not a single DER encoded value."
+                ))
+            })
+    }
+
+    /// Add the extension or replace an existing one with the same `oid`.
+    fn set_encoded_extension(&mut self, oid: &[u32], critical: bool, extn_value: &[u8]) {
+        let extension = rasn_pkix::Extension {
+            extn_id: ObjectIdentifier::new_unchecked(oid.to_vec().into()),
+            critical,
+            extn_value: OctetString::from(extn_value.to_vec()),
+        };
+        if let Some(position) = self.position(oid) {
+            self.extensions[position] = extension;
+        } else {
+            self.extensions.push(extension);
+        }
+    }
+
+    fn set_extension<T: rasn::Encode>(
+        &mut self,
+        oid: &[u32],
         critical: bool,
         rasn_type: &T,
     ) -> Result<(), EncodeError> {
-        let bytes = rasn::der::encode(rasn_type).unwrap();
-        self.extensions.push(rasn_pkix::Extension {
-            extn_id: ObjectIdentifier::new_unchecked(oid.into()),
-            critical,
-            extn_value: OctetString::from(bytes),
-        });
+        let bytes = rasn::der::encode(rasn_type)?;
+        self.set_encoded_extension(oid, critical, &bytes);
         Ok(())
     }
 
     /// See [BasicConstraints] for details.
     pub fn add_basic_constraints(&mut self, basic_constraints: &BasicConstraints) {
-        self.add_extension(
+        self.set_extension(
             BasicConstraints::OID,
             basic_constraints.is_ca(),
             &basic_constraints.to_rasn_type(),
@@ -95,7 +289,7 @@ impl Extensions {
     /// See [KeyUsage] for details.
     pub fn add_key_usage(&mut self, key_usages: &[KeyUsage]) {
         if !key_usages.is_empty() {
-            self.add_extension(KeyUsage::OID, true, &KeyUsage::to_rasn_type(key_usages))
+            self.set_extension(KeyUsage::OID, true, &KeyUsage::to_rasn_type(key_usages))
                 .unwrap();
         }
     }
@@ -106,7 +300,7 @@ impl Extensions {
         access_descriptions: &[AuthorityInfoAccessDescription],
     ) {
         if !access_descriptions.is_empty() {
-            self.add_extension(
+            self.set_extension(
                 AuthorityInfoAccessDescription::OID,
                 false,
                 &AuthorityInfoAccessDescription::to_rasn_type(access_descriptions),
@@ -118,7 +312,7 @@ impl Extensions {
     /// See [CertificatePolicy] for details.
     pub fn add_certificate_policies(&mut self, certificate_policies: &[CertificatePolicy]) {
         if !certificate_policies.is_empty() {
-            self.add_extension(
+            self.set_extension(
                 CertificatePolicy::OID,
                 false,
                 &CertificatePolicy::to_rasn_type(certificate_policies),
@@ -129,7 +323,7 @@ impl Extensions {
 
     /// See [CrlDistributionPoint] for details.
     pub fn add_crl_distribution_points(&mut self, crl_distribution_point_uri: &str) {
-        self.add_extension(
+        self.set_extension(
             CrlDistributionPoint::OID,
             false,
             &CrlDistributionPoint::to_rasn_type(crl_distribution_point_uri),
@@ -142,17 +336,17 @@ impl Extensions {
         &mut self,
         authority_key_identifier: &AuthorityKeyIdentifier,
     ) {
-        self.add_extension(
+        self.set_extension(
             AuthorityKeyIdentifier::OID,
             false,
-            authority_key_identifier.to_rasn_type(),
+            &authority_key_identifier.to_rasn_type(),
         )
         .unwrap();
     }
 
     /// See [SubjectKeyIdentifier] for details.
     pub fn add_subject_key_identifier(&mut self, subject_key_identifier: &SubjectKeyIdentifier) {
-        self.add_extension(
+        self.set_extension(
             SubjectKeyIdentifier::OID,
             false,
             subject_key_identifier.to_rasn_type(),
@@ -163,7 +357,7 @@ impl Extensions {
     /// See [ExtendedKeyUsage] for details.
     pub fn add_extended_key_usage(&mut self, extended_key_usages: &[ExtendedKeyUsage]) {
         if !extended_key_usages.is_empty() {
-            self.add_extension(
+            self.set_extension(
                 ExtendedKeyUsage::OID,
                 false,
                 &ExtendedKeyUsage::to_rasn_type(extended_key_usages),
@@ -185,7 +379,7 @@ impl Extensions {
         subject_dn_empty: bool,
     ) {
         if !subject_alternative_names.is_empty() {
-            self.add_extension(
+            self.set_extension(
                 AlternativeName::OID_SUBJECT_AN,
                 subject_dn_empty,
                 &AlternativeName::to_rasn_type(subject_alternative_names),
@@ -200,7 +394,7 @@ impl Extensions {
         issuer_alternative_names: &[(WellKnownGeneralName, String)],
     ) {
         if !issuer_alternative_names.is_empty() {
-            self.add_extension(
+            self.set_extension(
                 AlternativeName::OID_ISSUER_AN,
                 false,
                 &AlternativeName::to_rasn_type(issuer_alternative_names),
@@ -208,4 +402,112 @@ impl Extensions {
             .unwrap();
         }
     }
+
+    /// Add the critical precertificate poison extension. See
+    /// [SignedCertificateTimestamp::OID_PRECERTIFICATE_POISON].
+    ///
+    /// [RFC6962 3.1](https://www.rfc-editor.org/rfc/rfc6962#section-3.1):
+    ///
+    /// "The Precertificate is constructed from the certificate to be issued
+    /// by adding a special critical poison extension"
+    pub fn add_precertificate_poison(&mut self) {
+        self.set_extension(
+            SignedCertificateTimestamp::OID_PRECERTIFICATE_POISON,
+            true,
+            &(),
+        )
+        .unwrap();
+    }
+
+    /// See [SignedCertificateTimestamp] for details.
+    ///
+    /// Fails when the SCTs don't fit in a `SignedCertificateTimestampList`.
+    pub fn add_signed_certificate_timestamps(
+        &mut self,
+        scts: &[SignedCertificateTimestamp],
+    ) -> Result<(), DecodingError> {
+        if !scts.is_empty() {
+            self.set_extension(
+                SignedCertificateTimestamp::OID,
+                false,
+                &OctetString::from(SignedCertificateTimestamp::to_list_bytes(scts)?),
+            )
+            .unwrap();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn custom_extensions_and_criticality() {
+        let mut extensions = Extensions::default();
+        extensions.add_extended_key_usage(&[ExtendedKeyUsage::PkixServerAuth]);
+        extensions.add_key_usage(&[KeyUsage::DigitalSignature]);
+        assert_eq!(extensions.is_critical(ExtendedKeyUsage::OID), Some(false));
+        extensions
+            .set_critical(ExtendedKeyUsage::OID, true)
+            .unwrap();
+        assert_eq!(extensions.is_critical(ExtendedKeyUsage::OID), Some(true));
+        // Typed adders replace in place
+        extensions.add_extended_key_usage(&[ExtendedKeyUsage::PkixClientAuth]);
+        assert_eq!(
+            extensions.get_oids(),
+            vec![ExtendedKeyUsage::OID.to_vec(), KeyUsage::OID.to_vec()]
+        );
+        let custom_oid = &[1, 3, 6, 1, 4, 1, 99999, 1];
+        extensions
+            .add_extension(custom_oid, false, &[0x05, 0x00])
+            .unwrap();
+        assert!(extensions
+            .add_extension(custom_oid, false, &[0x05, 0x00])
+            .is_err());
+        assert!(extensions
+            .add_extension(&[1, 3, 6, 1, 4, 1, 99999, 2], false, &[0x05])
+            .is_err());
+        extensions
+            .replace_extension(custom_oid, true, &[0x0c, 0x01, 0x41])
+            .unwrap();
+        assert_eq!(
+            extensions.as_encoded_extension_value(custom_oid),
+            Some(&[0x0c, 0x01, 0x41][..])
+        );
+        extensions.sort_by_oid();
+        assert_eq!(extensions.get_oids()[0], custom_oid.to_vec());
+        assert!(extensions.remove_extension(custom_oid));
+        assert!(!extensions.remove_extension(custom_oid));
+        assert!(extensions.set_critical(custom_oid, true).is_err());
+    }
+
+    #[test]
+    fn extensions_from_certificate() {
+        let cp = crate::test_utils::load_test_certificate("h1_leaf.b64");
+        let extensions = Extensions::from_certificate(&cp).unwrap();
+        assert_eq!(extensions.get_oids(), cp.get_extension_oids());
+        let encoded = rasn::der::encode(&extensions.to_rasn_type().unwrap()).unwrap();
+        assert!(cp
+            .as_encoded_tbs_certificate()
+            .windows(encoded.len())
+            .any(|window| window == encoded));
+    }
+
+    #[test]
+    fn reject_duplicate_extensions_from_certificate() {
+        let mut certificate = rasn::der::decode::<rasn_pkix::Certificate>(
+            &crate::test_utils::load_test_certificate_der("h1_leaf.b64"),
+        )
+        .unwrap();
+        let tbs_certificate = &mut certificate.tbs_certificate;
+        let mut extensions = tbs_certificate.extensions.clone().unwrap().to_vec();
+        extensions.push(extensions[0].clone());
+        tbs_certificate.extensions = Some(rasn_pkix::Extensions::from(extensions));
+        let cp = CertificateParser::from_bytes(&rasn::der::encode(&certificate).unwrap()).unwrap();
+        assert!(matches!(
+            Extensions::from_certificate(&cp).unwrap_err().kind(),
+            ExtensionsErrorKind::DuplicateExtension
+        ));
+    }
 }
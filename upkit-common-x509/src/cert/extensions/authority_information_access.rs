@@ -118,18 +118,19 @@ impl AuthorityInfoAccessDescription {
             .collect()
     }
 
-    /// Return a Vec of new instances.
+    /// Return a Vec of new instances (ignoring any access location that is
+    /// not a well-known GeneralName).
     pub fn from_rasn_type(accesss_descriptions: &SequenceOf<AccessDescription>) -> Vec<Self> {
         accesss_descriptions
             .iter()
-            .map(Self::from_accesss_description)
+            .filter_map(Self::from_accesss_description)
             .collect()
     }
 
-    fn from_accesss_description(access_description: &AccessDescription) -> Self {
+    fn from_accesss_description(access_description: &AccessDescription) -> Option<Self> {
         let access_location =
-            WellKnownGeneralName::from_rasn_type(&access_description.access_location).unwrap();
-        match access_description.access_method.to_vec().as_slice() {
+            WellKnownGeneralName::from_rasn_type(&access_description.access_location)?;
+        Some(match access_description.access_method.to_vec().as_slice() {
             Self::OID_ACCESS_METHOD_OCSP => Self::Ocsp {
                 uri: access_location.1,
             },
@@ -138,6 +139,6 @@ impl AuthorityInfoAccessDescription {
                 oid: other_oid.to_vec(),
                 access_location,
             },
-        }
+        })
     }
 }
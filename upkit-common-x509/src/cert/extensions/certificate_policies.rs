@@ -17,6 +17,7 @@
 
 //! X.509 Certificate Policies.
 
+use crate::encdec::DecodingError;
 use rasn::types::Integer;
 use rasn::types::{Any, Ia5String, ObjectIdentifier, SequenceOf};
 use rasn_pkix::{DisplayText, NoticeReference, PolicyInformation, PolicyQualifierInfo, UserNotice};
@@ -130,12 +131,23 @@ impl CertificatePolicy {
     const OID_QUALIFIER_ID_CPS: &[u32] = &[1, 3, 6, 1, 5, 5, 7, 2, 1];
     const OID_QUALIFIER_ID_UNOTICE: &[u32] = &[1, 3, 6, 1, 5, 5, 7, 2, 2];
 
-    /// Return a Vec of new instances.
-    pub fn from_rasn_type(policies: &SequenceOf<PolicyInformation>) -> Vec<Self> {
+    /// Return the policy object identifier.
+    pub fn get_oid(&self) -> &[u32] {
+        match self {
+            Self::OidPolicy { oid }
+            | Self::CspPolicy { oid, .. }
+            | Self::UserNoticePolicy { oid, .. } => oid,
+        }
+    }
+
+    /// Return a Vec of new instances (ignoring any unknown policy qualifier).
+    pub fn from_rasn_type(
+        policies: &SequenceOf<PolicyInformation>,
+    ) -> Result<Vec<Self>, DecodingError> {
         policies.iter().map(Self::from_policy_information).collect()
     }
 
-    fn from_policy_information(policy: &PolicyInformation) -> Self {
+    fn from_policy_information(policy: &PolicyInformation) -> Result<Self, DecodingError> {
         let oid = policy.policy_identifier.to_vec();
         if let Some(policy_qualifiers) = &policy.policy_qualifiers {
             for policy_qualifier in policy_qualifiers {
@@ -149,43 +161,55 @@ impl CertificatePolicy {
                             policy_qualifier.qualifier.as_bytes(),
                         )
                     }
-                    unknown_qualifier => todo!("Unknown CP qualifier {unknown_qualifier:?}."),
+                    unknown_qualifier => {
+                        log::debug!("Ignoring unknown CP qualifier {unknown_qualifier:?}.");
+                    }
                 }
             }
         }
-        Self::OidPolicy { oid }
+        Ok(Self::OidPolicy { oid })
     }
 
-    fn from_csp_policy(oid: Vec<u32>, qualifier: &[u8]) -> Self {
-        Self::CspPolicy {
+    fn from_csp_policy(oid: Vec<u32>, qualifier: &[u8]) -> Result<Self, DecodingError> {
+        let display_text = rasn::der::decode::<DisplayText>(qualifier).map_err(|e| {
+            DecodingError::with_msg(&format!("Failed to decode CPS qualifier: {e:?}"))
+        })?;
+        Ok(Self::CspPolicy {
             oid,
-            uri: crate::encdec::rasn::display_text_as_string(
-                &rasn::der::decode::<DisplayText>(qualifier).unwrap(),
-            ),
-        }
+            uri: crate::encdec::rasn::display_text_as_string(&display_text),
+        })
     }
 
-    fn from_user_notice_policy(oid: Vec<u32>, qualifier: &[u8]) -> Self {
-        let user_notice = rasn::der::decode::<UserNotice>(qualifier).unwrap();
+    fn from_user_notice_policy(oid: Vec<u32>, qualifier: &[u8]) -> Result<Self, DecodingError> {
+        let user_notice = rasn::der::decode::<UserNotice>(qualifier).map_err(|e| {
+            DecodingError::with_msg(&format!("Failed to decode user notice qualifier: {e:?}"))
+        })?;
         //if let Some(notice_ref) = user_notice.notice_ref {}
         let explicit_text = user_notice
             .explicit_text
             .map(|display_text| crate::encdec::rasn::display_text_as_string(&display_text));
-        let notice_ref = user_notice.notice_ref.map(|notice_reference| {
-            let organisation =
-                crate::encdec::rasn::display_text_as_string(&notice_reference.organisation);
-            let notice_numbers = notice_reference
-                .notice_numbers
-                .iter()
-                .map(crate::encdec::rasn::integer_as_isize)
-                .collect();
-            (organisation, notice_numbers)
-        });
-        Self::UserNoticePolicy {
+        let notice_ref = user_notice
+            .notice_ref
+            .map(|notice_reference| {
+                let organisation =
+                    crate::encdec::rasn::display_text_as_string(&notice_reference.organisation);
+                let notice_numbers = notice_reference
+                    .notice_numbers
+                    .iter()
+                    .map(|notice_number| {
+                        crate::encdec::rasn::integer_try_as_isize(notice_number).ok_or_else(|| {
+                            DecodingError::with_msg("Notice number is out of range.")
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok::<_, DecodingError>((organisation, notice_numbers))
+            })
+            .transpose()?;
+        Ok(Self::UserNoticePolicy {
             oid,
             notice_ref,
             explicit_text,
-        }
+        })
     }
 
     /// Return value in a form that is easy to process by the ASN.1 library `rasn`.
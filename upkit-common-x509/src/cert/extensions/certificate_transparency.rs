@@ -0,0 +1,316 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Certificate Transparency Signed Certificate Timestamps.
+
+use crate::encdec::DecodingError;
+
+/** Signed Certificate Timestamp (SCT) as defined in
+[RFC6962 3.2](https://www.rfc-editor.org/rfc/rfc6962#section-3.2).
+
+```text
+struct {
+    Version sct_version;
+    LogID id;
+    uint64 timestamp;
+    CtExtensions extensions;
+    digitally-signed struct {
+        ...
+    };
+} SignedCertificateTimestamp;
+```
+
+SCTs are embedded in the certificate in the non-critical
+SignedCertificateTimestampList extension as a TLS encoded list.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedCertificateTimestamp {
+    version: u8,
+    log_id: Vec<u8>,
+    timestamp_millis: u64,
+    extensions: Vec<u8>,
+    hash_algorithm: u8,
+    signature_algorithm: u8,
+    signature: Vec<u8>,
+}
+
+impl SignedCertificateTimestamp {
+    /// iso(1) identified-organization(3) dod(6) internet(1) private(4)
+    /// enterprise(1) google(11129) 2 4 2
+    pub const OID: &[u32] = &[1, 3, 6, 1, 4, 1, 11129, 2, 4, 2];
+
+    /// Precertificate poison extension OID `1.3.6.1.4.1.11129.2.4.3`.
+    pub const OID_PRECERTIFICATE_POISON: &[u32] = &[1, 3, 6, 1, 4, 1, 11129, 2, 4, 3];
+
+    /// `v1(0)`
+    pub const VERSION_V1: u8 = 0;
+    /// TLS `HashAlgorithm` `sha256(4)`
+    pub const HASH_ALGORITHM_SHA256: u8 = 4;
+    /// TLS `SignatureAlgorithm` `rsa(1)`
+    pub const SIGNATURE_ALGORITHM_RSA: u8 = 1;
+    /// TLS `SignatureAlgorithm` `ecdsa(3)`
+    pub const SIGNATURE_ALGORITHM_ECDSA: u8 = 3;
+
+    /// Length of the SHA-256 hash of the log's public key in a `LogID`.
+    pub const LOG_ID_LENGTH: usize = 32;
+
+    /** Return a new v1 instance, e.g. from the response of a log.
+
+    The `log_id` must be [Self::LOG_ID_LENGTH] octets and the `extensions`
+    and `signature` must fit in a TLS `opaque<0..2^16-1>`.
+    */
+    pub fn new(
+        log_id: &[u8],
+        timestamp_millis: u64,
+        extensions: &[u8],
+        hash_algorithm: u8,
+        signature_algorithm: u8,
+        signature: &[u8],
+    ) -> Result<Self, DecodingError> {
+        if log_id.len() != Self::LOG_ID_LENGTH {
+            return Err(DecodingError::with_msg(&format!(
+                "SCT log ID is {} octets, but must be {}.",
+                log_id.len(),
+                Self::LOG_ID_LENGTH
+            )));
+        }
+        let ret = Self {
+            version: Self::VERSION_V1,
+            log_id: log_id.to_vec(),
+            timestamp_millis,
+            extensions: extensions.to_vec(),
+            hash_algorithm,
+            signature_algorithm,
+            signature: signature.to_vec(),
+        };
+        ret.to_bytes()?;
+        Ok(ret)
+    }
+
+    /// Return the TLS encoded `SignedCertificateTimestampList` for embedding
+    /// in a certificate.
+    pub fn to_list_bytes(scts: &[Self]) -> Result<Vec<u8>, DecodingError> {
+        let mut list = vec![];
+        for sct in scts {
+            list.extend(with_u16_length(&sct.to_bytes()?)?);
+        }
+        with_u16_length(&list)
+    }
+
+    /// Return the TLS encoded `SignedCertificateTimestamp`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DecodingError> {
+        let mut ret = vec![self.version];
+        ret.extend_from_slice(&self.log_id);
+        ret.extend_from_slice(&self.timestamp_millis.to_be_bytes());
+        ret.extend_from_slice(&with_u16_length(&self.extensions)?);
+        ret.extend_from_slice(&[self.hash_algorithm, self.signature_algorithm]);
+        ret.extend_from_slice(&with_u16_length(&self.signature)?);
+        Ok(ret)
+    }
+
+    /// Parse the TLS encoded `SignedCertificateTimestampList` (the content of
+    /// the extension's `OCTET STRING`).
+    pub fn from_list_bytes(sct_list: &[u8]) -> Result<Vec<Self>, DecodingError> {
+        let mut reader = TlsReader::new(sct_list);
+        let mut list = TlsReader::new(reader.read_vec(2)?);
+        reader.finish()?;
+        let mut ret = vec![];
+        while !list.is_empty() {
+            ret.push(Self::from_bytes(list.read_vec(2)?)?);
+        }
+        if ret.is_empty() {
+            return Err(DecodingError::with_msg("Empty SCT list."));
+        }
+        Ok(ret)
+    }
+
+    /// Parse a single TLS encoded `SignedCertificateTimestamp`.
+    pub fn from_bytes(sct: &[u8]) -> Result<Self, DecodingError> {
+        let mut reader = TlsReader::new(sct);
+        let version = reader.read_u8()?;
+        if version != Self::VERSION_V1 {
+            let msg = format!("Unsupported SCT version {version}.");
+            return Err(DecodingError::with_msg(&msg));
+        }
+        let ret = Self {
+            version,
+            log_id: reader.read_fixed(Self::LOG_ID_LENGTH)?.to_vec(),
+            timestamp_millis: u64::from_be_bytes(reader.read_fixed(8)?.try_into().unwrap()),
+            extensions: reader.read_vec(2)?.to_vec(),
+            hash_algorithm: reader.read_u8()?,
+            signature_algorithm: reader.read_u8()?,
+            signature: reader.read_vec(2)?.to_vec(),
+        };
+        reader.finish()?;
+        Ok(ret)
+    }
+
+    /// Return the SHA-256 hash of the log's public key.
+    pub fn get_log_id(&self) -> &[u8] {
+        &self.log_id
+    }
+
+    /// Return the time the log issued the SCT in milliseconds since
+    /// 1970-01-01 00:00:00.
+    pub fn get_timestamp_millis(&self) -> u64 {
+        self.timestamp_millis
+    }
+
+    /// Return the TLS `HashAlgorithm` and `SignatureAlgorithm` of the
+    /// signature.
+    pub fn get_signature_algorithm(&self) -> (u8, u8) {
+        (self.hash_algorithm, self.signature_algorithm)
+    }
+
+    /// Return the signature of the log.
+    pub fn get_signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /** Return the data signed by the log for a precertificate entry.
+
+    `issuer_key_hash` is the SHA-256 hash of the issuer's DER encoded
+    SubjectPublicKeyInfo and `tbs_certificate` is the DER encoded
+    precertificate `TBSCertificate` (see
+    [crate::cert::parse::CertificateParser::get_encoded_precertificate_tbs_certificate]).
+    */
+    pub fn get_precertificate_signed_data(
+        &self,
+        issuer_key_hash: &[u8],
+        tbs_certificate: &[u8],
+    ) -> Result<Vec<u8>, DecodingError> {
+        let tbs_certificate_len = u32::try_from(tbs_certificate.len())
+            .ok()
+            .filter(|len| *len < 1 << 24)
+            .ok_or_else(|| {
+                DecodingError::with_msg("TBSCertificate doesn't fit in a TLS opaque<1..2^24-1>.")
+            })?;
+        let mut ret = vec![
+            self.version,
+            // signature_type: certificate_timestamp(0)
+            0,
+        ];
+        ret.extend_from_slice(&self.timestamp_millis.to_be_bytes());
+        // entry_type: precert_entry(1)
+        ret.extend_from_slice(&1u16.to_be_bytes());
+        ret.extend_from_slice(issuer_key_hash);
+        ret.extend_from_slice(&tbs_certificate_len.to_be_bytes()[1..]);
+        ret.extend_from_slice(tbs_certificate);
+        ret.extend_from_slice(&with_u16_length(&self.extensions)?);
+        Ok(ret)
+    }
+}
+
+/// Return `data` prefixed with its length as a TLS `uint16`.
+fn with_u16_length(data: &[u8]) -> Result<Vec<u8>, DecodingError> {
+    let len = u16::try_from(data.len()).map_err(|_| {
+        DecodingError::with_msg(&format!(
+            "{} octets don't fit in a TLS opaque<0..2^16-1>.",
+            data.len()
+        ))
+    })?;
+    let mut ret = len.to_be_bytes().to_vec();
+    ret.extend_from_slice(data);
+    Ok(ret)
+}
+
+/// Minimal reader of TLS presentation language encoded data.
+struct TlsReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> TlsReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn read_fixed(&mut self, len: usize) -> Result<&'a [u8], DecodingError> {
+        if self.data.len() < len {
+            return Err(DecodingError::with_msg("Truncated TLS structure."));
+        }
+        let (ret, remaining) = self.data.split_at(len);
+        self.data = remaining;
+        Ok(ret)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, DecodingError> {
+        self.read_fixed(1).map(|value| value[0])
+    }
+
+    /// Read a variable length vector with a `length_octets` long length prefix.
+    fn read_vec(&mut self, length_octets: usize) -> Result<&'a [u8], DecodingError> {
+        let len = self
+            .read_fixed(length_octets)?
+            .iter()
+            .fold(0usize, |len, octet| len << 8 | usize::from(*octet));
+        self.read_fixed(len)
+    }
+
+    fn finish(&self) -> Result<(), DecodingError> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(DecodingError::with_msg(
+                "Trailing data after TLS structure.",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sct_list_round_trip() {
+        let sct = SignedCertificateTimestamp::new(
+            &[7u8; 32],
+            1_700_000_000_000,
+            &[],
+            4,
+            3,
+            &[0xaa, 0xbb],
+        )
+        .unwrap();
+        let sct_list =
+            SignedCertificateTimestamp::to_list_bytes(&[sct.clone(), sct.clone()]).unwrap();
+        let scts = SignedCertificateTimestamp::from_list_bytes(&sct_list).unwrap();
+        assert_eq!(scts, vec![sct.clone(), sct]);
+        assert_eq!(scts[0].get_log_id(), &[7u8; 32]);
+        assert_eq!(scts[0].get_timestamp_millis(), 1_700_000_000_000);
+        assert_eq!(scts[0].get_signature_algorithm(), (4, 3));
+        assert!(SignedCertificateTimestamp::from_list_bytes(&sct_list[..10]).is_err());
+    }
+
+    #[test]
+    fn sct_encoding_limits() {
+        // The log ID is the SHA-256 hash of the log key
+        for log_id in [&[7u8; 20][..], &[7u8; 33]] {
+            assert!(SignedCertificateTimestamp::new(log_id, 0, &[], 4, 3, &[0xaa]).is_err());
+        }
+        let signature = vec![0xaa; usize::from(u16::MAX) + 1];
+        assert!(SignedCertificateTimestamp::new(&[7u8; 32], 0, &[], 4, 3, &signature).is_err());
+        // Each SCT fits, but not the list
+        let sct = SignedCertificateTimestamp::new(&[7u8; 32], 0, &[], 4, 3, &signature[..40_000])
+            .unwrap();
+        assert!(SignedCertificateTimestamp::to_list_bytes(&[sct.clone()]).is_ok());
+        assert!(SignedCertificateTimestamp::to_list_bytes(&[sct.clone(), sct]).is_err());
+    }
+}
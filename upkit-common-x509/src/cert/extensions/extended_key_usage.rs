@@ -175,6 +175,11 @@ static INSTANCE: LazyLock<ExtendedKeyUsageLookup> = LazyLock::new(|| {
             ExtendedKeyUsage::AdobeAuthenticDocumentsTrust,
             &[1, 2, 840, 113583, 1, 1, 5],
         ),
+        // RFC 6962
+        (
+            ExtendedKeyUsage::CtPrecertificateSigning,
+            &[1, 3, 6, 1, 4, 1, 11129, 2, 4, 4],
+        ),
     ]
     .into_iter()
     .for_each(|(eku, oid)| {
@@ -269,6 +274,8 @@ pub enum ExtendedKeyUsage {
     IntelAmt,
     /// Adobe Authentic Documents Trust (PDF signing) `1.2.840.113583.1.1.5`
     AdobeAuthenticDocumentsTrust,
+    /// RFC 6962 `1.3.6.1.4.1.11129.2.4.4` Precertificate Signing Certificate
+    CtPrecertificateSigning,
     /// Unknown EKU with OID
     Custom {
         /// Object identifier
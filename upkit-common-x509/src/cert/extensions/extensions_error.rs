@@ -0,0 +1,85 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! X.509 Certificate extensions errors.
+
+use std::error::Error;
+use std::fmt;
+
+/// Cause of [Extensions](super::Extensions) error.
+#[derive(Debug)]
+pub enum ExtensionsErrorKind {
+    /// An extension with the same OID is already present.
+    DuplicateExtension,
+    /// No extension with the OID is present.
+    MissingExtension,
+    /// The extension value is not a single DER encoded value.
+    InvalidEncoding,
+}
+
+impl ExtensionsErrorKind {
+    /// Create a new instance with an error message.
+    pub fn error_with_msg(self, msg: &str) -> ExtensionsError {
+        ExtensionsError {
+            kind: self,
+            msg: Some(msg.to_string()),
+        }
+    }
+
+    /// Create a new instance without an error message.
+    pub fn error(self) -> ExtensionsError {
+        ExtensionsError {
+            kind: self,
+            msg: None,
+        }
+    }
+}
+
+impl fmt::Display for ExtensionsErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/** X.509 Certificate extensions error.
+
+Create a new instance via [ExtensionsErrorKind].
+*/
+#[derive(Debug)]
+pub struct ExtensionsError {
+    kind: ExtensionsErrorKind,
+    msg: Option<String>,
+}
+
+impl ExtensionsError {
+    /// Return the [ExtensionsErrorKind] type of this error.
+    pub fn kind(&self) -> &ExtensionsErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ExtensionsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(msg) = &self.msg {
+            write!(f, "{} {}", self.kind, msg)
+        } else {
+            write!(f, "{}", self.kind)
+        }
+    }
+}
+
+impl Error for ExtensionsError {}
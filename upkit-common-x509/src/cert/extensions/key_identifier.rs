@@ -17,13 +17,149 @@
 
 //! X.509 Certificate AuthorityKeyIdentifier and SubjectKeyIdentifier.
 
+use crate::cert::parse::CertificateParser;
+use crate::cert::parse::CertificateParsingError;
+use crate::cert::types::SerialNumber;
+use crate::encdec::DecodingError;
 use rasn::types::OctetString;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+use serde_with::skip_serializing_none;
+use sha1::Sha1;
+use sha2::Digest;
+use sha2::Sha256;
+use sha2::Sha384;
+use sha2::Sha512;
+use strum::EnumIter;
+use strum::IntoEnumIterator;
 use tyst::traits::se::PublicKey;
+use tyst::traits::se::ToPublicKey;
 use tyst::Tyst;
 
-/// Authority Key Identifier as defined in [RFC5280 4.2.1.1](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.1).
+/** Method used to derive a key identifier from a public key.
+
+[RFC5280 4.2.1.2](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.2)
+and [RFC7093 2](https://www.rfc-editor.org/rfc/rfc7093#section-2) describe
+common methods. Unless stated otherwise, the hash is calculated over the
+value of the BIT STRING `subjectPublicKey` (excluding the tag, length and
+number of unused bits).
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyIdentifierMethod {
+    /// SHA3-256 of the raw public key as provided by TYST (32 octets).
+    ///
+    /// SHA3-256 is not strictly neccessary according to the (now old)
+    /// RFC5280, but this decrease collission risk conciderable and allows
+    /// SHA-1 to be phased out at the cost of 12 additional bytes per
+    /// KeyIdentifier.
+    #[default]
+    Sha3_256,
+    /// RFC5280 method 1: SHA-1 (20 octets).
+    Rfc5280Method1,
+    /// RFC5280 method 2: Four-bit type field `0100` followed by the least
+    /// significant 60 bits of the SHA-1 (8 octets).
+    Rfc5280Method2,
+    /// RFC7093 method 1: Leftmost 160 bits of SHA-256.
+    Rfc7093Method1,
+    /// RFC7093 method 2: Leftmost 160 bits of SHA-384.
+    Rfc7093Method2,
+    /// RFC7093 method 3: Leftmost 160 bits of SHA-512.
+    Rfc7093Method3,
+    /// RFC7093 method 4 using SHA-256 of the DER encoded
+    /// SubjectPublicKeyInfo (32 octets).
+    Rfc7093Method4Sha256,
+}
+
+impl KeyIdentifierMethod {
+    /// Number of octets of the truncated RFC7093 identifiers.
+    const TRUNCATED_OCTETS: usize = 20;
+
+    /// Return the key identifier of the `public_key`.
+    pub fn key_identifier(&self, public_key: &dyn PublicKey) -> Result<Vec<u8>, DecodingError> {
+        if self == &Self::Sha3_256 {
+            return public_key
+                .try_as_raw()
+                .map(|raw| Self::sha3_256(&raw))
+                .ok_or_else(|| DecodingError::with_msg("Public key has no raw form."));
+        }
+        let encoded_spki = public_key.try_as_spki().ok_or_else(|| {
+            DecodingError::with_msg("Public key has no SubjectPublicKeyInfo form.")
+        })?;
+        self.key_identifier_from_encoded_spki(&encoded_spki)
+            .ok_or_else(|| DecodingError::with_msg("Malformed SubjectPublicKeyInfo."))
+    }
+
+    /// Return the key identifier of the DER encoded SubjectPublicKeyInfo or
+    /// `None` if it can't be decoded.
+    pub fn key_identifier_from_encoded_spki(&self, encoded_spki: &[u8]) -> Option<Vec<u8>> {
+        let spki = rasn::der::decode::<rasn_pkix::SubjectPublicKeyInfo>(encoded_spki).ok()?;
+        let subject_public_key = spki.subject_public_key.as_raw_slice();
+        Some(match self {
+            Self::Sha3_256 => Self::sha3_256(&encoded_spki.to_vec().to_public_key().try_as_raw()?),
+            Self::Rfc5280Method1 => Sha1::digest(subject_public_key).to_vec(),
+            Self::Rfc5280Method2 => {
+                let mut kid = Sha1::digest(subject_public_key)[12..].to_vec();
+                kid[0] = 0x40 | (kid[0] & 0x0f);
+                kid
+            }
+            Self::Rfc7093Method1 => {
+                Sha256::digest(subject_public_key)[..Self::TRUNCATED_OCTETS].to_vec()
+            }
+            Self::Rfc7093Method2 => {
+                Sha384::digest(subject_public_key)[..Self::TRUNCATED_OCTETS].to_vec()
+            }
+            Self::Rfc7093Method3 => {
+                Sha512::digest(subject_public_key)[..Self::TRUNCATED_OCTETS].to_vec()
+            }
+            Self::Rfc7093Method4Sha256 => Sha256::digest(encoded_spki).to_vec(),
+        })
+    }
+
+    fn sha3_256(data: &[u8]) -> Vec<u8> {
+        Tyst::instance()
+            .digests()
+            .by_name("SHA3-256")
+            .unwrap()
+            .hash(data)
+    }
+
+    /// Return the first known method that derives `key_identifier` from the
+    /// DER encoded SubjectPublicKeyInfo.
+    pub fn detect(key_identifier: &[u8], encoded_spki: &[u8]) -> Option<Self> {
+        Self::iter().find(|method| {
+            method
+                .key_identifier_from_encoded_spki(encoded_spki)
+                .is_some_and(|kid| kid.eq(key_identifier))
+        })
+    }
+}
+
+/** Authority Key Identifier as defined in [RFC5280 4.2.1.1](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.1).
+
+```text
+AuthorityKeyIdentifier ::= SEQUENCE {
+    keyIdentifier             [0] KeyIdentifier           OPTIONAL,
+    authorityCertIssuer       [1] GeneralNames            OPTIONAL,
+    authorityCertSerialNumber [2] CertificateSerialNumber OPTIONAL  }
+```
+
+The `authorityCertIssuer` is the issuer of the issuer certificate and is kept
+as the DER encoded `Name` of the first `directoryName` GeneralName. Other
+GeneralName types are ignored. `authorityCertIssuer` and
+`authorityCertSerialNumber` are always present together or not at all.
+*/
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 pub struct AuthorityKeyIdentifier {
-    aki: rasn_pkix::AuthorityKeyIdentifier,
+    #[serde_as(as = "Option<serde_with::hex::Hex>")]
+    key_identifier: Option<Vec<u8>>,
+    #[serde_as(as = "Option<serde_with::hex::Hex>")]
+    authority_cert_issuer: Option<Vec<u8>>,
+    authority_cert_serial_number: Option<SerialNumber>,
 }
 
 impl AuthorityKeyIdentifier {
@@ -33,32 +169,157 @@ impl AuthorityKeyIdentifier {
     /// Make an exact byte copy of the issuers Subject Key Identifier.
     pub fn from_issuers_subject_key_identifier(issuer_subject_key_identifier: &[u8]) -> Self {
         Self {
-            aki: rasn_pkix::AuthorityKeyIdentifier {
-                key_identifier: Some(rasn_pkix::SubjectKeyIdentifier::copy_from_slice(
-                    issuer_subject_key_identifier,
-                )),
-                authority_cert_issuer: None,
-                authority_cert_serial_number: None,
-            },
+            key_identifier: Some(issuer_subject_key_identifier.to_vec()),
+            ..Self::default()
         }
     }
 
     /// Return an AKI where only the key identifier fields is populated.
     pub fn from_public_key(public_key: &dyn PublicKey) -> Self {
         Self {
-            aki: rasn_pkix::AuthorityKeyIdentifier {
-                key_identifier: Some(OctetString::from(
-                    SubjectKeyIdentifier::get_key_identifier_bytes(public_key),
-                )),
-                authority_cert_issuer: None,
-                authority_cert_serial_number: None,
-            },
+            key_identifier: Some(SubjectKeyIdentifier::get_key_identifier_bytes(public_key)),
+            ..Self::default()
         }
     }
 
-    /// Return an AKI where only the key identifier fields is populated.
-    pub fn to_rasn_type(&self) -> &rasn_pkix::AuthorityKeyIdentifier {
-        &self.aki
+    /// Return an AKI where only the key identifier fields is populated using
+    /// the [KeyIdentifierMethod].
+    pub fn from_public_key_with_method(
+        public_key: &dyn PublicKey,
+        method: KeyIdentifierMethod,
+    ) -> Result<Self, DecodingError> {
+        Ok(Self {
+            key_identifier: Some(method.key_identifier(public_key)?),
+            ..Self::default()
+        })
+    }
+
+    /** Return an AKI identifying the `issuer` certificate.
+
+    The key identifier is copied from the issuer's Subject Key Identifier
+    when present. With `include_issuer_and_serial_number` the issuer's
+    issuer name and serial number are also populated.
+    */
+    pub fn from_issuer_certificate(
+        issuer: &CertificateParser,
+        include_issuer_and_serial_number: bool,
+    ) -> Result<Self, CertificateParsingError> {
+        let ret = Self {
+            key_identifier: issuer.get_subject_key_identifier_kid()?,
+            ..Self::default()
+        };
+        Ok(if include_issuer_and_serial_number {
+            ret.with_authority_cert_issuer_and_serial_number(
+                issuer.as_encoded_issuer(),
+                issuer.get_serial_number(),
+            )
+        } else {
+            ret
+        })
+    }
+
+    /// Populate `authorityCertIssuer` with the DER encoded name of the issuer
+    /// certificate's issuer and `authorityCertSerialNumber` with the issuer
+    /// certificate's serial number.
+    pub fn with_authority_cert_issuer_and_serial_number(
+        mut self,
+        encoded_authority_cert_issuer: &[u8],
+        authority_cert_serial_number: SerialNumber,
+    ) -> Self {
+        self.authority_cert_issuer = Some(encoded_authority_cert_issuer.to_vec());
+        self.authority_cert_serial_number = Some(authority_cert_serial_number);
+        self
+    }
+
+    /// Return the `keyIdentifier` if present.
+    pub fn get_key_identifier(&self) -> Option<&[u8]> {
+        self.key_identifier.as_deref()
+    }
+
+    /// Return the DER encoded `directoryName` of the `authorityCertIssuer` if
+    /// present.
+    pub fn get_encoded_authority_cert_issuer(&self) -> Option<&[u8]> {
+        self.authority_cert_issuer.as_deref()
+    }
+
+    /// Return the `authorityCertSerialNumber` if present.
+    pub fn get_authority_cert_serial_number(&self) -> Option<&SerialNumber> {
+        self.authority_cert_serial_number.as_ref()
+    }
+
+    /** Return `true` unless a populated field contradicts the `issuer`
+    certificate.
+
+    The `keyIdentifier` is compared with the issuer's Subject Key Identifier
+    (when present) and the `authorityCertIssuer` and
+    `authorityCertSerialNumber` with the issuer's issuer name and serial
+    number.
+    */
+    pub fn matches_issuer(&self, issuer: &CertificateParser) -> bool {
+        if let Some(kid) = &self.key_identifier {
+            match issuer.get_subject_key_identifier_kid() {
+                Ok(Some(ski)) if !kid.eq(&ski) => return false,
+                Err(_) => return false,
+                _ => {}
+            }
+        }
+        if let Some(authority_cert_issuer) = &self.authority_cert_issuer {
+            if !authority_cert_issuer
+                .as_slice()
+                .eq(issuer.as_encoded_issuer())
+            {
+                return false;
+            }
+        }
+        if let Some(authority_cert_serial_number) = &self.authority_cert_serial_number {
+            if !authority_cert_serial_number.eq(&issuer.get_serial_number()) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Return value in a form that is easy to process by the ASN.1 library `rasn`.
+    pub fn to_rasn_type(&self) -> rasn_pkix::AuthorityKeyIdentifier {
+        rasn_pkix::AuthorityKeyIdentifier {
+            key_identifier: self
+                .key_identifier
+                .as_ref()
+                .map(|kid| OctetString::from(kid.to_vec())),
+            authority_cert_issuer: self.authority_cert_issuer.as_ref().map(|encoded_name| {
+                vec![rasn_pkix::GeneralName::DirectoryName(
+                    rasn::der::decode(encoded_name).unwrap(),
+                )]
+            }),
+            authority_cert_serial_number: self
+                .authority_cert_serial_number
+                .as_ref()
+                .map(SerialNumber::to_rasn_type),
+        }
+    }
+
+    /// Return a new instance from the `rasn` ASN.1 library type.
+    pub fn from_rasn_type(aki: &rasn_pkix::AuthorityKeyIdentifier) -> Self {
+        Self {
+            key_identifier: aki.key_identifier.as_ref().map(|kid| kid.to_vec()),
+            authority_cert_issuer: aki
+                .authority_cert_issuer
+                .as_ref()
+                .and_then(|general_names| {
+                    general_names
+                        .iter()
+                        .find_map(|general_name| match general_name {
+                            rasn_pkix::GeneralName::DirectoryName(name) => {
+                                Some(rasn::der::encode(name).unwrap())
+                            }
+                            _ => None,
+                        })
+                }),
+            authority_cert_serial_number: aki
+                .authority_cert_serial_number
+                .as_ref()
+                .map(SerialNumber::from_rasn_type),
+        }
     }
 }
 
@@ -78,22 +339,93 @@ impl SubjectKeyIdentifier {
         }
     }
 
+    /// Return a new instance using the [KeyIdentifierMethod].
+    pub fn from_public_key_with_method(
+        public_key: &dyn PublicKey,
+        method: KeyIdentifierMethod,
+    ) -> Result<Self, DecodingError> {
+        Ok(Self {
+            ski: OctetString::from(method.key_identifier(public_key)?),
+        })
+    }
+
     /// Return value in a form that is easy to process by the ASN.1 library `rasn`.
     pub fn to_rasn_type(&self) -> &rasn_pkix::SubjectKeyIdentifier {
         &self.ski
     }
 
-    /** Calculate KID similar to RFC 5280 type 1 identifier.
-
-    SHA3-256 is not strictly neccessary according to the (now old) RFC5280, but
-    this decrease collission risk conciderable and allows SHA-1 to be phased out
-    at the cost of 12 additional bytes per KeyIdentifier.
-    */
+    /// Calculate KID similar to RFC 5280 type 1 identifier using
+    /// [KeyIdentifierMethod::Sha3_256].
     pub fn get_key_identifier_bytes(public_key: &dyn PublicKey) -> Vec<u8> {
-        Tyst::instance()
-            .digests()
-            .by_name("SHA3-256")
+        KeyIdentifierMethod::default()
+            .key_identifier(public_key)
             .unwrap()
-            .hash(&public_key.try_as_raw().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detect_key_identifier_method() {
+        let cp = crate::test_utils::load_test_certificate("h1_leaf.b64");
+        let spki = cp.as_encoded_subject_public_key_info();
+        for method in KeyIdentifierMethod::iter() {
+            let kid = method.key_identifier_from_encoded_spki(spki).unwrap();
+            assert_eq!(KeyIdentifierMethod::detect(&kid, spki), Some(method));
+        }
+        let method2 = KeyIdentifierMethod::Rfc5280Method2
+            .key_identifier_from_encoded_spki(spki)
+            .unwrap();
+        assert_eq!(method2.len(), 8);
+        assert_eq!(method2[0] & 0xf0, 0x40);
+        assert_eq!(KeyIdentifierMethod::detect(&[0u8; 20], spki), None);
+    }
+
+    #[test]
+    fn default_key_identifier_is_unchanged() {
+        // SKI of a certificate issued before KeyIdentifierMethod existed
+        let cp = crate::test_utils::load_test_certificate("h1_leaf.b64");
+        let ski = cp.get_subject_key_identifier_kid().unwrap().unwrap();
+        let public_key = cp.get_encoded_subject_public_key_info().to_public_key();
+        assert_eq!(
+            SubjectKeyIdentifier::get_key_identifier_bytes(public_key.as_ref()),
+            ski
+        );
+        assert_eq!(
+            KeyIdentifierMethod::default()
+                .key_identifier_from_encoded_spki(cp.as_encoded_subject_public_key_info())
+                .unwrap(),
+            ski
+        );
+        for method in KeyIdentifierMethod::iter() {
+            assert_eq!(
+                method.key_identifier(public_key.as_ref()).unwrap(),
+                method
+                    .key_identifier_from_encoded_spki(cp.as_encoded_subject_public_key_info())
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn authority_key_identifier_issuer_and_serial_number() {
+        let root_ca = crate::test_utils::load_test_certificate("h1_root_ca.b64");
+        let sub_ca = crate::test_utils::load_test_certificate("h1_sub_ca.b64");
+        let aki = AuthorityKeyIdentifier::from_issuer_certificate(&sub_ca, true).unwrap();
+        let decoded = AuthorityKeyIdentifier::from_rasn_type(&aki.to_rasn_type());
+        assert_eq!(decoded, aki);
+        assert_eq!(
+            decoded.get_authority_cert_serial_number(),
+            Some(&sub_ca.get_serial_number())
+        );
+        assert!(decoded.matches_issuer(&sub_ca));
+        assert!(!decoded.matches_issuer(&root_ca));
+        let json = serde_json::to_string(&aki).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AuthorityKeyIdentifier>(&json).unwrap(),
+            aki
+        );
     }
 }
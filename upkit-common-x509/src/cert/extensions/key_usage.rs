@@ -30,7 +30,7 @@ See [RFC5280 4.2.1.3](https://www.rfc-editor.org/rfc/rfc5280.html#section-4.2.1.
 */
 #[serde_as]
 #[skip_serializing_none]
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum KeyUsage {
     /// Allow use for verifying digital signatures, other than signatures on
@@ -86,6 +86,12 @@ impl KeyUsage {
             .unwrap()
     }
 
+    /// Return the [KeyUsage] at `index` where 0 means [Self::DigitalSignature]
+    /// and 8 means [Self::DecipherOnly].
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::MSB_ORDERED_KUS.get(index).cloned()
+    }
+
     /// Convert a slice of [KeyUsage] to [BitString].
     pub fn to_rasn_type(key_usages: &[KeyUsage]) -> BitString {
         let mut bv = BitVec::<u8, bitvec::order::Msb0>::new();
@@ -97,4 +103,15 @@ impl KeyUsage {
         }
         bv
     }
+
+    /// Return the asserted [KeyUsage]s of the [BitString] (ignoring unknown
+    /// bits).
+    pub fn from_rasn_type(key_usage: &BitString) -> Vec<KeyUsage> {
+        key_usage
+            .iter()
+            .enumerate()
+            .filter(|(_index, bit)| **bit)
+            .filter_map(|(index, _bit)| Self::from_index(index))
+            .collect()
+    }
 }
@@ -41,48 +41,94 @@ TBSCertificate  ::=  SEQUENCE  {
                          -- If present, version MUST be v3 --  }
 */
 
+mod certificate_reader;
+mod certificate_summary;
+mod encoded_ranges;
 mod parsing_error;
 
 use rasn::types::ObjectIdentifier;
 use rasn::types::SequenceOf;
+use std::sync::Arc;
+use std::sync::OnceLock;
 
+pub use self::certificate_reader::CertificateReader;
+pub use self::certificate_summary::CertificateSummary;
+pub use self::certificate_summary::ExtensionSummary;
 pub use self::parsing_error::CertificateParsingError;
 pub use self::parsing_error::CertificateParsingErrorKind;
 
+use self::encoded_ranges::EncodedRanges;
+
 use super::extensions::AlternativeName;
 use super::extensions::AuthorityInfoAccessDescription;
 use super::extensions::AuthorityKeyIdentifier;
 use super::extensions::BasicConstraints;
 use super::extensions::CertificatePolicy;
+use super::extensions::CrlDistributionPoint;
 use super::extensions::ExtendedKeyUsage;
 use super::extensions::KeyUsage;
+use super::extensions::SignedCertificateTimestamp;
 use super::extensions::SubjectKeyIdentifier;
 use super::types::DistinguishedName;
 use super::types::SerialNumber;
 use super::types::Validity;
+use super::types::WellKnownAlgorithm;
 use super::types::WellKnownGeneralName;
+use crate::encdec::tlv::Tlv;
+use crate::encdec::DecodingError;
+
+/// Result of decoding a part of the certificate.
+type Decoded<T> = OnceLock<Result<T, CertificateParsingError>>;
+
+/// Lazily decoded parts of the certificate.
+#[derive(Clone, Default)]
+struct DecodedCache {
+    issuer: Decoded<DistinguishedName>,
+    subject: Decoded<DistinguishedName>,
+    validity: Decoded<Validity>,
+    basic_constraints: Decoded<Option<BasicConstraints>>,
+    key_usage: Decoded<Option<Vec<KeyUsage>>>,
+    extended_key_usage: Decoded<Vec<ExtendedKeyUsage>>,
+    authority_key_identifier: Decoded<Option<AuthorityKeyIdentifier>>,
+    subject_key_identifier_kid: Decoded<Option<Vec<u8>>>,
+    certificate_policies: Decoded<Vec<CertificatePolicy>>,
+    subject_alternative_name: Decoded<Vec<(WellKnownGeneralName, String)>>,
+}
+
+/** Certificate parser.
+
+The original encoding is kept and only the DER structure is walked while
+parsing to record the byte ranges of the parts of the certificate together
+with the algorithm and extension OIDs. Encoded parts are returned exactly as
+received without re-encoding, so the TBSCertificate is the data that was
+actually signed.
 
-/// Certificate parser.
+The issuer, subject, validity and extension values are decoded on first use
+and cached. A part that fails to decode is reported as a
+[CertificateParsingError] by its getter.
+*/
 #[derive(Clone)]
 pub struct CertificateParser {
-    certificate: rasn_pkix::Certificate,
     fingerprint: String,
+    encoded: Arc<[u8]>,
+    ranges: EncodedRanges,
+    decoded_cache: DecodedCache,
 }
 impl CertificateParser {
     /// Create a new instance from `encoded_certificate` bytes.
     pub fn from_bytes(encoded_certificate: &[u8]) -> Result<Self, CertificateParsingError> {
         let fingerprint = crate::fingerprint_data(encoded_certificate);
-        rasn::der::decode::<rasn_pkix::Certificate>(encoded_certificate)
-            .map_err(|e|{
-                let msg = format!("Error while decoding data with fingerprint '{fingerprint}'. kind: '{:?}', codec: '{:?}'", e.kind, e.codec);
-                CertificateParsingErrorKind::CertificateDecodingError.error_with_msg(&msg)
-            })
-            .map(|certificate| {
-                Self {
-                    certificate,
-                    fingerprint,
-                }
-            })
+        let ranges = EncodedRanges::from_bytes(encoded_certificate).map_err(|e| {
+            CertificateParsingErrorKind::CertificateDecodingError.error_with_msg(&format!(
+                "Error while decoding data with fingerprint '{fingerprint}': {e}"
+            ))
+        })?;
+        Ok(Self {
+            fingerprint,
+            encoded: Arc::from(encoded_certificate),
+            ranges,
+            decoded_cache: DecodedCache::default(),
+        })
     }
 
     /// Return [fingerprint](crate::fingerprint_data) of the encoded certificate.
@@ -90,247 +136,539 @@ impl CertificateParser {
         &self.fingerprint
     }
 
-    /// Return certificate valdity (in Unix Epoch seconds).
-    pub fn get_validity(&self) -> Validity {
-        Validity::from_rasn_type(&self.certificate.tbs_certificate.validity)
+    /// Return the DER encoded certificate.
+    pub fn get_encoded_certificate(&self) -> Vec<u8> {
+        self.as_encoded_certificate().to_vec()
+    }
+
+    /// Return the DER encoded certificate without copying.
+    pub fn as_encoded_certificate(&self) -> &[u8] {
+        &self.encoded[self.ranges.certificate.clone()]
+    }
+
+    /// Return certificate valdity (in Unix Epoch seconds) or an error if
+    /// the times are not encoded as required by
+    /// [RFC5280 4.1.2.5](https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.5).
+    pub fn get_validity(&self) -> Result<Validity, CertificateParsingError> {
+        self.decoded_cache
+            .validity
+            .get_or_init(|| {
+                rasn::der::decode::<rasn_pkix::Validity>(
+                    &self.encoded[self.ranges.validity.clone()],
+                )
+                .map_err(|e| DecodingError::with_msg(&format!("{e:?}")))
+                .and_then(|validity| Validity::try_from_rasn_type(&validity))
+                .map_err(|e| {
+                    CertificateParsingErrorKind::CertificateDecodingError.error_with_msg(&format!(
+                        "Invalid validity in certificate with fingerprint '{}': {e}",
+                        self.fingerprint
+                    ))
+                })
+            })
+            .clone()
     }
 
     /// Return the part of the certificate that is covered by the signature
     /// as DER encoded bytes.
     pub fn get_encoded_tbs_certificate(&self) -> Vec<u8> {
-        rasn::der::encode(&self.certificate.tbs_certificate).unwrap()
+        self.as_encoded_tbs_certificate().to_vec()
+    }
+
+    /// Return the part of the certificate that is covered by the signature
+    /// exactly as received without copying.
+    pub fn as_encoded_tbs_certificate(&self) -> &[u8] {
+        &self.encoded[self.ranges.tbs_certificate.clone()]
     }
 
     /// Return the certificate serial number.
     pub fn get_serial_number(&self) -> SerialNumber {
-        SerialNumber::from_rasn_type(&self.certificate.tbs_certificate.serial_number)
+        SerialNumber::from_signed_bytes_be(&self.encoded[self.ranges.serial_number.clone()])
     }
 
     /// Return the subject distinguished name.
     pub fn get_subject(&self) -> Result<DistinguishedName, CertificateParsingError> {
-        DistinguishedName::from_rasn_type(&self.certificate.tbs_certificate.subject).map_err(|e| {
-            CertificateParsingErrorKind::CertificateDecodingError.error_with_msg(&format!(
-                "Failed to parse Subject Distinguished Name of certifiate: {e:?}"
-            ))
-        })
+        self.decoded_cache
+            .subject
+            .get_or_init(|| Self::decode_name(self.as_encoded_subject(), "Subject"))
+            .clone()
     }
 
-    /// Return the subject distinguished name.
+    /// Return the issuer distinguished name.
     pub fn get_issuer(&self) -> Result<DistinguishedName, CertificateParsingError> {
-        DistinguishedName::from_rasn_type(&self.certificate.tbs_certificate.issuer).map_err(|e| {
+        self.decoded_cache
+            .issuer
+            .get_or_init(|| Self::decode_name(self.as_encoded_issuer(), "Issuer"))
+            .clone()
+    }
+
+    fn decode_name(
+        encoded_name: &[u8],
+        name: &str,
+    ) -> Result<DistinguishedName, CertificateParsingError> {
+        let error = |e: &dyn std::fmt::Debug| {
             CertificateParsingErrorKind::CertificateDecodingError.error_with_msg(&format!(
-                "Failed to parse Issuer Distinguished Name of certifiate: {e:?}"
+                "Failed to parse {name} Distinguished Name of certifiate: {e:?}"
             ))
-        })
+        };
+        let rasn_name =
+            rasn::der::decode::<rasn_pkix::Name>(encoded_name).map_err(|e| error(&e))?;
+        DistinguishedName::from_rasn_type(&rasn_name).map_err(|e| error(&e))
     }
 
     /// Return the subject distinguished name as DER encoded bytes.
     pub fn get_encoded_subject(&self) -> Vec<u8> {
-        rasn::der::encode(&self.certificate.tbs_certificate.subject).unwrap()
+        self.as_encoded_subject().to_vec()
+    }
+
+    /// Return the subject distinguished name as DER encoded bytes without
+    /// copying.
+    pub fn as_encoded_subject(&self) -> &[u8] {
+        &self.encoded[self.ranges.subject.clone()]
     }
 
     /// Return the issuer distinguished name as DER encoded bytes.
     pub fn get_encoded_issuer(&self) -> Vec<u8> {
-        rasn::der::encode(&self.certificate.tbs_certificate.issuer).unwrap()
+        self.as_encoded_issuer().to_vec()
+    }
+
+    /// Return the issuer distinguished name as DER encoded bytes without
+    /// copying.
+    pub fn as_encoded_issuer(&self) -> &[u8] {
+        &self.encoded[self.ranges.issuer.clone()]
     }
 
     /// Return [fingerprint](crate::fingerprint_data) of subject distinguished
     /// name as DER encoded bytes.
     pub fn get_subject_fingerprint(&self) -> String {
-        crate::fingerprint_data(&self.get_encoded_subject())
+        crate::fingerprint_data(self.as_encoded_subject())
     }
 
     /// Return [fingerprint](crate::fingerprint_data) of issuer distinguished
     /// name as DER encoded bytes.
     pub fn get_issuer_fingerprint(&self) -> String {
-        crate::fingerprint_data(&self.get_encoded_issuer())
+        crate::fingerprint_data(self.as_encoded_issuer())
+    }
+
+    /// Return the [WellKnownAlgorithm] of the Subject Public Key Info if
+    /// known.
+    pub fn get_subject_public_key_algorithm(&self) -> Option<WellKnownAlgorithm> {
+        WellKnownAlgorithm::from_oid(&self.ranges.subject_public_key_algorithm)
     }
 
     /// Return the Subject Public Key Info as DER encoded bytes.
     pub fn get_encoded_subject_public_key_info(&self) -> Vec<u8> {
-        rasn::der::encode(&self.certificate.tbs_certificate.subject_public_key_info).unwrap()
+        self.as_encoded_subject_public_key_info().to_vec()
+    }
+
+    /// Return the Subject Public Key Info as DER encoded bytes without
+    /// copying.
+    pub fn as_encoded_subject_public_key_info(&self) -> &[u8] {
+        &self.encoded[self.ranges.subject_public_key_info.clone()]
     }
 
-    /// Return the signature OID and data as DER encoded bytes.
+    /// Return the signature OID and the signature data without copying.
     ///
     /// NOTE: This currently ignores the signature parameters.
-    pub fn get_encoded_signature(&self) -> (String, Vec<u8>) {
-        let alg_id = &self.certificate.signature_algorithm;
-        let oid = alg_id
-            .algorithm
-            .iter()
-            .map(|part| part.to_string())
-            .collect::<Vec<_>>()
-            .join(".")
-            .to_owned();
-        // TODO: This is too simplified to support every alg, but its good enough for now...
-        //let params = &alg_id.parameters;
+    pub fn get_encoded_signature(&self) -> (String, &[u8]) {
         (
-            oid,
-            self.certificate.signature_value.as_raw_slice().to_vec(),
+            crate::encdec::oid::as_string(&self.ranges.signature_algorithm),
+            &self.encoded[self.ranges.signature.clone()],
         )
     }
 
+    /// Return a vector of OIDs of all extensions in order of appearance.
+    pub fn get_extension_oids(&self) -> Vec<Vec<u32>> {
+        self.ranges
+            .extensions
+            .iter()
+            .map(|extension| extension.oid.to_vec())
+            .collect()
+    }
+
     /// Return a vector of OIDs of all the critical extensions.
     pub fn get_critical_extension_oids(&self) -> Vec<Vec<u32>> {
-        self.certificate
-            .tbs_certificate
+        self.ranges
             .extensions
-            .as_ref()
-            .map(|x509extension| {
-                x509extension
-                    .iter()
-                    .filter(|extension| extension.critical)
-                    .map(|extension| extension.extn_id.to_vec())
-                    .collect::<Vec<_>>()
-            })
-            .unwrap_or_default()
+            .iter()
+            .filter(|extension| extension.critical)
+            .map(|extension| extension.oid.to_vec())
+            .collect()
     }
 
-    fn extensions_by_oid(&self, oid: &[u32]) -> Vec<rasn_pkix::Extension> {
-        self.certificate
-            .tbs_certificate
+    /// Return the DER encoded `Extensions` `SEQUENCE` without copying if
+    /// present.
+    pub fn as_encoded_extensions(&self) -> Option<&[u8]> {
+        self.ranges
+            .encoded_extensions
+            .clone()
+            .map(|range| &self.encoded[range])
+    }
+
+    /// Return the DER encoded value (the content of `extnValue`) of the first
+    /// extension with the `oid` if present.
+    pub fn as_encoded_extension_value(&self, oid: &[u32]) -> Option<&[u8]> {
+        self.ranges
             .extensions
-            .as_ref()
-            .map(|x509extension| {
-                x509extension
-                    .iter()
-                    .filter_map(|extension| {
-                        if rasn::types::ObjectIdentifier::new_unchecked(oid.to_vec().into())
-                            .eq(&extension.extn_id)
-                        {
-                            Some(extension.clone())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<rasn_pkix::Extension>>()
+            .iter()
+            .find(|extension| extension.oid == oid)
+            .map(|extension| &self.encoded[extension.value.clone()])
+    }
+
+    /// Decode the value of the first extension with the `oid` if present.
+    fn decode_extension_value<T: rasn::Decode>(
+        &self,
+        oid: &[u32],
+    ) -> Result<Option<T>, CertificateParsingError> {
+        self.as_encoded_extension_value(oid)
+            .map(|value| {
+                rasn::der::decode::<T>(value).map_err(|e| {
+                    CertificateParsingErrorKind::ExtensionDecodingError.error_with_msg(&format!(
+                        "Failed to decode extension '{}' of certificate with fingerprint '{}': {e:?}",
+                        crate::encdec::oid::as_string(oid),
+                        self.fingerprint
+                    ))
+                })
             })
-            .unwrap_or_default()
+            .transpose()
     }
 
     /// Return Basic Constraints (when present)
-    pub fn get_basic_constraints(&self) -> Option<BasicConstraints> {
-        self.extensions_by_oid(BasicConstraints::OID)
-            .first()
-            .map(|extension| {
-                rasn::der::decode::<rasn_pkix::BasicConstraints>(&extension.extn_value).unwrap()
+    pub fn get_basic_constraints(
+        &self,
+    ) -> Result<Option<BasicConstraints>, CertificateParsingError> {
+        self.decoded_cache
+            .basic_constraints
+            .get_or_init(|| {
+                self.decode_extension_value::<rasn_pkix::BasicConstraints>(BasicConstraints::OID)
+                    .map(|value| value.as_ref().map(BasicConstraints::from_rasn_type))
             })
-            .as_ref()
-            .map(BasicConstraints::from_rasn_type)
-    }
-
-    /** Returns bit array with `[0]` as `DigitalSignature` to `[8]` as `DecipherOnly`
-
-    ```text
-    [0]: DigitalSignature,
-    [1]: NonRepudiation,
-    [2]: KeyEncipherment,
-    [3]: DataEncipherment,
-    [4]: KeyAgreement,
-    [5]: KeyCertSign,
-    [6]: CRLSign,
-    [7]: EncipherOnly,
-    [8]: DecipherOnly,
-    ```
-     */
-    pub fn get_key_usage(&self) -> Option<[bool; 9]> {
-        if let Some(extension) = self.extensions_by_oid(KeyUsage::OID).first() {
-            let key_usage =
-                rasn::der::decode::<rasn_pkix::KeyUsage>(&extension.extn_value).unwrap();
-            let mut ret = [false; 9];
-            for (i, b) in key_usage.into_iter().enumerate() {
-                ret[i] = b;
-            }
-            Some(ret)
-        } else {
-            None
-        }
+            .clone()
+    }
+
+    /// Return the asserted [KeyUsage]s if the extension is present.
+    pub fn get_key_usage(&self) -> Result<Option<Vec<KeyUsage>>, CertificateParsingError> {
+        self.decoded_cache
+            .key_usage
+            .get_or_init(|| {
+                self.decode_extension_value::<rasn_pkix::KeyUsage>(KeyUsage::OID)
+                    .map(|value| value.as_ref().map(KeyUsage::from_rasn_type))
+            })
+            .clone()
     }
 
     /// Return the all [ExtendedKeyUsage]s if present.
-    pub fn get_extended_key_usage(&self) -> Vec<ExtendedKeyUsage> {
-        if let Some(extension) = self.extensions_by_oid(ExtendedKeyUsage::OID).first() {
-            let ekus =
-                rasn::der::decode::<SequenceOf<ObjectIdentifier>>(&extension.extn_value).unwrap();
-            ekus.iter()
-                .map(|oid| ExtendedKeyUsage::from_oid(oid))
-                .collect()
-        } else {
-            Vec::default()
-        }
+    pub fn get_extended_key_usage(&self) -> Result<Vec<ExtendedKeyUsage>, CertificateParsingError> {
+        self.decoded_cache
+            .extended_key_usage
+            .get_or_init(|| {
+                self.decode_extension_value::<SequenceOf<ObjectIdentifier>>(ExtendedKeyUsage::OID)
+                    .map(|value| {
+                        value
+                            .iter()
+                            .flatten()
+                            .map(|oid| ExtendedKeyUsage::from_oid(oid))
+                            .collect()
+                    })
+            })
+            .clone()
+    }
+
+    /// Return the [AuthorityKeyIdentifier] if present.
+    pub fn get_authority_key_identifier(
+        &self,
+    ) -> Result<Option<AuthorityKeyIdentifier>, CertificateParsingError> {
+        self.decoded_cache
+            .authority_key_identifier
+            .get_or_init(|| {
+                self.decode_extension_value::<rasn_pkix::AuthorityKeyIdentifier>(
+                    AuthorityKeyIdentifier::OID,
+                )
+                .map(|value| value.as_ref().map(AuthorityKeyIdentifier::from_rasn_type))
+            })
+            .clone()
     }
 
     /// Return the [AuthorityKeyIdentifier] `key_identifier` bytes if present.
-    pub fn get_authority_key_identifier_kid(&self) -> Option<Vec<u8>> {
-        if let Some(extension) = self.extensions_by_oid(AuthorityKeyIdentifier::OID).first() {
-            let aki = rasn::der::decode::<rasn_pkix::AuthorityKeyIdentifier>(&extension.extn_value)
-                .unwrap();
-            if let Some(kid) = aki.key_identifier {
-                return Some(kid.to_vec());
-            }
-        }
-        None
+    pub fn get_authority_key_identifier_kid(
+        &self,
+    ) -> Result<Option<Vec<u8>>, CertificateParsingError> {
+        Ok(self
+            .get_authority_key_identifier()?
+            .and_then(|aki| aki.get_key_identifier().map(<[u8]>::to_vec)))
     }
 
     /// Return the [SubjectKeyIdentifier] `key_identifier` bytes if present.
-    pub fn get_subject_key_identifier_kid(&self) -> Option<Vec<u8>> {
-        if let Some(extension) = self.extensions_by_oid(SubjectKeyIdentifier::OID).first() {
-            let ski = rasn::der::decode::<rasn_pkix::SubjectKeyIdentifier>(&extension.extn_value)
-                .unwrap();
-            return Some(ski.to_vec());
-        }
-        None
+    pub fn get_subject_key_identifier_kid(
+        &self,
+    ) -> Result<Option<Vec<u8>>, CertificateParsingError> {
+        self.decoded_cache
+            .subject_key_identifier_kid
+            .get_or_init(|| {
+                self.decode_extension_value::<rasn_pkix::SubjectKeyIdentifier>(
+                    SubjectKeyIdentifier::OID,
+                )
+                .map(|value| value.map(|ski| ski.to_vec()))
+            })
+            .clone()
     }
 
     /// Return the all [CertificatePolicy] if present.
-    pub fn get_certificate_policies(&self) -> Vec<CertificatePolicy> {
-        if let Some(extension) = self.extensions_by_oid(CertificatePolicy::OID).first() {
-            let policies = rasn::der::decode::<SequenceOf<rasn_pkix::PolicyInformation>>(
-                &extension.extn_value,
-            )
-            .unwrap();
-            CertificatePolicy::from_rasn_type(&policies)
-        } else {
-            Vec::default()
-        }
+    pub fn get_certificate_policies(
+        &self,
+    ) -> Result<Vec<CertificatePolicy>, CertificateParsingError> {
+        self.decoded_cache
+            .certificate_policies
+            .get_or_init(|| {
+                self.decode_extension_value::<SequenceOf<rasn_pkix::PolicyInformation>>(
+                    CertificatePolicy::OID,
+                )?
+                .map(|policies| {
+                    CertificatePolicy::from_rasn_type(&policies).map_err(|e| {
+                        CertificateParsingErrorKind::ExtensionDecodingError
+                            .error_with_msg(&format!("Failed to decode certificate policies: {e}"))
+                    })
+                })
+                .transpose()
+                .map(Option::unwrap_or_default)
+            })
+            .clone()
     }
 
     /// Return the all [CertificatePolicy] if present.
-    pub fn get_authority_information_access(&self) -> Vec<AuthorityInfoAccessDescription> {
-        if let Some(extension) = self
-            .extensions_by_oid(AuthorityInfoAccessDescription::OID)
-            .first()
-        {
-            let access_descriptions =
-                rasn::der::decode::<SequenceOf<rasn_pkix::AccessDescription>>(
-                    &extension.extn_value,
-                )
-                .unwrap();
-            AuthorityInfoAccessDescription::from_rasn_type(&access_descriptions)
+    pub fn get_authority_information_access(
+        &self,
+    ) -> Result<Vec<AuthorityInfoAccessDescription>, CertificateParsingError> {
+        Ok(self
+            .decode_extension_value::<SequenceOf<rasn_pkix::AccessDescription>>(
+                AuthorityInfoAccessDescription::OID,
+            )?
+            .map(|access_descriptions| {
+                AuthorityInfoAccessDescription::from_rasn_type(&access_descriptions)
+            })
+            .unwrap_or_default())
+    }
+
+    /// Return the CA issued [CrlDistributionPoint] URI if present.
+    pub fn get_crl_distribution_point(&self) -> Result<Option<String>, CertificateParsingError> {
+        Ok(self
+            .decode_extension_value::<SequenceOf<rasn_pkix::DistributionPoint>>(
+                CrlDistributionPoint::OID,
+            )?
+            .and_then(|cdps| CrlDistributionPoint::from_rasn_type(&cdps)))
+    }
+
+    /// Return all embedded [SignedCertificateTimestamp]s if present.
+    pub fn get_signed_certificate_timestamps(
+        &self,
+    ) -> Result<Vec<SignedCertificateTimestamp>, DecodingError> {
+        if let Some(value) = self.as_encoded_extension_value(SignedCertificateTimestamp::OID) {
+            let sct_list = rasn::der::decode::<rasn::types::OctetString>(value).map_err(|e| {
+                DecodingError::with_msg(&format!("Failed to decode SCT list: {e:?}"))
+            })?;
+            SignedCertificateTimestamp::from_list_bytes(&sct_list)
         } else {
-            Vec::default()
+            Ok(Vec::default())
+        }
+    }
+
+    /** Return the DER encoded precertificate `TBSCertificate` as signed by
+    Certificate Transparency logs.
+
+    This is the original `TBSCertificate` with the SignedCertificateTimestampList
+    and precertificate poison extensions removed. The `extensions` field is
+    omitted when no other extension remains. See
+    [RFC6962 3.2](https://www.rfc-editor.org/rfc/rfc6962#section-3.2).
+    */
+    pub fn get_encoded_precertificate_tbs_certificate(&self) -> Result<Vec<u8>, DecodingError> {
+        let removed = [
+            SignedCertificateTimestamp::OID,
+            SignedCertificateTimestamp::OID_PRECERTIFICATE_POISON,
+        ]
+        .map(|oid| ObjectIdentifier::new_unchecked(oid.to_vec().into()));
+        let tbs_certificate = self.get_encoded_tbs_certificate();
+        let mut content = vec![];
+        for field in Tlv::read(&tbs_certificate, 0)?.children(&tbs_certificate)? {
+            if field.tag != TAG_EXTENSIONS {
+                content.extend_from_slice(&tbs_certificate[field.range]);
+                continue;
+            }
+            let mut extensions = vec![];
+            for sequence in field.children(&tbs_certificate)? {
+                for extension in sequence.children(&tbs_certificate)? {
+                    let extn_id = extension
+                        .children(&tbs_certificate)?
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| DecodingError::with_msg("Truncated Extension."))?;
+                    let extn_id =
+                        rasn::der::decode::<ObjectIdentifier>(&tbs_certificate[extn_id.range])
+                            .map_err(|e| {
+                                DecodingError::with_msg(&format!("Invalid extnID: {e}"))
+                            })?;
+                    if !removed.contains(&extn_id) {
+                        extensions.extend_from_slice(&tbs_certificate[extension.range]);
+                    }
+                }
+            }
+            if !extensions.is_empty() {
+                content.extend(der_tlv(TAG_EXTENSIONS, &der_tlv(TAG_SEQUENCE, &extensions)));
+            }
         }
+        Ok(der_tlv(TAG_SEQUENCE, &content))
+    }
+
+    /// Return a serializable summary of the certificate.
+    pub fn to_summary(&self) -> CertificateSummary {
+        CertificateSummary::from_certificate_parser(self)
+    }
+
+    /// Return a human readable textual dump of the certificate similar to
+    /// `openssl x509 -text`.
+    pub fn to_text(&self) -> String {
+        self.to_summary().to_text()
     }
 
     /// Return the all [WellKnownGeneralName] if present.
-    pub fn get_issuer_alternative_name(&self) -> Vec<(WellKnownGeneralName, String)> {
+    pub fn get_issuer_alternative_name(
+        &self,
+    ) -> Result<Vec<(WellKnownGeneralName, String)>, CertificateParsingError> {
         self.get_alternative_name(AlternativeName::OID_ISSUER_AN)
     }
 
     /// Return the all [WellKnownGeneralName] if present.
-    pub fn get_subject_alternative_name(&self) -> Vec<(WellKnownGeneralName, String)> {
-        self.get_alternative_name(AlternativeName::OID_SUBJECT_AN)
+    pub fn get_subject_alternative_name(
+        &self,
+    ) -> Result<Vec<(WellKnownGeneralName, String)>, CertificateParsingError> {
+        self.decoded_cache
+            .subject_alternative_name
+            .get_or_init(|| self.get_alternative_name(AlternativeName::OID_SUBJECT_AN))
+            .clone()
     }
 
-    fn get_alternative_name(&self, oid: &[u32]) -> Vec<(WellKnownGeneralName, String)> {
-        if let Some(extension) = self.extensions_by_oid(oid).first() {
-            let general_names =
-                rasn::der::decode::<SequenceOf<rasn_pkix::GeneralName>>(&extension.extn_value)
-                    .unwrap();
-            AlternativeName::from_rasn_type(&general_names)
-        } else {
-            Vec::default()
+    fn get_alternative_name(
+        &self,
+        oid: &[u32],
+    ) -> Result<Vec<(WellKnownGeneralName, String)>, CertificateParsingError> {
+        Ok(self
+            .decode_extension_value::<SequenceOf<rasn_pkix::GeneralName>>(oid)?
+            .map(|general_names| AlternativeName::from_rasn_type(&general_names))
+            .unwrap_or_default())
+    }
+}
+
+/// `SEQUENCE`
+const TAG_SEQUENCE: u8 = 0x30;
+/// `[3] EXPLICIT Extensions` of the `TBSCertificate`.
+const TAG_EXTENSIONS: u8 = 0xa3;
+
+/// Return the DER encoded TLV of the constructed `tag` with the `content`.
+fn der_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut ret = vec![tag];
+    match u8::try_from(content.len()) {
+        Ok(len) if len < 0x80 => ret.push(len),
+        _ => {
+            let len = content.len().to_be_bytes();
+            let len = &len[len.iter().take_while(|octet| **octet == 0).count()..];
+            ret.push(0x80 | len.len() as u8);
+            ret.extend_from_slice(len);
         }
     }
+    ret.extend_from_slice(content);
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn malformed_extension_is_reported_by_getter() {
+        init_logger();
+        let der = load_test_certificate_der_with_malformed_extension(
+            "h1_leaf.b64",
+            ExtendedKeyUsage::OID,
+        );
+        let cp = CertificateParser::from_bytes(&der).unwrap();
+        let e = cp.get_extended_key_usage().unwrap_err();
+        assert!(matches!(
+            e.kind(),
+            CertificateParsingErrorKind::ExtensionDecodingError
+        ));
+        // The failure is cached and reported again.
+        assert!(cp.get_extended_key_usage().is_err());
+        // Other extensions are still available.
+        assert!(cp.get_basic_constraints().unwrap().is_some());
+        assert!(cp.get_authority_key_identifier_kid().unwrap().is_some());
+    }
+
+    #[test]
+    fn malformed_crl_distribution_point_is_reported_by_getter() {
+        init_logger();
+        let der = load_test_certificate_der_with_malformed_extension(
+            "ec_cdp_leaf.b64",
+            CrlDistributionPoint::OID,
+        );
+        let cp = CertificateParser::from_bytes(&der).unwrap();
+        assert!(matches!(
+            cp.get_crl_distribution_point().unwrap_err().kind(),
+            CertificateParsingErrorKind::ExtensionDecodingError
+        ));
+        // The summary reports the extension as invalid instead of panicking.
+        let summary = cp.to_summary();
+        let cdp = summary
+            .extensions
+            .iter()
+            .find(|extension| extension.oid == "2.5.29.31")
+            .unwrap();
+        assert!(cdp.invalid);
+        let cp = load_test_certificate("ec_cdp_leaf.b64");
+        assert_eq!(
+            cp.get_crl_distribution_point().unwrap().as_deref(),
+            Some("http://crl.example.com/ec_ca.crl")
+        );
+    }
+
+    #[test]
+    fn malformed_certificate_policies_are_reported_by_getter() {
+        init_logger();
+        let der = load_test_certificate_der_with_malformed_extension(
+            "h1_leaf.b64",
+            CertificatePolicy::OID,
+        );
+        let cp = CertificateParser::from_bytes(&der).unwrap();
+        assert!(matches!(
+            cp.get_certificate_policies().unwrap_err().kind(),
+            CertificateParsingErrorKind::ExtensionDecodingError
+        ));
+        assert!(
+            cp.to_summary()
+                .extensions
+                .iter()
+                .find(|extension| extension.oid == "2.5.29.32")
+                .unwrap()
+                .invalid
+        );
+    }
+
+    #[test]
+    fn invalid_validity_is_reported_by_getter() {
+        init_logger();
+        let mut certificate =
+            rasn::der::decode::<rasn_pkix::Certificate>(&load_test_certificate_der("h1_leaf.b64"))
+                .unwrap();
+        // RFC5280 4.1.2.5: Dates before 2050 MUST be encoded as UTCTime
+        let rasn_pkix::Time::Utc(not_before) = certificate.tbs_certificate.validity.not_before
+        else {
+            panic!("Expected UTCTime.");
+        };
+        certificate.tbs_certificate.validity.not_before =
+            rasn_pkix::Time::General(not_before.fixed_offset());
+        let cp = CertificateParser::from_bytes(&rasn::der::encode(&certificate).unwrap()).unwrap();
+        let e = cp.get_validity().unwrap_err();
+        assert!(matches!(
+            e.kind(),
+            CertificateParsingErrorKind::CertificateDecodingError
+        ));
+    }
 }
@@ -0,0 +1,81 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Streaming reader of certificates.
+
+use super::CertificateParser;
+use super::CertificateParsingError;
+use super::CertificateParsingErrorKind;
+use crate::encdec::pem::Marker;
+use crate::encdec::pem::PemParsingMode;
+use crate::encdec::reader::EncodedObjectReader;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+
+/** Streaming reader of PEM or concatenated DER encoded certificates.
+
+Yields one [CertificateParser] at a time. PEM objects of other types than
+certificates are skipped. Failure to decode an individual certificate is
+reported as an error item and reading continues with the next object.
+*/
+pub struct CertificateReader<R: BufRead> {
+    encoded_object_reader: EncodedObjectReader<R>,
+}
+
+impl<R: Read> CertificateReader<BufReader<R>> {
+    /// Return a new instance that wraps a [Read] in a [BufReader].
+    pub fn from_read(read: R) -> Self {
+        Self::new(BufReader::new(read))
+    }
+}
+
+impl<R: BufRead> CertificateReader<R> {
+    /// Return a new instance using [PemParsingMode::Lax] for PEM input.
+    pub fn new(inner: R) -> Self {
+        Self::from_encoded_object_reader(EncodedObjectReader::new(inner, PemParsingMode::Lax))
+    }
+
+    /// Return a new instance from a configured [EncodedObjectReader].
+    pub fn from_encoded_object_reader(encoded_object_reader: EncodedObjectReader<R>) -> Self {
+        Self {
+            encoded_object_reader,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for CertificateReader<R> {
+    type Item = Result<CertificateParser, CertificateParsingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.encoded_object_reader.by_ref() {
+            match item {
+                Ok((Marker::Certificate, der)) => {
+                    return Some(CertificateParser::from_bytes(&der));
+                }
+                Ok((marker, _)) => {
+                    log::debug!("Skipping object of type '{}'.", marker.as_str());
+                }
+                Err(e) => {
+                    return Some(Err(CertificateParsingErrorKind::CertificateDecodingError
+                        .error_with_msg(&e.to_string())));
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,513 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Human readable X.509 Certificate summary.
+
+use super::encoded_ranges::EncodedExtension;
+use super::CertificateParser;
+use crate::cert::extensions::AlternativeName;
+use crate::cert::extensions::AuthorityInfoAccessDescription;
+use crate::cert::extensions::AuthorityKeyIdentifier;
+use crate::cert::extensions::BasicConstraints;
+use crate::cert::extensions::CertificatePolicy;
+use crate::cert::extensions::CrlDistributionPoint;
+use crate::cert::extensions::ExtendedKeyUsage;
+use crate::cert::extensions::KeyUsage;
+use crate::cert::extensions::SubjectKeyIdentifier;
+use crate::cert::types::DistinguishedName;
+use crate::cert::types::WellKnownAlgorithm;
+use crate::cert::types::WellKnownNamedCurve;
+use crate::encdec::DecodingError;
+use rasn::types::Integer;
+use rasn::types::ObjectIdentifier;
+use rasn::types::SequenceOf;
+use rasn_pkix::AccessDescription;
+use rasn_pkix::DistributionPoint;
+use rasn_pkix::GeneralName;
+use rasn_pkix::PolicyInformation;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+use serde_with::skip_serializing_none;
+use tyst::encdec::hex::ToHex;
+use tyst::Tyst;
+
+/** Summary of a single certificate extension.
+
+Extensions known by this library are decoded into their typed representation
+and serialized as JSON. Unknown extensions and known extensions that cannot be
+decoded are represented by the hex encoded `extnValue`.
+*/
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ExtensionSummary {
+    /// Extension object identifier in dotted form.
+    pub oid: String,
+    /// Human readable name of the extension if known.
+    pub name: Option<String>,
+    /// `true` if the extension is marked as critical.
+    pub critical: bool,
+    /// `true` if the value of a known extension could not be decoded.
+    #[serde(default)]
+    pub invalid: bool,
+    /// Decoded value of a known extension.
+    pub value: Option<serde_json::Value>,
+    /// Hex encoded `extnValue` of an unknown extension.
+    pub value_hex: Option<String>,
+}
+
+/** Serializable summary of an X.509 Certificate.
+
+This is intended for presentation and inventory purposes. Use
+[CertificateParser] directly to make decisions based on the certificate
+content.
+*/
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct CertificateSummary {
+    /// Certificate version (1, 2 or 3).
+    pub version: u8,
+    /// Lower case hex encoded serial number.
+    pub serial_number: String,
+    /// Name of the signature algorithm (or OID in dotted form if unknown).
+    pub signature_algorithm: String,
+    /// Object identifier of the signature algorithm in dotted form.
+    pub signature_algorithm_oid: String,
+    /// Issuer in [RFC4514](https://www.rfc-editor.org/rfc/rfc4514) form.
+    pub issuer: String,
+    /// Subject in [RFC4514](https://www.rfc-editor.org/rfc/rfc4514) form.
+    pub subject: String,
+    /// Start of the validity period in ISO 8601 format.
+    pub not_before: String,
+    /// End of the validity period in ISO 8601 format.
+    pub not_after: String,
+    /// Name of the subject public key algorithm (or OID in dotted form if
+    /// unknown).
+    pub subject_public_key_algorithm: String,
+    /// Name of the elliptic curve for `id-ecPublicKey`.
+    pub subject_public_key_curve: Option<String>,
+    /// Size of the subject public key in bits when this is well-defined for
+    /// the algorithm.
+    pub subject_public_key_size: Option<usize>,
+    /// All certificate extensions in order of appearance.
+    pub extensions: Vec<ExtensionSummary>,
+    /// Lower case hex encoded SHA-256 fingerprint of the encoded certificate.
+    pub fingerprint_sha256: String,
+    /// Lower case hex encoded SHA3-512 fingerprint of the encoded certificate.
+    ///
+    /// This is the same as [CertificateParser::fingerprint].
+    pub fingerprint_sha3_512: String,
+}
+
+impl CertificateSummary {
+    /// Return a new instance from a parsed certificate.
+    pub fn from_certificate_parser(cp: &CertificateParser) -> Self {
+        let version = cp
+            .ranges
+            .version
+            .clone()
+            .and_then(|range| rasn::der::decode::<Integer>(&cp.encoded[range]).ok())
+            .and_then(|version| crate::encdec::rasn::integer_try_as_u64(&version));
+        let version = match version {
+            Some(2) => 3,
+            Some(1) => 2,
+            _ => 1,
+        };
+        let (signature_algorithm_oid, _signature) = cp.get_encoded_signature();
+        let signature_algorithm = WellKnownAlgorithm::name_from_oid(&cp.ranges.signature_algorithm);
+        let (not_before, not_after) = match cp.get_validity() {
+            Ok(validity) => (
+                Self::epoch_seconds_as_iso8601(validity.get_not_before()),
+                Self::epoch_seconds_as_iso8601(validity.get_not_after()),
+            ),
+            Err(e) => (e.to_string(), e.to_string()),
+        };
+        let spki = rasn::der::decode::<rasn_pkix::SubjectPublicKeyInfo>(
+            cp.as_encoded_subject_public_key_info(),
+        )
+        .ok();
+        let curve = spki
+            .as_ref()
+            .and_then(|spki| Self::named_curve(&spki.algorithm));
+        let extensions = cp
+            .ranges
+            .extensions
+            .iter()
+            .map(|extension| {
+                Self::extension_summary(extension, &cp.encoded[extension.value.clone()])
+            })
+            .collect();
+        let encoded_certificate = cp.as_encoded_certificate();
+        Self {
+            version,
+            serial_number: cp.get_serial_number().as_bytes().to_hex(),
+            signature_algorithm,
+            signature_algorithm_oid,
+            issuer: Self::rfc4514(cp.as_encoded_issuer()),
+            subject: Self::rfc4514(cp.as_encoded_subject()),
+            not_before,
+            not_after,
+            subject_public_key_algorithm: WellKnownAlgorithm::name_from_oid(
+                &cp.ranges.subject_public_key_algorithm,
+            ),
+            subject_public_key_curve: curve.map(|curve| curve.as_name().to_string()),
+            subject_public_key_size: spki.as_ref().and_then(Self::public_key_size),
+            extensions,
+            fingerprint_sha256: Tyst::instance()
+                .digests()
+                .by_name("SHA-256")
+                .unwrap()
+                .hash(encoded_certificate)
+                .to_hex(),
+            fingerprint_sha3_512: cp.fingerprint().to_string(),
+        }
+    }
+
+    /// Return the encoded name in RFC4514 form or the decoding error.
+    fn rfc4514(encoded_name: &[u8]) -> String {
+        rasn::der::decode::<rasn_pkix::Name>(encoded_name)
+            .map(|name| DistinguishedName::rfc4514_from_rasn_type(&name))
+            .unwrap_or_else(|e| format!("<invalid name: {e:?}>"))
+    }
+
+    /// Return the time as ISO 8601 in UTC with second precision.
+    fn epoch_seconds_as_iso8601(epoch_seconds: u64) -> String {
+        i64::try_from(epoch_seconds)
+            .ok()
+            .and_then(|epoch_seconds| chrono::DateTime::from_timestamp(epoch_seconds, 0))
+            .map(|date_time| date_time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+            .unwrap_or_else(|| format!("@{epoch_seconds}"))
+    }
+
+    fn named_curve(
+        algorithm_identifier: &rasn_pkix::AlgorithmIdentifier,
+    ) -> Option<WellKnownNamedCurve> {
+        if !WellKnownAlgorithm::EcPublicKey
+            .as_oid()
+            .eq(algorithm_identifier.algorithm.to_vec().as_slice())
+        {
+            return None;
+        }
+        algorithm_identifier
+            .parameters
+            .as_ref()
+            .and_then(|parameters| {
+                rasn::der::decode::<ObjectIdentifier>(parameters.as_bytes()).ok()
+            })
+            .and_then(|curve_oid| WellKnownNamedCurve::from_oid(&curve_oid))
+    }
+
+    /// Return the key size in bits where this is meaningful.
+    fn public_key_size(spki: &rasn_pkix::SubjectPublicKeyInfo) -> Option<usize> {
+        let algorithm_oid = spki.algorithm.algorithm.to_vec();
+        match WellKnownAlgorithm::from_oid(&algorithm_oid)? {
+            WellKnownAlgorithm::RsaEncryption | WellKnownAlgorithm::RsaSsaPss => {
+                // RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+                let rsa_public_key = rasn::der::decode::<SequenceOf<Integer>>(
+                    spki.subject_public_key.as_raw_slice(),
+                )
+                .ok()?;
+                let modulus = crate::encdec::rasn::integer_as_bytes_be(rsa_public_key.first()?);
+                let modulus = modulus
+                    .iter()
+                    .skip_while(|octet| **octet == 0)
+                    .collect::<Vec<_>>();
+                let first = **modulus.first()?;
+                Some(modulus.len() * 8 - first.leading_zeros() as usize)
+            }
+            WellKnownAlgorithm::EcPublicKey => {
+                Self::named_curve(&spki.algorithm).map(|curve| curve.key_size())
+            }
+            WellKnownAlgorithm::Ed25519 | WellKnownAlgorithm::X25519 => Some(256),
+            WellKnownAlgorithm::Ed448 => Some(456),
+            WellKnownAlgorithm::X448 => Some(448),
+            _ => None,
+        }
+    }
+
+    fn extension_name(oid: &[u32]) -> Option<&'static str> {
+        Some(match oid {
+            BasicConstraints::OID => "Basic Constraints",
+            KeyUsage::OID => "Key Usage",
+            ExtendedKeyUsage::OID => "Extended Key Usage",
+            SubjectKeyIdentifier::OID => "Subject Key Identifier",
+            AuthorityKeyIdentifier::OID => "Authority Key Identifier",
+            CertificatePolicy::OID => "Certificate Policies",
+            AuthorityInfoAccessDescription::OID => "Authority Information Access",
+            AlternativeName::OID_SUBJECT_AN => "Subject Alternative Name",
+            AlternativeName::OID_ISSUER_AN => "Issuer Alternative Name",
+            CrlDistributionPoint::OID => "CRL Distribution Points",
+            _ => return None,
+        })
+    }
+
+    fn extension_summary(extension: &EncodedExtension, extn_value: &[u8]) -> ExtensionSummary {
+        let oid = &extension.oid;
+        let decoded = match oid.as_slice() {
+            BasicConstraints::OID => {
+                Self::decoded_value(extn_value, |value: rasn_pkix::BasicConstraints| {
+                    Ok(BasicConstraints::from_rasn_type(&value))
+                })
+            }
+            KeyUsage::OID => Self::decoded_value(extn_value, |value: rasn_pkix::KeyUsage| {
+                Ok(KeyUsage::from_rasn_type(&value))
+            }),
+            ExtendedKeyUsage::OID => {
+                Self::decoded_value(extn_value, |value: SequenceOf<ObjectIdentifier>| {
+                    Ok(value
+                        .iter()
+                        .map(|oid| ExtendedKeyUsage::from_oid(oid))
+                        .collect::<Vec<_>>())
+                })
+            }
+            SubjectKeyIdentifier::OID => {
+                Self::decoded_value(extn_value, |value: rasn_pkix::SubjectKeyIdentifier| {
+                    Ok(value.to_vec().to_hex())
+                })
+            }
+            AuthorityKeyIdentifier::OID => {
+                Self::decoded_value(extn_value, |value: rasn_pkix::AuthorityKeyIdentifier| {
+                    Ok(AuthorityKeyIdentifier::from_rasn_type(&value))
+                })
+            }
+            CertificatePolicy::OID => {
+                Self::decoded_value(extn_value, |value: SequenceOf<PolicyInformation>| {
+                    CertificatePolicy::from_rasn_type(&value)
+                })
+            }
+            AuthorityInfoAccessDescription::OID => {
+                Self::decoded_value(extn_value, |value: SequenceOf<AccessDescription>| {
+                    Ok(AuthorityInfoAccessDescription::from_rasn_type(&value))
+                })
+            }
+            AlternativeName::OID_SUBJECT_AN | AlternativeName::OID_ISSUER_AN => {
+                Self::decoded_value(extn_value, |value: SequenceOf<GeneralName>| {
+                    Ok(AlternativeName::from_rasn_type(&value))
+                })
+            }
+            CrlDistributionPoint::OID => {
+                Self::decoded_value(extn_value, |value: SequenceOf<DistributionPoint>| {
+                    Ok(CrlDistributionPoint::from_rasn_type(&value))
+                })
+            }
+            _ => None,
+        };
+        let (value, invalid) = match decoded {
+            Some(Ok(value)) => (Some(value), false),
+            Some(Err(e)) => {
+                log::debug!(
+                    "Failed to decode extension '{}': {e}",
+                    crate::encdec::oid::as_string(oid)
+                );
+                (None, true)
+            }
+            None => (None, false),
+        };
+        let value_hex = if value.is_none() {
+            Some(extn_value.to_hex())
+        } else {
+            None
+        };
+        ExtensionSummary {
+            oid: crate::encdec::oid::as_string(oid),
+            name: Self::extension_name(oid).map(str::to_string),
+            critical: extension.critical,
+            invalid,
+            value,
+            value_hex,
+        }
+    }
+
+    /// Decode the `extnValue` of a known extension and convert it into its
+    /// JSON representation.
+    fn decoded_value<T: rasn::Decode, S: Serialize>(
+        extn_value: &[u8],
+        convert: impl FnOnce(T) -> Result<S, DecodingError>,
+    ) -> Option<Result<serde_json::Value, DecodingError>> {
+        Some(
+            rasn::der::decode::<T>(extn_value)
+                .map_err(|e| DecodingError::with_msg(&format!("{e:?}")))
+                .and_then(convert)
+                .and_then(|value| {
+                    serde_json::to_value(value).map_err(|e| DecodingError::with_msg(&e.to_string()))
+                }),
+        )
+    }
+
+    /// Return a human readable textual dump similar to `openssl x509 -text`.
+    pub fn to_text(&self) -> String {
+        let mut ret = String::new();
+        ret.push_str("Certificate:\n");
+        ret.push_str("    Data:\n");
+        ret.push_str(&format!(
+            "        Version: {} (0x{:x})\n",
+            self.version,
+            self.version.saturating_sub(1)
+        ));
+        ret.push_str("        Serial Number:\n");
+        ret.push_str(&format!(
+            "            {}\n",
+            Self::colon_separated(&self.serial_number)
+        ));
+        ret.push_str(&format!(
+            "        Signature Algorithm: {}\n",
+            self.signature_algorithm
+        ));
+        ret.push_str(&format!("        Issuer: {}\n", self.issuer));
+        ret.push_str("        Validity\n");
+        ret.push_str(&format!("            Not Before: {}\n", self.not_before));
+        ret.push_str(&format!("            Not After : {}\n", self.not_after));
+        ret.push_str(&format!("        Subject: {}\n", self.subject));
+        ret.push_str("        Subject Public Key Info:\n");
+        ret.push_str(&format!(
+            "            Public Key Algorithm: {}\n",
+            self.subject_public_key_algorithm
+        ));
+        if let Some(curve) = &self.subject_public_key_curve {
+            ret.push_str(&format!("                Curve: {curve}\n"));
+        }
+        if let Some(key_size) = &self.subject_public_key_size {
+            ret.push_str(&format!("                Public-Key: ({key_size} bit)\n"));
+        }
+        if !self.extensions.is_empty() {
+            ret.push_str("        X509v3 extensions:\n");
+            for extension in &self.extensions {
+                ret.push_str(&format!(
+                    "            {}:{}\n",
+                    extension.name.as_ref().unwrap_or(&extension.oid),
+                    if extension.critical { " critical" } else { "" }
+                ));
+                if extension.invalid {
+                    ret.push_str("                <invalid>\n");
+                }
+                if let Some(value) = &extension.value {
+                    ret.push_str(&format!("                {value}\n"));
+                }
+                if let Some(value_hex) = &extension.value_hex {
+                    ret.push_str(&format!(
+                        "                {}\n",
+                        Self::colon_separated(value_hex)
+                    ));
+                }
+            }
+        }
+        ret.push_str(&format!(
+            "    Signature Algorithm: {}\n",
+            self.signature_algorithm
+        ));
+        ret.push_str("    Fingerprints:\n");
+        ret.push_str(&format!(
+            "        SHA-256: {}\n",
+            Self::colon_separated(&self.fingerprint_sha256)
+        ));
+        ret.push_str(&format!(
+            "        SHA3-512: {}\n",
+            Self::colon_separated(&self.fingerprint_sha3_512)
+        ));
+        ret
+    }
+
+    /// Format a hex string as colon separated octets like `0a:1b:2c`.
+    fn colon_separated(hex: &str) -> String {
+        hex.as_bytes()
+            .chunks(2)
+            .map(|octet| core::str::from_utf8(octet).unwrap())
+            .collect::<Vec<_>>()
+            .join(":")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn summary_of_test_certificate() {
+        let cp = crate::test_utils::load_test_certificate("h1_leaf.b64");
+        let summary = CertificateSummary::from_certificate_parser(&cp);
+        assert_eq!(summary.version, 3);
+        assert_eq!(
+            summary.serial_number,
+            "50b5b10f9a25f1539b4020b779b29846cf474822"
+        );
+        assert!(summary.subject.contains("CN=Master of the universe"));
+        assert!(summary.issuer.contains("CN=TLS Sub CA v1"));
+        assert_eq!(summary.not_before, "2025-02-14T12:16:28Z");
+        assert_eq!(summary.not_after, "2025-03-16T12:26:28Z");
+        assert_eq!(
+            summary.fingerprint_sha256,
+            "646b202779d6c6e2fc854043f81ecf721ad9ece98c28b478447d1b9ae09524f2"
+        );
+        let basic_constraints = summary
+            .extensions
+            .iter()
+            .find(|extension| extension.oid == "2.5.29.19")
+            .unwrap();
+        assert!(basic_constraints.critical);
+        assert_eq!(basic_constraints.name.as_deref(), Some("Basic Constraints"));
+        let json = serde_json::to_string(&summary).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CertificateSummary>(&json).unwrap(),
+            summary
+        );
+        let text = summary.to_text();
+        assert!(text.contains("            50:b5:b1:0f:9a:25:f1:53"));
+        assert!(text.contains("            Basic Constraints: critical\n"));
+        assert!(text.contains("SHA-256: 64:6b:20:27"));
+    }
+
+    #[test]
+    fn summary_of_small_serial_number() {
+        let cp = crate::test_utils::load_test_certificate("ec_cdp_leaf.b64");
+        let summary = CertificateSummary::from_certificate_parser(&cp);
+        assert_eq!(summary.serial_number, "5a5a02");
+        assert!(summary
+            .to_text()
+            .contains("        Serial Number:\n            5a:5a:02\n"));
+    }
+
+    #[test]
+    fn summary_of_malformed_extension() {
+        let der = crate::test_utils::load_test_certificate_der_with_malformed_extension(
+            "h1_leaf.b64",
+            ExtendedKeyUsage::OID,
+        );
+        let cp = CertificateParser::from_bytes(&der).unwrap();
+        let summary = CertificateSummary::from_certificate_parser(&cp);
+        let extended_key_usage = summary
+            .extensions
+            .iter()
+            .find(|extension| extension.oid == "2.5.29.37")
+            .unwrap();
+        assert!(extended_key_usage.invalid);
+        assert!(extended_key_usage.value.is_none());
+        assert!(extended_key_usage.value_hex.is_some());
+        let basic_constraints = summary
+            .extensions
+            .iter()
+            .find(|extension| extension.oid == "2.5.29.19")
+            .unwrap();
+        assert!(!basic_constraints.invalid);
+        assert!(summary
+            .to_text()
+            .contains("            Extended Key Usage:\n                <invalid>\n"));
+    }
+}
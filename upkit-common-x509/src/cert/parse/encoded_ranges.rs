@@ -0,0 +1,273 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Byte ranges of the parts of an encoded certificate.
+
+use crate::encdec::tlv::Tlv;
+use crate::encdec::DecodingError;
+use rasn::types::ObjectIdentifier;
+use std::ops::Range;
+
+/// `BOOLEAN`
+const TAG_BOOLEAN: u8 = 0x01;
+/// `INTEGER`
+const TAG_INTEGER: u8 = 0x02;
+/// `BIT STRING`
+const TAG_BIT_STRING: u8 = 0x03;
+/// `OCTET STRING`
+const TAG_OCTET_STRING: u8 = 0x04;
+/// `OBJECT IDENTIFIER`
+const TAG_OBJECT_IDENTIFIER: u8 = 0x06;
+/// `SEQUENCE`
+const TAG_SEQUENCE: u8 = 0x30;
+/// `[0] EXPLICIT Version` of the `TBSCertificate`.
+const TAG_VERSION: u8 = 0xa0;
+/// `[3] EXPLICIT Extensions` of the `TBSCertificate`.
+const TAG_EXTENSIONS: u8 = 0xa3;
+
+/// Byte range of an extension value with the extension's OID and criticality.
+#[derive(Debug, Clone)]
+pub(crate) struct EncodedExtension {
+    pub(crate) oid: Vec<u32>,
+    pub(crate) critical: bool,
+    /// The content of the `extnValue` `OCTET STRING`.
+    pub(crate) value: Range<usize>,
+}
+
+/** Byte ranges of the parts of an encoded certificate.
+
+Only the DER structure is walked to find the ranges. Apart from the
+algorithm and extension OIDs, the parts are not decoded.
+*/
+#[derive(Debug, Clone)]
+pub(crate) struct EncodedRanges {
+    pub(crate) certificate: Range<usize>,
+    pub(crate) tbs_certificate: Range<usize>,
+    /// The `Version` `INTEGER` inside the `[0] EXPLICIT` tag if present.
+    pub(crate) version: Option<Range<usize>>,
+    /// The content octets of the `serialNumber` `INTEGER`.
+    pub(crate) serial_number: Range<usize>,
+    pub(crate) issuer: Range<usize>,
+    pub(crate) validity: Range<usize>,
+    pub(crate) subject: Range<usize>,
+    pub(crate) subject_public_key_info: Range<usize>,
+    pub(crate) subject_public_key_algorithm: Vec<u32>,
+    pub(crate) signature_algorithm: Vec<u32>,
+    /// The content of the `signature` `BIT STRING` without the unused bits
+    /// octet.
+    pub(crate) signature: Range<usize>,
+    /// The `Extensions` `SEQUENCE` inside the `[3] EXPLICIT` tag if present.
+    pub(crate) encoded_extensions: Option<Range<usize>>,
+    pub(crate) extensions: Vec<EncodedExtension>,
+}
+
+impl EncodedRanges {
+    /// Record the byte ranges of `encoded_certificate`.
+    pub(crate) fn from_bytes(encoded_certificate: &[u8]) -> Result<Self, DecodingError> {
+        let certificate = Self::expect_tag(
+            Some(Tlv::read(encoded_certificate, 0)?),
+            TAG_SEQUENCE,
+            "Certificate",
+        )?;
+        // tbsCertificate, signatureAlgorithm, signature
+        let mut parts = certificate.children(encoded_certificate)?.into_iter();
+        let tbs_certificate = Self::expect_tag(parts.next(), TAG_SEQUENCE, "TBSCertificate")?;
+        let signature_algorithm =
+            Self::expect_tag(parts.next(), TAG_SEQUENCE, "signatureAlgorithm")?;
+        let signature = Self::expect_tag(parts.next(), TAG_BIT_STRING, "signature")?;
+        if parts.next().is_some() {
+            return Err(DecodingError::with_msg(
+                "Unexpected data after the certificate signature.",
+            ));
+        }
+        if signature.content.is_empty() {
+            return Err(DecodingError::with_msg("Empty signature BIT STRING."));
+        }
+        let fields = tbs_certificate.children(encoded_certificate)?;
+        // version, serialNumber, signature, issuer, validity, subject, subjectPublicKeyInfo
+        let version = fields
+            .first()
+            .filter(|tlv| tlv.tag == TAG_VERSION)
+            .map(|tlv| tlv.content.clone());
+        let skip = usize::from(version.is_some());
+        let field = |index: usize, tag: u8, name: &str| {
+            Self::expect_tag(fields.get(skip + index).cloned(), tag, name)
+        };
+        let serial_number = field(0, TAG_INTEGER, "serialNumber")?;
+        let issuer = field(2, TAG_SEQUENCE, "issuer")?;
+        let validity = field(3, TAG_SEQUENCE, "validity")?;
+        let subject = field(4, TAG_SEQUENCE, "subject")?;
+        let subject_public_key_info = field(5, TAG_SEQUENCE, "subjectPublicKeyInfo")?;
+        let spki_algorithm = subject_public_key_info
+            .children(encoded_certificate)?
+            .into_iter()
+            .next();
+        let spki_algorithm = Self::expect_tag(
+            spki_algorithm,
+            TAG_SEQUENCE,
+            "subjectPublicKeyInfo algorithm",
+        )?;
+        let mut encoded_extensions = None;
+        let mut extensions = vec![];
+        if let Some(explicit) = fields.iter().find(|tlv| tlv.tag == TAG_EXTENSIONS) {
+            let sequence = Self::expect_tag(
+                explicit.children(encoded_certificate)?.into_iter().next(),
+                TAG_SEQUENCE,
+                "Extensions",
+            )?;
+            for extension in sequence.children(encoded_certificate)? {
+                extensions.push(Self::extension(encoded_certificate, &extension)?);
+            }
+            encoded_extensions = Some(sequence.range);
+        }
+        Ok(Self {
+            certificate: certificate.range,
+            tbs_certificate: tbs_certificate.range,
+            version,
+            serial_number: serial_number.content,
+            issuer: issuer.range,
+            validity: validity.range,
+            subject: subject.range,
+            subject_public_key_info: subject_public_key_info.range,
+            subject_public_key_algorithm: Self::algorithm(encoded_certificate, &spki_algorithm)?,
+            signature_algorithm: Self::algorithm(encoded_certificate, &signature_algorithm)?,
+            signature: signature.content.start + 1..signature.content.end,
+            encoded_extensions,
+            extensions,
+        })
+    }
+
+    /// Return the TLV if it has the expected `tag`.
+    fn expect_tag(tlv: Option<Tlv>, tag: u8, name: &str) -> Result<Tlv, DecodingError> {
+        tlv.filter(|tlv| tlv.tag == tag)
+            .ok_or_else(|| DecodingError::with_msg(&format!("Missing or invalid {name}.")))
+    }
+
+    /// Return the decoded OID of an `OBJECT IDENTIFIER` TLV.
+    fn object_identifier(encoded: &[u8], tlv: Option<Tlv>) -> Result<Vec<u32>, DecodingError> {
+        let tlv = Self::expect_tag(tlv, TAG_OBJECT_IDENTIFIER, "OBJECT IDENTIFIER")?;
+        rasn::der::decode::<ObjectIdentifier>(&encoded[tlv.range])
+            .map(|oid| oid.to_vec())
+            .map_err(|e| DecodingError::with_msg(&format!("Invalid OBJECT IDENTIFIER: {e}")))
+    }
+
+    /// Return the `algorithm` OID of an `AlgorithmIdentifier`.
+    fn algorithm(encoded: &[u8], algorithm_identifier: &Tlv) -> Result<Vec<u32>, DecodingError> {
+        Self::object_identifier(
+            encoded,
+            algorithm_identifier.children(encoded)?.into_iter().next(),
+        )
+    }
+
+    fn extension(encoded: &[u8], extension: &Tlv) -> Result<EncodedExtension, DecodingError> {
+        // extnID, critical BOOLEAN DEFAULT FALSE, extnValue
+        let mut parts = Self::expect_tag(Some(extension.clone()), TAG_SEQUENCE, "Extension")?
+            .children(encoded)?
+            .into_iter()
+            .peekable();
+        let oid = Self::object_identifier(encoded, parts.next())?;
+        let critical = parts
+            .next_if(|tlv| tlv.tag == TAG_BOOLEAN)
+            .is_some_and(|tlv| encoded[tlv.content].iter().any(|octet| *octet != 0));
+        let value = Self::expect_tag(parts.next(), TAG_OCTET_STRING, "extnValue")?;
+        Ok(EncodedExtension {
+            oid,
+            critical,
+            value: value.content,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ranges_match_reencoding() {
+        let der = crate::test_utils::load_test_certificate_der("h1_leaf.b64");
+        let certificate = rasn::der::decode::<rasn_pkix::Certificate>(&der).unwrap();
+        let tbs = &certificate.tbs_certificate;
+        let ranges = EncodedRanges::from_bytes(&der).unwrap();
+        assert_eq!(ranges.certificate, 0..der.len());
+        assert_eq!(
+            der[ranges.tbs_certificate].to_vec(),
+            rasn::der::encode(tbs).unwrap()
+        );
+        assert_eq!(
+            der[ranges.version.unwrap()].to_vec(),
+            rasn::der::encode(&tbs.version).unwrap()
+        );
+        assert_eq!(
+            der[ranges.serial_number].to_vec(),
+            crate::encdec::rasn::integer_as_bytes_be(&tbs.serial_number)
+        );
+        assert_eq!(
+            der[ranges.issuer].to_vec(),
+            rasn::der::encode(&tbs.issuer).unwrap()
+        );
+        assert_eq!(
+            der[ranges.validity].to_vec(),
+            rasn::der::encode(&tbs.validity).unwrap()
+        );
+        assert_eq!(
+            der[ranges.subject].to_vec(),
+            rasn::der::encode(&tbs.subject).unwrap()
+        );
+        assert_eq!(
+            der[ranges.subject_public_key_info].to_vec(),
+            rasn::der::encode(&tbs.subject_public_key_info).unwrap()
+        );
+        assert_eq!(
+            ranges.subject_public_key_algorithm,
+            tbs.subject_public_key_info.algorithm.algorithm.to_vec()
+        );
+        assert_eq!(
+            ranges.signature_algorithm,
+            certificate.signature_algorithm.algorithm.to_vec()
+        );
+        assert_eq!(
+            der[ranges.signature],
+            *certificate.signature_value.as_raw_slice()
+        );
+        assert_eq!(
+            der[ranges.encoded_extensions.unwrap()].to_vec(),
+            rasn::der::encode(tbs.extensions.as_ref().unwrap()).unwrap()
+        );
+        let extensions = tbs.extensions.iter().flatten().collect::<Vec<_>>();
+        assert_eq!(ranges.extensions.len(), extensions.len());
+        for (encoded, extension) in ranges.extensions.iter().zip(extensions) {
+            assert_eq!(encoded.oid, extension.extn_id.to_vec());
+            assert_eq!(encoded.critical, extension.critical);
+            assert_eq!(der[encoded.value.clone()], extension.extn_value[..]);
+        }
+    }
+
+    #[test]
+    fn reject_invalid_structure() {
+        let der = crate::test_utils::load_test_certificate_der("h1_leaf.b64");
+        assert!(EncodedRanges::from_bytes(&der[..der.len() - 1]).is_err());
+        // Replace the signature BIT STRING tag with an OCTET STRING tag.
+        let mut der = der;
+        let signature = Tlv::read(&der, 0)
+            .unwrap()
+            .children(&der)
+            .unwrap()
+            .pop()
+            .unwrap();
+        der[signature.range.start] = TAG_OCTET_STRING;
+        assert!(EncodedRanges::from_bytes(&der).is_err());
+    }
+}
@@ -21,10 +21,12 @@ use std::error::Error;
 use std::fmt;
 
 /// Cause of certificate parsing error.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum CertificateParsingErrorKind {
     /// Failure to parse the DER encoded certificate.
     CertificateDecodingError,
+    /// Failure to decode the value of a certificate extension.
+    ExtensionDecodingError,
 }
 
 impl CertificateParsingErrorKind {
@@ -55,7 +57,7 @@ impl fmt::Display for CertificateParsingErrorKind {
 
 Create a new instance via [CertificateParsingErrorKind].
 */
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CertificateParsingError {
     kind: CertificateParsingErrorKind,
     msg: Option<String>,
@@ -0,0 +1,251 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Well-known algorithm identifiers.
+
+use serde::Deserialize;
+use serde::Serialize;
+use strum::EnumIter;
+use strum::IntoEnumIterator;
+
+/** Well-known algorithms that can appear in an `AlgorithmIdentifier`.
+
+This is used to present algorithms in a human readable form and to reason
+about the type of a public key. Actual cryptographic operations are always
+delegated to [TYST](https://github.com/mydriatech/tyst).
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+#[serde(rename_all = "snake_case")]
+pub enum WellKnownAlgorithm {
+    /// RFC 8017 `1.2.840.113549.1.1.1` `rsaEncryption`
+    RsaEncryption,
+    /// RFC 8017 `1.2.840.113549.1.1.5` `sha1WithRSAEncryption`
+    Sha1WithRsaEncryption,
+    /// RFC 8017 `1.2.840.113549.1.1.10` `id-RSASSA-PSS`
+    RsaSsaPss,
+    /// RFC 8017 `1.2.840.113549.1.1.11` `sha256WithRSAEncryption`
+    Sha256WithRsaEncryption,
+    /// RFC 8017 `1.2.840.113549.1.1.12` `sha384WithRSAEncryption`
+    Sha384WithRsaEncryption,
+    /// RFC 8017 `1.2.840.113549.1.1.13` `sha512WithRSAEncryption`
+    Sha512WithRsaEncryption,
+    /// RFC 5480 `1.2.840.10045.2.1` `id-ecPublicKey`
+    EcPublicKey,
+    /// RFC 5758 `1.2.840.10045.4.3.2` `ecdsa-with-SHA256`
+    EcdsaWithSha256,
+    /// RFC 5758 `1.2.840.10045.4.3.3` `ecdsa-with-SHA384`
+    EcdsaWithSha384,
+    /// RFC 5758 `1.2.840.10045.4.3.4` `ecdsa-with-SHA512`
+    EcdsaWithSha512,
+    /// RFC 8410 `1.3.101.110` `id-X25519`
+    X25519,
+    /// RFC 8410 `1.3.101.111` `id-X448`
+    X448,
+    /// RFC 8410 `1.3.101.112` `id-Ed25519`
+    Ed25519,
+    /// RFC 8410 `1.3.101.113` `id-Ed448`
+    Ed448,
+    /// FIPS 204 `2.16.840.1.101.3.4.3.17` `id-ml-dsa-44`
+    MlDsa44,
+    /// FIPS 204 `2.16.840.1.101.3.4.3.18` `id-ml-dsa-65`
+    MlDsa65,
+    /// FIPS 204 `2.16.840.1.101.3.4.3.19` `id-ml-dsa-87`
+    MlDsa87,
+    /// FIPS 205 `2.16.840.1.101.3.4.3.20` `id-slh-dsa-sha2-128s`
+    SlhDsaSha2_128s,
+    /// FIPS 205 `2.16.840.1.101.3.4.3.21` `id-slh-dsa-sha2-128f`
+    SlhDsaSha2_128f,
+    /// FIPS 205 `2.16.840.1.101.3.4.3.22` `id-slh-dsa-sha2-192s`
+    SlhDsaSha2_192s,
+    /// FIPS 205 `2.16.840.1.101.3.4.3.23` `id-slh-dsa-sha2-192f`
+    SlhDsaSha2_192f,
+    /// FIPS 205 `2.16.840.1.101.3.4.3.24` `id-slh-dsa-sha2-256s`
+    SlhDsaSha2_256s,
+    /// FIPS 205 `2.16.840.1.101.3.4.3.25` `id-slh-dsa-sha2-256f`
+    SlhDsaSha2_256f,
+    /// FIPS 203 `2.16.840.1.101.3.4.4.1` `id-alg-ml-kem-512`
+    MlKem512,
+    /// FIPS 203 `2.16.840.1.101.3.4.4.2` `id-alg-ml-kem-768`
+    MlKem768,
+    /// FIPS 203 `2.16.840.1.101.3.4.4.3` `id-alg-ml-kem-1024`
+    MlKem1024,
+    /// RFC 3279 `1.3.14.3.2.26` `id-sha1`
+    Sha1,
+    /// RFC 5754 `2.16.840.1.101.3.4.2.1` `id-sha256`
+    Sha256,
+    /// RFC 5754 `2.16.840.1.101.3.4.2.2` `id-sha384`
+    Sha384,
+    /// RFC 5754 `2.16.840.1.101.3.4.2.3` `id-sha512`
+    Sha512,
+    /// NIST `2.16.840.1.101.3.4.2.8` `id-sha3-256`
+    Sha3_256,
+    /// NIST `2.16.840.1.101.3.4.2.9` `id-sha3-384`
+    Sha3_384,
+    /// NIST `2.16.840.1.101.3.4.2.10` `id-sha3-512`
+    Sha3_512,
+}
+
+impl WellKnownAlgorithm {
+    /// Return the algorithm OID
+    pub fn as_oid(&self) -> &'static [u32] {
+        match self {
+            Self::RsaEncryption => &[1, 2, 840, 113549, 1, 1, 1],
+            Self::Sha1WithRsaEncryption => &[1, 2, 840, 113549, 1, 1, 5],
+            Self::RsaSsaPss => &[1, 2, 840, 113549, 1, 1, 10],
+            Self::Sha256WithRsaEncryption => &[1, 2, 840, 113549, 1, 1, 11],
+            Self::Sha384WithRsaEncryption => &[1, 2, 840, 113549, 1, 1, 12],
+            Self::Sha512WithRsaEncryption => &[1, 2, 840, 113549, 1, 1, 13],
+            Self::EcPublicKey => &[1, 2, 840, 10045, 2, 1],
+            Self::EcdsaWithSha256 => &[1, 2, 840, 10045, 4, 3, 2],
+            Self::EcdsaWithSha384 => &[1, 2, 840, 10045, 4, 3, 3],
+            Self::EcdsaWithSha512 => &[1, 2, 840, 10045, 4, 3, 4],
+            Self::X25519 => &[1, 3, 101, 110],
+            Self::X448 => &[1, 3, 101, 111],
+            Self::Ed25519 => &[1, 3, 101, 112],
+            Self::Ed448 => &[1, 3, 101, 113],
+            Self::MlDsa44 => &[2, 16, 840, 1, 101, 3, 4, 3, 17],
+            Self::MlDsa65 => &[2, 16, 840, 1, 101, 3, 4, 3, 18],
+            Self::MlDsa87 => &[2, 16, 840, 1, 101, 3, 4, 3, 19],
+            Self::SlhDsaSha2_128s => &[2, 16, 840, 1, 101, 3, 4, 3, 20],
+            Self::SlhDsaSha2_128f => &[2, 16, 840, 1, 101, 3, 4, 3, 21],
+            Self::SlhDsaSha2_192s => &[2, 16, 840, 1, 101, 3, 4, 3, 22],
+            Self::SlhDsaSha2_192f => &[2, 16, 840, 1, 101, 3, 4, 3, 23],
+            Self::SlhDsaSha2_256s => &[2, 16, 840, 1, 101, 3, 4, 3, 24],
+            Self::SlhDsaSha2_256f => &[2, 16, 840, 1, 101, 3, 4, 3, 25],
+            Self::MlKem512 => &[2, 16, 840, 1, 101, 3, 4, 4, 1],
+            Self::MlKem768 => &[2, 16, 840, 1, 101, 3, 4, 4, 2],
+            Self::MlKem1024 => &[2, 16, 840, 1, 101, 3, 4, 4, 3],
+            Self::Sha1 => &[1, 3, 14, 3, 2, 26],
+            Self::Sha256 => &[2, 16, 840, 1, 101, 3, 4, 2, 1],
+            Self::Sha384 => &[2, 16, 840, 1, 101, 3, 4, 2, 2],
+            Self::Sha512 => &[2, 16, 840, 1, 101, 3, 4, 2, 3],
+            Self::Sha3_256 => &[2, 16, 840, 1, 101, 3, 4, 2, 8],
+            Self::Sha3_384 => &[2, 16, 840, 1, 101, 3, 4, 2, 9],
+            Self::Sha3_512 => &[2, 16, 840, 1, 101, 3, 4, 2, 10],
+        }
+    }
+
+    /// Return the name commonly used for the algorithm in ASN.1 modules.
+    pub fn as_name(&self) -> &'static str {
+        match self {
+            Self::RsaEncryption => "rsaEncryption",
+            Self::Sha1WithRsaEncryption => "sha1WithRSAEncryption",
+            Self::RsaSsaPss => "rsassaPss",
+            Self::Sha256WithRsaEncryption => "sha256WithRSAEncryption",
+            Self::Sha384WithRsaEncryption => "sha384WithRSAEncryption",
+            Self::Sha512WithRsaEncryption => "sha512WithRSAEncryption",
+            Self::EcPublicKey => "id-ecPublicKey",
+            Self::EcdsaWithSha256 => "ecdsa-with-SHA256",
+            Self::EcdsaWithSha384 => "ecdsa-with-SHA384",
+            Self::EcdsaWithSha512 => "ecdsa-with-SHA512",
+            Self::X25519 => "X25519",
+            Self::X448 => "X448",
+            Self::Ed25519 => "ED25519",
+            Self::Ed448 => "ED448",
+            Self::MlDsa44 => "ML-DSA-44",
+            Self::MlDsa65 => "ML-DSA-65",
+            Self::MlDsa87 => "ML-DSA-87",
+            Self::SlhDsaSha2_128s => "SLH-DSA-SHA2-128s",
+            Self::SlhDsaSha2_128f => "SLH-DSA-SHA2-128f",
+            Self::SlhDsaSha2_192s => "SLH-DSA-SHA2-192s",
+            Self::SlhDsaSha2_192f => "SLH-DSA-SHA2-192f",
+            Self::SlhDsaSha2_256s => "SLH-DSA-SHA2-256s",
+            Self::SlhDsaSha2_256f => "SLH-DSA-SHA2-256f",
+            Self::MlKem512 => "ML-KEM-512",
+            Self::MlKem768 => "ML-KEM-768",
+            Self::MlKem1024 => "ML-KEM-1024",
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+            Self::Sha384 => "SHA-384",
+            Self::Sha512 => "SHA-512",
+            Self::Sha3_256 => "SHA3-256",
+            Self::Sha3_384 => "SHA3-384",
+            Self::Sha3_512 => "SHA3-512",
+        }
+    }
+
+    /// Return the [WellKnownAlgorithm] of the provided OID if known.
+    pub fn from_oid(oid: &[u32]) -> Option<Self> {
+        Self::iter().find(|alg| alg.as_oid().eq(oid))
+    }
+
+    /// Return the [WellKnownAlgorithm] by [human readable name](Self::as_name)
+    /// (case insensitive) if known.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::iter().find(|alg| alg.as_name().eq_ignore_ascii_case(name))
+    }
+
+    /// Return the human readable name of the algorithm or the OID as a
+    /// `String` if the algorithm is not known.
+    pub fn name_from_oid(oid: &[u32]) -> String {
+        Self::from_oid(oid)
+            .map(|alg| alg.as_name().to_string())
+            .unwrap_or_else(|| crate::encdec::oid::as_string(oid))
+    }
+}
+
+/// Well-known named elliptic curves used as `id-ecPublicKey` parameters.
+///
+/// See [RFC5480 2.1.1.1](https://www.rfc-editor.org/rfc/rfc5480#section-2.1.1.1).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+#[serde(rename_all = "snake_case")]
+pub enum WellKnownNamedCurve {
+    /// `1.2.840.10045.3.1.7` `secp256r1` a.k.a. NIST P-256
+    Secp256r1,
+    /// `1.3.132.0.34` `secp384r1` a.k.a. NIST P-384
+    Secp384r1,
+    /// `1.3.132.0.35` `secp521r1` a.k.a. NIST P-521
+    Secp521r1,
+    /// `1.3.132.0.10` `secp256k1`
+    Secp256k1,
+}
+
+impl WellKnownNamedCurve {
+    /// Return the curve OID
+    pub fn as_oid(&self) -> &'static [u32] {
+        match self {
+            Self::Secp256r1 => &[1, 2, 840, 10045, 3, 1, 7],
+            Self::Secp384r1 => &[1, 3, 132, 0, 34],
+            Self::Secp521r1 => &[1, 3, 132, 0, 35],
+            Self::Secp256k1 => &[1, 3, 132, 0, 10],
+        }
+    }
+
+    /// Return the name of the curve.
+    pub fn as_name(&self) -> &'static str {
+        match self {
+            Self::Secp256r1 => "P-256",
+            Self::Secp384r1 => "P-384",
+            Self::Secp521r1 => "P-521",
+            Self::Secp256k1 => "secp256k1",
+        }
+    }
+
+    /// Return the size of the curve's field in bits.
+    pub fn key_size(&self) -> usize {
+        match self {
+            Self::Secp256r1 | Self::Secp256k1 => 256,
+            Self::Secp384r1 => 384,
+            Self::Secp521r1 => 521,
+        }
+    }
+
+    /// Return the [WellKnownNamedCurve] of the provided OID if known.
+    pub fn from_oid(oid: &[u32]) -> Option<Self> {
+        Self::iter().find(|curve| curve.as_oid().eq(oid))
+    }
+}
@@ -26,6 +26,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use serde_with::serde_as;
 use serde_with::skip_serializing_none;
+use tyst::encdec::hex::ToHex;
 
 use super::IdentityFragment;
 use super::IdentityFragmentError;
@@ -179,6 +180,73 @@ impl DistinguishedName {
     pub fn to_der(&self) -> Vec<u8> {
         rasn::der::encode(&Self::to_rasn_type(&self.dn).unwrap()).unwrap()
     }
+
+    /// Return the [RFC4514](https://www.rfc-editor.org/rfc/rfc4514) string
+    /// representation (e.g. `CN=An entity,O=Org,C=SE`).
+    pub fn to_rfc4514_string(&self) -> String {
+        Self::rfc4514_from_rasn_type(&self.as_rasn_type().unwrap())
+    }
+
+    /** Return the [RFC4514](https://www.rfc-editor.org/rfc/rfc4514) string
+    representation of a [rasn_pkix::Name].
+
+    Unlike [Self::from_rasn_type], this will never fail on unknown attributes.
+    These are represented with a dotted OID and the hex encoded DER value as
+    described in RFC4514 2.4.
+    */
+    pub fn rfc4514_from_rasn_type(name: &rasn_pkix::Name) -> String {
+        match name {
+            rasn_pkix::Name::RdnSequence(rdns) => rdns
+                .iter()
+                // RFC4514 2.1: Output starts with the last RDN of the sequence
+                .rev()
+                .map(|rdn| {
+                    rdn.to_vec()
+                        .into_iter()
+                        .map(Self::rfc4514_attribute_type_and_value)
+                        .collect::<Vec<_>>()
+                        .join("+")
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+        }
+    }
+
+    fn rfc4514_attribute_type_and_value(atav: &rasn_pkix::AttributeTypeAndValue) -> String {
+        let oid = atav.r#type.to_vec();
+        let value = crate::encdec::rasn::any_as_string(&atav.value);
+        match (WellKnownAttribute::by_oid(&oid), value) {
+            (Ok(attribute), Some(value)) => format!(
+                "{}={}",
+                attribute.rfc4514_name(),
+                Self::rfc4514_escape(&value)
+            ),
+            _ => format!(
+                "{}=#{}",
+                crate::encdec::oid::as_string(&oid),
+                atav.value.as_bytes().to_vec().to_hex()
+            ),
+        }
+    }
+
+    /// Escape an attribute value according to [RFC4514 2.4](https://www.rfc-editor.org/rfc/rfc4514#section-2.4).
+    fn rfc4514_escape(value: &str) -> String {
+        let char_count = value.chars().count();
+        let mut ret = String::with_capacity(value.len());
+        for (i, c) in value.chars().enumerate() {
+            match c {
+                '"' | '+' | ',' | ';' | '<' | '>' | '\\' => {
+                    ret.push('\\');
+                    ret.push(c);
+                }
+                '#' if i == 0 => ret.push_str("\\#"),
+                ' ' if i == 0 || i + 1 == char_count => ret.push_str("\\ "),
+                '\0' => ret.push_str("\\00"),
+                _ => ret.push(c),
+            }
+        }
+        ret
+    }
 }
 
 #[cfg(test)]
@@ -195,4 +263,15 @@ mod test {
         .as_rasn_type()
         .unwrap();
     }
+
+    #[test]
+    fn check_rfc4514_string() {
+        let dn = DistinguishedName::new(vec![
+            vec![WellKnownAttribute::CountryName.with_value("SE")],
+            vec![WellKnownAttribute::OrganizationName.with_value("Org, Inc.")],
+            vec![WellKnownAttribute::CommonName.with_value(" An entity")],
+        ])
+        .unwrap();
+        assert_eq!(dn.to_rfc4514_string(), "CN=\\ An entity,O=Org\\, Inc.,C=SE");
+    }
 }
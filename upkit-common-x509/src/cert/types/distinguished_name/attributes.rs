@@ -354,9 +354,7 @@ impl WellKnownAttribute {
 
     /// Convert from a recognizable label
     pub fn by_oid(oid: &[u32]) -> Result<Self, IdentityFragmentError> {
-        let name = std::ops::Deref::deref(&INSTANCE_ATTRIBUTE_METADATA)
-            .by_oid(oid)
-            .unwrap();
+        let name = std::ops::Deref::deref(&INSTANCE_ATTRIBUTE_METADATA).by_oid(oid)?;
         Self::by_name(&name)
     }
 
@@ -365,6 +363,33 @@ impl WellKnownAttribute {
         IdentityFragment::new_unchecked(&self.as_name(), value)
     }
 
+    /// Return the attribute type descriptor used in string representations.
+    ///
+    /// [RFC4514 3](https://www.rfc-editor.org/rfc/rfc4514#section-3) lists the
+    /// short names that all implementations recognize. Remaining attributes
+    /// use their commonly recognized LDAP or OpenSSL descriptor.
+    pub fn rfc4514_name(&self) -> &'static str {
+        match self {
+            Self::SerialNumber => "serialNumber",
+            Self::DomainComponent => "DC",
+            Self::CountryName => "C",
+            Self::StateOrProvinceName => "ST",
+            Self::LocalityName => "L",
+            Self::PostalCode => "postalCode",
+            Self::StreetAddress => "STREET",
+            Self::OrganizationName => "O",
+            Self::Surname => "SN",
+            Self::GivenName => "GN",
+            Self::OrganizationalUnitName => "OU",
+            Self::CommonName => "CN",
+            Self::BusinessCategory => "businessCategory",
+            Self::JurisdictionCountry => "jurisdictionC",
+            Self::JurisdictionStateOrProvince => "jurisdictionST",
+            Self::JurisdictionLocality => "jurisdictionL",
+            Self::OrganizationIdentifier => "organizationIdentifier",
+        }
+    }
+
     /// Return [AttributeTypeAndValueInfo] for a [WellKnownAttribute].
     pub fn meta_data_by_name(
         name: &str,
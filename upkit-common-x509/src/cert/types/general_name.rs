@@ -108,29 +108,29 @@ impl WellKnownGeneralName {
                     _unknown_oid => None,
                 }
             }
-            GeneralName::Rfc822Name(rfc822_name) => Some(Self::to_rfc822_name(rfc822_name)),
+            GeneralName::Rfc822Name(rfc822_name) => Self::to_rfc822_name(rfc822_name),
             GeneralName::DnsName(dns_name) => Some(Self::to_dns_name(dns_name)),
             GeneralName::X400Address(_) => None,
             // TODO
-            GeneralName::DirectoryName(_distinguished_name) => todo!(),
+            GeneralName::DirectoryName(_distinguished_name) => None,
             GeneralName::EdiPartyName(_) => None,
             GeneralName::Uri(uri) => Some(Self::to_uri(uri)),
-            GeneralName::IpAddress(ip_address) => Some(Self::to_ip_address(ip_address)),
+            GeneralName::IpAddress(ip_address) => Self::to_ip_address(ip_address),
             GeneralName::RegisteredId(oid) => Some(Self::to_registrered_id(oid)),
         }
     }
 
-    fn to_rfc822_name(rfc822_name: &Ia5String) -> (Self, String) {
+    fn to_rfc822_name(rfc822_name: &Ia5String) -> Option<(Self, String)> {
         let rfc822_name = rfc822_name.to_string();
         let parts = rfc822_name.split('@').collect::<Vec<_>>();
         if parts.len() != 2 {
-            panic!()
+            return None;
         }
-        let local = parts.first().unwrap();
-        let domain = parts.get(1).unwrap();
+        let local = parts.first()?;
+        let domain = parts.get(1)?;
         let domain_utf8 = crate::encdec::puny_code::decode(domain);
         let rfc822_name = format!("{local}@{domain_utf8}");
-        (WellKnownGeneralName::Rfc822Name, rfc822_name)
+        Some((WellKnownGeneralName::Rfc822Name, rfc822_name))
     }
 
     fn to_dns_name(dns_name: &Ia5String) -> (Self, String) {
@@ -142,8 +142,8 @@ impl WellKnownGeneralName {
         (WellKnownGeneralName::Uri, uri.to_string())
     }
 
-    fn to_ip_address(ip_address: &OctetString) -> (Self, String) {
-        (
+    fn to_ip_address(ip_address: &OctetString) -> Option<(Self, String)> {
+        Some((
             WellKnownGeneralName::IpAddress,
             match ip_address.len() {
                 4 => {
@@ -156,9 +156,9 @@ impl WellKnownGeneralName {
                     bytes.copy_from_slice(&ip_address[0..16]);
                     IpAddr::from(bytes).to_string()
                 }
-                _ => panic!(),
+                _ => return None,
             },
-        )
+        ))
     }
 
     fn to_registrered_id(oid: &ObjectIdentifier) -> (Self, String) {
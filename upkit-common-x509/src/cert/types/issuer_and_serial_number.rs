@@ -0,0 +1,66 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Issuer and serial number lookup key.
+
+use super::SerialNumber;
+use crate::cert::parse::CertificateParser;
+
+/** Key that uniquely identifies a certificate.
+
+[RFC5280 4.1.2.2](https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.2):
+"the issuer name and serial number identify a unique certificate".
+
+The issuer is kept as the DER encoded distinguished name, so the key can be
+compared with the issuer of CRLs, OCSP responses and CMS
+`IssuerAndSerialNumber` without decoding. Ordering is by issuer and then by
+the numeric serial number, so the key can be used in ordered maps.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IssuerAndSerialNumber {
+    encoded_issuer: Vec<u8>,
+    serial_number: SerialNumber,
+}
+
+impl IssuerAndSerialNumber {
+    /// Return a new instance from the DER encoded issuer distinguished name
+    /// and the serial number.
+    pub fn new(encoded_issuer: &[u8], serial_number: SerialNumber) -> Self {
+        Self {
+            encoded_issuer: encoded_issuer.to_vec(),
+            serial_number,
+        }
+    }
+
+    /// Return the key of the certificate.
+    pub fn from_certificate(certificate: &CertificateParser) -> Self {
+        Self::new(
+            certificate.as_encoded_issuer(),
+            certificate.get_serial_number(),
+        )
+    }
+
+    /// Return the DER encoded issuer distinguished name.
+    pub fn get_encoded_issuer(&self) -> &[u8] {
+        &self.encoded_issuer
+    }
+
+    /// Return the serial number.
+    pub fn get_serial_number(&self) -> &SerialNumber {
+        &self.serial_number
+    }
+}
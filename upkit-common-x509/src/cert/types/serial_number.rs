@@ -17,11 +17,22 @@
 
 //! Certificate Serial Number.
 
+mod serial_number_strategy;
+
+pub use self::serial_number_strategy::RandomSerialNumberStrategy;
+pub use self::serial_number_strategy::SerialNumberStrategy;
+pub use self::serial_number_strategy::StructuredSerialNumberStrategy;
+
+use crate::encdec::DecodingError;
 use num_bigint::BigInt;
+use num_bigint::Sign;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_with::serde_as;
 use serde_with::skip_serializing_none;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
 use tyst::Tyst;
 
 /** Certificate Serial Number
@@ -45,16 +56,41 @@ Due to the fact that we already have the uniqness requirement for the
 certificate serial number per "issuer name" to mitigate the risk of serial
 number collision, using a large highly random number also reduces the need
 to syncronize distributed issuance with the performance impact it implies.
+
+The serial number is kept as the minimal big-endian two's complement
+encoding (the DER content octets), also when deserialized, so equality,
+hashing and ordering compare the numeric value. Zero and negative serial numbers are never generated, but
+are tolerated when parsing foreign certificates as recommended by RFC5280.
+
+[Display](fmt::Display) and [FromStr] use upper case hexadecimal notation
+with a `-` prefix for negative numbers. Use
+[to_decimal_string](Self::to_decimal_string) and
+[from_decimal_str](Self::from_decimal_str) for decimal notation.
 */
 #[serde_as]
 #[skip_serializing_none]
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case", from = "EncodedSerialNumber")]
 pub struct SerialNumber {
     #[serde_as(as = "serde_with::hex::Hex")]
     serial_number_hex: Vec<u8>,
 }
 
+/// Deserialized form of [SerialNumber] that might not be minimally encoded.
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+struct EncodedSerialNumber {
+    #[serde_as(as = "serde_with::hex::Hex")]
+    serial_number_hex: Vec<u8>,
+}
+
+impl From<EncodedSerialNumber> for SerialNumber {
+    fn from(value: EncodedSerialNumber) -> Self {
+        Self::from_signed_bytes_be(&value.serial_number_hex)
+    }
+}
+
 impl Default for SerialNumber {
     fn default() -> Self {
         Self::generate(None)
@@ -80,23 +116,188 @@ impl SerialNumber {
                 break;
             }
         }
-        Self {
-            serial_number_hex: rnd,
-        }
+        Self::from_bytes_be(&rnd)
+    }
+
+    /// Return a new instance from the unsigned big-endian `magnitude`.
+    pub fn from_bytes_be(magnitude: &[u8]) -> Self {
+        Self::from_bigint(&BigInt::from_bytes_be(Sign::Plus, magnitude))
+    }
+
+    /// Return a new instance from big-endian two's complement `octets` like
+    /// the content octets of a DER encoded INTEGER.
+    pub fn from_signed_bytes_be(octets: &[u8]) -> Self {
+        Self::from_bigint(&BigInt::from_signed_bytes_be(octets))
+    }
+
+    /// Return the minimal big-endian two's complement encoding (the content
+    /// octets of the DER encoded INTEGER).
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.serial_number_hex
+    }
+
+    /// Return `true` if the serial number is greater than zero as required
+    /// for conforming certificates.
+    pub fn is_positive(&self) -> bool {
+        self.to_bigint().sign() == Sign::Plus
+    }
+
+    /// Return `true` if the serial number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.to_bigint().sign() == Sign::NoSign
+    }
+
+    /// Return `true` if the serial number is negative.
+    pub fn is_negative(&self) -> bool {
+        self.to_bigint().sign() == Sign::Minus
+    }
+
+    /// Return the serial number in decimal notation.
+    pub fn to_decimal_string(&self) -> String {
+        self.to_bigint().to_str_radix(10)
+    }
+
+    /// Parse a serial number in decimal notation with an optional `-` prefix.
+    pub fn from_decimal_str(value: &str) -> Result<Self, DecodingError> {
+        Self::parse_radix(value, 10)
     }
 
     /// Return value in a form that is easy to process by the ASN.1 library `rasn`.
     pub fn to_rasn_type(&self) -> rasn::types::Integer {
-        rasn::types::Integer::from(BigInt::from_bytes_be(
-            num_bigint::Sign::Plus,
-            &self.serial_number_hex,
-        ))
+        rasn::types::Integer::from(self.to_bigint())
     }
 
     /// Return a new instance from the `rasn` ASN.1 library type.
     pub fn from_rasn_type(serial_number: &rasn::types::Integer) -> Self {
+        Self::from_signed_bytes_be(&crate::encdec::rasn::integer_as_bytes_be(serial_number))
+    }
+
+    fn from_bigint(value: &BigInt) -> Self {
         Self {
-            serial_number_hex: crate::encdec::rasn::integer_as_bytes_be(serial_number),
+            serial_number_hex: value.to_signed_bytes_be(),
         }
     }
+
+    fn to_bigint(&self) -> BigInt {
+        BigInt::from_signed_bytes_be(&self.serial_number_hex)
+    }
+
+    fn parse_radix(value: &str, radix: u32) -> Result<Self, DecodingError> {
+        let (negative, digits) = match value.trim().strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, value.trim()),
+        };
+        let digits = if radix == 16 {
+            let digits = digits
+                .strip_prefix("0x")
+                .or_else(|| digits.strip_prefix("0X"))
+                .unwrap_or(digits);
+            digits.replace(':', "")
+        } else {
+            digits.to_string()
+        };
+        let magnitude = BigInt::parse_bytes(digits.as_bytes(), radix)
+            .filter(|_| !digits.starts_with(['+', '-']))
+            .ok_or_else(|| DecodingError::with_msg(&format!("Invalid serial number '{value}'.")))?;
+        Ok(Self::from_bigint(&if negative {
+            -magnitude
+        } else {
+            magnitude
+        }))
+    }
+}
+
+impl Ord for SerialNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_bigint().cmp(&other.to_bigint())
+    }
+}
+
+impl PartialOrd for SerialNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for SerialNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self.to_bigint();
+        let sign = if value.sign() == Sign::Minus { "-" } else { "" };
+        let mut hex = value.magnitude().to_str_radix(16).to_uppercase();
+        if hex.len() % 2 == 1 {
+            hex.insert(0, '0');
+        }
+        write!(f, "{sign}{hex}")
+    }
+}
+
+impl FromStr for SerialNumber {
+    type Err = DecodingError;
+
+    /// Parse a serial number in hexadecimal notation with an optional `0x`
+    /// and `-` prefix. Colon separated octets are accepted.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse_radix(value, 16)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalized_parsing_and_formatting() {
+        let serial_number = SerialNumber::from_signed_bytes_be(&[0x00, 0x00, 0x01, 0xab]);
+        assert_eq!(serial_number.as_bytes(), &[0x01, 0xab]);
+        assert_eq!(serial_number.to_string(), "01AB");
+        assert_eq!(serial_number.to_decimal_string(), "427");
+        assert_eq!(SerialNumber::from_str("0x01ab").unwrap(), serial_number);
+        assert_eq!(SerialNumber::from_str("01:AB").unwrap(), serial_number);
+        assert_eq!(
+            SerialNumber::from_decimal_str("427").unwrap(),
+            serial_number
+        );
+        assert_eq!(
+            SerialNumber::from_bytes_be(&[0x80]).as_bytes(),
+            &[0x00, 0x80]
+        );
+        let negative = SerialNumber::from_signed_bytes_be(&[0xff]);
+        assert!(negative.is_negative());
+        assert_eq!(negative.to_string(), "-01");
+        assert!(SerialNumber::from_bytes_be(&[0x00]).is_zero());
+        assert!(negative < SerialNumber::from_bytes_be(&[0x00]));
+        assert!(SerialNumber::from_str("xyz").is_err());
+        let generated = SerialNumber::generate(None);
+        assert!(generated.is_positive());
+        assert_eq!(
+            SerialNumber::from_rasn_type(&generated.to_rasn_type()),
+            generated
+        );
+    }
+
+    #[test]
+    fn numeric_equality_and_hashing() {
+        use std::collections::HashSet;
+
+        let serial_number = SerialNumber::from_bytes_be(&[0x01, 0xab]);
+        let deserialized: SerialNumber =
+            serde_json::from_str(r#"{"serial_number_hex":"000001ab"}"#).unwrap();
+        assert_eq!(deserialized, serial_number);
+        assert_eq!(deserialized.as_bytes(), &[0x01, 0xab]);
+        assert_eq!(
+            SerialNumber::from_signed_bytes_be(&[0x00, 0x00, 0x01, 0xab]),
+            serial_number
+        );
+        let serial_numbers = HashSet::from([serial_number.clone(), deserialized]);
+        assert_eq!(serial_numbers.len(), 1);
+        assert_eq!(
+            serde_json::to_string(&serial_number).unwrap(),
+            r#"{"serial_number_hex":"01ab"}"#
+        );
+        // A leading 0x00 is needed to keep 0x80 positive
+        let positive: SerialNumber =
+            serde_json::from_str(r#"{"serial_number_hex":"0080"}"#).unwrap();
+        assert!(positive.is_positive());
+        assert_ne!(positive, SerialNumber::from_signed_bytes_be(&[0x80]));
+    }
 }
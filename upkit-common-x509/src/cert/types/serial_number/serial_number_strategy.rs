@@ -0,0 +1,143 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Pluggable serial number generation.
+
+use super::SerialNumber;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use tyst::Tyst;
+
+/// Strategy for generating unique certificate serial numbers.
+pub trait SerialNumberStrategy: Send + Sync {
+    /// Return a new positive serial number.
+    fn generate(&self) -> SerialNumber;
+}
+
+/// Random serial numbers. See [SerialNumber::generate].
+#[derive(Debug, Clone, Default)]
+pub struct RandomSerialNumberStrategy {
+    octets: Option<usize>,
+}
+
+impl RandomSerialNumberStrategy {
+    /// Return a new instance generating serial numbers of `octets` length.
+    pub fn new(octets: usize) -> Self {
+        Self {
+            octets: Some(octets),
+        }
+    }
+}
+
+impl SerialNumberStrategy for RandomSerialNumberStrategy {
+    fn generate(&self) -> SerialNumber {
+        SerialNumber::generate(self.octets)
+    }
+}
+
+/** Structured serial numbers for distributed issuance without coordination.
+
+Each serial number is 19 octets:
+
+```text
+[0]      0x01 (layout version, keeps the number positive)
+[1..3]   shard (issuer node) identifier, big-endian u16
+[3..11]  sequence number, big-endian u64
+[11..19] CSPRNG output (64 bits)
+```
+
+Serial numbers from different shards can never collide and each shard
+never repeats a sequence number. The sequence starts at the current time in
+microseconds since 1970-01-01 00:00:00 unless configured, so a restarted
+node continues above previously used values as long as its clock doesn't go
+backwards.
+
+The 64 bits of CSPRNG output fulfill the CA/Browser Forum Baseline
+Requirements.
+*/
+#[derive(Debug)]
+pub struct StructuredSerialNumberStrategy {
+    shard_id: u16,
+    sequence: AtomicU64,
+}
+
+impl StructuredSerialNumberStrategy {
+    const LAYOUT_VERSION: u8 = 0x01;
+    const OCTETS: usize = 19;
+
+    /// Return a new instance for the node identified by `shard_id`.
+    pub fn new(shard_id: u16) -> Self {
+        let now_micros = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| u64::try_from(duration.as_micros()).unwrap_or(u64::MAX))
+            .unwrap_or_default();
+        Self::with_sequence_start(shard_id, now_micros)
+    }
+
+    /// Return a new instance with an explicit (e.g. persisted) first
+    /// sequence number.
+    pub fn with_sequence_start(shard_id: u16, sequence_start: u64) -> Self {
+        Self {
+            shard_id,
+            sequence: AtomicU64::new(sequence_start),
+        }
+    }
+
+    /// Return the shard identifier and sequence number of a serial number
+    /// generated with this layout.
+    pub fn parse(serial_number: &SerialNumber) -> Option<(u16, u64)> {
+        let octets = serial_number.as_bytes();
+        if octets.len() != Self::OCTETS || octets[0] != Self::LAYOUT_VERSION {
+            return None;
+        }
+        let shard_id = u16::from_be_bytes(octets[1..3].try_into().unwrap());
+        let sequence = u64::from_be_bytes(octets[3..11].try_into().unwrap());
+        Some((shard_id, sequence))
+    }
+}
+
+impl SerialNumberStrategy for StructuredSerialNumberStrategy {
+    fn generate(&self) -> SerialNumber {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        let mut rnd = [0u8; 8];
+        Tyst::instance().prng_fill_with_random(None, &mut rnd);
+        let mut octets = Vec::with_capacity(Self::OCTETS);
+        octets.push(Self::LAYOUT_VERSION);
+        octets.extend_from_slice(&self.shard_id.to_be_bytes());
+        octets.extend_from_slice(&sequence.to_be_bytes());
+        octets.extend_from_slice(&rnd);
+        SerialNumber::from_bytes_be(&octets)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn structured_serial_numbers_are_unique_per_shard() {
+        let strategy = StructuredSerialNumberStrategy::with_sequence_start(7, 42);
+        let first = strategy.generate();
+        let second = strategy.generate();
+        assert!(first.is_positive());
+        assert_eq!(StructuredSerialNumberStrategy::parse(&first), Some((7, 42)));
+        assert_eq!(
+            StructuredSerialNumberStrategy::parse(&second),
+            Some((7, 43))
+        );
+    }
+}
@@ -17,7 +17,9 @@
 
 //! Certificate Validity.
 
-use chrono::TimeZone;
+use crate::encdec::DecodingError;
+use chrono::Datelike;
+use chrono::Timelike;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_with::serde_as;
@@ -41,6 +43,9 @@ MUST be encoded as GeneralizedTime."
 
 All times are in Unix Epoch seconds (seconds since 1970-01-01 00:00:00) and
 encoded as `u64`.
+
+Certificates without a well-defined expiration date use `notAfter`
+`99991231235959Z` ([NO_WELL_DEFINED_EXPIRATION](Self::NO_WELL_DEFINED_EXPIRATION)).
 */
 #[serde_as]
 #[skip_serializing_none]
@@ -52,6 +57,14 @@ pub struct Validity {
 }
 
 impl Validity {
+    /// `99991231235959Z` in Unix Epoch seconds.
+    ///
+    /// See [RFC5280 4.1.2.5](https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.5).
+    pub const NO_WELL_DEFINED_EXPIRATION: u64 = 253_402_300_799;
+
+    /// First year that MUST be encoded as GeneralizedTime.
+    const FIRST_GENERALIZED_TIME_YEAR: i32 = 2050;
+
     /// Return a new instance
     pub fn new(not_before: u64, not_after: u64) -> Self {
         if not_after < not_before {
@@ -74,11 +87,26 @@ impl Validity {
         Self::new(not_before, not_after)
     }
 
+    /// Return a new instance without a well-defined expiration date.
+    pub fn with_no_well_defined_expiration(not_before: u64) -> Self {
+        Self::new(not_before, Self::NO_WELL_DEFINED_EXPIRATION)
+    }
+
+    /// Return `true` if `not_after` is `99991231235959Z`.
+    pub fn has_no_well_defined_expiration(&self) -> bool {
+        self.not_after_epoch_seconds == Self::NO_WELL_DEFINED_EXPIRATION
+    }
+
     /// Return "now" as number of seconds since 1970-01-01 00:00:00.
     pub fn now_epoch_seconds() -> u64 {
         u64::try_from(chrono::Utc::now().timestamp()).unwrap()
     }
 
+    /// Return `not_before` as number of seconds since 1970-01-01 00:00:00.
+    pub fn get_not_before(&self) -> u64 {
+        self.not_before_epoch_seconds
+    }
+
     /// Return `not_after` as number of seconds since 1970-01-01 00:00:00.
     pub fn get_not_after(&self) -> u64 {
         self.not_after_epoch_seconds
@@ -103,7 +131,11 @@ impl Validity {
         }
     }
 
-    /// Return a new instance from the `rasn` ASN.1 library type.
+    /** Return a new instance from the `rasn` ASN.1 library type.
+
+    Times that can't be represented are clamped. Use
+    [try_from_rasn_type](Self::try_from_rasn_type) to reject these.
+    */
     pub fn from_rasn_type(validity: &rasn_pkix::Validity) -> Self {
         Self {
             not_before_epoch_seconds: Self::from_rasn_epoch_seconds(&validity.not_before),
@@ -111,22 +143,151 @@ impl Validity {
         }
     }
 
-    /// Return value in a form that is easy to process by the ASN.1 library `rasn`.
-    fn to_rasn_epoch_seconds(epoch_seconds: u64) -> rasn_pkix::Time {
-        let date_time_utc = chrono::Utc
-            .timestamp_micros(i64::try_from(epoch_seconds * 1_000_000).unwrap())
-            .unwrap();
-        // Certificate validity dates in 2050 or later MUST be encoded as GeneralizedTime
-        //rasn_pkix::Time::Utc(date_time_utc)
-        rasn_pkix::Time::General(date_time_utc.fixed_offset())
+    /// Return a new instance from the `rasn` ASN.1 library type or an error
+    /// if any of the times is invalid.
+    pub fn try_from_rasn_type(validity: &rasn_pkix::Validity) -> Result<Self, DecodingError> {
+        Ok(Self {
+            not_before_epoch_seconds: Self::try_from_rasn_epoch_seconds(&validity.not_before)?,
+            not_after_epoch_seconds: Self::try_from_rasn_epoch_seconds(&validity.not_after)?,
+        })
+    }
+
+    /** Return value in a form that is easy to process by the ASN.1 library `rasn`.
+
+    Times through the year 2049 are encoded as UTCTime and later times as
+    GeneralizedTime. Times after `99991231235959Z` are clamped since these
+    can't be encoded.
+    */
+    pub(crate) fn to_rasn_epoch_seconds(epoch_seconds: u64) -> rasn_pkix::Time {
+        let epoch_seconds = epoch_seconds.min(Self::NO_WELL_DEFINED_EXPIRATION);
+        // Whole seconds only, so no fractions are encoded
+        let date_time_utc =
+            chrono::DateTime::from_timestamp(i64::try_from(epoch_seconds).unwrap(), 0).unwrap();
+        if date_time_utc.year() < Self::FIRST_GENERALIZED_TIME_YEAR {
+            rasn_pkix::Time::Utc(date_time_utc)
+        } else {
+            rasn_pkix::Time::General(date_time_utc.fixed_offset())
+        }
     }
 
     /// Return a new instance from the `rasn` ASN.1 library type.
-    fn from_rasn_epoch_seconds(time: &rasn_pkix::Time) -> u64 {
+    ///
+    /// Times before 1970-01-01 00:00:00 are clamped to `0`.
+    pub(crate) fn from_rasn_epoch_seconds(time: &rasn_pkix::Time) -> u64 {
         u64::try_from(match time {
             rasn_pkix::Time::Utc(datetime_utc) => datetime_utc.timestamp(),
             rasn_pkix::Time::General(datetime_fixed_offset) => datetime_fixed_offset.timestamp(),
         })
-        .unwrap()
+        .unwrap_or_default()
+    }
+
+    /** Return a new instance from the `rasn` ASN.1 library type.
+
+    Times through the year 2049 must be UTCTime and later times must be
+    GeneralizedTime expressed in Zulu (UTC) without fractional seconds. Times
+    before 1970-01-01 00:00:00 can't be represented and are rejected.
+    */
+    pub(crate) fn try_from_rasn_epoch_seconds(
+        time: &rasn_pkix::Time,
+    ) -> Result<u64, DecodingError> {
+        let timestamp = match time {
+            rasn_pkix::Time::Utc(datetime_utc) => {
+                if datetime_utc.year() >= Self::FIRST_GENERALIZED_TIME_YEAR {
+                    return Err(DecodingError::with_msg(&format!(
+                        "UTCTime '{datetime_utc}' must be encoded as GeneralizedTime."
+                    )));
+                }
+                datetime_utc.timestamp()
+            }
+            rasn_pkix::Time::General(datetime_fixed_offset) => {
+                if datetime_fixed_offset.year() < Self::FIRST_GENERALIZED_TIME_YEAR {
+                    return Err(DecodingError::with_msg(&format!(
+                        "GeneralizedTime '{datetime_fixed_offset}' must be encoded as UTCTime."
+                    )));
+                }
+                if datetime_fixed_offset.offset().local_minus_utc() != 0 {
+                    return Err(DecodingError::with_msg(&format!(
+                        "GeneralizedTime '{datetime_fixed_offset}' is not expressed in Zulu."
+                    )));
+                }
+                if datetime_fixed_offset.nanosecond() != 0 {
+                    return Err(DecodingError::with_msg(&format!(
+                        "GeneralizedTime '{datetime_fixed_offset}' has fractional seconds."
+                    )));
+                }
+                datetime_fixed_offset.timestamp()
+            }
+        };
+        u64::try_from(timestamp).map_err(|_| {
+            DecodingError::with_msg(&format!("Time {timestamp} is before 1970-01-01 00:00:00."))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc5280_time_encoding() {
+        // 2049-12-31 23:59:59
+        let last_utc_time = 2_524_607_999;
+        assert!(matches!(
+            Validity::to_rasn_epoch_seconds(last_utc_time),
+            rasn_pkix::Time::Utc(_)
+        ));
+        assert!(matches!(
+            Validity::to_rasn_epoch_seconds(last_utc_time + 1),
+            rasn_pkix::Time::General(_)
+        ));
+        let encoded = rasn::der::encode(&Validity::to_rasn_epoch_seconds(u64::MAX)).unwrap();
+        assert_eq!(&encoded[2..], b"99991231235959Z");
+        let validity = Validity::with_no_well_defined_expiration(0);
+        let decoded = Validity::try_from_rasn_type(&validity.to_rasn_type()).unwrap();
+        assert!(decoded.has_no_well_defined_expiration());
+        assert_eq!(decoded.get_not_before(), 0);
+    }
+
+    #[test]
+    fn reject_pre_epoch_and_fractions() {
+        let pre_epoch = chrono::DateTime::from_timestamp(-1, 0).unwrap();
+        assert!(Validity::try_from_rasn_epoch_seconds(&rasn_pkix::Time::Utc(pre_epoch)).is_err());
+        assert_eq!(
+            Validity::from_rasn_epoch_seconds(&rasn_pkix::Time::Utc(pre_epoch)),
+            0
+        );
+        let fractional = chrono::DateTime::from_timestamp(2_524_608_000, 500).unwrap();
+        assert!(
+            Validity::try_from_rasn_epoch_seconds(&rasn_pkix::Time::General(
+                fractional.fixed_offset()
+            ))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn reject_wrong_time_type_for_year() {
+        // 2049-12-31 23:59:59 and 2050-01-01 00:00:00
+        let last_utc_time = chrono::DateTime::from_timestamp(2_524_607_999, 0).unwrap();
+        let first_generalized_time = chrono::DateTime::from_timestamp(2_524_608_000, 0).unwrap();
+        assert!(
+            Validity::try_from_rasn_epoch_seconds(&rasn_pkix::Time::Utc(last_utc_time)).is_ok()
+        );
+        assert!(
+            Validity::try_from_rasn_epoch_seconds(&rasn_pkix::Time::General(
+                last_utc_time.fixed_offset()
+            ))
+            .is_err()
+        );
+        assert!(
+            Validity::try_from_rasn_epoch_seconds(&rasn_pkix::Time::General(
+                first_generalized_time.fixed_offset()
+            ))
+            .is_ok()
+        );
+        assert!(Validity::try_from_rasn_epoch_seconds(&rasn_pkix::Time::Utc(
+            first_generalized_time
+        ))
+        .is_err());
     }
 }
@@ -18,13 +18,23 @@
 //! X.509 Certificate path validation.
 
 pub mod checkers;
+mod validation_cache;
 mod validation_error;
+mod validation_result;
+mod validation_time_policy;
 
 use self::checkers::*;
+use self::validation_cache::ValidationCache;
 pub use self::validation_error::CertificateValidationError;
 pub use self::validation_error::CertificateValidationErrorKind;
+pub use self::validation_result::ValidationModel;
+pub use self::validation_result::ValidationResult;
+pub use self::validation_result::ValidationWarning;
+pub use self::validation_time_policy::ValidationTimePolicy;
+use super::extensions::AuthorityKeyIdentifier;
 use super::extensions::BasicConstraints;
 use crate::cert::parse::CertificateParser;
+use crate::cert::types::Validity;
 use crossbeam_skiplist::SkipMap;
 use crossbeam_skiplist::SkipSet;
 use std::sync::Arc;
@@ -36,9 +46,11 @@ use tyst::Tyst;
 See [RFC5280 6](https://www.rfc-editor.org/rfc/rfc5280.html#section-6).
 */
 pub struct CertificatePathValidator {
-    fingerprint_by_subject: SkipMap<Vec<u8>, String>,
+    fingerprints_by_subject: SkipMap<Vec<u8>, Vec<String>>,
     trust_anchors_by_fingerprint: SkipMap<String, CertificateParser>,
     all_leafs_extension_checkers: Vec<Arc<dyn ExtensionChecker>>,
+    cache: Option<ValidationCache>,
+    time_policy: ValidationTimePolicy,
 }
 
 impl CertificatePathValidator {
@@ -46,9 +58,11 @@ impl CertificatePathValidator {
     /// certificates).
     pub fn new(trusted_anchors_der: Vec<Vec<u8>>) -> Result<Self, CertificateValidationError> {
         let ret = Self {
-            fingerprint_by_subject: SkipMap::default(),
+            fingerprints_by_subject: SkipMap::default(),
             trust_anchors_by_fingerprint: SkipMap::default(),
             all_leafs_extension_checkers: Vec::default(),
+            cache: None,
+            time_policy: ValidationTimePolicy::default(),
         };
         for trusted_anchor_der in trusted_anchors_der {
             match CertificateParser::from_bytes(&trusted_anchor_der) {
@@ -59,7 +73,14 @@ impl CertificatePathValidator {
                     let subject = trusted_anchor.get_encoded_subject();
                     ret.trust_anchors_by_fingerprint
                         .insert(fingerprint.to_owned(), trusted_anchor);
-                    ret.fingerprint_by_subject.insert(subject, fingerprint);
+                    // Several trust anchors may share the same subject (e.g. key rollover)
+                    let mut fingerprints = ret
+                        .fingerprints_by_subject
+                        .get(&subject)
+                        .map(|entry| entry.value().to_owned())
+                        .unwrap_or_default();
+                    fingerprints.push(fingerprint);
+                    ret.fingerprints_by_subject.insert(subject, fingerprints);
                 }
                 Err(e) => {
                     return Err(CertificateValidationErrorKind::CertificateParsingError
@@ -70,6 +91,15 @@ impl CertificatePathValidator {
         Ok(ret)
     }
 
+    /// Return the DER encoded subject distinguished names of all trust
+    /// anchors.
+    pub fn get_trust_anchor_subjects(&self) -> Vec<Vec<u8>> {
+        self.fingerprints_by_subject
+            .iter()
+            .map(|entry| entry.key().to_vec())
+            .collect()
+    }
+
     /// Add certificate extension checks that will be used for all leaf chains.
     pub fn add_extension_checkers(
         mut self,
@@ -94,6 +124,26 @@ impl CertificatePathValidator {
         self
     }
 
+    /// Use the [ValidationTimePolicy] instead of the strict default.
+    pub fn with_time_policy(mut self, time_policy: ValidationTimePolicy) -> Self {
+        self.time_policy = time_policy;
+        self
+    }
+
+    /** Enable caching of parsed CA certificates and verified signatures of CA
+    certificates.
+
+    At most `max_entries` parsed certificates and `max_entries` verified
+    signatures are kept. Signatures of leaf certificates are always verified.
+    A `max_entries` of `0` disables the cache.
+     */
+    pub fn with_cache(mut self, max_entries: usize) -> Self {
+        self.cache = Some(max_entries)
+            .filter(|max_entries| *max_entries > 0)
+            .map(ValidationCache::new);
+        self
+    }
+
     /** Order and validate the DER encoded leaf certificate chain.
 
     If no [CertificateValidationError] is returned the certificate is valid.
@@ -110,117 +160,219 @@ impl CertificatePathValidator {
         at_epoch_seconds: u64,
         additional_extension_checkers: Vec<Arc<dyn ExtensionChecker>>,
     ) -> Result<(), CertificateValidationError> {
+        self.validate_with_model(
+            leaf_certificate_chain_der,
+            at_epoch_seconds,
+            ValidationModel::Shell,
+            additional_extension_checkers,
+        )
+        .map(|_| ())
+    }
+
+    /** Validate many DER encoded leaf certificate chains in parallel.
+
+    The chains are distributed over the available CPU cores and share the
+    cache enabled by [`with_cache()`](Self::with_cache). The result for each
+    chain is returned in the same order as `leaf_certificate_chains_der`.
+    Chains are validated using the [ValidationModel::Shell].
+
+    See [`validate()`](Self::validate) for the other parameters.
+     */
+    pub fn validate_batch(
+        &self,
+        leaf_certificate_chains_der: Vec<Vec<Vec<u8>>>,
+        at_epoch_seconds: u64,
+        additional_extension_checkers: Vec<Arc<dyn ExtensionChecker>>,
+    ) -> Vec<Result<ValidationResult, CertificateValidationError>> {
+        let threads = std::thread::available_parallelism()
+            .map_or(1, std::num::NonZeroUsize::get)
+            .min(leaf_certificate_chains_der.len())
+            .max(1);
+        let chunk_size = leaf_certificate_chains_der.len().div_ceil(threads).max(1);
+        let mut chunks = vec![];
+        let mut chains = leaf_certificate_chains_der.into_iter();
+        loop {
+            let chunk = chains.by_ref().take(chunk_size).collect::<Vec<_>>();
+            if chunk.is_empty() {
+                break;
+            }
+            chunks.push(chunk);
+        }
+        let additional_extension_checkers = &additional_extension_checkers;
+        std::thread::scope(|scope| {
+            chunks
+                .into_iter()
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .into_iter()
+                            .map(|leaf_certificate_chain_der| {
+                                self.validate_with_model(
+                                    leaf_certificate_chain_der,
+                                    at_epoch_seconds,
+                                    ValidationModel::Shell,
+                                    additional_extension_checkers.clone(),
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect()
+        })
+    }
+
+    /** Order and validate the DER encoded leaf certificate chain using the
+    [ValidationModel].
+
+    For historical validation, `at_epoch_seconds` should be a trusted point in
+    time like the time of a verified time-stamp token (see
+    [crate::tsp::TimeStampTokenParser::get_gen_time]). Extension checkers that
+    depend on time, like the [CrlRevocationChecker], are invoked with the point
+    in time each certificate is validated at.
+
+    The returned [ValidationResult] reports the time used for each
+    certificate.
+     */
+    pub fn validate_with_model(
+        &self,
+        leaf_certificate_chain_der: Vec<Vec<u8>>,
+        at_epoch_seconds: u64,
+        validation_model: ValidationModel,
+        additional_extension_checkers: Vec<Arc<dyn ExtensionChecker>>,
+    ) -> Result<ValidationResult, CertificateValidationError> {
         // Don't assume that these are ordered
         let mut leaf_certificates = vec![];
         for leaf_certificate_der in leaf_certificate_chain_der {
-            let lcp = CertificateParser::from_bytes(&leaf_certificate_der).unwrap();
-            // Kick out certs that are not valid (time)
-            if lcp.get_validity().is_valid_at(at_epoch_seconds) {
-                if log::log_enabled!(log::Level::Trace) {
-                    log::trace!(
-                        "Loaded leaf chain cert with fingerprint '{}'.",
-                        lcp.fingerprint()
-                    );
-                }
-                leaf_certificates.push(lcp);
-            } else {
-                let msg = format!("Certificate is not valid at this point in time ({at_epoch_seconds} epoch seconds).");
-                return Err(CertificateValidationErrorKind::InvalidLifeSpan.error_with_msg(&msg));
-            }
-        }
-        // Check: There can only be a single leaf
-        let leafs = leaf_certificates
-            .iter()
-            .filter(|lcp| {
-                lcp.get_basic_constraints()
-                    .as_ref()
-                    .is_none_or(BasicConstraints::is_leaf)
-            })
-            .collect::<Vec<_>>();
-        if leafs.is_empty() {
-            return Err(CertificateValidationErrorKind::NotOneLeaf
-                .error_with_msg("No leaf certificate detected."));
-        }
-        if leafs.len() > 1 {
-            let msg = format!("More than one ({}) leaf certificate detected.", leafs.len());
-            return Err(CertificateValidationErrorKind::NotOneLeaf.error_with_msg(&msg));
-        }
-        // Order leaf chain: leaf to root
-        let leaf = leafs.first().unwrap();
-        let mut current_subject = leaf.get_encoded_subject();
-        let mut leaf_chain = vec![];
-        for _i in 0..leaf_certificates.len() {
-            if let Some(index) = leaf_certificates
-                .iter()
-                .position(|cp| cp.get_encoded_subject().eq(&current_subject))
-            {
-                let cp = leaf_certificates.remove(index);
-                current_subject = cp.get_encoded_issuer();
-                leaf_chain.push(cp);
-            } else {
-                let failed = leaf_chain.last().unwrap();
-                let msg = format!(
-                    "No parent for certificate with fingerprint '{}' detected.",
-                    failed.fingerprint()
+            let lcp = self.parse_certificate(&leaf_certificate_der)?;
+            if log::log_enabled!(log::Level::Trace) {
+                log::trace!(
+                    "Loaded leaf chain cert with fingerprint '{}'.",
+                    lcp.fingerprint()
                 );
-                return Err(CertificateValidationErrorKind::NotOneLeaf.error_with_msg(&msg));
             }
+            leaf_certificates.push(lcp);
         }
+        let leaf_chain = Self::order_leaf_chain(leaf_certificates)?;
         // From leaf to root: pick a trust anchor that has issued this level or is identical to this level
         let chain_with_trust = if let Some(pos) = leaf_chain.iter().position(|cp| {
             self.trust_anchors_by_fingerprint
                 .contains_key(cp.fingerprint())
         }) {
-            // Actual certificate in leaf chain is present already as trust anchor
-            leaf_chain[0..pos].to_vec()
-        } else if let Some(pos) = leaf_chain.iter().position(|cp| {
-            self.fingerprint_by_subject
-                .contains_key(&cp.get_encoded_issuer())
-        }) {
-            let cp = &leaf_chain[pos];
-            let issuer = &cp.get_encoded_issuer();
-            let fingerprint = self
-                .fingerprint_by_subject
-                .get(issuer)
-                .unwrap()
-                .value()
-                .to_owned();
-            let trusted = self
-                .trust_anchors_by_fingerprint
-                .get(&fingerprint)
-                .unwrap()
-                .value()
-                .to_owned();
+            // Actual certificate in leaf chain is present already as trust anchor.
+            // The trust anchor must be kept as the last element, since it is
+            // used as issuer when verifying the signature of the certificate
+            // below it.
+            leaf_chain[0..=pos].to_vec()
+        } else if let Some((pos, trusted)) = leaf_chain
+            .iter()
+            .enumerate()
+            .find_map(|(pos, cp)| self.select_trust_anchor(cp).map(|trusted| (pos, trusted)))
+        {
             let mut leaf_chain = leaf_chain[0..=pos].to_vec();
             leaf_chain.push(trusted);
             leaf_chain
         } else {
             return Err(CertificateValidationErrorKind::NotTrusted.error());
         };
-        // Check that selected trust anchor is valid
-        let selected_trust_anchor = chain_with_trust.last().unwrap();
-        if !selected_trust_anchor
-            .get_validity()
-            .is_valid_at(at_epoch_seconds)
+        // Check that every certificate, including the selected trust anchor, is valid
+        let validities = chain_with_trust
+            .iter()
+            .map(|cp| {
+                cp.get_validity().map_err(|e| {
+                    CertificateValidationErrorKind::CertificateParsingError
+                        .error_with_msg(&e.to_string())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let not_befores = validities
+            .iter()
+            .map(Validity::get_not_before)
+            .collect::<Vec<_>>();
+        let validation_times = validation_model.validation_times(&not_befores, at_epoch_seconds);
+        let mut warnings = vec![];
+        for (i, ((cp, validity), validation_time)) in chain_with_trust
+            .iter()
+            .zip(validities)
+            .zip(&validation_times)
+            .enumerate()
         {
-            let msg = format!("Matching trust anchor is not valid at this point in time ({at_epoch_seconds} epoch seconds).");
-            return Err(CertificateValidationErrorKind::InvalidLifeSpan.error_with_msg(&msg));
+            let fingerprint = cp.fingerprint().to_string();
+            // A leaf that is itself a trust anchor is still checked as a leaf
+            let is_leaf = i == 0;
+            let is_trust_anchor = !is_leaf && i + 1 == chain_with_trust.len();
+            if is_leaf && self.time_policy.is_leaf_lifetime_exceeded(&validity) {
+                let msg = format!(
+                    "Leaf certificate lifetime exceeds the maximum of {} seconds.",
+                    self.time_policy
+                        .get_max_leaf_lifetime_seconds()
+                        .unwrap_or_default()
+                );
+                return Err(CertificateValidationErrorKind::InvalidLifeSpan.error_with_msg(&msg));
+            }
+            if !self.time_policy.is_valid_at(&validity, *validation_time) {
+                if is_trust_anchor && !self.time_policy.is_trust_anchor_validity_enforced() {
+                    warnings.push(ValidationWarning::TrustAnchorNotValid { fingerprint });
+                    continue;
+                }
+                let msg = if is_trust_anchor {
+                    format!("Matching trust anchor is not valid at this point in time ({validation_time} epoch seconds).")
+                } else {
+                    format!("Certificate is not valid at this point in time ({validation_time} epoch seconds).")
+                };
+                return Err(CertificateValidationErrorKind::InvalidLifeSpan.error_with_msg(&msg));
+            }
+            if !validity.is_valid_at(*validation_time) {
+                warnings.push(ValidationWarning::WithinClockSkew {
+                    fingerprint: fingerprint.to_owned(),
+                });
+            }
+            if self
+                .time_policy
+                .is_expiring_soon(&validity, *validation_time)
+            {
+                warnings.push(ValidationWarning::ExpiresSoon {
+                    fingerprint,
+                    not_after: validity.get_not_after(),
+                });
+            }
+        }
+        if log::log_enabled!(log::Level::Debug) && !warnings.is_empty() {
+            log::debug!("Certificate path validation warnings: {warnings:?}");
         }
         // Validate signatures up to trusted
         let mut current_issuer = chain_with_trust.last().unwrap();
-        for current in chain_with_trust.iter().rev().skip(1) {
+        for (index, current) in chain_with_trust.iter().enumerate().rev().skip(1) {
+            // Signatures of CA certificates are cached when enabled
+            let cached = self.cache.as_ref().filter(|_| index > 0).map(|cache| {
+                let issuer_spki_fingerprint =
+                    crate::fingerprint_data(current_issuer.as_encoded_subject_public_key_info());
+                (cache, issuer_spki_fingerprint)
+            });
+            if let Some((cache, issuer_spki_fingerprint)) = &cached {
+                if cache.is_verified_signature(issuer_spki_fingerprint, current.fingerprint()) {
+                    current_issuer = current;
+                    continue;
+                }
+            }
             let spki = current_issuer.get_encoded_subject_public_key_info();
             let public_key = spki.to_public_key();
-            let tbs_certificate = current.get_encoded_tbs_certificate();
+            let tbs_certificate = current.as_encoded_tbs_certificate();
             let (oid, signature) = current.get_encoded_signature();
             //log::debug!("current.signature.oid: {oid}");
             if let Some(mut se) = Tyst::instance().ses().by_oid(&oid) {
-                if !se.verify(public_key.as_ref(), &signature, &tbs_certificate) {
+                if !se.verify(public_key.as_ref(), signature, tbs_certificate) {
                     let mut msg = format!("Unable to verify signature of cert with fp '{}' using issuer cert fp '{}'.", current.fingerprint(), current_issuer.fingerprint());
                     // Help out with troubleshooting: Is it self-signed??
                     let spki = current.get_encoded_subject_public_key_info();
                     let public_key = spki.to_public_key();
-                    if se.verify(public_key.as_ref(), &signature, &tbs_certificate) {
+                    if se.verify(public_key.as_ref(), &signature, tbs_certificate) {
                         msg += " The cert was self-signed.";
                     }
                     if log::log_enabled!(log::Level::Debug) {
@@ -237,6 +389,9 @@ impl CertificatePathValidator {
                 );
                 return Err(CertificateValidationErrorKind::UnknownSignature.error_with_msg(&msg));
             }
+            if let Some((cache, issuer_spki_fingerprint)) = &cached {
+                cache.insert_verified_signature(issuer_spki_fingerprint, current.fingerprint());
+            }
             current_issuer = current;
         }
         // Build list of all used critical extensions for the entire chain (except the trust anchor)
@@ -251,12 +406,16 @@ impl CertificatePathValidator {
         // Invoke all Extension checkers that apply to all chains
         // Note: We could potentially invoke in parallel, but this would imply
         //       additional overhead (e.g. async).
-        for extension_checker in &self.all_leafs_extension_checkers {
-            extension_checker.check_extensions(&chain_with_trust, &critical_extension_oids)?;
-        }
-        // Invoke all Extension checkers that apply to this chain
-        for extension_checker in &additional_extension_checkers {
-            extension_checker.check_extensions(&chain_with_trust, &critical_extension_oids)?;
+        for extension_checker in self
+            .all_leafs_extension_checkers
+            .iter()
+            .chain(additional_extension_checkers.iter())
+        {
+            extension_checker.check_extensions_at(
+                &chain_with_trust,
+                &critical_extension_oids,
+                &validation_times,
+            )?;
         }
         // Fail if there are still unresolved critical extensions
         if !critical_extension_oids.is_empty() {
@@ -268,6 +427,290 @@ impl CertificatePathValidator {
                 CertificateValidationErrorKind::UnhandledCriticalExtensions.error_with_msg(&msg)
             );
         }
-        Ok(())
+        Ok(ValidationResult::new(
+            validation_model,
+            at_epoch_seconds,
+            chain_with_trust
+                .iter()
+                .map(|cp| cp.fingerprint().to_string())
+                .zip(validation_times)
+                .collect(),
+            warnings,
+        ))
+    }
+
+    /** Return the trust anchor that issued the certificate.
+
+    When several trust anchors have the certificate's issuer as subject, the
+    first one matching the certificate's [AuthorityKeyIdentifier] is
+    preferred.
+     */
+    fn select_trust_anchor(&self, cp: &CertificateParser) -> Option<CertificateParser> {
+        let candidates = self
+            .fingerprints_by_subject
+            .get(cp.as_encoded_issuer())?
+            .value()
+            .iter()
+            .filter_map(|fingerprint| self.trust_anchors_by_fingerprint.get(fingerprint))
+            .map(|entry| entry.value().to_owned())
+            .collect::<Vec<_>>();
+        let aki = cp.get_authority_key_identifier().ok().flatten();
+        candidates
+            .iter()
+            .find(|trusted| aki.as_ref().is_none_or(|aki| aki.matches_issuer(trusted)))
+            .or(candidates.first())
+            .cloned()
+    }
+
+    /// Parse a DER encoded certificate or return the cached CA certificate.
+    fn parse_certificate(
+        &self,
+        encoded_certificate: &[u8],
+    ) -> Result<CertificateParser, CertificateValidationError> {
+        if let Some(cache) = &self.cache {
+            let fingerprint = crate::fingerprint_data(encoded_certificate);
+            if let Some(cp) = cache.get_certificate(&fingerprint) {
+                return Ok(cp);
+            }
+        }
+        let cp = CertificateParser::from_bytes(encoded_certificate).map_err(|e| {
+            CertificateValidationErrorKind::CertificateParsingError.error_with_msg(&e.to_string())
+        })?;
+        if let Some(cache) = &self.cache {
+            if cp
+                .get_basic_constraints()
+                .ok()
+                .flatten()
+                .is_some_and(|basic_constraints| !basic_constraints.is_leaf())
+            {
+                cache.insert_certificate(&cp);
+            }
+        }
+        Ok(cp)
+    }
+
+    /** Order the leaf certificate chain from leaf to the highest available
+    CA certificate.
+
+    The input may be in any order, but must contain exactly one leaf
+    certificate and every certificate must be the issuer of the previous one.
+    When several certificates share the same subject, the one matching the
+    [AuthorityKeyIdentifier] of the previous certificate is preferred.
+    Neither signatures nor validity are checked.
+     */
+    pub fn order_leaf_chain(
+        mut leaf_certificates: Vec<CertificateParser>,
+    ) -> Result<Vec<CertificateParser>, CertificateValidationError> {
+        // Check: There can only be a single leaf
+        let mut leafs = vec![];
+        for lcp in &leaf_certificates {
+            let basic_constraints = lcp.get_basic_constraints().map_err(|e| {
+                CertificateValidationErrorKind::CertificateParsingError
+                    .error_with_msg(&e.to_string())
+            })?;
+            if basic_constraints
+                .as_ref()
+                .is_none_or(BasicConstraints::is_leaf)
+            {
+                leafs.push(lcp);
+            }
+        }
+        if leafs.is_empty() {
+            return Err(CertificateValidationErrorKind::NotOneLeaf
+                .error_with_msg("No leaf certificate detected."));
+        }
+        if leafs.len() > 1 {
+            let msg = format!("More than one ({}) leaf certificate detected.", leafs.len());
+            return Err(CertificateValidationErrorKind::NotOneLeaf.error_with_msg(&msg));
+        }
+        // Order leaf chain: leaf to root
+        let leaf = leafs.first().unwrap();
+        let mut current_subject = leaf.get_encoded_subject();
+        let mut current_aki: Option<AuthorityKeyIdentifier> = None;
+        let mut leaf_chain = vec![];
+        for _i in 0..leaf_certificates.len() {
+            let is_candidate =
+                |cp: &CertificateParser| cp.as_encoded_subject().eq(current_subject.as_slice());
+            if let Some(index) = leaf_certificates
+                .iter()
+                .position(|cp| {
+                    is_candidate(cp)
+                        && current_aki
+                            .as_ref()
+                            .is_none_or(|aki| aki.matches_issuer(cp))
+                })
+                .or_else(|| leaf_certificates.iter().position(is_candidate))
+            {
+                let cp = leaf_certificates.remove(index);
+                current_subject = cp.get_encoded_issuer();
+                current_aki = cp.get_authority_key_identifier().ok().flatten();
+                leaf_chain.push(cp);
+            } else {
+                let failed = leaf_chain.last().unwrap();
+                let msg = format!(
+                    "No parent for certificate with fingerprint '{}' detected.",
+                    failed.fingerprint()
+                );
+                return Err(CertificateValidationErrorKind::NotOneLeaf.error_with_msg(&msg));
+            }
+        }
+        Ok(leaf_chain)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn leaf_checks_apply_to_trusted_leaf() {
+        init_logger();
+        let leaf = load_test_certificate_der("ec_recipient.b64");
+        let not_before = load_test_certificate("ec_recipient.b64")
+            .get_validity()
+            .unwrap()
+            .get_not_before();
+        let validator = CertificatePathValidator::new(vec![leaf.clone()]).unwrap();
+        assert!(validator
+            .validate_with_model(
+                vec![leaf.clone()],
+                not_before,
+                ValidationModel::Shell,
+                vec![]
+            )
+            .is_ok());
+        // The leaf is the trust anchor, but is still expected to be valid
+        let validator = CertificatePathValidator::new(vec![leaf.clone()])
+            .unwrap()
+            .with_time_policy(ValidationTimePolicy::default().with_trust_anchor_validity(false));
+        assert_kind(
+            validator.validate_with_model(
+                vec![leaf.clone()],
+                not_before - 3600,
+                ValidationModel::Shell,
+                vec![],
+            ),
+            CertificateValidationErrorKind::InvalidLifeSpan,
+        );
+        // ..and to not exceed the maximum leaf lifetime
+        let validator = CertificatePathValidator::new(vec![leaf.clone()])
+            .unwrap()
+            .with_time_policy(ValidationTimePolicy::default().with_max_leaf_lifetime(86_400));
+        assert_kind(
+            validator.validate_with_model(vec![leaf], not_before, ValidationModel::Shell, vec![]),
+            CertificateValidationErrorKind::InvalidLifeSpan,
+        );
+    }
+
+    #[test]
+    fn leaf_checks_apply_below_trust_anchor() {
+        init_logger();
+        let ca = load_test_certificate_der("ec_ca.b64");
+        let leaf = load_test_certificate_der("ec_recipient.b64");
+        let not_before = load_test_certificate("ec_recipient.b64")
+            .get_validity()
+            .unwrap()
+            .get_not_before();
+        let validator = CertificatePathValidator::new(vec![ca.clone()])
+            .unwrap()
+            .with_time_policy(ValidationTimePolicy::default().with_max_leaf_lifetime(86_400));
+        // The trust anchor is also part of the provided chain
+        assert_kind(
+            validator.validate_with_model(
+                vec![leaf, ca],
+                not_before,
+                ValidationModel::Shell,
+                vec![],
+            ),
+            CertificateValidationErrorKind::InvalidLifeSpan,
+        );
+    }
+
+    #[test]
+    fn batch_validation_shares_cache() {
+        init_logger();
+        let chain = vec![
+            load_test_certificate_der("h1_leaf.b64"),
+            load_test_certificate_der("h1_sub_ca.b64"),
+        ];
+        let validator =
+            CertificatePathValidator::new(vec![load_test_certificate_der("h1_root_ca.b64")])
+                .unwrap()
+                .with_cache(16);
+        let results = validator.validate_batch(vec![chain; 8], 1739555555, vec![]);
+        assert_eq!(results.len(), 8);
+        assert!(results.iter().all(Result::is_ok));
+        // Only the sub CA and its signature are cached, once
+        let cache = validator.cache.as_ref().unwrap();
+        assert_eq!(cache.certificates_len(), 1);
+        assert_eq!(cache.verified_signatures_len(), 1);
+    }
+
+    #[test]
+    fn expired_trust_anchor_warning() {
+        init_logger();
+        let sub_ca = load_test_certificate("ec_sub_ca.b64");
+        let leaf = vec![load_test_certificate_der("ec_sub_ca_leaf.b64")];
+        // 2027-06-01, after the sub CA (the trust anchor) but not the leaf has expired
+        let at_epoch_seconds = 1811808000;
+        let validator =
+            CertificatePathValidator::new(vec![sub_ca.get_encoded_certificate()]).unwrap();
+        assert_kind(
+            validator.validate_with_model(
+                leaf.clone(),
+                at_epoch_seconds,
+                ValidationModel::Shell,
+                vec![],
+            ),
+            CertificateValidationErrorKind::InvalidLifeSpan,
+        );
+        let validator = validator
+            .with_time_policy(ValidationTimePolicy::default().with_trust_anchor_validity(false));
+        let result = validator
+            .validate_with_model(leaf, at_epoch_seconds, ValidationModel::Shell, vec![])
+            .unwrap();
+        assert_eq!(
+            result.get_warnings(),
+            &[ValidationWarning::TrustAnchorNotValid {
+                fingerprint: sub_ca.fingerprint().to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn expires_soon_warning() {
+        init_logger();
+        let leaf = load_test_certificate("h1_leaf.b64");
+        let chain = vec![
+            leaf.get_encoded_certificate(),
+            load_test_certificate_der("h1_sub_ca.b64"),
+        ];
+        // 2025-02-14, a month before the leaf expires
+        let at_epoch_seconds = 1739555555;
+        let validator =
+            CertificatePathValidator::new(vec![load_test_certificate_der("h1_root_ca.b64")])
+                .unwrap();
+        let result = validator
+            .validate_with_model(
+                chain.clone(),
+                at_epoch_seconds,
+                ValidationModel::Shell,
+                vec![],
+            )
+            .unwrap();
+        assert!(result.get_warnings().is_empty());
+        let validator = validator
+            .with_time_policy(ValidationTimePolicy::default().with_expiry_warning(60 * 86_400));
+        let result = validator
+            .validate_with_model(chain, at_epoch_seconds, ValidationModel::Shell, vec![])
+            .unwrap();
+        assert_eq!(
+            result.get_warnings(),
+            &[ValidationWarning::ExpiresSoon {
+                fingerprint: leaf.fingerprint().to_string(),
+                not_after: leaf.get_validity().unwrap().get_not_after(),
+            }]
+        );
     }
 }
@@ -19,12 +19,16 @@
 
 mod basic_constraints_checker;
 mod certificate_policies_checker;
+mod crl_revocation_checker;
+mod ct_policy_checker;
 mod extended_key_usage_checker;
 mod key_identifier_checker;
 mod key_usage_checker;
 
 pub use self::basic_constraints_checker::BasicConstraintsChecker;
 pub use self::certificate_policies_checker::CertificatePoliciesChecker;
+pub use self::crl_revocation_checker::CrlRevocationChecker;
+pub use self::ct_policy_checker::CtPolicyChecker;
 pub use self::extended_key_usage_checker::ExtendedKeyUsageChecker;
 pub use self::key_identifier_checker::KeyIdentifierChecker;
 pub use self::key_usage_checker::KeyUsageChecker;
@@ -35,12 +39,11 @@ use crossbeam_skiplist::SkipSet;
 
 /* TODO:
  * Revocation checking: Caller must provide OCSP response with optional OCSP signing cert
- * Revocation checking: Caller must provide CRL
  * Name constraints validation
 */
 
 /// X.509 Certificate extension checker.
-pub trait ExtensionChecker {
+pub trait ExtensionChecker: Send + Sync {
     /** Validate one or more specific extensions of a certificate.
 
     `chain_of_trust` is provided in ordered form with the leaf certificate
@@ -54,4 +57,21 @@ pub trait ExtensionChecker {
         chain_with_trust: &[CertificateParser],
         unresolved_extensions: &SkipSet<Vec<u32>>,
     ) -> Result<(), CertificateValidationError>;
+
+    /** Validate like [Self::check_extensions] with the point in time each
+    certificate of `chain_with_trust` is validated at.
+
+    `validation_times` has the same order as `chain_with_trust`. Checkers
+    that depend on time, like revocation checking, should override this. The
+    default implementation ignores the time.
+     */
+    fn check_extensions_at(
+        &self,
+        chain_with_trust: &[CertificateParser],
+        unresolved_extensions: &SkipSet<Vec<u32>>,
+        validation_times: &[u64],
+    ) -> Result<(), CertificateValidationError> {
+        let _ = validation_times;
+        self.check_extensions(chain_with_trust, unresolved_extensions)
+    }
 }
@@ -41,7 +41,13 @@ impl ExtensionChecker for BasicConstraintsChecker {
             .iter()
             .enumerate()
             .skip(1)
-            .map(|(i, cp)| (i, cp.get_basic_constraints()))
+            .map(|(i, cp)| cp.get_basic_constraints().map(|bc| (i, bc)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                CertificateValidationErrorKind::ExtensionHandlingFailure
+                    .error_with_msg(&format!("Invalid Basic Constraints: {e}"))
+            })?
+            .into_iter()
             .any(|(i, basic_constraints)| {
                 !Self::is_ca_with_sufficient_path_len(basic_constraints, i)
             });
@@ -92,4 +98,24 @@ mod test {
             3
         ));
     }
+
+    #[test]
+    fn malformed_basic_constraints_fails_check() {
+        init_logger();
+        let sub_ca = load_test_certificate_der_with_malformed_extension(
+            "h1_sub_ca.b64",
+            BasicConstraints::OID,
+        );
+        let chain_with_trust = [
+            load_test_certificate("h1_leaf.b64"),
+            CertificateParser::from_bytes(&sub_ca).unwrap(),
+            load_test_certificate("h1_root_ca.b64"),
+        ];
+        let unresolved_extensions = SkipSet::new();
+        unresolved_extensions.insert(BasicConstraints::OID.to_vec());
+        assert!(BasicConstraintsChecker::default()
+            .check_extensions(&chain_with_trust, &unresolved_extensions)
+            .is_err());
+        assert!(unresolved_extensions.contains(BasicConstraints::OID));
+    }
 }
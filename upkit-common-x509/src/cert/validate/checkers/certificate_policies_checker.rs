@@ -62,33 +62,33 @@ impl ExtensionChecker for CertificatePoliciesChecker {
         chain_with_trust: &[CertificateParser],
         unresolved_extensions: &SkipSet<Vec<u32>>,
     ) -> Result<(), CertificateValidationError> {
-        let mut msg = String::new();
-        let failure = chain_with_trust.iter().enumerate().any(|(i, cp)| {
+        for (i, cp) in chain_with_trust.iter().enumerate() {
             let required_policies = if i > 0 {
                 &self.issuer_policies
             } else {
                 &self.leaf_policies
             };
-            let policies = cp.get_certificate_policies();
+            let policies = cp.get_certificate_policies().map_err(|e| {
+                CertificateValidationErrorKind::ExtensionHandlingFailure
+                    .error_with_msg(&format!("[{i}]: Invalid CertificatePolicy: {e}"))
+            })?;
             let failure = if policies.is_empty() {
                 !required_policies.is_empty()
             } else {
-                required_policies.iter().any(|required_policy| !policies.contains(required_policy))
+                required_policies
+                    .iter()
+                    .any(|required_policy| !policies.contains(required_policy))
             };
             if failure {
-                msg = format!(
+                let msg = format!(
                     "[{i}]: Missing CertificatePolicy. Required: {required_policies:?}, Actual: {policies:?}"
                 );
+                return Err(
+                    CertificateValidationErrorKind::ExtensionHandlingFailure.error_with_msg(&msg)
+                );
             }
-            failure
-        });
-        if failure {
-            return Err(
-                CertificateValidationErrorKind::ExtensionHandlingFailure.error_with_msg(&msg)
-            );
-        } else {
-            unresolved_extensions.remove(&CertificatePolicy::OID.to_vec());
         }
+        unresolved_extensions.remove(&CertificatePolicy::OID.to_vec());
         Ok(())
     }
 }
@@ -0,0 +1,481 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Revocation checking using Certificate Revocation Lists.
+
+use super::CertificateParser;
+use super::CertificateValidationError;
+use super::CertificateValidationErrorKind;
+use super::ExtensionChecker;
+use crate::cert::types::Validity;
+use crate::crl::CrlParser;
+use crate::crl::RevocationReason;
+use crossbeam_skiplist::SkipSet;
+
+/** X.509 Certificate revocation checking using caller provided CRLs.
+
+See [RFC5280 6.3](https://www.rfc-editor.org/rfc/rfc5280.html#section-6.3).
+
+Every certificate of the chain except the trust anchor is checked "as of"
+the point in time it is validated at. A CRL signed by the issuer of the
+certificate is usable for a point in time when it was issued at or after that
+time, or when the time is within its `thisUpdate` and `nextUpdate`.
+
+A certificate is considered revoked if it is listed with a revocation date at
+or before the point in time, so a certificate revoked after a trusted signing
+time is still valid for that signature.
+
+Only complete CRLs for all revocation reasons are used. Delta CRLs, CRLs with
+unsupported critical extensions and CRLs with an issuing distribution point
+that doesn't cover the certificate are ignored.
+ */
+pub struct CrlRevocationChecker {
+    crls: Vec<CrlParser>,
+    require_revocation_status: bool,
+}
+
+impl CrlRevocationChecker {
+    /// Return a new instance from DER encoded CRLs.
+    ///
+    /// By default validation fails if no usable CRL is available for a
+    /// certificate.
+    pub fn new(crls_der: &[Vec<u8>]) -> Result<Self, CertificateValidationError> {
+        let crls = crls_der
+            .iter()
+            .map(|crl_der| {
+                CrlParser::from_bytes(crl_der).map_err(|e| {
+                    CertificateValidationErrorKind::ExtensionHandlingFailure
+                        .error_with_msg(&e.to_string())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            crls,
+            require_revocation_status: true,
+        })
+    }
+
+    /// Don't fail validation when no usable CRL is available for a
+    /// certificate.
+    pub fn allow_unknown_revocation_status(mut self) -> Self {
+        self.require_revocation_status = false;
+        self
+    }
+
+    /** Return the usable CRLs for the `certificate` issued by `issuer` at
+    `validation_time`.
+
+    See [RFC5280 6.3.3](https://www.rfc-editor.org/rfc/rfc5280.html#section-6.3.3).
+    A CRL is not usable when it
+    * has another issuer name than the certificate,
+    * has critical extensions that are not processed (the status would be
+      UNDETERMINED),
+    * is a delta CRL, since this is never a complete list,
+    * has an [IssuingDistributionPoint](crate::crl::IssuingDistributionPoint) scope that doesn't cover the
+      certificate.
+    */
+    fn usable_crls(
+        &self,
+        certificate: &CertificateParser,
+        issuer: &CertificateParser,
+        validation_time: u64,
+    ) -> Vec<&CrlParser> {
+        self.crls
+            .iter()
+            .filter(|crl| crl.get_encoded_issuer().eq(certificate.as_encoded_issuer()))
+            .filter(|crl| {
+                crl.get_this_update() >= validation_time
+                    || crl.get_next_update().is_some_and(|next_update| {
+                        crl.get_this_update() <= validation_time && validation_time <= next_update
+                    })
+            })
+            .filter(|crl| {
+                let unsupported = crl.get_unsupported_critical_extension_oids();
+                if !unsupported.is_empty() {
+                    log::debug!(
+                        "CRL with fp '{}' has unsupported critical extensions {unsupported:?}.",
+                        crl.fingerprint()
+                    );
+                }
+                unsupported.is_empty()
+            })
+            .filter(|crl| !crl.is_delta_crl())
+            .filter(|crl| match crl.get_issuing_distribution_point() {
+                Ok(None) => true,
+                Ok(Some(idp)) => idp.is_in_scope(certificate).unwrap_or(false),
+                Err(e) => {
+                    log::debug!("CRL with fp '{}': {e}", crl.fingerprint());
+                    false
+                }
+            })
+            .filter(|crl| crl.verify_signature(issuer))
+            .collect()
+    }
+}
+
+impl ExtensionChecker for CrlRevocationChecker {
+    fn check_extensions(
+        &self,
+        chain_with_trust: &[CertificateParser],
+        unresolved_extensions: &SkipSet<Vec<u32>>,
+    ) -> Result<(), CertificateValidationError> {
+        self.check_extensions_at(
+            chain_with_trust,
+            unresolved_extensions,
+            &vec![Validity::now_epoch_seconds(); chain_with_trust.len()],
+        )
+    }
+
+    fn check_extensions_at(
+        &self,
+        chain_with_trust: &[CertificateParser],
+        _unresolved_extensions: &SkipSet<Vec<u32>>,
+        validation_times: &[u64],
+    ) -> Result<(), CertificateValidationError> {
+        for (i, pair) in chain_with_trust.windows(2).enumerate() {
+            let (cp, issuer) = (&pair[0], &pair[1]);
+            let validation_time = validation_times[i];
+            let crls = self.usable_crls(cp, issuer, validation_time);
+            if crls.is_empty() {
+                if self.require_revocation_status {
+                    let msg = format!(
+                        "[{i}]: No usable CRL for cert with fp '{}' at {validation_time} epoch seconds.",
+                        cp.fingerprint()
+                    );
+                    return Err(CertificateValidationErrorKind::RevocationStatusUnknown
+                        .error_with_msg(&msg));
+                }
+                continue;
+            }
+            let serial_number = cp.get_serial_number();
+            let revoked = crls.iter().find_map(|crl| {
+                crl.get_revoked_certificate(&serial_number).filter(|entry| {
+                    entry.get_revocation_epoch_seconds() <= validation_time
+                        && entry.get_reason() != Some(RevocationReason::RemoveFromCrl)
+                })
+            });
+            if let Some(entry) = revoked {
+                let msg = format!(
+                    "[{i}]: Cert with fp '{}' was revoked at {} epoch seconds (reason: {:?}).",
+                    cp.fingerprint(),
+                    entry.get_revocation_epoch_seconds(),
+                    entry.get_reason()
+                );
+                return Err(CertificateValidationErrorKind::Revoked.error_with_msg(&msg));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::types::WellKnownAlgorithm;
+    use crate::cert::validate::CertificatePathValidator;
+    use crate::crl::CrlNumber;
+    use crate::crl::IssuingDistributionPoint;
+    use crate::crl::NoSignatureTbsCertList;
+    use crate::crl::RevokedCertificateEntry;
+    use crate::test_utils::*;
+    use std::sync::Arc;
+
+    /// Return a CRL by the test CA where `certificate` is revoked at
+    /// `revocation_time`.
+    fn create_crl(
+        certificate: &CertificateParser,
+        revocation_time: u64,
+        this_update: u64,
+        next_update: u64,
+    ) -> Vec<u8> {
+        create_crl_with_extensions(certificate, revocation_time, this_update, next_update, &[])
+    }
+
+    /// Return a CRL like [create_crl] with additional CRL `extensions`.
+    fn create_crl_with_extensions(
+        certificate: &CertificateParser,
+        revocation_time: u64,
+        this_update: u64,
+        next_update: u64,
+        extensions: &[(&[u32], bool, Vec<u8>)],
+    ) -> Vec<u8> {
+        create_crl_with_issuer(
+            &load_test_certificate("ec_ca.b64"),
+            certificate,
+            revocation_time,
+            this_update,
+            next_update,
+            extensions,
+        )
+    }
+
+    /// Return a CRL like [create_crl_with_extensions] with the issuer name of
+    /// `crl_issuer`, but still signed by the test CA.
+    fn create_crl_with_issuer(
+        crl_issuer: &CertificateParser,
+        certificate: &CertificateParser,
+        revocation_time: u64,
+        this_update: u64,
+        next_update: u64,
+        extensions: &[(&[u32], bool, Vec<u8>)],
+    ) -> Vec<u8> {
+        let mut tbs_cert_list = NoSignatureTbsCertList::new(
+            crl_issuer,
+            this_update,
+            Some(next_update),
+            &CrlNumber::from(1),
+            &[RevokedCertificateEntry::new(
+                certificate.get_serial_number(),
+                revocation_time,
+                Some(RevocationReason::KeyCompromise),
+            )],
+        );
+        for (oid, critical, value) in extensions {
+            tbs_cert_list = tbs_cert_list.with_crl_extension(oid, *critical, value);
+        }
+        let signature_algorithm = crate::cms::signature_algorithm_identifier(
+            WellKnownAlgorithm::EcdsaWithSha256.as_oid(),
+        );
+        let signature = sign_with_test_key(
+            "ec_ca_key.pem",
+            &WellKnownAlgorithm::EcdsaWithSha256,
+            &tbs_cert_list.with_signature_algorithm_as_bytes(&signature_algorithm),
+        );
+        tbs_cert_list.to_certificate_list(&signature_algorithm, signature)
+    }
+
+    fn validate(
+        checker: CrlRevocationChecker,
+        at_epoch_seconds: u64,
+    ) -> Result<(), CertificateValidationError> {
+        validate_certificate("tsa.b64", checker, at_epoch_seconds)
+    }
+
+    fn validate_certificate(
+        name: &str,
+        checker: CrlRevocationChecker,
+        at_epoch_seconds: u64,
+    ) -> Result<(), CertificateValidationError> {
+        CertificatePathValidator::new(vec![load_test_certificate_der("ec_ca.b64")])
+            .unwrap()
+            .add_standard_extension_checkers()
+            .validate(
+                vec![load_test_certificate_der(name)],
+                at_epoch_seconds,
+                vec![Arc::new(checker)],
+            )
+    }
+
+    #[test]
+    fn revocation_is_checked_at_validation_time() {
+        let certificate = load_test_certificate("tsa.b64");
+        let not_before = certificate.get_validity().unwrap().get_not_before();
+        let crl = create_crl(
+            &certificate,
+            not_before + 1000,
+            not_before + 2000,
+            not_before + 100_000,
+        );
+        let checker = || CrlRevocationChecker::new(&[crl.clone()]).unwrap();
+        // Not yet revoked at this point in time, e.g. a trusted signing time
+        validate(checker(), not_before + 500).unwrap();
+        let err = validate(checker(), not_before + 1500).unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            CertificateValidationErrorKind::Revoked
+        ));
+        let err = validate(checker(), not_before + 50_000).unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            CertificateValidationErrorKind::Revoked
+        ));
+        // The CRL has expired
+        let err = validate(checker(), not_before + 200_000).unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            CertificateValidationErrorKind::RevocationStatusUnknown
+        ));
+        validate(
+            checker().allow_unknown_revocation_status(),
+            not_before + 200_000,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn crl_of_other_issuer_is_not_used() {
+        let certificate = load_test_certificate("tsa.b64");
+        let not_before = certificate.get_validity().unwrap().get_not_before();
+        let crl = create_crl(
+            &certificate,
+            not_before + 1000,
+            not_before + 2000,
+            not_before + 100_000,
+        );
+        // Only CRLs signed by the issuer of the certificate are usable
+        let err = CertificatePathValidator::new(vec![load_test_certificate_der("h1_root_ca.b64")])
+            .unwrap()
+            .add_standard_extension_checkers()
+            .validate(
+                vec![
+                    load_test_certificate_der("h1_leaf.b64"),
+                    load_test_certificate_der("h1_sub_ca.b64"),
+                ],
+                1739555555,
+                vec![Arc::new(CrlRevocationChecker::new(&[crl]).unwrap())],
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            CertificateValidationErrorKind::RevocationStatusUnknown
+        ));
+    }
+
+    #[test]
+    fn crl_with_other_issuer_name_is_not_used() {
+        let certificate = load_test_certificate("tsa.b64");
+        let not_before = certificate.get_validity().unwrap().get_not_before();
+        // Signed by the issuer of the certificate, but for another CA name
+        let crl = create_crl_with_issuer(
+            &load_test_certificate("h1_root_ca.b64"),
+            &certificate,
+            not_before + 1000,
+            not_before + 2000,
+            not_before + 100_000,
+            &[],
+        );
+        let err = validate(
+            CrlRevocationChecker::new(&[crl]).unwrap(),
+            not_before + 50_000,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            CertificateValidationErrorKind::RevocationStatusUnknown
+        ));
+    }
+
+    /// Return the result of validating the certificate `name` with a CRL
+    /// that revokes it and has the additional CRL `extensions`.
+    fn validate_with_crl_extensions(
+        name: &str,
+        extensions: &[(&[u32], bool, Vec<u8>)],
+    ) -> Result<(), CertificateValidationError> {
+        let certificate = load_test_certificate(name);
+        let not_before = certificate.get_validity().unwrap().get_not_before();
+        let crl = create_crl_with_extensions(
+            &certificate,
+            not_before + 1000,
+            not_before + 2000,
+            not_before + 100_000,
+            extensions,
+        );
+        validate_certificate(
+            name,
+            CrlRevocationChecker::new(&[crl]).unwrap(),
+            not_before + 50_000,
+        )
+    }
+
+    #[test]
+    fn crl_with_unsupported_critical_extension_is_not_used() {
+        let unknown_oid: &[u32] = &[1, 3, 6, 1, 4, 1, 99999, 1];
+        let value = rasn::der::encode(&()).unwrap();
+        // Unknown non-critical extensions are ignored
+        assert_kind(
+            validate_with_crl_extensions("tsa.b64", &[(unknown_oid, false, value.clone())]),
+            CertificateValidationErrorKind::Revoked,
+        );
+        assert_kind(
+            validate_with_crl_extensions("tsa.b64", &[(unknown_oid, true, value)]),
+            CertificateValidationErrorKind::RevocationStatusUnknown,
+        );
+    }
+
+    #[test]
+    fn delta_crl_is_not_used() {
+        let base_crl_number = rasn::der::encode(&CrlNumber::from(1).to_rasn_type()).unwrap();
+        assert_kind(
+            validate_with_crl_extensions(
+                "tsa.b64",
+                &[(
+                    NoSignatureTbsCertList::OID_DELTA_CRL_INDICATOR,
+                    true,
+                    base_crl_number,
+                )],
+            ),
+            CertificateValidationErrorKind::RevocationStatusUnknown,
+        );
+    }
+
+    #[test]
+    fn issuing_distribution_point_scope_is_honored() {
+        let idp = |idp: IssuingDistributionPoint| {
+            vec![(IssuingDistributionPoint::OID, true, idp.as_bytes())]
+        };
+        // Scope without limitations
+        assert_kind(
+            validate_with_crl_extensions("tsa.b64", &idp(IssuingDistributionPoint::new(None))),
+            CertificateValidationErrorKind::Revoked,
+        );
+        // Only user certificates
+        assert_kind(
+            validate_with_crl_extensions(
+                "tsa.b64",
+                &idp(IssuingDistributionPoint::new(None).only_contains_user_certs()),
+            ),
+            CertificateValidationErrorKind::Revoked,
+        );
+        // Only CA certificates
+        assert_kind(
+            validate_with_crl_extensions(
+                "tsa.b64",
+                &idp(IssuingDistributionPoint::new(None).only_contains_ca_certs()),
+            ),
+            CertificateValidationErrorKind::RevocationStatusUnknown,
+        );
+        // Matching distribution point name
+        assert_kind(
+            validate_with_crl_extensions(
+                "ec_cdp_leaf.b64",
+                &idp(IssuingDistributionPoint::new(Some(
+                    "http://crl.example.com/ec_ca.crl",
+                ))),
+            ),
+            CertificateValidationErrorKind::Revoked,
+        );
+        // Other distribution point name
+        assert_kind(
+            validate_with_crl_extensions(
+                "ec_cdp_leaf.b64",
+                &idp(IssuingDistributionPoint::new(Some(
+                    "http://crl.example.com/other.crl",
+                ))),
+            ),
+            CertificateValidationErrorKind::RevocationStatusUnknown,
+        );
+        // The certificate has no distribution point
+        assert_kind(
+            validate_with_crl_extensions(
+                "tsa.b64",
+                &idp(IssuingDistributionPoint::new(Some(
+                    "http://crl.example.com/ec_ca.crl",
+                ))),
+            ),
+            CertificateValidationErrorKind::RevocationStatusUnknown,
+        );
+    }
+}
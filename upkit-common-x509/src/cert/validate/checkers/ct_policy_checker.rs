@@ -0,0 +1,175 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Certificate Transparency policy validation.
+
+use super::CertificateParser;
+use super::CertificateValidationError;
+use super::CertificateValidationErrorKind;
+use super::ExtensionChecker;
+use crate::cert::extensions::SignedCertificateTimestamp;
+use crate::cert::types::Validity;
+use crate::ct::CtLogList;
+use crossbeam_skiplist::SkipSet;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/** Certificate Transparency policy validation of the leaf certificate.
+
+Requires a minimum number of embedded SCTs that verify against the
+[CtLogList] and that these are issued by a minimum number of distinct log
+operators. See [RFC6962 3.3](https://www.rfc-editor.org/rfc/rfc6962#section-3.3).
+ */
+pub struct CtPolicyChecker {
+    log_list: Arc<CtLogList>,
+    minimum_scts: usize,
+    minimum_operators: usize,
+}
+
+impl CtPolicyChecker {
+    /// Return a new instance requiring `minimum_scts` valid SCTs from at least
+    /// `minimum_operators` distinct log operators.
+    pub fn new(log_list: Arc<CtLogList>, minimum_scts: usize, minimum_operators: usize) -> Self {
+        Self {
+            log_list,
+            minimum_scts,
+            minimum_operators,
+        }
+    }
+}
+
+impl ExtensionChecker for CtPolicyChecker {
+    fn check_extensions(
+        &self,
+        chain_with_trust: &[CertificateParser],
+        unresolved_extensions: &SkipSet<Vec<u32>>,
+    ) -> Result<(), CertificateValidationError> {
+        self.check_extensions_at(
+            chain_with_trust,
+            unresolved_extensions,
+            &vec![Validity::now_epoch_seconds(); chain_with_trust.len()],
+        )
+    }
+
+    fn check_extensions_at(
+        &self,
+        chain_with_trust: &[CertificateParser],
+        unresolved_extensions: &SkipSet<Vec<u32>>,
+        validation_times: &[u64],
+    ) -> Result<(), CertificateValidationError> {
+        let (Some(leaf), Some(issuer)) = (chain_with_trust.first(), chain_with_trust.get(1)) else {
+            return Err(CertificateValidationErrorKind::ExtensionHandlingFailure
+                .error_with_msg("CT policy requires the leaf and its issuer."));
+        };
+        let scts = leaf.get_signed_certificate_timestamps().map_err(|e| {
+            CertificateValidationErrorKind::ExtensionHandlingFailure
+                .error_with_msg(&format!("[0]: Failed to parse SCT list: {e}"))
+        })?;
+        let mut valid_scts = 0;
+        let mut operators = HashSet::new();
+        for sct in &scts {
+            match self
+                .log_list
+                .verify_at(sct, leaf, issuer, validation_times[0])
+            {
+                Ok(log) => {
+                    valid_scts += 1;
+                    operators.insert(log.get_operator().to_string());
+                }
+                Err(e) => log::debug!("[0]: Ignoring SCT: {e}"),
+            }
+        }
+        if valid_scts < self.minimum_scts || operators.len() < self.minimum_operators {
+            return Err(
+                CertificateValidationErrorKind::ExtensionHandlingFailure.error_with_msg(&format!(
+                    "[0]: CT policy requires {} SCT(s) from {} operator(s). Valid: {valid_scts} SCT(s) from {} operator(s) of {} embedded.",
+                    self.minimum_scts,
+                    self.minimum_operators,
+                    operators.len(),
+                    scts.len(),
+                )),
+            );
+        }
+        unresolved_extensions.remove(&SignedCertificateTimestamp::OID.to_vec());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::validate::CertificatePathValidator;
+    use crate::ct::test_utils::*;
+    use crate::test_utils::*;
+
+    /// Timestamp of the SCT embedded in `ct_leaf.b64` in epoch seconds.
+    const SCT_TIMESTAMP: u64 = 1_792_337_400;
+
+    fn validate(
+        checker: CtPolicyChecker,
+        at_epoch_seconds: u64,
+    ) -> Result<(), CertificateValidationError> {
+        CertificatePathValidator::new(vec![load_test_certificate_der("ec_ca.b64")])
+            .unwrap()
+            .add_standard_extension_checkers()
+            .validate(
+                vec![load_test_certificate_der("ct_leaf.b64")],
+                at_epoch_seconds,
+                vec![Arc::new(checker)],
+            )
+    }
+
+    #[test]
+    fn ct_policy() {
+        init_logger();
+        let log_list = Arc::new(load_test_ct_log_list("usable"));
+        validate(
+            CtPolicyChecker::new(Arc::clone(&log_list), 1, 1),
+            SCT_TIMESTAMP + 60,
+        )
+        .unwrap();
+        // Not enough SCTs
+        let err = validate(
+            CtPolicyChecker::new(Arc::clone(&log_list), 2, 1),
+            SCT_TIMESTAMP + 60,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            CertificateValidationErrorKind::ExtensionHandlingFailure
+        ));
+        // The SCT is not valid before it was issued
+        let err = validate(
+            CtPolicyChecker::new(Arc::clone(&log_list), 1, 1),
+            SCT_TIMESTAMP - 60,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            CertificateValidationErrorKind::ExtensionHandlingFailure
+        ));
+        // Unknown log
+        let err = validate(
+            CtPolicyChecker::new(Arc::new(CtLogList::default()), 1, 1),
+            SCT_TIMESTAMP + 60,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            CertificateValidationErrorKind::ExtensionHandlingFailure
+        ));
+    }
+}
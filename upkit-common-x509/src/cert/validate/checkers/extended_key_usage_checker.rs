@@ -63,33 +63,33 @@ impl ExtensionChecker for ExtendedKeyUsageChecker {
         chain_with_trust: &[CertificateParser],
         unresolved_extensions: &SkipSet<Vec<u32>>,
     ) -> Result<(), CertificateValidationError> {
-        let mut msg = String::new();
-        let failure = chain_with_trust.iter().enumerate().any(|(i, cp)| {
+        for (i, cp) in chain_with_trust.iter().enumerate() {
             let required_ekus = if i > 0 {
                 &self.issuer_ekus
             } else {
                 &self.leaf_ekus
             };
-            let ekus = cp.get_extended_key_usage();
+            let ekus = cp.get_extended_key_usage().map_err(|e| {
+                CertificateValidationErrorKind::ExtensionHandlingFailure
+                    .error_with_msg(&format!("[{i}]: Invalid ExtendedKeyUsage: {e}"))
+            })?;
             let failure = if ekus.is_empty() {
                 !required_ekus.is_empty()
             } else {
-                required_ekus.iter().any(|required_eku| !ekus.contains(required_eku))
+                required_ekus
+                    .iter()
+                    .any(|required_eku| !ekus.contains(required_eku))
             };
             if failure {
-                msg = format!(
+                let msg = format!(
                     "[{i}]: Missing ExtendedKeyUsage(s). Required: {required_ekus:?}, Actual: {ekus:?}"
                 );
+                return Err(
+                    CertificateValidationErrorKind::ExtensionHandlingFailure.error_with_msg(&msg)
+                );
             }
-            failure
-        });
-        if failure {
-            return Err(
-                CertificateValidationErrorKind::ExtensionHandlingFailure.error_with_msg(&msg)
-            );
-        } else {
-            unresolved_extensions.remove(&ExtendedKeyUsage::OID.to_vec());
         }
+        unresolved_extensions.remove(&ExtendedKeyUsage::OID.to_vec());
         Ok(())
     }
 }
@@ -19,22 +19,39 @@
 
 use super::ExtensionChecker;
 use crate::cert::extensions::AuthorityKeyIdentifier;
+use crate::cert::extensions::KeyIdentifierMethod;
 use crate::cert::extensions::SubjectKeyIdentifier;
 use crate::cert::parse::CertificateParser;
+use crate::cert::parse::CertificateParsingError;
 use crate::cert::validate::CertificateValidationError;
 use crate::cert::validate::CertificateValidationErrorKind;
 use crossbeam_skiplist::SkipSet;
 use tyst::encdec::hex::ToHex;
 
-/** X.509 Certificate Key Usage validation.
+/** X.509 Certificate key identifier validation.
 
 Authority Key Identifier as defined in
 [RFC5280 4.2.1.1](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.1) and
 Subject Key Identifier as defined in
 [RFC5280 4.2.1.2](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.2).
+
+Optionally the Subject Key Identifier of every certificate is recomputed
+with each known [KeyIdentifierMethod] to detect mismatched or forged
+identifiers.
  */
 #[derive(Default)]
-pub struct KeyIdentifierChecker {}
+pub struct KeyIdentifierChecker {
+    recompute_subject_key_identifier: bool,
+}
+
+impl KeyIdentifierChecker {
+    /// Reject certificates where the Subject Key Identifier can't be derived
+    /// from the public key using any known [KeyIdentifierMethod].
+    pub fn with_subject_key_identifier_recomputation(mut self) -> Self {
+        self.recompute_subject_key_identifier = true;
+        self
+    }
+}
 
 impl ExtensionChecker for KeyIdentifierChecker {
     fn check_extensions(
@@ -42,29 +59,181 @@ impl ExtensionChecker for KeyIdentifierChecker {
         chain_with_trust: &[CertificateParser],
         unresolved_extensions: &SkipSet<Vec<u32>>,
     ) -> Result<(), CertificateValidationError> {
-        let mut last_aki: Option<Vec<u8>> = None;
+        let mut last_aki: Option<AuthorityKeyIdentifier> = None;
         for (i, cp) in chain_with_trust.iter().enumerate() {
+            let decoding_error = |e: CertificateParsingError| {
+                CertificateValidationErrorKind::ExtensionHandlingFailure
+                    .error_with_msg(&format!("[{i}]: Invalid key identifier: {e}"))
+            };
+            let subject_key_identifier_kid = cp
+                .get_subject_key_identifier_kid()
+                .map_err(decoding_error)?;
             if let Some(last_aki) = last_aki {
-                if let Some(ski) = cp.get_subject_key_identifier_kid() {
-                    if !ski.eq(&last_aki) {
+                if let Some(kid) = last_aki.get_key_identifier() {
+                    if let Some(ski) = &subject_key_identifier_kid {
+                        if !ski.eq(kid) {
+                            let msg = format!(
+                                "[{i}]: AuthorityKeyIdentifier '{}' did not match issuer's SubjectKeyIdentifier '{}'.",
+                                kid.to_vec().to_hex(), ski.to_hex(),
+                            );
+                            return Err(CertificateValidationErrorKind::ExtensionHandlingFailure
+                                .error_with_msg(&msg));
+                        }
+                    } else {
+                        return Err(CertificateValidationErrorKind::ExtensionHandlingFailure
+                            .error_with_msg(
+                                "Leaf has AuthorityKeyIdentifier, but issuer is missing SubjectKeyIdentifier."
+                            ));
+                    }
+                }
+                if !last_aki.matches_issuer(cp) {
+                    let msg = format!(
+                        "[{i}]: AuthorityKeyIdentifier issuer and serial number did not match issuer certificate with fingerprint '{}'.",
+                        cp.fingerprint(),
+                    );
+                    return Err(CertificateValidationErrorKind::ExtensionHandlingFailure
+                        .error_with_msg(&msg));
+                }
+            }
+            if self.recompute_subject_key_identifier {
+                if let Some(ski) = &subject_key_identifier_kid {
+                    let spki = cp.as_encoded_subject_public_key_info();
+                    if KeyIdentifierMethod::detect(ski, spki).is_none() {
                         let msg = format!(
-                            "[{i}]: AuthorityKeyIdentifier '{}' did not match issuer's SubjectKeyIdentifier '{}'.",
-                            last_aki.to_hex(), ski.to_hex(),
+                            "[{i}]: SubjectKeyIdentifier '{}' was not derived from the public key using any known method.",
+                            ski.to_hex(),
                         );
                         return Err(CertificateValidationErrorKind::ExtensionHandlingFailure
                             .error_with_msg(&msg));
                     }
-                } else {
-                    return Err(CertificateValidationErrorKind::ExtensionHandlingFailure
-                        .error_with_msg(
-                            "Leaf has AuthorityKeyIdentifier, but issuer is missing SubjectKeyIdentifier."
-                        ));
                 }
             }
-            last_aki = cp.get_authority_key_identifier_kid();
+            last_aki = cp.get_authority_key_identifier().map_err(decoding_error)?;
         }
         unresolved_extensions.remove(&AuthorityKeyIdentifier::OID.to_vec());
         unresolved_extensions.remove(&SubjectKeyIdentifier::OID.to_vec());
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::build::NoSignatureTbsCertificate;
+    use crate::cert::extensions::Extensions;
+    use crate::cert::types::WellKnownAlgorithm;
+    use crate::test_utils::*;
+    use tyst::traits::se::ToPublicKey;
+
+    /// Return a certificate for the public key and subject of the test
+    /// certificate `subject` issued by the test EC CA with the `extensions`.
+    fn issue(subject: &str, extensions: Extensions) -> CertificateParser {
+        let issuer = load_test_certificate("ec_ca.b64");
+        let subject = load_test_certificate(subject);
+        let tbs_certificate = NoSignatureTbsCertificate::new(
+            issuer.get_subject().unwrap(),
+            subject.get_validity().unwrap().get_not_after(),
+            subject.get_subject().unwrap(),
+            subject
+                .get_encoded_subject_public_key_info()
+                .to_public_key()
+                .as_ref(),
+            extensions,
+        );
+        let signature_algorithm = crate::cms::signature_algorithm_identifier(
+            WellKnownAlgorithm::EcdsaWithSha256.as_oid(),
+        );
+        let signature = sign_with_test_key(
+            "ec_ca_key.pem",
+            &WellKnownAlgorithm::EcdsaWithSha256,
+            &tbs_certificate.with_signature_algorithm_as_bytes(&signature_algorithm),
+        );
+        let certificate = tbs_certificate.to_certificate(&signature_algorithm, signature);
+        CertificateParser::from_bytes(&rasn::der::encode(&certificate).unwrap()).unwrap()
+    }
+
+    fn check(
+        checker: &KeyIdentifierChecker,
+        chain_with_trust: &[CertificateParser],
+    ) -> Result<(), CertificateValidationError> {
+        checker.check_extensions(chain_with_trust, &SkipSet::default())
+    }
+
+    #[test]
+    fn subject_key_identifier_recomputation() {
+        init_logger();
+        let checker = KeyIdentifierChecker::default().with_subject_key_identifier_recomputation();
+        // SHA3-256 identifiers
+        let chain = ["h1_leaf.b64", "h1_sub_ca.b64", "h1_root_ca.b64"].map(load_test_certificate);
+        check(&checker, &chain).unwrap();
+        // RFC5280 method 1 identifiers
+        let chain = ["ec_recipient.b64", "ec_ca.b64"].map(load_test_certificate);
+        check(&checker, &chain).unwrap();
+        // Subject Key Identifier of the issuer's public key
+        let ca = load_test_certificate("ec_ca.b64");
+        let mut extensions = Extensions::default();
+        extensions.add_subject_key_identifier(&SubjectKeyIdentifier::from_public_key(
+            ca.get_encoded_subject_public_key_info()
+                .to_public_key()
+                .as_ref(),
+        ));
+        let chain = [issue("ec_recipient.b64", extensions), ca];
+        check(&KeyIdentifierChecker::default(), &chain).unwrap();
+        let e = check(&checker, &chain).unwrap_err();
+        assert!(matches!(
+            e.kind(),
+            CertificateValidationErrorKind::ExtensionHandlingFailure
+        ));
+    }
+
+    #[test]
+    fn authority_key_identifier_mismatch() {
+        init_logger();
+        let checker = KeyIdentifierChecker::default();
+        // Issued by the EC CA, but the chain continues with another CA
+        let chain = ["ec_recipient.b64", "h1_root_ca.b64"].map(load_test_certificate);
+        let e = check(&checker, &chain).unwrap_err();
+        assert!(matches!(
+            e.kind(),
+            CertificateValidationErrorKind::ExtensionHandlingFailure
+        ));
+        // Matching key identifier, but not the issuer's issuer and serial number
+        let ca = load_test_certificate("ec_ca.b64");
+        let other_ca = load_test_certificate("h1_root_ca.b64");
+        let mut extensions = Extensions::default();
+        extensions.add_authority_key_identifier(
+            &AuthorityKeyIdentifier::from_issuer_certificate(&ca, false)
+                .unwrap()
+                .with_authority_cert_issuer_and_serial_number(
+                    other_ca.as_encoded_issuer(),
+                    other_ca.get_serial_number(),
+                ),
+        );
+        let chain = [issue("ec_recipient.b64", extensions), ca.clone()];
+        assert!(check(&checker, &chain).is_err());
+        let mut extensions = Extensions::default();
+        extensions.add_authority_key_identifier(
+            &AuthorityKeyIdentifier::from_issuer_certificate(&ca, true).unwrap(),
+        );
+        let chain = [issue("ec_recipient.b64", extensions), ca];
+        check(&checker, &chain).unwrap();
+    }
+
+    #[test]
+    fn missing_key_identifiers() {
+        init_logger();
+        let checker = KeyIdentifierChecker::default();
+        let ca = load_test_certificate("ec_ca.b64");
+        // The Authority Key Identifier is optional for this checker
+        let chain = [issue("ec_recipient.b64", Extensions::default()), ca];
+        check(&checker, &chain).unwrap();
+        // ..but an issuer without Subject Key Identifier can't match it
+        let leaf = load_test_certificate("ec_recipient.b64");
+        let chain = [leaf, issue("ec_ca.b64", Extensions::default())];
+        let e = check(&checker, &chain).unwrap_err();
+        assert!(matches!(
+            e.kind(),
+            CertificateValidationErrorKind::ExtensionHandlingFailure
+        ));
+    }
+}
@@ -61,8 +61,7 @@ impl ExtensionChecker for KeyUsageChecker {
         chain_with_trust: &[CertificateParser],
         unresolved_extensions: &SkipSet<Vec<u32>>,
     ) -> Result<(), CertificateValidationError> {
-        let mut msg = String::new();
-        let failure = chain_with_trust.iter().enumerate().any(|(i, cp)| {
+        for (i, cp) in chain_with_trust.iter().enumerate() {
             let required_kus = if i > 0 {
                 &self.issuer_kus
                 // Check: Must have "Certificate Sign" to have issued cert
@@ -71,28 +70,27 @@ impl ExtensionChecker for KeyUsageChecker {
                 //self.leaf_kus;
                 &self.leaf_kus
             };
-            let failure = if let Some(ku) = cp.get_key_usage() {
+            let key_usage = cp.get_key_usage().map_err(|e| {
+                CertificateValidationErrorKind::ExtensionHandlingFailure
+                    .error_with_msg(&format!("[{i}]: Invalid KeyUsage: {e}"))
+            })?;
+            let failure = if let Some(ku) = &key_usage {
                 required_kus
                     .iter()
-                    .any(|required_key_usage| !ku[required_key_usage.index()])
+                    .any(|required_key_usage| !ku.contains(required_key_usage))
             } else {
                 !required_kus.is_empty()
             };
             if failure {
-                msg = format!(
-                    "[{i}]: Missing KeyUsage(s). Required: {required_kus:?}, Actual: {:?}",
-                    cp.get_key_usage()
+                let msg = format!(
+                    "[{i}]: Missing KeyUsage(s). Required: {required_kus:?}, Actual: {key_usage:?}"
+                );
+                return Err(
+                    CertificateValidationErrorKind::ExtensionHandlingFailure.error_with_msg(&msg)
                 );
             }
-            failure
-        });
-        if failure {
-            return Err(
-                CertificateValidationErrorKind::ExtensionHandlingFailure.error_with_msg(&msg)
-            );
-        } else {
-            unresolved_extensions.remove(&KeyUsage::OID.to_vec());
         }
+        unresolved_extensions.remove(&KeyUsage::OID.to_vec());
         Ok(())
     }
 }
@@ -0,0 +1,175 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Bounded cache of parsed intermediate certificates and verified signatures.
+
+use crate::cert::parse::CertificateParser;
+use crossbeam_skiplist::SkipMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/** Bounded and thread-safe cache used by the
+[CertificatePathValidator](super::CertificatePathValidator).
+
+Verified signatures are keyed by the fingerprint of the issuer's
+SubjectPublicKeyInfo and the fingerprint of the signed certificate. Parsed
+certificates are keyed by the fingerprint of the DER encoded certificate.
+
+When a cache is full the oldest entry is evicted. The size is approximately
+bounded: concurrent inserts into a full cache may briefly exceed
+`max_entries` by the number of inserting threads.
+*/
+pub(crate) struct ValidationCache {
+    verified_signatures: FifoCache<(String, String), ()>,
+    certificates_by_fingerprint: FifoCache<String, CertificateParser>,
+}
+
+impl ValidationCache {
+    /// Return a new instance holding at most `max_entries` of each kind.
+    pub(crate) fn new(max_entries: usize) -> Self {
+        Self {
+            verified_signatures: FifoCache::new(max_entries),
+            certificates_by_fingerprint: FifoCache::new(max_entries),
+        }
+    }
+
+    /// Return `true` if the signature of the certificate with
+    /// `child_fingerprint` was verified using the issuer public key with
+    /// `issuer_spki_fingerprint`.
+    pub(crate) fn is_verified_signature(
+        &self,
+        issuer_spki_fingerprint: &str,
+        child_fingerprint: &str,
+    ) -> bool {
+        self.verified_signatures
+            .get(&(
+                issuer_spki_fingerprint.to_string(),
+                child_fingerprint.to_string(),
+            ))
+            .is_some()
+    }
+
+    /// Remember that the signature was successfully verified.
+    pub(crate) fn insert_verified_signature(
+        &self,
+        issuer_spki_fingerprint: &str,
+        child_fingerprint: &str,
+    ) {
+        self.verified_signatures.insert(
+            (
+                issuer_spki_fingerprint.to_string(),
+                child_fingerprint.to_string(),
+            ),
+            (),
+        );
+    }
+
+    /// Return the number of remembered verified signatures.
+    #[cfg(test)]
+    pub(crate) fn verified_signatures_len(&self) -> usize {
+        self.verified_signatures.entries.len()
+    }
+
+    /// Return a previously parsed certificate.
+    pub(crate) fn get_certificate(&self, fingerprint: &str) -> Option<CertificateParser> {
+        self.certificates_by_fingerprint
+            .get(&fingerprint.to_string())
+    }
+
+    /// Remember a parsed certificate.
+    pub(crate) fn insert_certificate(&self, cp: &CertificateParser) {
+        self.certificates_by_fingerprint
+            .insert(cp.fingerprint().to_string(), cp.clone());
+    }
+
+    /// Return the number of remembered parsed certificates.
+    #[cfg(test)]
+    pub(crate) fn certificates_len(&self) -> usize {
+        self.certificates_by_fingerprint.entries.len()
+    }
+}
+
+/// Thread-safe map that evicts the oldest entry when full.
+struct FifoCache<K, V> {
+    max_entries: usize,
+    next_sequence: AtomicU64,
+    /// Keys by insertion sequence, oldest first.
+    insertion_order: SkipMap<u64, K>,
+    /// Values with the insertion sequence of the key.
+    entries: SkipMap<K, (u64, V)>,
+}
+
+impl<K, V> FifoCache<K, V>
+where
+    K: Ord + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+{
+    fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            next_sequence: AtomicU64::new(0),
+            insertion_order: SkipMap::default(),
+            entries: SkipMap::default(),
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        self.entries.get(key).map(|entry| entry.value().1.clone())
+    }
+
+    fn insert(&self, key: K, value: V) {
+        if self.entries.contains_key(&key) {
+            return;
+        }
+        while self.entries.len() >= self.max_entries {
+            let Some(oldest) = self.insertion_order.pop_front() else {
+                break;
+            };
+            // The key might have been re-inserted after a concurrent eviction
+            if let Some(entry) = self.entries.get(oldest.value()) {
+                if entry.value().0 == *oldest.key() {
+                    entry.remove();
+                }
+            }
+        }
+        let sequence = self.next_sequence.fetch_add(1, Ordering::Relaxed);
+        self.insertion_order.insert(sequence, key.clone());
+        self.entries.insert(key, (sequence, value));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bounded_verified_signatures() {
+        let cache = ValidationCache::new(2);
+        cache.insert_verified_signature("issuer", "b");
+        cache.insert_verified_signature("issuer", "a");
+        cache.insert_verified_signature("issuer", "c");
+        // The oldest entry is evicted, not the lowest
+        assert!(!cache.is_verified_signature("issuer", "b"));
+        assert!(cache.is_verified_signature("issuer", "a"));
+        assert!(cache.is_verified_signature("issuer", "c"));
+        assert_eq!(cache.verified_signatures_len(), 2);
+        // Inserting a present entry doesn't evict anything
+        cache.insert_verified_signature("issuer", "a");
+        assert!(cache.is_verified_signature("issuer", "c"));
+        assert_eq!(cache.verified_signatures_len(), 2);
+    }
+}
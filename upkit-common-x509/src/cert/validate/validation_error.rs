@@ -30,7 +30,7 @@ pub enum CertificateValidationErrorKind {
     /// An unknown signature algorithm was used to sign the certificate.
     UnknownSignature,
     /// The certificate was not yet valid or had expired for the point in time
-    /// of validation.
+    /// of validation, or the leaf exceeded the maximum allowed lifetime.
     InvalidLifeSpan,
     /// None or more than one leaf was provided in the certicate chain to
     /// validate.
@@ -41,6 +41,10 @@ pub enum CertificateValidationErrorKind {
     UnhandledCriticalExtensions,
     /// Rejected certificate while verifying one of the certificate extensions.
     ExtensionHandlingFailure,
+    /// The certificate was revoked at the point in time of validation.
+    Revoked,
+    /// No usable revocation information was available for the certificate.
+    RevocationStatusUnknown,
 }
 
 impl CertificateValidationErrorKind {
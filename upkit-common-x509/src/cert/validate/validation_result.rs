@@ -0,0 +1,143 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Result of certificate path validation.
+
+/** How the point in time of validation is applied to a certificate chain.
+
+The shell model is the traditional [RFC5280](https://www.rfc-editor.org/rfc/rfc5280)
+model and appropriate when validating "now".
+
+The chain model is used by some long-term signature schemes (e.g. qualified
+signatures), where a signature remains valid if every certificate was valid
+when it was used: the leaf certificate at the trusted signing time and each CA
+certificate at the time it issued the certificate below it.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValidationModel {
+    /// Every certificate of the chain is validated at the same point in time.
+    #[default]
+    Shell,
+    /// The leaf is validated at the point in time of validation and each
+    /// issuer at the issuance time (`notBefore`) of the certificate it issued.
+    Chain,
+}
+
+impl ValidationModel {
+    /// Return the point in time each certificate should be validated at from
+    /// the `notBefore` of each certificate ordered from leaf to trust anchor.
+    pub(crate) fn validation_times(&self, not_befores: &[u64], at_epoch_seconds: u64) -> Vec<u64> {
+        match self {
+            Self::Shell => vec![at_epoch_seconds; not_befores.len()],
+            Self::Chain => std::iter::once(at_epoch_seconds)
+                .chain(
+                    not_befores
+                        .iter()
+                        .take(not_befores.len().saturating_sub(1))
+                        .copied(),
+                )
+                .collect(),
+        }
+    }
+}
+
+/// Non-fatal finding of a successful certificate path validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationWarning {
+    /// The certificate expires within the warning period of the
+    /// [ValidationTimePolicy](super::ValidationTimePolicy).
+    ExpiresSoon {
+        /// Fingerprint of the certificate.
+        fingerprint: String,
+        /// `notAfter` in seconds since 1970-01-01 00:00:00.
+        not_after: u64,
+    },
+    /// The certificate was only accepted due to the tolerated clock skew.
+    WithinClockSkew {
+        /// Fingerprint of the certificate.
+        fingerprint: String,
+    },
+    /// The trust anchor was not valid, but its validity is not enforced.
+    TrustAnchorNotValid {
+        /// Fingerprint of the trust anchor.
+        fingerprint: String,
+    },
+}
+
+/// Details of a successful certificate path validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationResult {
+    validation_model: ValidationModel,
+    at_epoch_seconds: u64,
+    validation_times: Vec<(String, u64)>,
+    warnings: Vec<ValidationWarning>,
+}
+
+impl ValidationResult {
+    pub(crate) fn new(
+        validation_model: ValidationModel,
+        at_epoch_seconds: u64,
+        validation_times: Vec<(String, u64)>,
+        warnings: Vec<ValidationWarning>,
+    ) -> Self {
+        Self {
+            validation_model,
+            at_epoch_seconds,
+            validation_times,
+            warnings,
+        }
+    }
+
+    /// Return the [ValidationModel] that was used.
+    pub fn get_validation_model(&self) -> ValidationModel {
+        self.validation_model
+    }
+
+    /// Return the point in time of validation (e.g. the trusted signing time)
+    /// in seconds since 1970-01-01 00:00:00.
+    pub fn get_at_epoch_seconds(&self) -> u64 {
+        self.at_epoch_seconds
+    }
+
+    /// Return the fingerprint of each certificate of the validated chain from
+    /// leaf to trust anchor with the point in time it was validated at.
+    pub fn get_validation_times(&self) -> &[(String, u64)] {
+        &self.validation_times
+    }
+
+    /// Return the [ValidationWarning]s of the validation.
+    pub fn get_warnings(&self) -> &[ValidationWarning] {
+        &self.warnings
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn chain_model_uses_issuance_time_of_child() {
+        let not_befores = [300, 200, 100];
+        assert_eq!(
+            ValidationModel::Shell.validation_times(&not_befores, 1000),
+            vec![1000, 1000, 1000]
+        );
+        assert_eq!(
+            ValidationModel::Chain.validation_times(&not_befores, 1000),
+            vec![1000, 300, 200]
+        );
+    }
+}
@@ -0,0 +1,142 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Policy for how certificate validity periods are enforced.
+
+use crate::cert::types::Validity;
+
+/** Policy for how certificate validity periods are enforced during path
+validation.
+
+The default policy is strict: No clock skew is tolerated, the trust anchor
+must be valid, the leaf lifetime is not limited and no warnings are produced.
+
+Enforcing the validity of the trust anchor is optional according to
+[RFC5280 6.1.1](https://www.rfc-editor.org/rfc/rfc5280.html#section-6.1.1).
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationTimePolicy {
+    clock_skew_seconds: u64,
+    enforce_trust_anchor_validity: bool,
+    max_leaf_lifetime_seconds: Option<u64>,
+    expiry_warning_seconds: Option<u64>,
+}
+
+impl Default for ValidationTimePolicy {
+    fn default() -> Self {
+        Self {
+            clock_skew_seconds: 0,
+            enforce_trust_anchor_validity: true,
+            max_leaf_lifetime_seconds: None,
+            expiry_warning_seconds: None,
+        }
+    }
+}
+
+impl ValidationTimePolicy {
+    /// Tolerate that the point in time of validation is up to
+    /// `clock_skew_seconds` before `notBefore` or after `notAfter`.
+    pub fn with_clock_skew_tolerance(mut self, clock_skew_seconds: u64) -> Self {
+        self.clock_skew_seconds = clock_skew_seconds;
+        self
+    }
+
+    /// Set if the validity of the trust anchor is enforced (default `true`).
+    pub fn with_trust_anchor_validity(mut self, enforce_trust_anchor_validity: bool) -> Self {
+        self.enforce_trust_anchor_validity = enforce_trust_anchor_validity;
+        self
+    }
+
+    /// Reject leaf certificates where the time between `notBefore` and
+    /// `notAfter` exceeds `max_leaf_lifetime_seconds` (e.g. 398 days).
+    pub fn with_max_leaf_lifetime(mut self, max_leaf_lifetime_seconds: u64) -> Self {
+        self.max_leaf_lifetime_seconds = Some(max_leaf_lifetime_seconds);
+        self
+    }
+
+    /// Warn when a certificate in the chain expires within
+    /// `expiry_warning_seconds` from the point in time of validation.
+    pub fn with_expiry_warning(mut self, expiry_warning_seconds: u64) -> Self {
+        self.expiry_warning_seconds = Some(expiry_warning_seconds);
+        self
+    }
+
+    /// Return the tolerated clock skew in seconds.
+    pub fn get_clock_skew_seconds(&self) -> u64 {
+        self.clock_skew_seconds
+    }
+
+    /// Return `true` if the validity of the trust anchor is enforced.
+    pub fn is_trust_anchor_validity_enforced(&self) -> bool {
+        self.enforce_trust_anchor_validity
+    }
+
+    /// Return the maximum allowed leaf lifetime in seconds if limited.
+    pub fn get_max_leaf_lifetime_seconds(&self) -> Option<u64> {
+        self.max_leaf_lifetime_seconds
+    }
+
+    /// Return `true` if `validity` is valid at `at_epoch_seconds` with the
+    /// tolerated clock skew.
+    pub fn is_valid_at(&self, validity: &Validity, at_epoch_seconds: u64) -> bool {
+        Validity::new(
+            validity
+                .get_not_before()
+                .saturating_sub(self.clock_skew_seconds),
+            validity
+                .get_not_after()
+                .saturating_add(self.clock_skew_seconds),
+        )
+        .is_valid_at(at_epoch_seconds)
+    }
+
+    /// Return `true` if `validity` expires within the configured warning
+    /// period from `at_epoch_seconds`.
+    pub fn is_expiring_soon(&self, validity: &Validity, at_epoch_seconds: u64) -> bool {
+        self.expiry_warning_seconds.is_some_and(|seconds| {
+            validity.get_not_after() < at_epoch_seconds.saturating_add(seconds)
+        })
+    }
+
+    /// Return `true` if the lifetime of the leaf certificate `validity`
+    /// exceeds the maximum allowed.
+    pub fn is_leaf_lifetime_exceeded(&self, validity: &Validity) -> bool {
+        self.max_leaf_lifetime_seconds.is_some_and(|max| {
+            validity
+                .get_not_after()
+                .saturating_sub(validity.get_not_before())
+                > max
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn clock_skew_tolerance() {
+        let validity = Validity::new(1000, 2000);
+        let strict = ValidationTimePolicy::default();
+        assert!(!strict.is_valid_at(&validity, 990));
+        assert!(!strict.is_valid_at(&validity, 2010));
+        let tolerant = ValidationTimePolicy::default().with_clock_skew_tolerance(60);
+        assert!(tolerant.is_valid_at(&validity, 990));
+        assert!(tolerant.is_valid_at(&validity, 2010));
+        assert!(!tolerant.is_valid_at(&validity, 2061));
+    }
+}
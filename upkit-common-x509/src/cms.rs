@@ -0,0 +1,286 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! [RFC5652](https://www.rfc-editor.org/rfc/rfc5652) Cryptographic Message
+//! Syntax (CMS).
+
+/*
+ContentInfo ::= SEQUENCE {
+    contentType ContentType,
+    content [0] EXPLICIT ANY DEFINED BY contentType }
+
+IssuerAndSerialNumber ::= SEQUENCE {
+    issuer Name,
+    serialNumber CertificateSerialNumber }
+
+SignerIdentifier ::= CHOICE {
+    issuerAndSerialNumber IssuerAndSerialNumber,
+    subjectKeyIdentifier [0] SubjectKeyIdentifier }
+
+RecipientIdentifier ::= CHOICE {
+    issuerAndSerialNumber IssuerAndSerialNumber,
+    subjectKeyIdentifier [0] SubjectKeyIdentifier }
+*/
+
+mod cms_error;
+mod enveloped_data;
+mod enveloped_data_parser;
+mod recipient_info;
+mod signed_data;
+mod signed_data_parser;
+
+pub use self::cms_error::CmsError;
+pub use self::cms_error::CmsErrorKind;
+pub use self::enveloped_data::EnvelopedDataBuilder;
+pub use self::enveloped_data_parser::EnvelopedDataParser;
+pub use self::signed_data::SignedDataBuilder;
+pub use self::signed_data_parser::SignedDataParser;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::SerialNumber;
+use crate::cert::types::WellKnownAlgorithm;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+use sha2::Digest;
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct ContentInfo {
+    pub(crate) content_type: ObjectIdentifier,
+    #[rasn(tag(explicit(context, 0)))]
+    pub(crate) content: Any,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct IssuerAndSerialNumber {
+    pub(crate) issuer: rasn_pkix::Name,
+    pub(crate) serial_number: Integer,
+}
+
+impl IssuerAndSerialNumber {
+    pub(crate) fn from_certificate(certificate: &CertificateParser) -> Self {
+        Self {
+            issuer: rasn::der::decode(certificate.as_encoded_issuer()).unwrap(),
+            serial_number: certificate.get_serial_number().to_rasn_type(),
+        }
+    }
+
+    /// Return the lookup key.
+    pub(crate) fn to_key(&self) -> crate::cert::types::IssuerAndSerialNumber {
+        crate::cert::types::IssuerAndSerialNumber::new(
+            &rasn::der::encode(&self.issuer).unwrap(),
+            SerialNumber::from_rasn_type(&self.serial_number),
+        )
+    }
+
+    pub(crate) fn matches(&self, certificate: &CertificateParser) -> bool {
+        self.to_key()
+            .eq(&crate::cert::types::IssuerAndSerialNumber::from_certificate(certificate))
+    }
+}
+
+/// `SignerIdentifier` or `RecipientIdentifier`.
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+#[rasn(choice)]
+pub(crate) enum CertificateIdentifier {
+    IssuerAndSerialNumber(IssuerAndSerialNumber),
+    #[rasn(tag(context, 0))]
+    SubjectKeyIdentifier(OctetString),
+}
+
+impl CertificateIdentifier {
+    /// Return `None` if a Subject Key Identifier is requested, but the
+    /// certificate doesn't have one.
+    pub(crate) fn from_certificate(
+        certificate: &CertificateParser,
+        certificate_identifier_type: CertificateIdentifierType,
+    ) -> Option<Self> {
+        match certificate_identifier_type {
+            CertificateIdentifierType::IssuerAndSerialNumber => Some(Self::IssuerAndSerialNumber(
+                IssuerAndSerialNumber::from_certificate(certificate),
+            )),
+            CertificateIdentifierType::SubjectKeyIdentifier => certificate
+                .get_subject_key_identifier_kid()
+                .ok()
+                .flatten()
+                .map(|kid| Self::SubjectKeyIdentifier(OctetString::from(kid))),
+        }
+    }
+
+    pub(crate) fn matches(&self, certificate: &CertificateParser) -> bool {
+        match self {
+            Self::IssuerAndSerialNumber(issuer_and_serial_number) => {
+                issuer_and_serial_number.matches(certificate)
+            }
+            Self::SubjectKeyIdentifier(kid) => certificate
+                .get_subject_key_identifier_kid()
+                .ok()
+                .flatten()
+                .is_some_and(|ski| ski.eq(kid.as_ref())),
+        }
+    }
+}
+
+/// `id-data`
+const OID_DATA: &[u32] = &[1, 2, 840, 113549, 1, 7, 1];
+/// `id-signedData`
+const OID_SIGNED_DATA: &[u32] = &[1, 2, 840, 113549, 1, 7, 2];
+/// `id-envelopedData`
+const OID_ENVELOPED_DATA: &[u32] = &[1, 2, 840, 113549, 1, 7, 3];
+/// `id-ct-authEnvelopedData`
+const OID_AUTH_ENVELOPED_DATA: &[u32] = &[1, 2, 840, 113549, 1, 9, 16, 1, 23];
+/// `id-contentType`
+const OID_ATTRIBUTE_CONTENT_TYPE: &[u32] = &[1, 2, 840, 113549, 1, 9, 3];
+/// `id-messageDigest`
+const OID_ATTRIBUTE_MESSAGE_DIGEST: &[u32] = &[1, 2, 840, 113549, 1, 9, 4];
+/// `id-signingTime`
+const OID_ATTRIBUTE_SIGNING_TIME: &[u32] = &[1, 2, 840, 113549, 1, 9, 5];
+/// `id-aa-signatureTimeStampToken`
+const OID_ATTRIBUTE_SIGNATURE_TIME_STAMP_TOKEN: &[u32] = &[1, 2, 840, 113549, 1, 9, 16, 2, 14];
+/// `id-aa-signingCertificate`
+const OID_ATTRIBUTE_SIGNING_CERTIFICATE: &[u32] = &[1, 2, 840, 113549, 1, 9, 16, 2, 12];
+/// `id-aa-signingCertificateV2`
+const OID_ATTRIBUTE_SIGNING_CERTIFICATE_V2: &[u32] = &[1, 2, 840, 113549, 1, 9, 16, 2, 47];
+
+/// How a recipient or signer certificate is referenced in a message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CertificateIdentifierType {
+    /// Certificate issuer and serial number. (Version 1 structures.)
+    #[default]
+    IssuerAndSerialNumber,
+    /// Subject Key Identifier extension of the certificate.
+    SubjectKeyIdentifier,
+}
+
+/// Message digest algorithm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CmsDigestAlgorithm {
+    /// SHA-256
+    #[default]
+    Sha256,
+    /// SHA-384
+    Sha384,
+    /// SHA-512 (required for e.g. Ed25519 by RFC8419)
+    Sha512,
+}
+
+impl CmsDigestAlgorithm {
+    /// Return the algorithm OID
+    pub fn as_oid(&self) -> &'static [u32] {
+        match self {
+            Self::Sha256 => WellKnownAlgorithm::Sha256.as_oid(),
+            Self::Sha384 => WellKnownAlgorithm::Sha384.as_oid(),
+            Self::Sha512 => WellKnownAlgorithm::Sha512.as_oid(),
+        }
+    }
+
+    /// Return a new instance from the algorithm OID
+    pub fn from_oid(oid: &[u32]) -> Option<Self> {
+        [Self::Sha256, Self::Sha384, Self::Sha512]
+            .into_iter()
+            .find(|alg| alg.as_oid().eq(oid))
+    }
+
+    /// Return the digest of the `data`.
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha256 => sha2::Sha256::digest(data).to_vec(),
+            Self::Sha384 => sha2::Sha384::digest(data).to_vec(),
+            Self::Sha512 => sha2::Sha512::digest(data).to_vec(),
+        }
+    }
+
+    /// Parameters are absent as recommended by RFC5754.
+    pub(crate) fn to_algorithm_identifier(self) -> AlgorithmIdentifier {
+        AlgorithmIdentifier {
+            algorithm: ObjectIdentifier::new_unchecked(self.as_oid().into()),
+            parameters: None,
+        }
+    }
+
+    pub(crate) fn from_algorithm_identifier(
+        algorithm_identifier: &AlgorithmIdentifier,
+    ) -> Result<Self, CmsError> {
+        Self::from_oid(&algorithm_identifier.algorithm).ok_or_else(|| {
+            let msg = format!(
+                "Unsupported digest algorithm '{}'.",
+                crate::encdec::oid::as_string(&algorithm_identifier.algorithm)
+            );
+            CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+        })
+    }
+}
+
+/** Return the DER encoded signature `AlgorithmIdentifier`.
+
+[RFC4055 5](https://www.rfc-editor.org/rfc/rfc4055#section-5) requires `NULL`
+parameters for the PKCS #1 v1.5 algorithms. All other supported signature
+algorithms have absent parameters.
+*/
+pub fn signature_algorithm_identifier(signature_algorithm_oid: &[u32]) -> Vec<u8> {
+    let parameters = match WellKnownAlgorithm::from_oid(signature_algorithm_oid) {
+        Some(
+            WellKnownAlgorithm::Sha1WithRsaEncryption
+            | WellKnownAlgorithm::Sha256WithRsaEncryption
+            | WellKnownAlgorithm::Sha384WithRsaEncryption
+            | WellKnownAlgorithm::Sha512WithRsaEncryption,
+        ) => Some(Any::new(rasn::der::encode(&()).unwrap())),
+        _ => None,
+    };
+    rasn::der::encode(&AlgorithmIdentifier {
+        algorithm: ObjectIdentifier::new_unchecked(signature_algorithm_oid.to_vec().into()),
+        parameters,
+    })
+    .unwrap()
+}
+
+fn attribute(oid: &[u32], value: Vec<u8>) -> crate::encdec::rasn::Attribute {
+    let mut values = SetOf::new();
+    values.insert(Any::new(value));
+    crate::encdec::rasn::Attribute {
+        r#type: ObjectIdentifier::new_unchecked(oid.to_vec().into()),
+        values,
+    }
+}
+
+/// Return the single value of the attribute with the `oid` (if present).
+fn attribute_value<'a>(
+    attributes: &'a SetOf<crate::encdec::rasn::Attribute>,
+    oid: &[u32],
+) -> Result<Option<&'a Any>, CmsError> {
+    let matching = attributes
+        .to_vec()
+        .into_iter()
+        .filter(|attribute| attribute.r#type.to_vec().eq(oid))
+        .collect::<Vec<_>>();
+    match matching.as_slice() {
+        [] => Ok(None),
+        [attribute] if attribute.values.to_vec().len() == 1 => {
+            Ok(attribute.values.to_vec().into_iter().next())
+        }
+        _ => {
+            let msg = format!(
+                "Attribute '{}' must occur once with a single value.",
+                crate::encdec::oid::as_string(oid)
+            );
+            Err(CmsErrorKind::InvalidAttributes.error_with_msg(&msg))
+        }
+    }
+}
+
+fn decode<T: rasn::Decode>(encoded: &[u8]) -> Result<T, CmsError> {
+    rasn::ber::decode::<T>(encoded)
+        .map_err(|e| CmsErrorKind::DecodingError.error_with_msg(&e.to_string()))
+}
@@ -0,0 +1,99 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Cryptographic Message Syntax errors.
+
+use std::error::Error;
+use std::fmt;
+
+/// Cause of Cryptographic Message Syntax error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CmsErrorKind {
+    /// Failure to decode the BER or DER encoded message or one of its parts.
+    DecodingError,
+    /// The message uses an algorithm that is not supported.
+    UnsupportedAlgorithm,
+    /// No certificate matching the signer identifier was found.
+    SignerNotFound,
+    /// A mandatory signed attribute is missing or does not match the content.
+    InvalidAttributes,
+    /// The signature could not be verified.
+    InvalidSignature,
+    /// The signer certificate failed path validation.
+    InvalidSignerCertificate,
+    /// No recipient information matching the recipient certificate was found.
+    RecipientNotFound,
+    /// The Key Usage of the certificate doesn't allow the intended use.
+    InvalidKeyUsage,
+    /// Failure to encrypt the content or content-encryption key.
+    EncryptionFailure,
+    /// Failure to decrypt the content or content-encryption key.
+    DecryptionFailure,
+}
+
+impl CmsErrorKind {
+    /// Create a new instance with an error message.
+    pub fn error_with_msg(self, msg: &str) -> CmsError {
+        CmsError {
+            kind: self,
+            msg: Some(msg.to_string()),
+        }
+    }
+
+    /// Create a new instance without an error message.
+    pub fn error(self) -> CmsError {
+        CmsError {
+            kind: self,
+            msg: None,
+        }
+    }
+}
+
+impl fmt::Display for CmsErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/** Cryptographic Message Syntax error.
+
+Create a new instance via [CmsErrorKind].
+*/
+#[derive(Debug)]
+pub struct CmsError {
+    kind: CmsErrorKind,
+    msg: Option<String>,
+}
+
+impl CmsError {
+    /// Return the [CmsErrorKind] type of this error.
+    pub fn kind(&self) -> &CmsErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for CmsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(msg) = &self.msg {
+            write!(f, "{} {}", self.kind, msg)
+        } else {
+            write!(f, "{}", self.kind)
+        }
+    }
+}
+
+impl Error for CmsError {}
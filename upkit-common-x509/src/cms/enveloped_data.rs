@@ -0,0 +1,271 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! [RFC5652 6](https://www.rfc-editor.org/rfc/rfc5652#section-6) EnvelopedData
+//! and [RFC5083](https://www.rfc-editor.org/rfc/rfc5083) AuthEnvelopedData
+//! creation.
+
+/*
+EnvelopedData ::= SEQUENCE {
+    version CMSVersion,
+    originatorInfo [0] IMPLICIT OriginatorInfo OPTIONAL,
+    recipientInfos RecipientInfos,
+    encryptedContentInfo EncryptedContentInfo,
+    unprotectedAttrs [1] IMPLICIT UnprotectedAttributes OPTIONAL }
+
+AuthEnvelopedData ::= SEQUENCE {
+    version CMSVersion,
+    originatorInfo [0] IMPLICIT OriginatorInfo OPTIONAL,
+    recipientInfos RecipientInfos,
+    authEncryptedContentInfo EncryptedContentInfo,
+    authAttrs [1] IMPLICIT AuthAttributes OPTIONAL,
+    mac MessageAuthenticationCode,
+    unauthAttrs [2] IMPLICIT UnauthAttributes OPTIONAL }
+
+OriginatorInfo ::= SEQUENCE {
+    certs [0] IMPLICIT CertificateSet OPTIONAL,
+    crls [1] IMPLICIT RevocationInfoChoices OPTIONAL }
+
+EncryptedContentInfo ::= SEQUENCE {
+    contentType ContentType,
+    contentEncryptionAlgorithm ContentEncryptionAlgorithmIdentifier,
+    encryptedContent [0] IMPLICIT EncryptedContent OPTIONAL }
+*/
+
+use super::recipient_info::RecipientInfo;
+use super::recipient_info::RecipientKeyType;
+use super::CertificateIdentifierType;
+use super::CmsError;
+use super::CmsErrorKind;
+use super::ContentInfo;
+use crate::cert::parse::CertificateParser;
+use crate::encdec::rasn::Attribute;
+use crate::key::GcmParameters;
+use crate::key::PbeCipher;
+use crate::key::PbeParameters;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct EnvelopedData {
+    pub(crate) version: Integer,
+    #[rasn(tag(context, 0))]
+    pub(crate) originator_info: Option<OriginatorInfo>,
+    pub(crate) recipient_infos: SetOf<RecipientInfo>,
+    pub(crate) encrypted_content_info: EncryptedContentInfo,
+    #[rasn(tag(context, 1))]
+    pub(crate) unprotected_attrs: Option<SetOf<Attribute>>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct AuthEnvelopedData {
+    pub(crate) version: Integer,
+    #[rasn(tag(context, 0))]
+    pub(crate) originator_info: Option<OriginatorInfo>,
+    pub(crate) recipient_infos: SetOf<RecipientInfo>,
+    pub(crate) auth_encrypted_content_info: EncryptedContentInfo,
+    #[rasn(tag(context, 1))]
+    pub(crate) auth_attrs: Option<SetOf<Attribute>>,
+    pub(crate) mac: OctetString,
+    #[rasn(tag(context, 2))]
+    pub(crate) unauth_attrs: Option<SetOf<Attribute>>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct OriginatorInfo {
+    #[rasn(tag(context, 0))]
+    pub(crate) certs: Option<SetOf<Any>>,
+    #[rasn(tag(context, 1))]
+    pub(crate) crls: Option<SetOf<Any>>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct EncryptedContentInfo {
+    pub(crate) content_type: ObjectIdentifier,
+    pub(crate) content_encryption_algorithm: AlgorithmIdentifier,
+    #[rasn(tag(context, 0))]
+    pub(crate) encrypted_content: Option<OctetString>,
+}
+
+/// Length of the authentication tag of AES-GCM in bytes.
+pub(crate) const GCM_ICV_LENGTH: usize = 16;
+
+/** CMS EnvelopedData builder.
+
+The content is encrypted with a random AES-256 content-encryption key, which
+is then encrypted for each recipient based on the certificate's public key:
+
+* RSA: `KeyTransRecipientInfo` with RSAES-OAEP (SHA-256).
+* P-256 and P-384: `KeyAgreeRecipientInfo` with ephemeral-static ECDH, the
+  X9.63 KDF with SHA-256 and AES-256 key wrap.
+* ML-KEM: `KEMRecipientInfo` ([RFC9629](https://www.rfc-editor.org/rfc/rfc9629))
+  with HKDF-SHA256 and AES-256 key wrap.
+
+The content is encrypted with AES-256-CBC, or with AES-256-GCM in an
+AuthEnvelopedData when [Self::with_authenticated_encryption] is used.
+*/
+pub struct EnvelopedDataBuilder {
+    content_type: Vec<u32>,
+    content: Vec<u8>,
+    recipients: Vec<(CertificateParser, RecipientKeyType)>,
+    recipient_identifier_type: CertificateIdentifierType,
+    authenticated: bool,
+}
+
+impl EnvelopedDataBuilder {
+    /// Return a new instance that will encrypt the `content`.
+    pub fn new(content: &[u8]) -> Self {
+        Self {
+            content_type: super::OID_DATA.to_vec(),
+            content: content.to_vec(),
+            recipients: vec![],
+            recipient_identifier_type: CertificateIdentifierType::default(),
+            authenticated: false,
+        }
+    }
+
+    /// Use a content type other than `id-data`.
+    pub fn with_content_type(mut self, content_type: &[u32]) -> Self {
+        self.content_type = content_type.to_vec();
+        self
+    }
+
+    /** Add a recipient by DER encoded certificate.
+
+    Fails if the public key algorithm is not supported or if the Key Usage of
+    the certificate doesn't allow `keyEncipherment` (RSA and KEM) or
+    `keyAgreement` (EC).
+    */
+    pub fn add_recipient(mut self, recipient_certificate_der: &[u8]) -> Result<Self, CmsError> {
+        let certificate = CertificateParser::from_bytes(recipient_certificate_der)
+            .map_err(|e| CmsErrorKind::DecodingError.error_with_msg(&e.to_string()))?;
+        let recipient_key_type = RecipientKeyType::from_certificate(&certificate)?;
+        self.recipients.push((certificate, recipient_key_type));
+        Ok(self)
+    }
+
+    /// Identify the recipients by something other than issuer and serial
+    /// number.
+    pub fn with_recipient_identifier_type(
+        mut self,
+        recipient_identifier_type: CertificateIdentifierType,
+    ) -> Self {
+        self.recipient_identifier_type = recipient_identifier_type;
+        self
+    }
+
+    /// Use AES-256-GCM in an AuthEnvelopedData instead of AES-256-CBC in an
+    /// EnvelopedData.
+    pub fn with_authenticated_encryption(mut self) -> Self {
+        self.authenticated = true;
+        self
+    }
+
+    /// Return the DER encoded `ContentInfo` with the EnvelopedData or
+    /// AuthEnvelopedData.
+    pub fn build(&self) -> Result<Vec<u8>, CmsError> {
+        if self.recipients.is_empty() {
+            return Err(CmsErrorKind::RecipientNotFound.error_with_msg("No recipients."));
+        }
+        let cipher = if self.authenticated {
+            PbeCipher::Aes256Gcm
+        } else {
+            PbeCipher::Aes256Cbc
+        };
+        let cek = PbeParameters::random(cipher.key_length());
+        let mut recipient_infos = SetOf::new();
+        let mut has_other_recipient_info = false;
+        let mut all_version_zero = true;
+        for (certificate, recipient_key_type) in &self.recipients {
+            let recipient_info = recipient_key_type.encrypt_key(
+                certificate,
+                self.recipient_identifier_type,
+                &cek,
+            )?;
+            has_other_recipient_info |= recipient_info.is_other();
+            all_version_zero &= recipient_info.version() == 0;
+            recipient_infos.insert(recipient_info);
+        }
+        let content_type = ObjectIdentifier::new_unchecked(self.content_type.to_vec().into());
+        let (content_type_oid, content) = if self.authenticated {
+            let nonce = PbeParameters::random(12);
+            let mut ciphertext =
+                PbeParameters::gcm_encrypt::<aes_gcm::Aes256Gcm>(&cek, &nonce, &self.content)
+                    .map_err(|e| CmsErrorKind::EncryptionFailure.error_with_msg(&e.to_string()))?;
+            let mac = ciphertext.split_off(ciphertext.len() - GCM_ICV_LENGTH);
+            let parameters = GcmParameters {
+                aes_nonce: OctetString::from(nonce),
+                aes_icv_len: Some(Integer::from(GCM_ICV_LENGTH)),
+            };
+            let auth_enveloped_data = AuthEnvelopedData {
+                version: Integer::from(0),
+                originator_info: None,
+                recipient_infos,
+                auth_encrypted_content_info: EncryptedContentInfo {
+                    content_type,
+                    content_encryption_algorithm: PbeParameters::algorithm_identifier(
+                        cipher.as_oid(),
+                        &parameters,
+                    ),
+                    encrypted_content: Some(OctetString::from(ciphertext)),
+                },
+                auth_attrs: None,
+                mac: OctetString::from(mac),
+                unauth_attrs: None,
+            };
+            (
+                super::OID_AUTH_ENVELOPED_DATA,
+                rasn::der::encode(&auth_enveloped_data).unwrap(),
+            )
+        } else {
+            let iv = PbeParameters::random(16);
+            let ciphertext = PbeParameters::cbc_encrypt::<aes::Aes256>(&cek, &iv, &self.content)
+                .map_err(|e| CmsErrorKind::EncryptionFailure.error_with_msg(&e.to_string()))?;
+            // RFC5652 6.1 version selection without originatorInfo and
+            // unprotectedAttrs
+            let version = if has_other_recipient_info {
+                3
+            } else if all_version_zero {
+                0
+            } else {
+                2
+            };
+            let enveloped_data = EnvelopedData {
+                version: Integer::from(version),
+                originator_info: None,
+                recipient_infos,
+                encrypted_content_info: EncryptedContentInfo {
+                    content_type,
+                    content_encryption_algorithm: PbeParameters::algorithm_identifier(
+                        cipher.as_oid(),
+                        &OctetString::from(iv),
+                    ),
+                    encrypted_content: Some(OctetString::from(ciphertext)),
+                },
+                unprotected_attrs: None,
+            };
+            (
+                super::OID_ENVELOPED_DATA,
+                rasn::der::encode(&enveloped_data).unwrap(),
+            )
+        };
+        Ok(rasn::der::encode(&ContentInfo {
+            content_type: ObjectIdentifier::new_unchecked(content_type_oid.to_vec().into()),
+            content: Any::new(content),
+        })
+        .unwrap())
+    }
+}
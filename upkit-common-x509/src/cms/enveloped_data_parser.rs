@@ -0,0 +1,369 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! [RFC5652 6](https://www.rfc-editor.org/rfc/rfc5652#section-6) EnvelopedData
+//! and [RFC5083](https://www.rfc-editor.org/rfc/rfc5083) AuthEnvelopedData
+//! parsing and decryption.
+
+use super::enveloped_data::AuthEnvelopedData;
+use super::enveloped_data::EncryptedContentInfo;
+use super::enveloped_data::EnvelopedData;
+use super::enveloped_data::GCM_ICV_LENGTH;
+use super::recipient_info;
+use super::recipient_info::RecipientInfo;
+use super::CmsError;
+use super::CmsErrorKind;
+use super::ContentInfo;
+use crate::cert::parse::CertificateParser;
+use crate::key::GcmParameters;
+use crate::key::PbeCipher;
+use crate::key::PbeParameters;
+use crate::key::Pkcs8PrivateKey;
+use rasn::prelude::*;
+
+/// CMS EnvelopedData and AuthEnvelopedData parser.
+pub struct EnvelopedDataParser {
+    recipient_infos: Vec<RecipientInfo>,
+    encrypted_content_info: EncryptedContentInfo,
+    /// Authentication tag of AuthEnvelopedData
+    mac: Option<Vec<u8>>,
+}
+
+impl EnvelopedDataParser {
+    /// Create a new instance from a BER or DER encoded `ContentInfo`.
+    pub fn from_bytes(encoded_content_info: &[u8]) -> Result<Self, CmsError> {
+        let content_info = super::decode::<ContentInfo>(encoded_content_info)?;
+        let content_type = content_info.content_type.to_vec();
+        if content_type.eq(super::OID_ENVELOPED_DATA) {
+            let enveloped_data = super::decode::<EnvelopedData>(content_info.content.as_bytes())?;
+            Ok(Self {
+                recipient_infos: enveloped_data
+                    .recipient_infos
+                    .to_vec()
+                    .into_iter()
+                    .cloned()
+                    .collect(),
+                encrypted_content_info: enveloped_data.encrypted_content_info,
+                mac: None,
+            })
+        } else if content_type.eq(super::OID_AUTH_ENVELOPED_DATA) {
+            let auth_enveloped_data =
+                super::decode::<AuthEnvelopedData>(content_info.content.as_bytes())?;
+            if auth_enveloped_data.auth_attrs.is_some() {
+                return Err(CmsErrorKind::UnsupportedAlgorithm
+                    .error_with_msg("Authenticated attributes are not supported."));
+            }
+            Ok(Self {
+                recipient_infos: auth_enveloped_data
+                    .recipient_infos
+                    .to_vec()
+                    .into_iter()
+                    .cloned()
+                    .collect(),
+                encrypted_content_info: auth_enveloped_data.auth_encrypted_content_info,
+                mac: Some(auth_enveloped_data.mac.to_vec()),
+            })
+        } else {
+            let msg = format!(
+                "Content type '{}' is not EnvelopedData or AuthEnvelopedData.",
+                crate::encdec::oid::as_string(&content_type)
+            );
+            Err(CmsErrorKind::DecodingError.error_with_msg(&msg))
+        }
+    }
+
+    /// Return `true` if this is an AuthEnvelopedData.
+    pub fn is_authenticated(&self) -> bool {
+        self.mac.is_some()
+    }
+
+    /// Return the content type of the encrypted content.
+    pub fn get_content_type(&self) -> Vec<u32> {
+        self.encrypted_content_info.content_type.to_vec()
+    }
+
+    /// Decrypt the content using the DER encoded recipient certificate and
+    /// the corresponding private key.
+    pub fn decrypt(
+        &self,
+        recipient_certificate_der: &[u8],
+        private_key: &Pkcs8PrivateKey,
+    ) -> Result<Vec<u8>, CmsError> {
+        let certificate = CertificateParser::from_bytes(recipient_certificate_der)
+            .map_err(|e| CmsErrorKind::DecodingError.error_with_msg(&e.to_string()))?;
+        let mut cek = None;
+        for recipient_info in &self.recipient_infos {
+            cek = recipient_info::decrypt_key(recipient_info, &certificate, private_key)?;
+            if cek.is_some() {
+                break;
+            }
+        }
+        let cek = cek.ok_or_else(|| {
+            CmsErrorKind::RecipientNotFound
+                .error_with_msg("No recipient information matches the certificate.")
+        })?;
+        let algorithm = &self.encrypted_content_info.content_encryption_algorithm;
+        let ciphertext = self
+            .encrypted_content_info
+            .encrypted_content
+            .as_ref()
+            .ok_or_else(|| {
+                CmsErrorKind::DecodingError.error_with_msg("Detached content is not supported.")
+            })?;
+        let algorithm_oid = algorithm.algorithm.to_vec();
+        let unsupported = || {
+            let msg = format!(
+                "Unsupported content encryption algorithm '{}'{}.",
+                crate::encdec::oid::as_string(&algorithm_oid),
+                if self.mac.is_some() {
+                    " for AuthEnvelopedData"
+                } else {
+                    " for EnvelopedData"
+                }
+            );
+            CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+        };
+        let cipher = PbeCipher::from_oid(&algorithm_oid).ok_or_else(unsupported)?;
+        if cek.len() != cipher.key_length() {
+            let msg = format!(
+                "Content-encryption key of {} bytes does not match '{}' that requires {} bytes.",
+                cek.len(),
+                crate::encdec::oid::as_string(&algorithm_oid),
+                cipher.key_length()
+            );
+            return Err(CmsErrorKind::DecryptionFailure.error_with_msg(&msg));
+        }
+        let ret = match (&self.mac, cipher) {
+            (Some(mac), PbeCipher::Aes128Gcm | PbeCipher::Aes256Gcm) => {
+                let parameters = PbeParameters::decode_parameters::<GcmParameters>(algorithm)
+                    .map_err(from_key_error)?;
+                if parameters
+                    .aes_icv_len
+                    .is_some_and(|icv_len| icv_len != Integer::from(GCM_ICV_LENGTH))
+                    || mac.len() != GCM_ICV_LENGTH
+                    || parameters.aes_nonce.len() != 12
+                {
+                    return Err(CmsErrorKind::UnsupportedAlgorithm
+                        .error_with_msg("Only 12 byte nonces and 16 byte tags are supported."));
+                }
+                let mut ciphertext_and_tag = ciphertext.to_vec();
+                ciphertext_and_tag.extend_from_slice(mac);
+                if cipher == PbeCipher::Aes128Gcm {
+                    PbeParameters::gcm_decrypt::<aes_gcm::Aes128Gcm>(
+                        &cek,
+                        &parameters.aes_nonce,
+                        &ciphertext_and_tag,
+                    )
+                } else {
+                    PbeParameters::gcm_decrypt::<aes_gcm::Aes256Gcm>(
+                        &cek,
+                        &parameters.aes_nonce,
+                        &ciphertext_and_tag,
+                    )
+                }
+            }
+            (None, PbeCipher::Aes128Cbc | PbeCipher::Aes192Cbc | PbeCipher::Aes256Cbc) => {
+                let iv = PbeParameters::decode_parameters::<OctetString>(algorithm)
+                    .map_err(from_key_error)?;
+                match cipher {
+                    PbeCipher::Aes128Cbc => {
+                        PbeParameters::cbc_decrypt::<aes::Aes128>(&cek, &iv, ciphertext)
+                    }
+                    PbeCipher::Aes192Cbc => {
+                        PbeParameters::cbc_decrypt::<aes::Aes192>(&cek, &iv, ciphertext)
+                    }
+                    _ => PbeParameters::cbc_decrypt::<aes::Aes256>(&cek, &iv, ciphertext),
+                }
+            }
+            _ => return Err(unsupported()),
+        };
+        ret.map_err(|e| CmsErrorKind::DecryptionFailure.error_with_msg(&e.to_string()))
+    }
+}
+
+fn from_key_error(key_error: crate::key::KeyError) -> CmsError {
+    CmsErrorKind::DecodingError.error_with_msg(&key_error.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cms::CertificateIdentifierType;
+    use crate::cms::EnvelopedDataBuilder;
+    use crate::test_utils::*;
+
+    const CONTENT: &[u8] = b"Enveloped content";
+
+    fn round_trip(name: &str, builder: EnvelopedDataBuilder, authenticated: bool) {
+        let recipient_der = load_test_certificate_der(&format!("{name}_recipient.b64"));
+        let private_key = load_test_private_key(&format!("{name}_recipient_key.pem"));
+        let encoded = builder
+            .add_recipient(&recipient_der)
+            .unwrap()
+            .build()
+            .unwrap();
+        let edp = EnvelopedDataParser::from_bytes(&encoded).unwrap();
+        assert_eq!(edp.is_authenticated(), authenticated);
+        assert_eq!(edp.get_content_type(), crate::cms::OID_DATA);
+        assert_eq!(edp.decrypt(&recipient_der, &private_key).unwrap(), CONTENT);
+        // Another certificate must not match any of the recipients
+        let err = edp
+            .decrypt(
+                &load_test_certificate_der("ec_ca.b64"),
+                &load_test_private_key("ec_ca_key.pem"),
+            )
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), &CmsErrorKind::RecipientNotFound);
+    }
+
+    #[test]
+    fn key_transport_round_trip() {
+        round_trip("rsa", EnvelopedDataBuilder::new(CONTENT), false);
+        round_trip(
+            "rsa",
+            EnvelopedDataBuilder::new(CONTENT)
+                .with_recipient_identifier_type(CertificateIdentifierType::SubjectKeyIdentifier),
+            false,
+        );
+    }
+
+    #[test]
+    fn key_agreement_round_trip() {
+        round_trip("ec", EnvelopedDataBuilder::new(CONTENT), false);
+        round_trip(
+            "ec",
+            EnvelopedDataBuilder::new(CONTENT)
+                .with_recipient_identifier_type(CertificateIdentifierType::SubjectKeyIdentifier),
+            false,
+        );
+    }
+
+    #[test]
+    fn kem_round_trip() {
+        round_trip("mlkem", EnvelopedDataBuilder::new(CONTENT), false);
+        round_trip(
+            "mlkem",
+            EnvelopedDataBuilder::new(CONTENT).with_authenticated_encryption(),
+            true,
+        );
+    }
+
+    #[test]
+    fn key_usage_is_enforced() {
+        // The Key Usage of the CA only allows keyCertSign and cRLSign
+        let err = EnvelopedDataBuilder::new(CONTENT)
+            .add_recipient(&load_test_certificate_der("ec_ca.b64"))
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), &CmsErrorKind::InvalidKeyUsage);
+    }
+
+    #[test]
+    fn authenticated_content_tampering_is_detected() {
+        let recipient_der = load_test_certificate_der("ec_recipient.b64");
+        let private_key = load_test_private_key("ec_recipient_key.pem");
+        let encoded = EnvelopedDataBuilder::new(CONTENT)
+            .with_authenticated_encryption()
+            .add_recipient(&recipient_der)
+            .unwrap()
+            .build()
+            .unwrap();
+        let edp = EnvelopedDataParser::from_bytes(&encoded).unwrap();
+        assert!(edp.is_authenticated());
+        assert_eq!(edp.decrypt(&recipient_der, &private_key).unwrap(), CONTENT);
+        // The authentication tag is the last field of the AuthEnvelopedData
+        let mut tampered_mac = encoded.clone();
+        *tampered_mac.last_mut().unwrap() ^= 0x01;
+        let err = EnvelopedDataParser::from_bytes(&tampered_mac)
+            .unwrap()
+            .decrypt(&recipient_der, &private_key)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), &CmsErrorKind::DecryptionFailure);
+        // Flip a bit in the first octet of the encrypted content
+        let mut tampered_content = encoded.clone();
+        let ciphertext = edp
+            .encrypted_content_info
+            .encrypted_content
+            .as_ref()
+            .unwrap();
+        let offset = encoded
+            .windows(ciphertext.len())
+            .position(|window| window.eq(ciphertext.as_ref()))
+            .unwrap();
+        tampered_content[offset] ^= 0x01;
+        let err = EnvelopedDataParser::from_bytes(&tampered_content)
+            .unwrap()
+            .decrypt(&recipient_der, &private_key)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), &CmsErrorKind::DecryptionFailure);
+    }
+
+    #[test]
+    fn content_encryption_key_must_match_algorithm() {
+        let recipient_der = load_test_certificate_der("ec_recipient.b64");
+        let private_key = load_test_private_key("ec_recipient_key.pem");
+        // Replace the last arc of the content encryption algorithm OID
+        let decrypt_with_last_arc = |builder: EnvelopedDataBuilder, from: u32, to: u8| {
+            let mut encoded = builder
+                .add_recipient(&recipient_der)
+                .unwrap()
+                .build()
+                .unwrap();
+            let oid = rasn::der::encode(
+                &ObjectIdentifier::new(vec![2, 16, 840, 1, 101, 3, 4, 1, from]).unwrap(),
+            )
+            .unwrap();
+            let offset = encoded
+                .windows(oid.len())
+                .position(|window| window.eq(oid.as_slice()))
+                .unwrap();
+            encoded[offset + oid.len() - 1] = to;
+            EnvelopedDataParser::from_bytes(&encoded)
+                .unwrap()
+                .decrypt(&recipient_der, &private_key)
+                .err()
+                .unwrap()
+                .kind()
+                .clone()
+        };
+        // AES-256-CBC key used as AES-128-CBC
+        assert_eq!(
+            decrypt_with_last_arc(EnvelopedDataBuilder::new(CONTENT), 42, 2),
+            CmsErrorKind::DecryptionFailure
+        );
+        // AES-256-GCM key used as AES-128-GCM
+        assert_eq!(
+            decrypt_with_last_arc(
+                EnvelopedDataBuilder::new(CONTENT).with_authenticated_encryption(),
+                46,
+                6
+            ),
+            CmsErrorKind::DecryptionFailure
+        );
+        // CBC is not authenticated encryption
+        assert_eq!(
+            decrypt_with_last_arc(
+                EnvelopedDataBuilder::new(CONTENT).with_authenticated_encryption(),
+                46,
+                42
+            ),
+            CmsErrorKind::UnsupportedAlgorithm
+        );
+    }
+}
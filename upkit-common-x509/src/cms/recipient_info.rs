@@ -0,0 +1,859 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! CMS recipient information and content-encryption key management.
+
+/*
+RecipientInfo ::= CHOICE {
+    ktri KeyTransRecipientInfo,
+    kari [1] KeyAgreeRecipientInfo,
+    kekri [2] KEKRecipientInfo,
+    pwri [3] PasswordRecipientInfo,
+    ori [4] OtherRecipientInfo }
+
+KeyTransRecipientInfo ::= SEQUENCE {
+    version CMSVersion,  -- always set to 0 or 2
+    rid RecipientIdentifier,
+    keyEncryptionAlgorithm KeyEncryptionAlgorithmIdentifier,
+    encryptedKey EncryptedKey }
+
+KeyAgreeRecipientInfo ::= SEQUENCE {
+    version CMSVersion,  -- always set to 3
+    originator [0] EXPLICIT OriginatorIdentifierOrKey,
+    ukm [1] EXPLICIT UserKeyingMaterial OPTIONAL,
+    keyEncryptionAlgorithm KeyEncryptionAlgorithmIdentifier,
+    recipientEncryptedKeys RecipientEncryptedKeys }
+
+OriginatorIdentifierOrKey ::= CHOICE {
+    issuerAndSerialNumber IssuerAndSerialNumber,
+    subjectKeyIdentifier [0] SubjectKeyIdentifier,
+    originatorKey [1] OriginatorPublicKey }
+
+OriginatorPublicKey ::= SEQUENCE {
+    algorithm AlgorithmIdentifier,
+    publicKey BIT STRING }
+
+RecipientEncryptedKey ::= SEQUENCE {
+    rid KeyAgreeRecipientIdentifier,
+    encryptedKey EncryptedKey }
+
+KeyAgreeRecipientIdentifier ::= CHOICE {
+    issuerAndSerialNumber IssuerAndSerialNumber,
+    rKeyId [0] IMPLICIT RecipientKeyIdentifier }
+
+RecipientKeyIdentifier ::= SEQUENCE {
+    subjectKeyIdentifier SubjectKeyIdentifier,
+    date GeneralizedTime OPTIONAL,
+    other OtherKeyAttribute OPTIONAL }
+
+OtherRecipientInfo ::= SEQUENCE {
+    oriType OBJECT IDENTIFIER,
+    oriValue ANY DEFINED BY oriType }
+
+KEMRecipientInfo ::= SEQUENCE {
+    version CMSVersion,  -- always set to 0
+    rid RecipientIdentifier,
+    kem KEMAlgorithmIdentifier,
+    kemct OCTET STRING,
+    kdf KeyDerivationAlgorithmIdentifier,
+    kekLength INTEGER (1..65535),
+    ukm [0] EXPLICIT UserKeyingMaterial OPTIONAL,
+    wrap KeyEncryptionAlgorithmIdentifier,
+    encryptedKey EncryptedKey }
+
+CMSORIforKEMOtherInfo ::= SEQUENCE {
+    wrap KeyEncryptionAlgorithmIdentifier,
+    kekLength INTEGER (1..65535),
+    ukm [0] EXPLICIT UserKeyingMaterial OPTIONAL }
+
+ECC-CMS-SharedInfo ::= SEQUENCE {
+    keyInfo AlgorithmIdentifier,
+    entityUInfo [0] EXPLICIT OCTET STRING OPTIONAL,
+    suppPubInfo [2] EXPLICIT OCTET STRING }
+
+RSAES-OAEP-params ::= SEQUENCE {
+    hashFunc [0] AlgorithmIdentifier DEFAULT sha1Identifier,
+    maskGenFunc [1] AlgorithmIdentifier DEFAULT mgf1SHA1Identifier,
+    pSourceFunc [2] AlgorithmIdentifier DEFAULT pSpecifiedEmptyIdentifier }
+*/
+
+use super::CertificateIdentifier;
+use super::CertificateIdentifierType;
+use super::CmsError;
+use super::CmsErrorKind;
+use super::IssuerAndSerialNumber;
+use crate::cert::extensions::KeyUsage;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::WellKnownAlgorithm;
+use crate::encdec::oid;
+use crate::key::Pkcs8PrivateKey;
+use elliptic_curve::ecdh::EphemeralSecret;
+use elliptic_curve::pkcs8::AssociatedOid;
+use elliptic_curve::pkcs8::DecodePrivateKey;
+use elliptic_curve::pkcs8::DecodePublicKey;
+use elliptic_curve::sec1::FromEncodedPoint;
+use elliptic_curve::sec1::ModulusSize;
+use elliptic_curve::sec1::ToEncodedPoint;
+use elliptic_curve::AffinePoint;
+use elliptic_curve::CurveArithmetic;
+use elliptic_curve::FieldBytesSize;
+use p256::elliptic_curve;
+use rand_core::CryptoRng;
+use rand_core::RngCore;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+use sha2::Digest;
+use tyst::traits::pke::Pke;
+use tyst::traits::se::ToPublicKey;
+use tyst::Tyst;
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+#[rasn(choice)]
+pub(crate) enum RecipientInfo {
+    Ktri(KeyTransRecipientInfo),
+    #[rasn(tag(context, 1))]
+    Kari(KeyAgreeRecipientInfo),
+    #[rasn(tag(context, 4))]
+    Ori(OtherRecipientInfo),
+}
+
+impl RecipientInfo {
+    /// Return the `CMSVersion` of this structure.
+    pub(crate) fn version(&self) -> u8 {
+        match self {
+            Self::Ktri(ktri) => match ktri.rid {
+                CertificateIdentifier::IssuerAndSerialNumber(_) => 0,
+                CertificateIdentifier::SubjectKeyIdentifier(_) => 2,
+            },
+            Self::Kari(_) => 3,
+            Self::Ori(_) => 0,
+        }
+    }
+
+    pub(crate) fn is_other(&self) -> bool {
+        matches!(self, Self::Ori(_))
+    }
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct KeyTransRecipientInfo {
+    pub(crate) version: Integer,
+    pub(crate) rid: CertificateIdentifier,
+    pub(crate) key_encryption_algorithm: AlgorithmIdentifier,
+    pub(crate) encrypted_key: OctetString,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct KeyAgreeRecipientInfo {
+    pub(crate) version: Integer,
+    #[rasn(tag(explicit(context, 0)))]
+    pub(crate) originator: OriginatorIdentifierOrKey,
+    #[rasn(tag(explicit(context, 1)))]
+    pub(crate) ukm: Option<OctetString>,
+    pub(crate) key_encryption_algorithm: AlgorithmIdentifier,
+    pub(crate) recipient_encrypted_keys: SequenceOf<RecipientEncryptedKey>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+#[rasn(choice)]
+pub(crate) enum OriginatorIdentifierOrKey {
+    IssuerAndSerialNumber(IssuerAndSerialNumber),
+    #[rasn(tag(context, 0))]
+    SubjectKeyIdentifier(OctetString),
+    #[rasn(tag(context, 1))]
+    OriginatorKey(OriginatorPublicKey),
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct OriginatorPublicKey {
+    pub(crate) algorithm: AlgorithmIdentifier,
+    pub(crate) public_key: BitString,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct RecipientEncryptedKey {
+    pub(crate) rid: KeyAgreeRecipientIdentifier,
+    pub(crate) encrypted_key: OctetString,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+#[rasn(choice)]
+pub(crate) enum KeyAgreeRecipientIdentifier {
+    IssuerAndSerialNumber(IssuerAndSerialNumber),
+    #[rasn(tag(context, 0))]
+    RKeyId(RecipientKeyIdentifier),
+}
+
+impl KeyAgreeRecipientIdentifier {
+    fn matches(&self, certificate: &CertificateParser) -> bool {
+        match self {
+            Self::IssuerAndSerialNumber(issuer_and_serial_number) => {
+                issuer_and_serial_number.matches(certificate)
+            }
+            Self::RKeyId(recipient_key_identifier) => certificate
+                .get_subject_key_identifier_kid()
+                .ok()
+                .flatten()
+                .is_some_and(|ski| {
+                    ski.eq(recipient_key_identifier.subject_key_identifier.as_ref())
+                }),
+        }
+    }
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct RecipientKeyIdentifier {
+    pub(crate) subject_key_identifier: OctetString,
+    pub(crate) date: Option<GeneralizedTime>,
+    pub(crate) other: Option<Any>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct OtherRecipientInfo {
+    pub(crate) ori_type: ObjectIdentifier,
+    pub(crate) ori_value: Any,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct KemRecipientInfo {
+    pub(crate) version: Integer,
+    pub(crate) rid: CertificateIdentifier,
+    pub(crate) kem: AlgorithmIdentifier,
+    pub(crate) kemct: OctetString,
+    pub(crate) kdf: AlgorithmIdentifier,
+    pub(crate) kek_length: Integer,
+    #[rasn(tag(explicit(context, 0)))]
+    pub(crate) ukm: Option<OctetString>,
+    pub(crate) wrap: AlgorithmIdentifier,
+    pub(crate) encrypted_key: OctetString,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct CmsOriForKemOtherInfo {
+    wrap: AlgorithmIdentifier,
+    kek_length: Integer,
+    #[rasn(tag(explicit(context, 0)))]
+    ukm: Option<OctetString>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct EccCmsSharedInfo {
+    key_info: AlgorithmIdentifier,
+    #[rasn(tag(explicit(context, 0)))]
+    entity_u_info: Option<OctetString>,
+    #[rasn(tag(explicit(context, 2)))]
+    supp_pub_info: OctetString,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct RsaesOaepParams {
+    #[rasn(tag(explicit(context, 0)))]
+    hash_func: Option<AlgorithmIdentifier>,
+    #[rasn(tag(explicit(context, 1)))]
+    mask_gen_func: Option<AlgorithmIdentifier>,
+    #[rasn(tag(explicit(context, 2)))]
+    p_source_func: Option<AlgorithmIdentifier>,
+}
+
+/// `id-RSAES-OAEP`
+const OID_RSAES_OAEP: &[u32] = &[1, 2, 840, 113549, 1, 1, 7];
+/// `id-mgf1`
+const OID_MGF1: &[u32] = &[1, 2, 840, 113549, 1, 1, 8];
+/// `dhSinglePass-stdDH-sha256kdf-scheme`
+const OID_DH_SINGLE_PASS_STD_DH_SHA256_KDF: &[u32] = &[1, 3, 132, 1, 11, 1];
+/// `id-aes256-wrap`
+const OID_AES256_WRAP: &[u32] = &[2, 16, 840, 1, 101, 3, 4, 1, 45];
+/// `id-ori-kem`
+const OID_ORI_KEM: &[u32] = &[1, 2, 840, 113549, 1, 9, 16, 13, 3];
+/// `id-alg-hkdf-with-sha256`
+const OID_HKDF_WITH_SHA256: &[u32] = &[1, 2, 840, 113549, 1, 9, 16, 3, 28];
+/// `secp256r1`
+const OID_CURVE_P256: &[u32] = &[1, 2, 840, 10045, 3, 1, 7];
+/// `secp384r1`
+const OID_CURVE_P384: &[u32] = &[1, 3, 132, 0, 34];
+
+/// Length of the AES-256 key-encryption key in bytes.
+const KEK_LENGTH: usize = 32;
+
+/// How the content-encryption key is transferred to a recipient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RecipientKeyType {
+    /// RSAES-OAEP key transport ([RFC8017](https://www.rfc-editor.org/rfc/rfc8017)).
+    Rsa,
+    /// Ephemeral-static ECDH key agreement on P-256 ([RFC5753](https://www.rfc-editor.org/rfc/rfc5753)).
+    EcP256,
+    /// Ephemeral-static ECDH key agreement on P-384 ([RFC5753](https://www.rfc-editor.org/rfc/rfc5753)).
+    EcP384,
+    /// Key encapsulation mechanism ([RFC9629](https://www.rfc-editor.org/rfc/rfc9629)).
+    Kem(&'static [u32]),
+}
+
+impl RecipientKeyType {
+    /** Return the key type of the recipient certificate.
+
+    The Key Usage extension is checked when present: key transport and KEM
+    recipients require `keyEncipherment` and key agreement recipients require
+    `keyAgreement`.
+    */
+    pub(crate) fn from_certificate(certificate: &CertificateParser) -> Result<Self, CmsError> {
+        let spki = decode_subject_public_key_info(certificate)?;
+        let ret = match WellKnownAlgorithm::from_oid(&spki.algorithm.algorithm) {
+            Some(WellKnownAlgorithm::RsaEncryption) => Self::Rsa,
+            Some(WellKnownAlgorithm::EcPublicKey) => {
+                let curve = spki
+                    .algorithm
+                    .parameters
+                    .as_ref()
+                    .and_then(|parameters| {
+                        rasn::der::decode::<ObjectIdentifier>(parameters.as_bytes()).ok()
+                    })
+                    .map(|curve| curve.to_vec())
+                    .unwrap_or_default();
+                if curve.eq(OID_CURVE_P256) {
+                    Self::EcP256
+                } else if curve.eq(OID_CURVE_P384) {
+                    Self::EcP384
+                } else {
+                    let msg = format!("Unsupported curve '{}'.", oid::as_string(&curve));
+                    return Err(CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&msg));
+                }
+            }
+            Some(
+                kem @ (WellKnownAlgorithm::MlKem512
+                | WellKnownAlgorithm::MlKem768
+                | WellKnownAlgorithm::MlKem1024),
+            ) => Self::Kem(kem.as_oid()),
+            _ => {
+                let msg = format!(
+                    "Unsupported recipient public key algorithm '{}'.",
+                    oid::as_string(&spki.algorithm.algorithm)
+                );
+                return Err(CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&msg));
+            }
+        };
+        let required_key_usage = match ret {
+            Self::EcP256 | Self::EcP384 => KeyUsage::KeyAgreement,
+            Self::Rsa | Self::Kem(_) => KeyUsage::KeyEncipherment,
+        };
+        let key_usage = certificate.get_key_usage().map_err(|e| {
+            CmsErrorKind::DecodingError
+                .error_with_msg(&format!("Invalid recipient certificate Key Usage: {e}"))
+        })?;
+        if let Some(key_usage) = key_usage {
+            if !key_usage.contains(&required_key_usage) {
+                let msg = format!(
+                    "Recipient certificate Key Usage does not allow {required_key_usage:?}."
+                );
+                return Err(CmsErrorKind::InvalidKeyUsage.error_with_msg(&msg));
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Return the `RecipientInfo` with the content-encryption key `cek`
+    /// encrypted for the recipient.
+    pub(crate) fn encrypt_key(
+        &self,
+        certificate: &CertificateParser,
+        certificate_identifier_type: CertificateIdentifierType,
+        cek: &[u8],
+    ) -> Result<RecipientInfo, CmsError> {
+        let rid = CertificateIdentifier::from_certificate(certificate, certificate_identifier_type)
+            .ok_or_else(|| {
+                CmsErrorKind::RecipientNotFound
+                    .error_with_msg("Recipient certificate has no Subject Key Identifier.")
+            })?;
+        let spki = certificate.get_encoded_subject_public_key_info();
+        match self {
+            Self::Rsa => {
+                let encrypted_key = rsa_oaep_encrypt(spki, cek)?;
+                Ok(RecipientInfo::Ktri(KeyTransRecipientInfo {
+                    version: Integer::from(match rid {
+                        CertificateIdentifier::IssuerAndSerialNumber(_) => 0,
+                        CertificateIdentifier::SubjectKeyIdentifier(_) => 2,
+                    }),
+                    rid,
+                    key_encryption_algorithm: rsaes_oaep_algorithm_identifier(),
+                    encrypted_key: OctetString::from(encrypted_key),
+                }))
+            }
+            Self::EcP256 | Self::EcP384 => {
+                let (ephemeral_public_key, shared_secret) = if self.eq(&Self::EcP256) {
+                    ecdh_ephemeral::<p256::NistP256>(&spki)
+                } else {
+                    ecdh_ephemeral::<p384::NistP384>(&spki)
+                }?;
+                let wrap = wrap_algorithm_identifier();
+                let kek = x963_kdf_sha256(&shared_secret, &ecc_cms_shared_info(&wrap), KEK_LENGTH);
+                let encrypted_key = aes_key_wrap(&kek, cek)?;
+                let rid = match rid {
+                    CertificateIdentifier::IssuerAndSerialNumber(issuer_and_serial_number) => {
+                        KeyAgreeRecipientIdentifier::IssuerAndSerialNumber(issuer_and_serial_number)
+                    }
+                    CertificateIdentifier::SubjectKeyIdentifier(subject_key_identifier) => {
+                        KeyAgreeRecipientIdentifier::RKeyId(RecipientKeyIdentifier {
+                            subject_key_identifier,
+                            date: None,
+                            other: None,
+                        })
+                    }
+                };
+                let mut recipient_encrypted_keys = SequenceOf::new();
+                recipient_encrypted_keys.push(RecipientEncryptedKey {
+                    rid,
+                    encrypted_key: OctetString::from(encrypted_key),
+                });
+                Ok(RecipientInfo::Kari(KeyAgreeRecipientInfo {
+                    version: Integer::from(3),
+                    originator: OriginatorIdentifierOrKey::OriginatorKey(OriginatorPublicKey {
+                        algorithm: algorithm_identifier(
+                            WellKnownAlgorithm::EcPublicKey.as_oid(),
+                            None,
+                        ),
+                        public_key: BitString::from_vec(ephemeral_public_key),
+                    }),
+                    ukm: None,
+                    key_encryption_algorithm: algorithm_identifier(
+                        OID_DH_SINGLE_PASS_STD_DH_SHA256_KDF,
+                        Some(rasn::der::encode(&wrap).unwrap()),
+                    ),
+                    recipient_encrypted_keys,
+                }))
+            }
+            Self::Kem(kem_oid) => {
+                let (shared_secret, kemct) = kem_encapsulate(kem_oid, spki)?;
+                let wrap = wrap_algorithm_identifier();
+                let kek = hkdf_sha256(&shared_secret, &kem_other_info(&wrap, None), KEK_LENGTH);
+                let encrypted_key = aes_key_wrap(&kek, cek)?;
+                let kem_recipient_info = KemRecipientInfo {
+                    version: Integer::from(0),
+                    rid,
+                    kem: algorithm_identifier(kem_oid, None),
+                    kemct: OctetString::from(kemct),
+                    kdf: algorithm_identifier(OID_HKDF_WITH_SHA256, None),
+                    kek_length: Integer::from(KEK_LENGTH),
+                    ukm: None,
+                    wrap,
+                    encrypted_key: OctetString::from(encrypted_key),
+                };
+                Ok(RecipientInfo::Ori(OtherRecipientInfo {
+                    ori_type: ObjectIdentifier::new_unchecked(OID_ORI_KEM.to_vec().into()),
+                    ori_value: Any::new(rasn::der::encode(&kem_recipient_info).unwrap()),
+                }))
+            }
+        }
+    }
+}
+
+/** Return the decrypted content-encryption key.
+
+Returns `Ok(None)` when the `recipient_info` is not intended for the
+`certificate`.
+*/
+pub(crate) fn decrypt_key(
+    recipient_info: &RecipientInfo,
+    certificate: &CertificateParser,
+    private_key: &Pkcs8PrivateKey,
+) -> Result<Option<Vec<u8>>, CmsError> {
+    match recipient_info {
+        RecipientInfo::Ktri(ktri) => {
+            if !ktri.rid.matches(certificate) {
+                return Ok(None);
+            }
+            check_rsaes_oaep_algorithm_identifier(&ktri.key_encryption_algorithm)?;
+            rsa_oaep_decrypt(private_key, ktri.encrypted_key.as_ref()).map(Some)
+        }
+        RecipientInfo::Kari(kari) => {
+            let Some(recipient_encrypted_key) = kari
+                .recipient_encrypted_keys
+                .iter()
+                .find(|recipient_encrypted_key| recipient_encrypted_key.rid.matches(certificate))
+            else {
+                return Ok(None);
+            };
+            let OriginatorIdentifierOrKey::OriginatorKey(originator_key) = &kari.originator else {
+                return Err(CmsErrorKind::UnsupportedAlgorithm
+                    .error_with_msg("Only ephemeral-static ECDH originator keys are supported."));
+            };
+            if !kari
+                .key_encryption_algorithm
+                .algorithm
+                .to_vec()
+                .eq(OID_DH_SINGLE_PASS_STD_DH_SHA256_KDF)
+            {
+                return Err(unsupported_algorithm(&kari.key_encryption_algorithm));
+            }
+            let wrap = kari
+                .key_encryption_algorithm
+                .parameters
+                .as_ref()
+                .and_then(|parameters| {
+                    rasn::der::decode::<AlgorithmIdentifier>(parameters.as_bytes()).ok()
+                })
+                .ok_or_else(|| {
+                    CmsErrorKind::DecodingError
+                        .error_with_msg("Missing key wrap algorithm parameters.")
+                })?;
+            let kek_length = key_wrap_length(&wrap)?;
+            let ephemeral_public_key = originator_key.public_key.as_raw_slice();
+            let shared_secret = match RecipientKeyType::from_certificate(certificate)? {
+                RecipientKeyType::EcP256 => {
+                    ecdh_static::<p256::NistP256>(&private_key.to_der(), ephemeral_public_key)
+                }
+                RecipientKeyType::EcP384 => {
+                    ecdh_static::<p384::NistP384>(&private_key.to_der(), ephemeral_public_key)
+                }
+                _ => Err(CmsErrorKind::UnsupportedAlgorithm
+                    .error_with_msg("Recipient certificate is not an EC key.")),
+            }?;
+            let shared_info = EccCmsSharedInfo {
+                key_info: wrap,
+                entity_u_info: kari.ukm.clone(),
+                supp_pub_info: OctetString::from(
+                    u32::try_from(kek_length * 8)
+                        .unwrap()
+                        .to_be_bytes()
+                        .to_vec(),
+                ),
+            };
+            let kek = x963_kdf_sha256(
+                &shared_secret,
+                &rasn::der::encode(&shared_info).unwrap(),
+                kek_length,
+            );
+            aes_key_unwrap(&kek, recipient_encrypted_key.encrypted_key.as_ref()).map(Some)
+        }
+        RecipientInfo::Ori(ori) => {
+            if !ori.ori_type.to_vec().eq(OID_ORI_KEM) {
+                return Ok(None);
+            }
+            let kem_recipient_info = super::decode::<KemRecipientInfo>(ori.ori_value.as_bytes())?;
+            if !kem_recipient_info.rid.matches(certificate) {
+                return Ok(None);
+            }
+            if !kem_recipient_info
+                .kdf
+                .algorithm
+                .to_vec()
+                .eq(OID_HKDF_WITH_SHA256)
+            {
+                return Err(unsupported_algorithm(&kem_recipient_info.kdf));
+            }
+            let kek_length = key_wrap_length(&kem_recipient_info.wrap)?;
+            if !kem_recipient_info.kek_length.eq(&Integer::from(kek_length)) {
+                return Err(CmsErrorKind::DecodingError
+                    .error_with_msg("KEK length does not match the key wrap algorithm."));
+            }
+            let shared_secret = kem_decapsulate(
+                &kem_recipient_info.kem.algorithm,
+                private_key,
+                kem_recipient_info.kemct.as_ref(),
+            )?;
+            let kek = hkdf_sha256(
+                &shared_secret,
+                &kem_other_info(&kem_recipient_info.wrap, kem_recipient_info.ukm.clone()),
+                kek_length,
+            );
+            aes_key_unwrap(&kek, kem_recipient_info.encrypted_key.as_ref()).map(Some)
+        }
+    }
+}
+
+fn decode_subject_public_key_info(
+    certificate: &CertificateParser,
+) -> Result<rasn_pkix::SubjectPublicKeyInfo, CmsError> {
+    super::decode(&certificate.get_encoded_subject_public_key_info())
+}
+
+fn algorithm_identifier(oid: &[u32], parameters: Option<Vec<u8>>) -> AlgorithmIdentifier {
+    AlgorithmIdentifier {
+        algorithm: ObjectIdentifier::new_unchecked(oid.to_vec().into()),
+        parameters: parameters.map(Any::new),
+    }
+}
+
+fn unsupported_algorithm(algorithm: &AlgorithmIdentifier) -> CmsError {
+    let msg = format!(
+        "Unsupported key management algorithm '{}'.",
+        oid::as_string(&algorithm.algorithm)
+    );
+    CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+}
+
+/// RSAES-OAEP with SHA-256 and MGF1 with SHA-256 as recommended by RFC8017.
+fn rsaes_oaep_algorithm_identifier() -> AlgorithmIdentifier {
+    let sha256 = algorithm_identifier(WellKnownAlgorithm::Sha256.as_oid(), None);
+    let parameters = RsaesOaepParams {
+        hash_func: Some(sha256.clone()),
+        mask_gen_func: Some(algorithm_identifier(
+            OID_MGF1,
+            Some(rasn::der::encode(&sha256).unwrap()),
+        )),
+        p_source_func: None,
+    };
+    algorithm_identifier(
+        OID_RSAES_OAEP,
+        Some(rasn::der::encode(&parameters).unwrap()),
+    )
+}
+
+fn check_rsaes_oaep_algorithm_identifier(algorithm: &AlgorithmIdentifier) -> Result<(), CmsError> {
+    let parameters = algorithm
+        .parameters
+        .as_ref()
+        .and_then(|parameters| rasn::der::decode::<RsaesOaepParams>(parameters.as_bytes()).ok());
+    let supported = algorithm.algorithm.to_vec().eq(OID_RSAES_OAEP)
+        && parameters.is_some_and(|parameters| {
+            parameters.p_source_func.is_none()
+                && parameters.hash_func.is_some_and(|hash_func| {
+                    hash_func
+                        .algorithm
+                        .to_vec()
+                        .eq(WellKnownAlgorithm::Sha256.as_oid())
+                })
+        });
+    if supported {
+        Ok(())
+    } else {
+        Err(unsupported_algorithm(algorithm))
+    }
+}
+
+fn wrap_algorithm_identifier() -> AlgorithmIdentifier {
+    algorithm_identifier(OID_AES256_WRAP, None)
+}
+
+/// Return the key-encryption key length in bytes of the AES key wrap algorithm.
+fn key_wrap_length(wrap: &AlgorithmIdentifier) -> Result<usize, CmsError> {
+    if wrap.algorithm.to_vec().eq(OID_AES256_WRAP) {
+        Ok(KEK_LENGTH)
+    } else {
+        Err(unsupported_algorithm(wrap))
+    }
+}
+
+/// Return the DER encoded `ECC-CMS-SharedInfo` used as X9.63 KDF input.
+fn ecc_cms_shared_info(wrap: &AlgorithmIdentifier) -> Vec<u8> {
+    rasn::der::encode(&EccCmsSharedInfo {
+        key_info: wrap.clone(),
+        entity_u_info: None,
+        supp_pub_info: OctetString::from(
+            u32::try_from(KEK_LENGTH * 8)
+                .unwrap()
+                .to_be_bytes()
+                .to_vec(),
+        ),
+    })
+    .unwrap()
+}
+
+/// Return the DER encoded `CMSORIforKEMOtherInfo` used as HKDF info.
+fn kem_other_info(wrap: &AlgorithmIdentifier, ukm: Option<OctetString>) -> Vec<u8> {
+    rasn::der::encode(&CmsOriForKemOtherInfo {
+        wrap: wrap.clone(),
+        kek_length: Integer::from(KEK_LENGTH),
+        ukm,
+    })
+    .unwrap()
+}
+
+/// Adapter for RustCrypto primitives to use the Tyst PRNG.
+struct TystRng;
+
+impl RngCore for TystRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        Tyst::instance().prng_fill_with_random(None, dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for TystRng {}
+
+/** Return the `cek` encrypted with RSAES-OAEP by the `tyst` provider.
+
+The provider is looked up by the `id-RSAES-OAEP` OID and uses SHA-256 with
+MGF1-SHA-256 as announced by [rsaes_oaep_algorithm_identifier].
+*/
+fn rsa_oaep_encrypt(spki: Vec<u8>, cek: &[u8]) -> Result<Vec<u8>, CmsError> {
+    let public_key = spki.to_public_key();
+    Ok(rsa_oaep()?.encrypt(public_key.as_ref(), cek))
+}
+
+/** Return the `encrypted_key` decrypted with RSAES-OAEP by the `tyst`
+provider.
+
+Decryption is left to the provider, since a constant time implementation is
+required to avoid padding oracle attacks like the Marvin attack.
+*/
+fn rsa_oaep_decrypt(
+    private_key: &Pkcs8PrivateKey,
+    encrypted_key: &[u8],
+) -> Result<Vec<u8>, CmsError> {
+    rsa_oaep()?
+        .decrypt(private_key.to_private_key().as_ref(), encrypted_key)
+        .ok_or_else(|| CmsErrorKind::DecryptionFailure.error_with_msg("RSAES-OAEP failed."))
+}
+
+fn rsa_oaep() -> Result<Box<dyn Pke>, CmsError> {
+    Tyst::instance()
+        .pkes()
+        .by_oid(&oid::as_string(OID_RSAES_OAEP))
+        .ok_or_else(|| {
+            let msg = format!(
+                "Unsupported key transport '{}'.",
+                oid::as_string(OID_RSAES_OAEP)
+            );
+            CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+        })
+}
+
+/// Return the uncompressed ephemeral public key and the shared secret.
+fn ecdh_ephemeral<C>(spki: &[u8]) -> Result<(Vec<u8>, Vec<u8>), CmsError>
+where
+    C: CurveArithmetic + AssociatedOid,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    let public_key = elliptic_curve::PublicKey::<C>::from_public_key_der(spki)
+        .map_err(|e| CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&e.to_string()))?;
+    let ephemeral_secret = EphemeralSecret::<C>::random(&mut TystRng);
+    let shared_secret = ephemeral_secret.diffie_hellman(&public_key);
+    let ephemeral_public_key = ephemeral_secret
+        .public_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    Ok((
+        ephemeral_public_key,
+        shared_secret.raw_secret_bytes().to_vec(),
+    ))
+}
+
+fn ecdh_static<C>(pkcs8: &[u8], ephemeral_public_key: &[u8]) -> Result<Vec<u8>, CmsError>
+where
+    C: CurveArithmetic + AssociatedOid,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    let secret_key = elliptic_curve::SecretKey::<C>::from_pkcs8_der(pkcs8)
+        .map_err(|e| CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&e.to_string()))?;
+    let public_key = elliptic_curve::PublicKey::<C>::from_sec1_bytes(ephemeral_public_key)
+        .map_err(|e| CmsErrorKind::DecryptionFailure.error_with_msg(&e.to_string()))?;
+    let shared_secret = elliptic_curve::ecdh::diffie_hellman(
+        secret_key.to_nonzero_scalar(),
+        public_key.as_affine(),
+    );
+    Ok(shared_secret.raw_secret_bytes().to_vec())
+}
+
+/// ANSI X9.63 KDF with SHA-256 as specified by RFC5753.
+fn x963_kdf_sha256(shared_secret: &[u8], shared_info: &[u8], len: usize) -> Vec<u8> {
+    let mut ret = Vec::with_capacity(len);
+    let mut counter = 1u32;
+    while ret.len() < len {
+        ret.extend(
+            sha2::Sha256::new()
+                .chain_update(shared_secret)
+                .chain_update(counter.to_be_bytes())
+                .chain_update(shared_info)
+                .finalize(),
+        );
+        counter += 1;
+    }
+    ret.truncate(len);
+    ret
+}
+
+/// HKDF with SHA-256 without salt as specified by RFC8619.
+fn hkdf_sha256(ikm: &[u8], info: &[u8], len: usize) -> Vec<u8> {
+    let mut okm = vec![0u8; len];
+    hkdf::Hkdf::<sha2::Sha256>::new(None, ikm)
+        .expand(info, &mut okm)
+        .unwrap();
+    okm
+}
+
+fn aes_key_wrap(kek: &[u8], cek: &[u8]) -> Result<Vec<u8>, CmsError> {
+    let ret = match kek.len() {
+        16 => aes_kw::KekAes128::try_from(kek)
+            .ok()
+            .and_then(|kek| kek.wrap_vec(cek).ok()),
+        24 => aes_kw::KekAes192::try_from(kek)
+            .ok()
+            .and_then(|kek| kek.wrap_vec(cek).ok()),
+        32 => aes_kw::KekAes256::try_from(kek)
+            .ok()
+            .and_then(|kek| kek.wrap_vec(cek).ok()),
+        _ => None,
+    };
+    ret.ok_or_else(|| CmsErrorKind::EncryptionFailure.error_with_msg("AES key wrap failed."))
+}
+
+fn aes_key_unwrap(kek: &[u8], wrapped: &[u8]) -> Result<Vec<u8>, CmsError> {
+    let ret = match kek.len() {
+        16 => aes_kw::KekAes128::try_from(kek)
+            .ok()
+            .and_then(|kek| kek.unwrap_vec(wrapped).ok()),
+        24 => aes_kw::KekAes192::try_from(kek)
+            .ok()
+            .and_then(|kek| kek.unwrap_vec(wrapped).ok()),
+        32 => aes_kw::KekAes256::try_from(kek)
+            .ok()
+            .and_then(|kek| kek.unwrap_vec(wrapped).ok()),
+        _ => None,
+    };
+    ret.ok_or_else(|| CmsErrorKind::DecryptionFailure.error_with_msg("AES key unwrap failed."))
+}
+
+/// Return the shared secret and the ciphertext encapsulated to the public key.
+fn kem_encapsulate(kem_oid: &[u32], spki: Vec<u8>) -> Result<(Vec<u8>, Vec<u8>), CmsError> {
+    let mut kem = Tyst::instance()
+        .kems()
+        .by_oid(&oid::as_string(kem_oid))
+        .ok_or_else(|| {
+            let msg = format!("Unsupported KEM '{}'.", oid::as_string(kem_oid));
+            CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+        })?;
+    let public_key = spki.to_public_key();
+    Ok(kem.encapsulate(public_key.as_ref()))
+}
+
+fn kem_decapsulate(
+    kem_oid: &[u32],
+    private_key: &Pkcs8PrivateKey,
+    kemct: &[u8],
+) -> Result<Vec<u8>, CmsError> {
+    let mut kem = Tyst::instance()
+        .kems()
+        .by_oid(&oid::as_string(kem_oid))
+        .ok_or_else(|| {
+            let msg = format!("Unsupported KEM '{}'.", oid::as_string(kem_oid));
+            CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+        })?;
+    Ok(kem.decapsulate(private_key.to_private_key().as_ref(), kemct))
+}
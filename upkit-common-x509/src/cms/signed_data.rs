@@ -0,0 +1,411 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! [RFC5652 5](https://www.rfc-editor.org/rfc/rfc5652#section-5) SignedData
+//! creation.
+
+/*
+SignedData ::= SEQUENCE {
+    version CMSVersion,
+    digestAlgorithms DigestAlgorithmIdentifiers,
+    encapContentInfo EncapsulatedContentInfo,
+    certificates [0] IMPLICIT CertificateSet OPTIONAL,
+    crls [1] IMPLICIT RevocationInfoChoices OPTIONAL,
+    signerInfos SignerInfos }
+
+EncapsulatedContentInfo ::= SEQUENCE {
+    eContentType ContentType,
+    eContent [0] EXPLICIT OCTET STRING OPTIONAL }
+
+SignerInfo ::= SEQUENCE {
+    version CMSVersion,
+    sid SignerIdentifier,
+    digestAlgorithm DigestAlgorithmIdentifier,
+    signedAttrs [0] IMPLICIT SignedAttributes OPTIONAL,
+    signatureAlgorithm SignatureAlgorithmIdentifier,
+    signature SignatureValue,
+    unsignedAttrs [1] IMPLICIT UnsignedAttributes OPTIONAL }
+
+SigningCertificate ::=  SEQUENCE {
+    certs        SEQUENCE OF ESSCertID,
+    policies     SEQUENCE OF PolicyInformation OPTIONAL }
+
+ESSCertID ::=  SEQUENCE {
+    certHash                 Hash,
+    issuerSerial             IssuerSerial OPTIONAL }
+
+SigningCertificateV2 ::=  SEQUENCE {
+    certs        SEQUENCE OF ESSCertIDv2,
+    policies     SEQUENCE OF PolicyInformation OPTIONAL }
+
+ESSCertIDv2 ::=  SEQUENCE {
+    hashAlgorithm           AlgorithmIdentifier DEFAULT {algorithm id-sha256},
+    certHash                Hash,
+    issuerSerial            IssuerSerial OPTIONAL }
+
+IssuerSerial ::= SEQUENCE {
+    issuer                   GeneralNames,
+    serialNumber             CertificateSerialNumber }
+*/
+
+use super::CertificateIdentifier;
+use super::CertificateIdentifierType;
+use super::CmsDigestAlgorithm;
+use super::CmsError;
+use super::CmsErrorKind;
+use super::ContentInfo;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::Validity;
+use crate::encdec::rasn::Attribute;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+use sha1::Digest;
+use tyst::traits::se::PrivateKey;
+use tyst::Tyst;
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct SignedData {
+    pub(crate) version: Integer,
+    pub(crate) digest_algorithms: SetOf<AlgorithmIdentifier>,
+    pub(crate) encap_content_info: EncapsulatedContentInfo,
+    /// `CertificateChoices` kept as encoded, so certificates can be parsed
+    /// from the received bytes.
+    #[rasn(tag(context, 0))]
+    pub(crate) certificates: Option<SetOf<Any>>,
+    #[rasn(tag(context, 1))]
+    pub(crate) crls: Option<SetOf<Any>>,
+    pub(crate) signer_infos: SetOf<SignerInfo>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct EncapsulatedContentInfo {
+    pub(crate) e_content_type: ObjectIdentifier,
+    #[rasn(tag(explicit(context, 0)))]
+    pub(crate) e_content: Option<OctetString>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct SignerInfo {
+    pub(crate) version: Integer,
+    pub(crate) sid: CertificateIdentifier,
+    pub(crate) digest_algorithm: AlgorithmIdentifier,
+    #[rasn(tag(context, 0))]
+    pub(crate) signed_attrs: Option<SetOf<Attribute>>,
+    pub(crate) signature_algorithm: AlgorithmIdentifier,
+    pub(crate) signature: OctetString,
+    #[rasn(tag(context, 1))]
+    pub(crate) unsigned_attrs: Option<SetOf<Attribute>>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct SigningCertificate {
+    pub(crate) certs: SequenceOf<EssCertId>,
+    pub(crate) policies: Option<SequenceOf<rasn_pkix::PolicyInformation>>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct EssCertId {
+    /// SHA-1 hash of the certificate
+    pub(crate) cert_hash: OctetString,
+    pub(crate) issuer_serial: Option<IssuerSerial>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct SigningCertificateV2 {
+    pub(crate) certs: SequenceOf<EssCertIdV2>,
+    pub(crate) policies: Option<SequenceOf<rasn_pkix::PolicyInformation>>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct EssCertIdV2 {
+    /// `None` means SHA-256
+    pub(crate) hash_algorithm: Option<AlgorithmIdentifier>,
+    pub(crate) cert_hash: OctetString,
+    pub(crate) issuer_serial: Option<IssuerSerial>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct IssuerSerial {
+    pub(crate) issuer: rasn_pkix::GeneralNames,
+    pub(crate) serial_number: Integer,
+}
+
+/** CMS SignedData builder.
+
+Signing is done in two phases like for certificates: first the
+[Self::get_signed_attributes_to_be_signed] are signed by the holder of the
+private key and then [Self::to_content_info] assembles the message. For keys
+available through `tyst`, [Self::sign] does both.
+
+The signed attributes always include the content-type, message-digest,
+signing-time and ESS signing-certificate-v2
+([RFC5035](https://www.rfc-editor.org/rfc/rfc5035)) attributes.
+*/
+pub struct SignedDataBuilder {
+    content_type: Vec<u32>,
+    content: Vec<u8>,
+    detached: bool,
+    signer_certificate: CertificateParser,
+    certificates: Vec<CertificateParser>,
+    include_certificates: bool,
+    signer_identifier_type: CertificateIdentifierType,
+    digest_algorithm: CmsDigestAlgorithm,
+    signing_time: u64,
+}
+
+impl SignedDataBuilder {
+    /// Return a new instance that will sign the `content` using the key of
+    /// the DER encoded signer certificate.
+    pub fn new(content: &[u8], signer_certificate_der: &[u8]) -> Result<Self, CmsError> {
+        let signer_certificate = CertificateParser::from_bytes(signer_certificate_der)
+            .map_err(|e| CmsErrorKind::DecodingError.error_with_msg(&e.to_string()))?;
+        Ok(Self {
+            content_type: super::OID_DATA.to_vec(),
+            content: content.to_vec(),
+            detached: false,
+            signer_certificate,
+            certificates: vec![],
+            include_certificates: true,
+            signer_identifier_type: CertificateIdentifierType::default(),
+            digest_algorithm: CmsDigestAlgorithm::default(),
+            signing_time: Validity::now_epoch_seconds(),
+        })
+    }
+
+    /// Use a content type other than `id-data`.
+    pub fn with_content_type(mut self, content_type: &[u32]) -> Self {
+        self.content_type = content_type.to_vec();
+        self
+    }
+
+    /// Don't include the content in the message.
+    pub fn with_detached_content(mut self) -> Self {
+        self.detached = true;
+        self
+    }
+
+    /// Include the DER encoded CA certificates in the message. The signer
+    /// certificate is always included.
+    pub fn with_certificates(mut self, certificates_der: &[Vec<u8>]) -> Result<Self, CmsError> {
+        for certificate_der in certificates_der {
+            let certificate = CertificateParser::from_bytes(certificate_der)
+                .map_err(|e| CmsErrorKind::DecodingError.error_with_msg(&e.to_string()))?;
+            self.certificates.push(certificate);
+        }
+        Ok(self)
+    }
+
+    /// Don't include any certificates in the message. The recipient must
+    /// then obtain the signer certificate by other means.
+    pub fn without_certificates(mut self) -> Self {
+        self.include_certificates = false;
+        self
+    }
+
+    /// Identify the signer by something other than issuer and serial number.
+    pub fn with_signer_identifier_type(
+        mut self,
+        signer_identifier_type: CertificateIdentifierType,
+    ) -> Self {
+        self.signer_identifier_type = signer_identifier_type;
+        self
+    }
+
+    /// Use a non-default message digest algorithm.
+    pub fn with_digest_algorithm(mut self, digest_algorithm: CmsDigestAlgorithm) -> Self {
+        self.digest_algorithm = digest_algorithm;
+        self
+    }
+
+    /// Use a signing time other than the creation time of this builder.
+    pub fn with_signing_time(mut self, signing_time_epoch_seconds: u64) -> Self {
+        self.signing_time = signing_time_epoch_seconds;
+        self
+    }
+
+    /// Return the DER encoded signed attributes that should be signed.
+    pub fn get_signed_attributes_to_be_signed(&self) -> Vec<u8> {
+        rasn::der::encode(&self.signed_attributes()).unwrap()
+    }
+
+    /** Return the DER encoded `ContentInfo` with the SignedData.
+
+    `signature_algorithm` is a DER encoded `AlgorithmIdentifier` (see
+    [super::signature_algorithm_identifier]) and `signature` is the signature
+    of [Self::get_signed_attributes_to_be_signed].
+    */
+    pub fn to_content_info(
+        &self,
+        signature_algorithm: &[u8],
+        signature: Vec<u8>,
+    ) -> Result<Vec<u8>, CmsError> {
+        let sid = CertificateIdentifier::from_certificate(
+            &self.signer_certificate,
+            self.signer_identifier_type,
+        )
+        .ok_or_else(|| {
+            CmsErrorKind::SignerNotFound
+                .error_with_msg("Signer certificate has no Subject Key Identifier.")
+        })?;
+        let signer_info_version = match sid {
+            CertificateIdentifier::IssuerAndSerialNumber(_) => 1,
+            CertificateIdentifier::SubjectKeyIdentifier(_) => 3,
+        };
+        let signature_algorithm = rasn::der::decode::<AlgorithmIdentifier>(signature_algorithm)
+            .map_err(|e| CmsErrorKind::DecodingError.error_with_msg(&e.to_string()))?;
+        let signer_info = SignerInfo {
+            version: Integer::from(signer_info_version),
+            sid,
+            digest_algorithm: self.digest_algorithm.to_algorithm_identifier(),
+            signed_attrs: Some(self.signed_attributes()),
+            signature_algorithm,
+            signature: OctetString::from(signature),
+            unsigned_attrs: None,
+        };
+        // RFC5652 5.1: v3 if any SignerInfo is v3 or the content isn't id-data
+        let version = if signer_info_version == 3 || !self.content_type.eq(super::OID_DATA) {
+            3
+        } else {
+            1
+        };
+        let mut digest_algorithms = SetOf::new();
+        digest_algorithms.insert(self.digest_algorithm.to_algorithm_identifier());
+        let mut certificates = SetOf::new();
+        for certificate in [&self.signer_certificate]
+            .into_iter()
+            .chain(self.certificates.iter())
+        {
+            certificates.insert(Any::new(certificate.get_encoded_certificate()));
+        }
+        let mut signer_infos = SetOf::new();
+        signer_infos.insert(signer_info);
+        let signed_data = SignedData {
+            version: Integer::from(version),
+            digest_algorithms,
+            encap_content_info: EncapsulatedContentInfo {
+                e_content_type: ObjectIdentifier::new_unchecked(
+                    self.content_type.to_owned().into(),
+                ),
+                e_content: (!self.detached).then(|| OctetString::from(self.content.to_owned())),
+            },
+            certificates: self.include_certificates.then_some(certificates),
+            crls: None,
+            signer_infos,
+        };
+        Ok(rasn::der::encode(&ContentInfo {
+            content_type: ObjectIdentifier::new_unchecked(super::OID_SIGNED_DATA.into()),
+            content: Any::new(rasn::der::encode(&signed_data).unwrap()),
+        })
+        .unwrap())
+    }
+
+    /// Sign using a `tyst` private key and return the DER encoded
+    /// `ContentInfo` with the SignedData.
+    pub fn sign(
+        &self,
+        private_key: &dyn PrivateKey,
+        signature_algorithm_oid: &[u32],
+    ) -> Result<Vec<u8>, CmsError> {
+        let oid = crate::encdec::oid::as_string(signature_algorithm_oid);
+        let mut se = Tyst::instance().ses().by_oid(&oid).ok_or_else(|| {
+            let msg = format!("Unsupported signature algorithm '{oid}'.");
+            CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+        })?;
+        let signature = se.sign(private_key, &self.get_signed_attributes_to_be_signed());
+        self.to_content_info(
+            &super::signature_algorithm_identifier(signature_algorithm_oid),
+            signature,
+        )
+    }
+
+    fn signed_attributes(&self) -> SetOf<Attribute> {
+        let mut ret = SetOf::new();
+        ret.insert(super::attribute(
+            super::OID_ATTRIBUTE_CONTENT_TYPE,
+            rasn::der::encode(&ObjectIdentifier::new_unchecked(
+                self.content_type.to_owned().into(),
+            ))
+            .unwrap(),
+        ));
+        ret.insert(super::attribute(
+            super::OID_ATTRIBUTE_MESSAGE_DIGEST,
+            rasn::der::encode(&OctetString::from(
+                self.digest_algorithm.digest(&self.content),
+            ))
+            .unwrap(),
+        ));
+        ret.insert(super::attribute(
+            super::OID_ATTRIBUTE_SIGNING_TIME,
+            rasn::der::encode(&Validity::to_rasn_epoch_seconds(self.signing_time)).unwrap(),
+        ));
+        ret.insert(super::attribute(
+            super::OID_ATTRIBUTE_SIGNING_CERTIFICATE_V2,
+            rasn::der::encode(&SigningCertificateV2::from_certificate(
+                &self.signer_certificate,
+            ))
+            .unwrap(),
+        ));
+        ret
+    }
+}
+
+impl SigningCertificate {
+    /// Return `true` if the first ESSCertID references the `certificate`.
+    pub(crate) fn matches(&self, certificate: &CertificateParser) -> bool {
+        self.certs.first().is_some_and(|ess_cert_id| {
+            sha1::Sha1::digest(certificate.as_encoded_certificate())
+                .as_slice()
+                .eq(ess_cert_id.cert_hash.as_ref())
+        })
+    }
+}
+
+impl SigningCertificateV2 {
+    fn from_certificate(certificate: &CertificateParser) -> Self {
+        let issuer =
+            rasn::der::decode::<rasn_pkix::Name>(&certificate.get_encoded_issuer()).unwrap();
+        Self {
+            certs: vec![EssCertIdV2 {
+                hash_algorithm: None,
+                cert_hash: OctetString::from(
+                    CmsDigestAlgorithm::Sha256.digest(&certificate.get_encoded_certificate()),
+                ),
+                issuer_serial: Some(IssuerSerial {
+                    issuer: vec![rasn_pkix::GeneralName::DirectoryName(issuer)],
+                    serial_number: certificate.get_serial_number().to_rasn_type(),
+                }),
+            }],
+            policies: None,
+        }
+    }
+
+    /// Return `true` if the first ESSCertIDv2 references the `certificate`.
+    pub(crate) fn matches(&self, certificate: &CertificateParser) -> bool {
+        let Some(ess_cert_id) = self.certs.first() else {
+            return false;
+        };
+        let digest_algorithm = match &ess_cert_id.hash_algorithm {
+            None => Some(CmsDigestAlgorithm::Sha256),
+            Some(hash_algorithm) => CmsDigestAlgorithm::from_oid(&hash_algorithm.algorithm),
+        };
+        digest_algorithm.is_some_and(|digest_algorithm| {
+            digest_algorithm
+                .digest(&certificate.get_encoded_certificate())
+                .eq(ess_cert_id.cert_hash.as_ref())
+        })
+    }
+}
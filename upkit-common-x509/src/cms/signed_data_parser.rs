@@ -0,0 +1,712 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! [RFC5652 5](https://www.rfc-editor.org/rfc/rfc5652#section-5) SignedData
+//! parsing and verification.
+
+use super::signed_data::SignedData;
+use super::signed_data::SignerInfo;
+use super::signed_data::SigningCertificate;
+use super::signed_data::SigningCertificateV2;
+use super::CmsDigestAlgorithm;
+use super::CmsError;
+use super::CmsErrorKind;
+use super::ContentInfo;
+use crate::cert::extensions::BasicConstraints;
+use crate::cert::extensions::ExtendedKeyUsage;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::Validity;
+use crate::cert::validate::checkers::ExtendedKeyUsageChecker;
+use crate::cert::validate::CertificatePathValidator;
+use crate::encdec::tlv::Tlv;
+use crate::encdec::DecodingError;
+use rasn::prelude::*;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tyst::traits::se::ToPublicKey;
+use tyst::Tyst;
+
+/// `SEQUENCE` tag of a `Certificate` in `CertificateChoices`.
+const TAG_SEQUENCE: u8 = 0x30;
+/// `SET OF` tag that replaces the `[0] IMPLICIT` tag of `signedAttrs` when
+/// the signature is computed.
+const TAG_SET: u8 = 0x31;
+/// `[0] IMPLICIT SignedAttributes` of a `SignerInfo`.
+const TAG_SIGNED_ATTRS: u8 = 0xa0;
+
+/** CMS SignedData parser.
+
+Certificates and signed attributes are verified as received, without
+re-encoding.
+*/
+pub struct SignedDataParser {
+    signed_data: SignedData,
+    certificates: Vec<CertificateParser>,
+    /// Each `SignerInfo` with its signed attributes as they were signed.
+    signer_infos: Vec<(SignerInfo, Option<Vec<u8>>)>,
+}
+
+impl SignedDataParser {
+    /// Create a new instance from a BER or DER encoded `ContentInfo`.
+    pub fn from_bytes(encoded_content_info: &[u8]) -> Result<Self, CmsError> {
+        let content_info = super::decode::<ContentInfo>(encoded_content_info)?;
+        if !content_info
+            .content_type
+            .to_vec()
+            .eq(super::OID_SIGNED_DATA)
+        {
+            let msg = format!(
+                "Content type '{}' is not SignedData.",
+                crate::encdec::oid::as_string(&content_info.content_type)
+            );
+            return Err(CmsErrorKind::DecodingError.error_with_msg(&msg));
+        }
+        let encoded_signed_data = content_info.content.as_bytes();
+        let signed_data = super::decode::<SignedData>(encoded_signed_data)?;
+        // Other CertificateChoices (e.g. attribute certificates) are ignored
+        let certificates = signed_data
+            .certificates
+            .iter()
+            .flat_map(|certificates| certificates.to_vec())
+            .filter(|certificate| certificate.as_bytes().first() == Some(&TAG_SEQUENCE))
+            .map(|certificate| {
+                CertificateParser::from_bytes(certificate.as_bytes())
+                    .map_err(|e| CmsErrorKind::DecodingError.error_with_msg(&e.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let signer_infos = match Self::encoded_signer_infos(encoded_signed_data) {
+            Ok(signer_infos) => signer_infos,
+            // BER with indefinite lengths can't be walked, but RFC5652 5.4
+            // requires the signed attributes to be DER encoded anyway.
+            Err(_) => signed_data
+                .signer_infos
+                .to_vec()
+                .into_iter()
+                .map(|signer_info| {
+                    let signed_attrs = signer_info
+                        .signed_attrs
+                        .as_ref()
+                        .map(rasn::der::encode)
+                        .transpose()
+                        .map_err(|e| CmsErrorKind::DecodingError.error_with_msg(&e.to_string()))?;
+                    Ok((signer_info.to_owned(), signed_attrs))
+                })
+                .collect::<Result<Vec<_>, CmsError>>()?,
+        };
+        Ok(Self {
+            signed_data,
+            certificates,
+            signer_infos,
+        })
+    }
+
+    /// Return each `SignerInfo` of the encoded SignedData with the received
+    /// signed attributes, where the `[0] IMPLICIT` tag is replaced by the
+    /// `SET OF` tag as described in
+    /// [RFC5652 5.4](https://www.rfc-editor.org/rfc/rfc5652#section-5.4).
+    fn encoded_signer_infos(
+        encoded_signed_data: &[u8],
+    ) -> Result<Vec<(SignerInfo, Option<Vec<u8>>)>, DecodingError> {
+        let signed_data = Tlv::read(encoded_signed_data, 0)?;
+        let signer_infos = signed_data
+            .children(encoded_signed_data)?
+            .pop()
+            .ok_or_else(|| DecodingError::with_msg("Missing SignerInfos."))?;
+        signer_infos
+            .children(encoded_signed_data)?
+            .into_iter()
+            .map(|tlv| {
+                let signer_info =
+                    rasn::ber::decode::<SignerInfo>(&encoded_signed_data[tlv.range.clone()])
+                        .map_err(|e| DecodingError::with_msg(&e.to_string()))?;
+                let signed_attrs = tlv
+                    .children(encoded_signed_data)?
+                    .into_iter()
+                    .find(|child| child.tag == TAG_SIGNED_ATTRS)
+                    .map(|child| {
+                        let mut signed_attrs = encoded_signed_data[child.range].to_vec();
+                        signed_attrs[0] = TAG_SET;
+                        signed_attrs
+                    });
+                Ok((signer_info, signed_attrs))
+            })
+            .collect()
+    }
+
+    /// Return the content type of the encapsulated content.
+    pub fn get_content_type(&self) -> Vec<u32> {
+        self.signed_data.encap_content_info.e_content_type.to_vec()
+    }
+
+    /// Return the encapsulated content or `None` if the content is detached.
+    pub fn get_content(&self) -> Option<&[u8]> {
+        self.signed_data
+            .encap_content_info
+            .e_content
+            .as_ref()
+            .map(|e_content| e_content.as_ref())
+    }
+
+    /// Return the certificates included in the message.
+    pub fn get_certificates(&self) -> &[CertificateParser] {
+        &self.certificates
+    }
+
+    /// Return the signing time of the first signer (if present).
+    pub fn get_signing_time(&self) -> Option<u64> {
+        let signer_info = self.signer_infos().into_iter().next()?;
+        let signed_attrs = signer_info.signed_attrs.as_ref()?;
+        super::attribute_value(signed_attrs, super::OID_ATTRIBUTE_SIGNING_TIME)
+            .ok()
+            .flatten()
+            .and_then(|value| rasn::der::decode::<rasn_pkix::Time>(value.as_bytes()).ok())
+            .and_then(|time| Validity::try_from_rasn_epoch_seconds(&time).ok())
+    }
+
+    /** Return the signature value and DER encoded `TimeStampToken` of each
+    signer that has an unsigned signature time-stamp attribute
+    ([RFC3161 Appendix A](https://www.rfc-editor.org/rfc/rfc3161#appendix-A)).
+
+    The message imprint of the time-stamp token is the digest of the signature
+    value.
+    */
+    pub fn get_signature_time_stamp_tokens(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.signer_infos()
+            .into_iter()
+            .filter_map(|signer_info| {
+                let unsigned_attrs = signer_info.unsigned_attrs.as_ref()?;
+                super::attribute_value(
+                    unsigned_attrs,
+                    super::OID_ATTRIBUTE_SIGNATURE_TIME_STAMP_TOKEN,
+                )
+                .ok()
+                .flatten()
+                .map(|value| (signer_info.signature.to_vec(), value.as_bytes().to_vec()))
+            })
+            .collect()
+    }
+
+    /** Verify the signatures of all signers and return their certificates.
+
+    `detached_content` must be provided when the content is not encapsulated
+    and must be `None` otherwise.
+
+    This only proves that the content was signed by the keys of the returned
+    certificates. Use [Self::validate] to also check that the certificates
+    are trusted.
+    */
+    pub fn verify_signatures(
+        &self,
+        detached_content: Option<&[u8]>,
+    ) -> Result<Vec<CertificateParser>, CmsError> {
+        let content = match (self.get_content(), detached_content) {
+            (Some(content), None) | (None, Some(content)) => content,
+            (Some(_), Some(_)) => {
+                return Err(CmsErrorKind::DecodingError.error_with_msg(
+                    "Detached content was provided, but the content is encapsulated.",
+                ))
+            }
+            (None, None) => {
+                return Err(CmsErrorKind::DecodingError.error_with_msg("Missing content."))
+            }
+        };
+        if self.signer_infos.is_empty() {
+            return Err(CmsErrorKind::SignerNotFound.error_with_msg("No SignerInfo present."));
+        }
+        self.signer_infos
+            .iter()
+            .map(|(signer_info, encoded_signed_attrs)| {
+                self.verify_signer_info(signer_info, encoded_signed_attrs.as_deref(), content)
+            })
+            .collect()
+    }
+
+    /** Verify the signatures and validate the signer certificate chains.
+
+    `detached_content` is handled like by [Self::verify_signatures].
+
+    Each signer certificate is validated with the `certificate_path_validator`
+    at `at_epoch_seconds` together with the CA certificates in the message
+    that chain to it. The signer certificates must have all `required_ekus`.
+    */
+    pub fn validate(
+        &self,
+        detached_content: Option<&[u8]>,
+        certificate_path_validator: &CertificatePathValidator,
+        at_epoch_seconds: u64,
+        required_ekus: &[ExtendedKeyUsage],
+    ) -> Result<(), CmsError> {
+        for signer_certificate in self.verify_signatures(detached_content)? {
+            self.validate_signer_certificate(
+                &signer_certificate,
+                certificate_path_validator,
+                at_epoch_seconds,
+                required_ekus,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Validate the certificate of a signer, whose signature has already
+    /// been verified, like [Self::validate] does.
+    pub(crate) fn validate_signer_certificate(
+        &self,
+        signer_certificate: &CertificateParser,
+        certificate_path_validator: &CertificatePathValidator,
+        at_epoch_seconds: u64,
+        required_ekus: &[ExtendedKeyUsage],
+    ) -> Result<(), CmsError> {
+        let chain = self.issuer_chain(signer_certificate);
+        certificate_path_validator
+            .validate(
+                chain,
+                at_epoch_seconds,
+                vec![Arc::new(ExtendedKeyUsageChecker::new(required_ekus))],
+            )
+            .map_err(|e| CmsErrorKind::InvalidSignerCertificate.error_with_msg(&e.to_string()))?;
+        Ok(())
+    }
+
+    /** Return the DER encoded signer certificate followed by the CA
+    certificates in the message that chain to it.
+
+    An issuer is found by matching its subject with the issuer of the
+    certificate below it, or its subject key identifier with the authority key
+    identifier. Each certificate is included at most once.
+    */
+    fn issuer_chain(&self, signer_certificate: &CertificateParser) -> Vec<Vec<u8>> {
+        let mut fingerprints = HashSet::from([signer_certificate.fingerprint()]);
+        let mut chain = vec![signer_certificate.get_encoded_certificate()];
+        let mut current = signer_certificate;
+        while let Some(issuer) = self.certificates.iter().find(|certificate| {
+            !fingerprints.contains(certificate.fingerprint())
+                && certificate
+                    .get_basic_constraints()
+                    .ok()
+                    .flatten()
+                    .as_ref()
+                    .is_some_and(BasicConstraints::is_ca)
+                && Self::is_issued_by(current, certificate)
+        }) {
+            fingerprints.insert(issuer.fingerprint());
+            chain.push(issuer.get_encoded_certificate());
+            current = issuer;
+        }
+        chain
+    }
+
+    /// Return `true` if the `issuer` name or key identifier matches the
+    /// `certificate`.
+    fn is_issued_by(certificate: &CertificateParser, issuer: &CertificateParser) -> bool {
+        if certificate
+            .as_encoded_issuer()
+            .eq(issuer.as_encoded_subject())
+        {
+            return true;
+        }
+        match (
+            certificate.get_authority_key_identifier_kid(),
+            issuer.get_subject_key_identifier_kid(),
+        ) {
+            (Ok(Some(aki)), Ok(Some(ski))) => aki.eq(&ski),
+            _ => false,
+        }
+    }
+
+    /** Return `true` if every signer has an ESS signing-certificate
+    ([RFC2634 5.4](https://www.rfc-editor.org/rfc/rfc2634#section-5.4)) or
+    signing-certificate-v2 signed attribute.
+
+    The attributes are checked against the signer certificates by
+    [Self::verify_signatures].
+    */
+    pub(crate) fn has_signing_certificate_attributes(&self) -> bool {
+        self.signer_infos().into_iter().all(|signer_info| {
+            signer_info
+                .signed_attrs
+                .as_ref()
+                .is_some_and(|signed_attrs| {
+                    [
+                        super::OID_ATTRIBUTE_SIGNING_CERTIFICATE,
+                        super::OID_ATTRIBUTE_SIGNING_CERTIFICATE_V2,
+                    ]
+                    .into_iter()
+                    .any(|oid| {
+                        super::attribute_value(signed_attrs, oid)
+                            .ok()
+                            .flatten()
+                            .is_some()
+                    })
+                })
+        })
+    }
+
+    /// Return `true` if the certificates of all signers are included in the
+    /// message.
+    pub(crate) fn includes_signer_certificates(&self) -> bool {
+        self.signer_infos().into_iter().all(|signer_info| {
+            self.certificates
+                .iter()
+                .any(|certificate| signer_info.sid.matches(certificate))
+        })
+    }
+
+    fn signer_infos(&self) -> Vec<&SignerInfo> {
+        self.signer_infos
+            .iter()
+            .map(|(signer_info, _)| signer_info)
+            .collect()
+    }
+
+    fn verify_signer_info(
+        &self,
+        signer_info: &SignerInfo,
+        encoded_signed_attrs: Option<&[u8]>,
+        content: &[u8],
+    ) -> Result<CertificateParser, CmsError> {
+        let signer_certificate = self
+            .certificates
+            .iter()
+            .find(|certificate| signer_info.sid.matches(certificate))
+            .ok_or_else(|| CmsErrorKind::SignerNotFound.error())?;
+        let digest_algorithm =
+            CmsDigestAlgorithm::from_algorithm_identifier(&signer_info.digest_algorithm)?;
+        let e_content_type = &self.signed_data.encap_content_info.e_content_type;
+        let signed_data = if let (Some(signed_attrs), Some(encoded_signed_attrs)) =
+            (&signer_info.signed_attrs, encoded_signed_attrs)
+        {
+            // RFC5652 5.3: content-type and message-digest are mandatory
+            let content_type =
+                super::attribute_value(signed_attrs, super::OID_ATTRIBUTE_CONTENT_TYPE)?
+                    .and_then(|value| rasn::der::decode::<ObjectIdentifier>(value.as_bytes()).ok());
+            if !content_type.is_some_and(|content_type| content_type.eq(e_content_type)) {
+                return Err(CmsErrorKind::InvalidAttributes
+                    .error_with_msg("Content type attribute is missing or does not match."));
+            }
+            let message_digest =
+                super::attribute_value(signed_attrs, super::OID_ATTRIBUTE_MESSAGE_DIGEST)?
+                    .and_then(|value| rasn::der::decode::<OctetString>(value.as_bytes()).ok());
+            let digest = digest_algorithm.digest(content);
+            if !message_digest.is_some_and(|message_digest| digest.eq(message_digest.as_ref())) {
+                return Err(CmsErrorKind::InvalidAttributes
+                    .error_with_msg("Message digest attribute is missing or does not match."));
+            }
+            if let Some(value) =
+                super::attribute_value(signed_attrs, super::OID_ATTRIBUTE_SIGNING_CERTIFICATE)?
+            {
+                let signing_certificate = super::decode::<SigningCertificate>(value.as_bytes())?;
+                if !signing_certificate.matches(signer_certificate) {
+                    return Err(CmsErrorKind::InvalidAttributes
+                        .error_with_msg("Signing certificate attribute does not match signer."));
+                }
+            }
+            if let Some(value) =
+                super::attribute_value(signed_attrs, super::OID_ATTRIBUTE_SIGNING_CERTIFICATE_V2)?
+            {
+                let signing_certificate = super::decode::<SigningCertificateV2>(value.as_bytes())?;
+                if !signing_certificate.matches(signer_certificate) {
+                    return Err(CmsErrorKind::InvalidAttributes
+                        .error_with_msg("Signing certificate attribute does not match signer."));
+                }
+            }
+            encoded_signed_attrs.to_vec()
+        } else {
+            if !e_content_type.to_vec().eq(super::OID_DATA) {
+                return Err(CmsErrorKind::InvalidAttributes
+                    .error_with_msg("Signed attributes are required for non-data content."));
+            }
+            content.to_vec()
+        };
+        let oid = crate::encdec::oid::as_string(&signer_info.signature_algorithm.algorithm);
+        let mut se = Tyst::instance().ses().by_oid(&oid).ok_or_else(|| {
+            let msg = format!("Unsupported signature algorithm '{oid}'.");
+            CmsErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+        })?;
+        let public_key = signer_certificate
+            .get_encoded_subject_public_key_info()
+            .to_public_key();
+        if !se.verify(public_key.as_ref(), &signer_info.signature, &signed_data) {
+            let msg = format!(
+                "Unable to verify signature using cert with fp '{}'.",
+                signer_certificate.fingerprint()
+            );
+            return Err(CmsErrorKind::InvalidSignature.error_with_msg(&msg));
+        }
+        Ok(signer_certificate.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::types::WellKnownAlgorithm;
+    use crate::cms::CertificateIdentifierType;
+    use crate::cms::SignedDataBuilder;
+    use crate::encdec::rasn::Attribute;
+    use crate::test_utils::*;
+    use sha1::Digest;
+
+    const CONTENT: &[u8] = b"Signed content";
+
+    fn sign(builder: SignedDataBuilder) -> SignedDataParser {
+        let private_key = load_test_private_key("ec_ca_key.pem").to_private_key();
+        let encoded = builder
+            .sign(
+                private_key.as_ref(),
+                WellKnownAlgorithm::EcdsaWithSha256.as_oid(),
+            )
+            .unwrap();
+        SignedDataParser::from_bytes(&encoded).unwrap()
+    }
+
+    fn error_kind(result: Result<Vec<CertificateParser>, CmsError>) -> CmsErrorKind {
+        result.err().unwrap().kind().clone()
+    }
+
+    #[test]
+    fn sign_and_verify_encapsulated_content() {
+        let signer_der = load_test_certificate_der("ec_ca.b64");
+        let sdp = sign(
+            SignedDataBuilder::new(CONTENT, &signer_der)
+                .unwrap()
+                .with_signing_time(1_700_000_000),
+        );
+        assert_eq!(sdp.get_content(), Some(CONTENT));
+        assert_eq!(sdp.get_content_type(), crate::cms::OID_DATA);
+        assert_eq!(sdp.get_signing_time(), Some(1_700_000_000));
+        let signers = sdp.verify_signatures(None).unwrap();
+        assert_eq!(signers.len(), 1);
+        assert_eq!(signers[0].get_encoded_certificate(), signer_der);
+        // Detached content must not replace the encapsulated content
+        assert_eq!(
+            error_kind(sdp.verify_signatures(Some(b"Other content"))),
+            CmsErrorKind::DecodingError
+        );
+        assert_eq!(
+            error_kind(sdp.verify_signatures(Some(CONTENT))),
+            CmsErrorKind::DecodingError
+        );
+        let validator = CertificatePathValidator::new(vec![signer_der]).unwrap();
+        let err = sdp
+            .validate(Some(b"Other content"), &validator, 1_700_000_000, &[])
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), &CmsErrorKind::DecodingError);
+    }
+
+    #[test]
+    fn sign_and_validate_with_leaf_certificate() {
+        let ca_der = load_test_certificate_der("ec_ca.b64");
+        let private_key = load_test_private_key("tsa_key.pem").to_private_key();
+        let encoded = SignedDataBuilder::new(CONTENT, &load_test_certificate_der("tsa.b64"))
+            .unwrap()
+            .with_certificates(&[
+                ca_der.clone(),
+                ca_der.clone(),
+                load_test_certificate_der("h1_root_ca.b64"),
+            ])
+            .unwrap()
+            .sign(
+                private_key.as_ref(),
+                WellKnownAlgorithm::EcdsaWithSha256.as_oid(),
+            )
+            .unwrap();
+        let sdp = SignedDataParser::from_bytes(&encoded).unwrap();
+        let signer = &sdp.verify_signatures(None).unwrap()[0];
+        // Only the issuer is included, once
+        assert_eq!(
+            sdp.issuer_chain(signer),
+            vec![signer.get_encoded_certificate(), ca_der.clone()]
+        );
+        let validator = CertificatePathValidator::new(vec![ca_der]).unwrap();
+        let now = Validity::now_epoch_seconds();
+        sdp.validate(None, &validator, now, &[ExtendedKeyUsage::PkixTimeStamping])
+            .unwrap();
+        let err = sdp
+            .validate(None, &validator, now, &[ExtendedKeyUsage::PkixCodeSigning])
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), &CmsErrorKind::InvalidSignerCertificate);
+    }
+
+    #[test]
+    fn sign_and_verify_detached_content() {
+        let signer_der = load_test_certificate_der("ec_ca.b64");
+        let sdp = sign(
+            SignedDataBuilder::new(CONTENT, &signer_der)
+                .unwrap()
+                .with_detached_content(),
+        );
+        assert_eq!(sdp.get_content(), None);
+        let signers = sdp.verify_signatures(Some(CONTENT)).unwrap();
+        assert_eq!(signers[0].get_encoded_certificate(), signer_der);
+        assert_eq!(
+            error_kind(sdp.verify_signatures(None)),
+            CmsErrorKind::DecodingError
+        );
+        assert_eq!(
+            error_kind(sdp.verify_signatures(Some(b"Other content"))),
+            CmsErrorKind::InvalidAttributes
+        );
+    }
+
+    #[test]
+    fn tampered_signed_attribute_fails() {
+        let signer_der = load_test_certificate_der("ec_ca.b64");
+        let private_key = load_test_private_key("ec_ca_key.pem").to_private_key();
+        let mut encoded = SignedDataBuilder::new(CONTENT, &signer_der)
+            .unwrap()
+            .with_signing_time(1_700_000_000)
+            .sign(
+                private_key.as_ref(),
+                WellKnownAlgorithm::EcdsaWithSha256.as_oid(),
+            )
+            .unwrap();
+        assert!(SignedDataParser::from_bytes(&encoded)
+            .unwrap()
+            .verify_signatures(None)
+            .is_ok());
+        // Move the signing time one second
+        let signing_time = b"231114221320Z";
+        let offset = encoded
+            .windows(signing_time.len())
+            .position(|window| window == signing_time)
+            .unwrap();
+        encoded[offset + signing_time.len() - 2] = b'1';
+        let sdp = SignedDataParser::from_bytes(&encoded).unwrap();
+        assert_eq!(sdp.get_signing_time(), Some(1_700_000_001));
+        assert_eq!(
+            error_kind(sdp.verify_signatures(None)),
+            CmsErrorKind::InvalidSignature
+        );
+    }
+
+    #[test]
+    fn sign_and_verify_with_subject_key_identifier() {
+        let signer_der = load_test_certificate_der("ec_ca.b64");
+        let sdp = sign(
+            SignedDataBuilder::new(CONTENT, &signer_der)
+                .unwrap()
+                .with_signer_identifier_type(CertificateIdentifierType::SubjectKeyIdentifier),
+        );
+        let signer_info = sdp.signer_infos()[0];
+        assert_eq!(signer_info.version, Integer::from(3));
+        assert!(matches!(
+            signer_info.sid,
+            crate::cms::CertificateIdentifier::SubjectKeyIdentifier(_)
+        ));
+        let signers = sdp.verify_signatures(None).unwrap();
+        assert_eq!(signers[0].get_encoded_certificate(), signer_der);
+    }
+
+    /// Sign the message again after modifying the signed attributes.
+    fn resign(encoded: &[u8], modify: impl FnOnce(Vec<Attribute>) -> Vec<Attribute>) -> Vec<u8> {
+        let content_info = rasn::der::decode::<ContentInfo>(encoded).unwrap();
+        let mut signed_data =
+            rasn::der::decode::<SignedData>(content_info.content.as_bytes()).unwrap();
+        let mut signer_info = signed_data.signer_infos.to_vec()[0].clone();
+        let mut signed_attrs = SetOf::new();
+        for attribute in modify(
+            signer_info
+                .signed_attrs
+                .take()
+                .unwrap()
+                .to_vec()
+                .into_iter()
+                .cloned()
+                .collect(),
+        ) {
+            signed_attrs.insert(attribute);
+        }
+        let private_key = load_test_private_key("ec_ca_key.pem").to_private_key();
+        let oid = crate::encdec::oid::as_string(WellKnownAlgorithm::EcdsaWithSha256.as_oid());
+        let mut se = Tyst::instance().ses().by_oid(&oid).unwrap();
+        signer_info.signature = OctetString::from(se.sign(
+            private_key.as_ref(),
+            &rasn::der::encode(&signed_attrs).unwrap(),
+        ));
+        signer_info.signed_attrs = Some(signed_attrs);
+        let mut signer_infos = SetOf::new();
+        signer_infos.insert(signer_info);
+        signed_data.signer_infos = signer_infos;
+        rasn::der::encode(&ContentInfo {
+            content_type: content_info.content_type,
+            content: Any::new(rasn::der::encode(&signed_data).unwrap()),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn signing_certificate_attributes() {
+        let signer_der = load_test_certificate_der("ec_ca.b64");
+        let private_key = load_test_private_key("ec_ca_key.pem").to_private_key();
+        let encoded = SignedDataBuilder::new(CONTENT, &signer_der)
+            .unwrap()
+            .sign(
+                private_key.as_ref(),
+                WellKnownAlgorithm::EcdsaWithSha256.as_oid(),
+            )
+            .unwrap();
+        let sdp = SignedDataParser::from_bytes(&encoded).unwrap();
+        assert!(sdp.has_signing_certificate_attributes());
+        assert!(sdp.includes_signer_certificates());
+        let without_v2 = |attributes: Vec<Attribute>| {
+            attributes
+                .into_iter()
+                .filter(|attribute| {
+                    !attribute
+                        .r#type
+                        .to_vec()
+                        .eq(crate::cms::OID_ATTRIBUTE_SIGNING_CERTIFICATE_V2)
+                })
+                .collect::<Vec<_>>()
+        };
+        let sdp = SignedDataParser::from_bytes(&resign(&encoded, without_v2)).unwrap();
+        assert!(!sdp.has_signing_certificate_attributes());
+        assert!(sdp.verify_signatures(None).is_ok());
+        // ESS signing-certificate (v1) with the SHA-1 hash of the signer
+        let with_v1 = |cert_hash: Vec<u8>| {
+            move |attributes: Vec<Attribute>| {
+                let mut attributes = without_v2(attributes);
+                attributes.push(crate::cms::attribute(
+                    crate::cms::OID_ATTRIBUTE_SIGNING_CERTIFICATE,
+                    rasn::der::encode(&SigningCertificate {
+                        certs: vec![crate::cms::signed_data::EssCertId {
+                            cert_hash: OctetString::from(cert_hash),
+                            issuer_serial: None,
+                        }],
+                        policies: None,
+                    })
+                    .unwrap(),
+                ));
+                attributes
+            }
+        };
+        let sdp = SignedDataParser::from_bytes(&resign(
+            &encoded,
+            with_v1(sha1::Sha1::digest(&signer_der).to_vec()),
+        ))
+        .unwrap();
+        assert!(sdp.has_signing_certificate_attributes());
+        assert!(sdp.verify_signatures(None).is_ok());
+        let sdp = SignedDataParser::from_bytes(&resign(&encoded, with_v1(vec![0; 20]))).unwrap();
+        assert_eq!(
+            error_kind(sdp.verify_signatures(None)),
+            CmsErrorKind::InvalidAttributes
+        );
+    }
+}
@@ -0,0 +1,554 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! [RFC5280 5](https://www.rfc-editor.org/rfc/rfc5280#section-5) Certificate Revocation List (CRL).
+
+mod crl_number;
+mod issuing_distribution_point;
+mod revoked_certificate_entry;
+
+pub use self::crl_number::CrlNumber;
+pub use self::issuing_distribution_point::IssuingDistributionPoint;
+pub use self::revoked_certificate_entry::RevocationReason;
+pub use self::revoked_certificate_entry::RevokedCertificateEntry;
+use crate::cert::extensions::AuthorityKeyIdentifier;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::IssuerAndSerialNumber;
+use crate::cert::types::SerialNumber;
+use crate::cert::types::Validity;
+use crate::encdec::tlv::Tlv;
+use crate::encdec::DecodingError;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+use rasn_pkix::Extension;
+use rasn_pkix::Extensions;
+use rasn_pkix::Name;
+use rasn_pkix::Time;
+use std::ops::Range;
+use std::sync::Arc;
+use tyst::traits::se::ToPublicKey;
+use tyst::Tyst;
+
+/*
+CertificateList  ::=  SEQUENCE  {
+     tbsCertList          TBSCertList,
+     signatureAlgorithm   AlgorithmIdentifier,
+     signatureValue       BIT STRING  }
+
+TBSCertList  ::=  SEQUENCE  {
+     version                 Version OPTIONAL,
+                                  -- if present, MUST be v2
+     signature               AlgorithmIdentifier,
+     issuer                  Name,
+     thisUpdate              Time,
+     nextUpdate              Time OPTIONAL,
+     revokedCertificates     SEQUENCE OF SEQUENCE  {
+          userCertificate         CertificateSerialNumber,
+          revocationDate          Time,
+          crlEntryExtensions      Extensions OPTIONAL
+                                   -- if present, version MUST be v2
+                               }  OPTIONAL,
+     crlExtensions           [0]  EXPLICIT Extensions OPTIONAL
+                                   -- if present, version MUST be v2
+                               }
+*/
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct CertificateList {
+    tbs_cert_list: TbsCertList,
+    signature_algorithm: AlgorithmIdentifier,
+    signature_value: BitString,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct TbsCertList {
+    version: Option<Integer>,
+    signature: AlgorithmIdentifier,
+    issuer: Name,
+    this_update: Time,
+    next_update: Option<Time>,
+    revoked_certificates: Option<SequenceOf<RevokedCertificate>>,
+    #[rasn(tag(explicit(context, 0)))]
+    crl_extensions: Option<Extensions>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct RevokedCertificate {
+    user_certificate: Integer,
+    revocation_date: Time,
+    crl_entry_extensions: Option<Extensions>,
+}
+
+/** Unsigned TBSCertList in preparation for signing.
+
+This allows assembly and encoding of all relevent pieces of information that
+goes into a CRL except for the actual signing of the data-structure.
+
+The CRL will always be a version 2 CRL with the `cRLNumber` and (when the issuer
+has a public key) `authorityKeyIdentifier` extensions as required by
+[RFC5280 5.2](https://www.rfc-editor.org/rfc/rfc5280#section-5.2).
+*/
+pub struct NoSignatureTbsCertList {
+    tbs_cert_list: Vec<u8>,
+}
+
+impl NoSignatureTbsCertList {
+    /// joint-iso-ccitt(2) ds(5) ce(29) cRLNumber(20)
+    pub const OID_CRL_NUMBER: &'static [u32] = &[2, 5, 29, 20];
+    /// joint-iso-ccitt(2) ds(5) ce(29) cRLReasons(21)
+    pub const OID_REASON_CODE: &'static [u32] = &[2, 5, 29, 21];
+    /// joint-iso-ccitt(2) ds(5) ce(29) invalidityDate(24)
+    pub const OID_INVALIDITY_DATE: &'static [u32] = &[2, 5, 29, 24];
+    /// joint-iso-ccitt(2) ds(5) ce(29) deltaCRLIndicator(27)
+    pub const OID_DELTA_CRL_INDICATOR: &'static [u32] = &[2, 5, 29, 27];
+
+    /// CRL extensions that are processed by [CrlParser].
+    const SUPPORTED_CRL_EXTENSIONS: &'static [&'static [u32]] = &[
+        Self::OID_CRL_NUMBER,
+        AuthorityKeyIdentifier::OID,
+        IssuingDistributionPoint::OID,
+    ];
+    /// CRL entry extensions that are processed by [CrlParser].
+    const SUPPORTED_CRL_ENTRY_EXTENSIONS: &'static [&'static [u32]] =
+        &[Self::OID_REASON_CODE, Self::OID_INVALIDITY_DATE];
+
+    /// Return a new instance.
+    ///
+    /// `issuer` is the certificate of the CA that will sign the CRL.
+    pub fn new(
+        issuer: &CertificateParser,
+        this_update_epoch_seconds: u64,
+        next_update_epoch_seconds: Option<u64>,
+        crl_number: &CrlNumber,
+        revoked_certificates: &[RevokedCertificateEntry],
+    ) -> Self {
+        let aki = if let Ok(Some(kid)) = issuer.get_subject_key_identifier_kid() {
+            AuthorityKeyIdentifier::from_issuers_subject_key_identifier(&kid)
+        } else {
+            let spki = issuer.get_encoded_subject_public_key_info();
+            AuthorityKeyIdentifier::from_public_key(spki.to_public_key().as_ref())
+        };
+        let crl_extensions = vec![
+            Self::extension(Self::OID_CRL_NUMBER, &crl_number.to_rasn_type()),
+            Self::extension(AuthorityKeyIdentifier::OID, aki.to_rasn_type()),
+        ];
+        let revoked_certificates = revoked_certificates
+            .iter()
+            .map(|entry| RevokedCertificate {
+                user_certificate: entry.get_serial_number().to_rasn_type(),
+                revocation_date: Validity::to_rasn_epoch_seconds(
+                    entry.get_revocation_epoch_seconds(),
+                ),
+                crl_entry_extensions: entry.get_reason().map(|reason| {
+                    Extensions::from(vec![Self::extension(Self::OID_REASON_CODE, &reason)])
+                }),
+            })
+            .collect::<Vec<_>>();
+        let tbs_cert_list = TbsCertList {
+            // v2
+            version: Some(Integer::from(1)),
+            signature: AlgorithmIdentifier {
+                algorithm: ObjectIdentifier::new_unchecked(
+                    crate::cert::build::NoSignatureTbsCertificate::OID_NO_SIGNATURE.into(),
+                ),
+                parameters: Some(Any::new(rasn::der::encode(&()).unwrap())),
+            },
+            issuer: rasn::der::decode(&issuer.get_encoded_subject()).unwrap(),
+            this_update: Validity::to_rasn_epoch_seconds(this_update_epoch_seconds),
+            next_update: next_update_epoch_seconds.map(Validity::to_rasn_epoch_seconds),
+            revoked_certificates: if revoked_certificates.is_empty() {
+                None
+            } else {
+                Some(revoked_certificates)
+            },
+            crl_extensions: Some(Extensions::from(crl_extensions)),
+        };
+        Self {
+            tbs_cert_list: rasn::der::encode(&tbs_cert_list).unwrap(),
+        }
+    }
+
+    /// Add an additional `crlExtension` with the DER encoded `value`, e.g. an
+    /// [IssuingDistributionPoint].
+    pub fn with_crl_extension(mut self, oid: &[u32], critical: bool, value: &[u8]) -> Self {
+        let mut tbs_cert_list = rasn::der::decode::<TbsCertList>(&self.tbs_cert_list).unwrap();
+        let mut crl_extensions = tbs_cert_list
+            .crl_extensions
+            .map(|extensions| extensions.to_vec())
+            .unwrap_or_default();
+        crl_extensions.push(Extension {
+            extn_id: ObjectIdentifier::new_unchecked(oid.to_vec().into()),
+            critical,
+            extn_value: OctetString::from(value.to_vec()),
+        });
+        tbs_cert_list.crl_extensions = Some(Extensions::from(crl_extensions));
+        self.tbs_cert_list = rasn::der::encode(&tbs_cert_list).unwrap();
+        self
+    }
+
+    fn extension<T: rasn::Encode>(oid: &'static [u32], rasn_type: &T) -> Extension {
+        Extension {
+            extn_id: ObjectIdentifier::new_unchecked(oid.into()),
+            critical: false,
+            extn_value: OctetString::from(rasn::der::encode(rasn_type).unwrap()),
+        }
+    }
+
+    /// Get the TBSCertList with an updated `TBSCertList.signature` as bytes.
+    ///
+    /// This is the data that the issuer should sign.
+    pub fn with_signature_algorithm_as_bytes(&self, signature_algorithm: &[u8]) -> Vec<u8> {
+        let mut tbs_cert_list = rasn::der::decode::<TbsCertList>(&self.tbs_cert_list).unwrap();
+        tbs_cert_list.signature = rasn::der::decode(signature_algorithm).unwrap();
+        rasn::der::encode(&tbs_cert_list).unwrap()
+    }
+
+    /// Transform the TBSCertList into a DER encoded signed CertificateList.
+    pub fn to_certificate_list(&self, signature_algorithm: &[u8], signature: Vec<u8>) -> Vec<u8> {
+        let mut tbs_cert_list = rasn::der::decode::<TbsCertList>(&self.tbs_cert_list).unwrap();
+        tbs_cert_list.signature = rasn::der::decode(signature_algorithm).unwrap();
+        let signature_algorithm = tbs_cert_list.signature.clone();
+        let certificate_list = CertificateList {
+            tbs_cert_list,
+            signature_algorithm,
+            signature_value: BitString::from_vec(signature),
+        };
+        rasn::der::encode(&certificate_list).unwrap()
+    }
+}
+
+/** Certificate Revocation List parser.
+
+The original encoding is kept, so the signature is verified over the
+`TBSCertList` exactly as received.
+*/
+#[derive(Clone)]
+pub struct CrlParser {
+    certificate_list: CertificateList,
+    fingerprint: String,
+    encoded: Arc<[u8]>,
+    tbs_cert_list: Range<usize>,
+}
+
+impl CrlParser {
+    const TAG_SEQUENCE: u8 = 0x30;
+
+    /// Create a new instance from `encoded_crl` bytes.
+    pub fn from_bytes(encoded_crl: &[u8]) -> Result<Self, DecodingError> {
+        let fingerprint = crate::fingerprint_data(encoded_crl);
+        rasn::der::decode::<CertificateList>(encoded_crl)
+            .map_err(|e| {
+                let msg = format!("Error while decoding CRL with fingerprint '{fingerprint}'. kind: '{:?}', codec: '{:?}'", e.kind, e.codec);
+                DecodingError::with_msg(&msg)
+            })
+            .and_then(|certificate_list| {
+                let tbs_cert_list = &certificate_list.tbs_cert_list;
+                std::iter::once(&tbs_cert_list.this_update)
+                    .chain(tbs_cert_list.next_update.iter())
+                    .chain(
+                        tbs_cert_list
+                            .revoked_certificates
+                            .iter()
+                            .flatten()
+                            .map(|revoked_certificate| &revoked_certificate.revocation_date),
+                    )
+                    .try_for_each(|time| Validity::try_from_rasn_epoch_seconds(time).map(|_| ()))?;
+                let tbs_cert_list = Tlv::read(encoded_crl, 0)?
+                    .children(encoded_crl)?
+                    .into_iter()
+                    .next()
+                    .filter(|tlv| tlv.tag == Self::TAG_SEQUENCE)
+                    .ok_or_else(|| DecodingError::with_msg("Missing TBSCertList."))?
+                    .range;
+                Ok(Self {
+                    certificate_list,
+                    fingerprint,
+                    encoded: Arc::from(encoded_crl),
+                    tbs_cert_list,
+                })
+            })
+    }
+
+    /// Return [fingerprint](crate::fingerprint_data) of the encoded CRL.
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// Return the Issuer as DER encoded bytes.
+    pub fn get_encoded_issuer(&self) -> Vec<u8> {
+        rasn::der::encode(&self.certificate_list.tbs_cert_list.issuer).unwrap()
+    }
+
+    /// Return the [RFC4514](https://www.rfc-editor.org/rfc/rfc4514) string
+    /// representation of the Issuer.
+    pub fn get_issuer_rfc4514_string(&self) -> String {
+        crate::cert::types::DistinguishedName::rfc4514_from_rasn_type(
+            &self.certificate_list.tbs_cert_list.issuer,
+        )
+    }
+
+    /// Return `thisUpdate` in Unix Epoch seconds.
+    pub fn get_this_update(&self) -> u64 {
+        Validity::from_rasn_epoch_seconds(&self.certificate_list.tbs_cert_list.this_update)
+    }
+
+    /// Return `nextUpdate` in Unix Epoch seconds if present.
+    pub fn get_next_update(&self) -> Option<u64> {
+        self.certificate_list
+            .tbs_cert_list
+            .next_update
+            .as_ref()
+            .map(Validity::from_rasn_epoch_seconds)
+    }
+
+    /// Return the `cRLNumber` extension value if present.
+    ///
+    /// An error is returned if the extension is present, but the value is not
+    /// a non-negative INTEGER of at most [CrlNumber::MAX_OCTETS] octets.
+    pub fn get_crl_number(&self) -> Result<Option<CrlNumber>, DecodingError> {
+        Self::extension_by_oid(
+            &self.certificate_list.tbs_cert_list.crl_extensions,
+            NoSignatureTbsCertList::OID_CRL_NUMBER,
+        )
+        .map(|extension| {
+            rasn::der::decode::<Integer>(&extension.extn_value)
+                .map_err(|e| DecodingError::with_msg(&format!("Failed to decode cRLNumber: {e}")))
+                .and_then(|crl_number| CrlNumber::from_rasn_type(&crl_number))
+        })
+        .transpose()
+    }
+
+    /// Return `true` if this is a delta CRL (the `deltaCRLIndicator`
+    /// extension is present).
+    ///
+    /// A delta CRL only lists changes since a base CRL and is never a
+    /// complete source of revocation status on its own.
+    pub fn is_delta_crl(&self) -> bool {
+        Self::extension_by_oid(
+            &self.certificate_list.tbs_cert_list.crl_extensions,
+            NoSignatureTbsCertList::OID_DELTA_CRL_INDICATOR,
+        )
+        .is_some()
+    }
+
+    /// Return the [IssuingDistributionPoint] extension value if present.
+    pub fn get_issuing_distribution_point(
+        &self,
+    ) -> Result<Option<IssuingDistributionPoint>, DecodingError> {
+        Self::extension_by_oid(
+            &self.certificate_list.tbs_cert_list.crl_extensions,
+            IssuingDistributionPoint::OID,
+        )
+        .map(|extension| IssuingDistributionPoint::from_bytes(&extension.extn_value))
+        .transpose()
+    }
+
+    /// Return the OIDs of critical CRL and CRL entry extensions that are not
+    /// processed by this parser.
+    ///
+    /// [RFC5280 5.2](https://www.rfc-editor.org/rfc/rfc5280#section-5.2)
+    /// forbids using a CRL with unrecognized critical extensions to determine
+    /// revocation status.
+    pub fn get_unsupported_critical_extension_oids(&self) -> Vec<Vec<u32>> {
+        let tbs_cert_list = &self.certificate_list.tbs_cert_list;
+        let unsupported = |extensions: &Option<Extensions>, supported: &[&[u32]]| {
+            extensions
+                .iter()
+                .flat_map(|extensions| extensions.iter())
+                .filter(|extension| extension.critical)
+                .map(|extension| extension.extn_id.to_vec())
+                .filter(|oid| !supported.contains(&oid.as_slice()))
+                .collect::<Vec<_>>()
+        };
+        let mut oids = unsupported(
+            &tbs_cert_list.crl_extensions,
+            NoSignatureTbsCertList::SUPPORTED_CRL_EXTENSIONS,
+        );
+        for revoked_certificate in tbs_cert_list.revoked_certificates.iter().flatten() {
+            oids.extend(unsupported(
+                &revoked_certificate.crl_entry_extensions,
+                NoSignatureTbsCertList::SUPPORTED_CRL_ENTRY_EXTENSIONS,
+            ));
+        }
+        oids.sort();
+        oids.dedup();
+        oids
+    }
+
+    /// Return all revoked certificates.
+    pub fn get_revoked_certificates(&self) -> Vec<RevokedCertificateEntry> {
+        self.certificate_list
+            .tbs_cert_list
+            .revoked_certificates
+            .as_ref()
+            .map(|revoked_certificates| {
+                revoked_certificates
+                    .iter()
+                    .map(|revoked_certificate| {
+                        RevokedCertificateEntry::new(
+                            SerialNumber::from_rasn_type(&revoked_certificate.user_certificate),
+                            Validity::from_rasn_epoch_seconds(&revoked_certificate.revocation_date),
+                            Self::extension_by_oid(
+                                &revoked_certificate.crl_entry_extensions,
+                                NoSignatureTbsCertList::OID_REASON_CODE,
+                            )
+                            .and_then(|extension| {
+                                rasn::der::decode::<RevocationReason>(&extension.extn_value).ok()
+                            }),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Return the matching [RevokedCertificateEntry] if the certificate with
+    /// the serial number is revoked.
+    pub fn get_revoked_certificate(
+        &self,
+        serial_number: &SerialNumber,
+    ) -> Option<RevokedCertificateEntry> {
+        self.get_revoked_certificates()
+            .into_iter()
+            .find(|entry| entry.get_serial_number().eq(serial_number))
+    }
+
+    /// Return the matching [RevokedCertificateEntry] if the certificate
+    /// identified by `issuer_and_serial_number` was issued by the CRL issuer
+    /// and is revoked.
+    pub fn get_revoked_certificate_by_issuer_and_serial_number(
+        &self,
+        issuer_and_serial_number: &IssuerAndSerialNumber,
+    ) -> Option<RevokedCertificateEntry> {
+        if !issuer_and_serial_number
+            .get_encoded_issuer()
+            .eq(self.get_encoded_issuer().as_slice())
+        {
+            return None;
+        }
+        self.get_revoked_certificate(issuer_and_serial_number.get_serial_number())
+    }
+
+    /// Return `true` if the CRL was signed by the `issuer` certificate's key.
+    pub fn verify_signature(&self, issuer: &CertificateParser) -> bool {
+        if !issuer.get_encoded_subject().eq(&self.get_encoded_issuer()) {
+            return false;
+        }
+        let oid =
+            crate::encdec::oid::as_string(&self.certificate_list.signature_algorithm.algorithm);
+        let public_key = issuer.get_encoded_subject_public_key_info().to_public_key();
+        let data = &self.encoded[self.tbs_cert_list.clone()];
+        Tyst::instance().ses().by_oid(&oid).is_some_and(|mut se| {
+            se.verify(
+                public_key.as_ref(),
+                self.certificate_list.signature_value.as_raw_slice(),
+                data,
+            )
+        })
+    }
+
+    fn extension_by_oid(extensions: &Option<Extensions>, oid: &[u32]) -> Option<Extension> {
+        let oid = ObjectIdentifier::new_unchecked(oid.to_vec().into());
+        extensions.as_ref().and_then(|extensions| {
+            extensions
+                .iter()
+                .find(|extension| oid.eq(&extension.extn_id))
+                .cloned()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::types::WellKnownAlgorithm;
+    use crate::test_utils::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn crl_round_trip() {
+        let issuer = load_test_certificate("ec_ca.b64");
+        let revoked = vec![
+            RevokedCertificateEntry::new(
+                SerialNumber::from_decimal_str("4711").unwrap(),
+                1_700_000_000,
+                Some(RevocationReason::KeyCompromise),
+            ),
+            RevokedCertificateEntry::new(SerialNumber::generate(None), 1_700_000_001, None),
+        ];
+        // Larger than u64::MAX
+        let crl_number = CrlNumber::from_str("18446744073709551616").unwrap();
+        let tbs_cert_list = NoSignatureTbsCertList::new(
+            &issuer,
+            1_700_000_100,
+            Some(1_700_086_500),
+            &crl_number,
+            &revoked,
+        );
+        let signature_algorithm = crate::cms::signature_algorithm_identifier(
+            WellKnownAlgorithm::EcdsaWithSha256.as_oid(),
+        );
+        let signature = sign_with_test_key(
+            "ec_ca_key.pem",
+            &WellKnownAlgorithm::EcdsaWithSha256,
+            &tbs_cert_list.with_signature_algorithm_as_bytes(&signature_algorithm),
+        );
+        let encoded = tbs_cert_list.to_certificate_list(&signature_algorithm, signature);
+        let crl = CrlParser::from_bytes(&encoded).unwrap();
+        assert!(crl.verify_signature(&issuer));
+        assert!(!crl.verify_signature(&load_test_certificate("h1_leaf.b64")));
+        assert_eq!(crl.get_encoded_issuer(), issuer.get_encoded_subject());
+        assert_eq!(crl.get_this_update(), 1_700_000_100);
+        assert_eq!(crl.get_next_update(), Some(1_700_086_500));
+        assert_eq!(crl.get_crl_number().unwrap(), Some(crl_number));
+        assert_eq!(crl.get_revoked_certificates(), revoked);
+        assert_eq!(
+            crl.get_revoked_certificate(revoked[0].get_serial_number()),
+            Some(revoked[0].clone())
+        );
+        assert!(crl
+            .get_revoked_certificate(&SerialNumber::from_decimal_str("4712").unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn verify_openssl_crl() {
+        // Created with `openssl ca -gencrl` after revoking `tsa.b64`
+        let crl = CrlParser::from_bytes(&load_test_resource_b64("ec_ca_crl.b64")).unwrap();
+        let issuer = load_test_certificate("ec_ca.b64");
+        assert!(crl.verify_signature(&issuer));
+        assert_eq!(crl.get_crl_number().unwrap(), Some(CrlNumber::from(4096)));
+        let tsa = load_test_certificate("tsa.b64");
+        assert_eq!(
+            crl.get_revoked_certificate(&tsa.get_serial_number())
+                .unwrap()
+                .get_reason(),
+            Some(RevocationReason::KeyCompromise)
+        );
+        // Any change to the signed data is detected
+        let mut tampered = load_test_resource_b64("ec_ca_crl.b64");
+        let serial_number = [0x02, 0x03, 0x5a, 0x5a, 0x01];
+        let offset = tampered
+            .windows(serial_number.len())
+            .position(|window| window == serial_number)
+            .unwrap();
+        tampered[offset + serial_number.len() - 1] = 0x02;
+        assert!(!CrlParser::from_bytes(&tampered)
+            .unwrap()
+            .verify_signature(&issuer));
+    }
+}
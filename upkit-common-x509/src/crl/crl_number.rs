@@ -0,0 +1,147 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! CRL number.
+
+use crate::encdec::DecodingError;
+use num_bigint::BigInt;
+use num_bigint::BigUint;
+use num_bigint::Sign;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/** Monotonically increasing sequence number of a CRL.
+
+[RFC5280 5.2.3](https://www.rfc-editor.org/rfc/rfc5280#section-5.2.3):
+
+"CRL verifiers MUST be able to handle CRLNumber values up to 20 octets.
+Conforming CRL issuers MUST NOT use CRLNumber values longer than 20 octets."
+
+[Display](fmt::Display), [FromStr] and the serialized form use decimal
+notation.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(try_from = "String", into = "String")]
+pub struct CrlNumber {
+    value: BigUint,
+}
+
+impl CrlNumber {
+    /// Maximum number of octets of the DER encoded INTEGER content.
+    pub const MAX_OCTETS: usize = 20;
+
+    /// Return the next CRL number.
+    pub fn next(&self) -> Self {
+        Self {
+            value: &self.value + 1u32,
+        }
+    }
+
+    /// Return value in a form that is easy to process by the ASN.1 library `rasn`.
+    pub fn to_rasn_type(&self) -> rasn::types::Integer {
+        rasn::types::Integer::from(BigInt::from(self.value.clone()))
+    }
+
+    /// Return a new instance from the `rasn` ASN.1 library type.
+    ///
+    /// Negative values and values longer than [Self::MAX_OCTETS] are rejected.
+    pub fn from_rasn_type(crl_number: &rasn::types::Integer) -> Result<Self, DecodingError> {
+        Self::from_bigint(&BigInt::from_signed_bytes_be(
+            &crate::encdec::rasn::integer_as_bytes_be(crl_number),
+        ))
+    }
+
+    fn from_bigint(value: &BigInt) -> Result<Self, DecodingError> {
+        if value.sign() == Sign::Minus {
+            return Err(DecodingError::with_msg(&format!(
+                "CRL number '{value}' is negative."
+            )));
+        }
+        if value.to_signed_bytes_be().len() > Self::MAX_OCTETS {
+            return Err(DecodingError::with_msg(&format!(
+                "CRL number '{value}' is longer than {} octets.",
+                Self::MAX_OCTETS
+            )));
+        }
+        Ok(Self {
+            value: value.magnitude().clone(),
+        })
+    }
+}
+
+impl From<u64> for CrlNumber {
+    fn from(value: u64) -> Self {
+        Self {
+            value: BigUint::from(value),
+        }
+    }
+}
+
+impl fmt::Display for CrlNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl FromStr for CrlNumber {
+    type Err = DecodingError;
+
+    /// Parse a CRL number in decimal notation.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        BigInt::parse_bytes(value.as_bytes(), 10)
+            .filter(|_| !value.starts_with(['+', '-']))
+            .ok_or_else(|| DecodingError::with_msg(&format!("Invalid CRL number '{value}'.")))
+            .and_then(|value| Self::from_bigint(&value))
+    }
+}
+
+impl TryFrom<String> for CrlNumber {
+    type Error = DecodingError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl From<CrlNumber> for String {
+    fn from(value: CrlNumber) -> Self {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn crl_number_bounds() {
+        let max = CrlNumber::from_str(&"9".repeat(47)).unwrap();
+        assert_eq!(CrlNumber::from_rasn_type(&max.to_rasn_type()).unwrap(), max);
+        assert!(CrlNumber::from_str(&"9".repeat(49)).is_err());
+        assert!(CrlNumber::from_rasn_type(&rasn::types::Integer::from(-1)).is_err());
+        let large = CrlNumber::from_str("18446744073709551616").unwrap();
+        assert_eq!(large, CrlNumber::from(u64::MAX).next());
+        assert_eq!(
+            serde_json::to_string(&large).unwrap(),
+            "\"18446744073709551616\""
+        );
+        assert!(CrlNumber::from(1) < CrlNumber::from(2));
+    }
+}
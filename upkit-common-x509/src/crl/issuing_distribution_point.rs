@@ -0,0 +1,165 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Issuing distribution point CRL extension.
+
+use crate::cert::extensions::CrlDistributionPoint;
+use crate::cert::parse::CertificateParser;
+use crate::cert::parse::CertificateParsingError;
+use crate::cert::parse::CertificateParsingErrorKind;
+use crate::encdec::DecodingError;
+use rasn::prelude::*;
+use rasn_pkix::DistributionPoint;
+use rasn_pkix::DistributionPointName;
+use rasn_pkix::GeneralName;
+
+/*
+IssuingDistributionPoint ::= SEQUENCE {
+     distributionPoint          [0] DistributionPointName OPTIONAL,
+     onlyContainsUserCerts      [1] BOOLEAN DEFAULT FALSE,
+     onlyContainsCACerts        [2] BOOLEAN DEFAULT FALSE,
+     onlySomeReasons            [3] ReasonFlags OPTIONAL,
+     indirectCRL                [4] BOOLEAN DEFAULT FALSE,
+     onlyContainsAttributeCerts [5] BOOLEAN DEFAULT FALSE }
+*/
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, Default, PartialEq)]
+struct IssuingDistributionPointSyntax {
+    #[rasn(tag(explicit(context, 0)))]
+    distribution_point: Option<DistributionPointName>,
+    #[rasn(tag(context, 1), default)]
+    only_contains_user_certs: bool,
+    #[rasn(tag(context, 2), default)]
+    only_contains_ca_certs: bool,
+    #[rasn(tag(context, 3))]
+    only_some_reasons: Option<BitString>,
+    #[rasn(tag(context, 4), default)]
+    indirect_crl: bool,
+    #[rasn(tag(context, 5), default)]
+    only_contains_attribute_certs: bool,
+}
+
+/** Scope of a (partitioned) CRL.
+
+See [RFC5280 5.2.5](https://www.rfc-editor.org/rfc/rfc5280#section-5.2.5).
+
+Only CRLs signed by the certificate issuer that cover all revocation reasons
+are supported, so an `indirectCRL`, `onlySomeReasons` or
+`onlyContainsAttributeCerts` scope never covers a certificate.
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IssuingDistributionPoint {
+    idp: IssuingDistributionPointSyntax,
+}
+
+impl IssuingDistributionPoint {
+    // joint-iso-ccitt(2) ds(5) ce(29) issuingDistributionPoint(28)
+    /// Issuing Distribution Point object identifier
+    pub const OID: &[u32] = &[2, 5, 29, 28];
+
+    /// Return a new instance for a CRL published at the `crl_uri`.
+    ///
+    /// The URI should match the [CrlDistributionPoint] of the certificates
+    /// in scope.
+    pub fn new(crl_uri: Option<&str>) -> Self {
+        Self {
+            idp: IssuingDistributionPointSyntax {
+                distribution_point: crl_uri.map(|crl_uri| {
+                    DistributionPointName::FullName(vec![GeneralName::Uri(
+                        Ia5String::try_from(crl_uri).unwrap(),
+                    )])
+                }),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Limit the scope to end entity certificates.
+    pub fn only_contains_user_certs(mut self) -> Self {
+        self.idp.only_contains_user_certs = true;
+        self
+    }
+
+    /// Limit the scope to CA certificates.
+    pub fn only_contains_ca_certs(mut self) -> Self {
+        self.idp.only_contains_ca_certs = true;
+        self
+    }
+
+    /// Return the DER encoded extension value.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        rasn::der::encode(&self.idp).unwrap()
+    }
+
+    /// Return a new instance from the DER encoded extension value.
+    pub fn from_bytes(encoded: &[u8]) -> Result<Self, DecodingError> {
+        rasn::der::decode::<IssuingDistributionPointSyntax>(encoded)
+            .map(|idp| Self { idp })
+            .map_err(|e| {
+                DecodingError::with_msg(&format!("Failed to decode issuingDistributionPoint: {e}"))
+            })
+    }
+
+    /** Return `true` if the `certificate` is within the scope of the CRL.
+
+    See [RFC5280 6.3.3](https://www.rfc-editor.org/rfc/rfc5280#section-6.3.3)
+    (b)(2). When a distribution point name is present, one of its names must
+    match a name of a CA issued [CrlDistributionPoint] of the certificate.
+    */
+    pub fn is_in_scope(
+        &self,
+        certificate: &CertificateParser,
+    ) -> Result<bool, CertificateParsingError> {
+        if self.idp.indirect_crl
+            || self.idp.only_some_reasons.is_some()
+            || self.idp.only_contains_attribute_certs
+        {
+            return Ok(false);
+        }
+        let is_ca = certificate
+            .get_basic_constraints()?
+            .is_some_and(|basic_constraints| basic_constraints.is_ca());
+        if (self.idp.only_contains_user_certs && is_ca)
+            || (self.idp.only_contains_ca_certs && !is_ca)
+        {
+            return Ok(false);
+        }
+        let Some(distribution_point) = &self.idp.distribution_point else {
+            return Ok(true);
+        };
+        let DistributionPointName::FullName(idp_names) = distribution_point else {
+            // nameRelativeToCRLIssuer is not supported
+            return Ok(false);
+        };
+        let Some(value) = certificate.as_encoded_extension_value(CrlDistributionPoint::OID) else {
+            return Ok(false);
+        };
+        let cdps = rasn::der::decode::<SequenceOf<DistributionPoint>>(value).map_err(|e| {
+            CertificateParsingErrorKind::ExtensionDecodingError
+                .error_with_msg(&format!("Failed to decode cRLDistributionPoints: {e}"))
+        })?;
+        Ok(cdps
+            .iter()
+            .filter(|dp| dp.crl_issuer.is_none())
+            .filter_map(|dp| match &dp.distribution_point {
+                Some(DistributionPointName::FullName(names)) => Some(names),
+                _ => None,
+            })
+            .flatten()
+            .any(|name| idp_names.contains(name)))
+    }
+}
@@ -0,0 +1,113 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Revoked certificate entry of a CRL.
+
+use crate::cert::types::SerialNumber;
+use rasn::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+use serde_with::skip_serializing_none;
+
+/** Revocation reason code.
+
+See [RFC5280 5.3.1](https://www.rfc-editor.org/rfc/rfc5280#section-5.3.1).
+
+```text
+CRLReason ::= ENUMERATED {
+     unspecified             (0),
+     keyCompromise           (1),
+     cACompromise            (2),
+     affiliationChanged      (3),
+     superseded              (4),
+     cessationOfOperation    (5),
+     certificateHold         (6),
+          -- value 7 is not used
+     removeFromCRL           (8),
+     privilegeWithdrawn      (9),
+     aACompromise           (10) }
+```
+*/
+#[derive(
+    AsnType, Decode, Encode, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash,
+)]
+#[rasn(enumerated)]
+#[serde(rename_all = "snake_case")]
+pub enum RevocationReason {
+    /// `unspecified`
+    Unspecified = 0,
+    /// `keyCompromise`
+    KeyCompromise = 1,
+    /// `cACompromise`
+    CaCompromise = 2,
+    /// `affiliationChanged`
+    AffiliationChanged = 3,
+    /// `superseded`
+    Superseded = 4,
+    /// `cessationOfOperation`
+    CessationOfOperation = 5,
+    /// `certificateHold`
+    CertificateHold = 6,
+    /// `removeFromCRL`
+    RemoveFromCrl = 8,
+    /// `privilegeWithdrawn`
+    PrivilegeWithdrawn = 9,
+    /// `aACompromise`
+    AaCompromise = 10,
+}
+
+/// A revoked certificate as listed in a CRL.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct RevokedCertificateEntry {
+    serial_number: SerialNumber,
+    revocation_epoch_seconds: u64,
+    reason: Option<RevocationReason>,
+}
+
+impl RevokedCertificateEntry {
+    /// Return a new instance.
+    pub fn new(
+        serial_number: SerialNumber,
+        revocation_epoch_seconds: u64,
+        reason: Option<RevocationReason>,
+    ) -> Self {
+        Self {
+            serial_number,
+            revocation_epoch_seconds,
+            reason,
+        }
+    }
+
+    /// Return the serial number of the revoked certificate.
+    pub fn get_serial_number(&self) -> &SerialNumber {
+        &self.serial_number
+    }
+
+    /// Return the time of revocation in Unix Epoch seconds.
+    pub fn get_revocation_epoch_seconds(&self) -> u64 {
+        self.revocation_epoch_seconds
+    }
+
+    /// Return the reason of revocation if present.
+    pub fn get_reason(&self) -> Option<RevocationReason> {
+        self.reason
+    }
+}
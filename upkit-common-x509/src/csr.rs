@@ -0,0 +1,299 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! [RFC2986](https://www.rfc-editor.org/rfc/rfc2986) PKCS #10 Certification Request.
+
+use crate::cert::extensions::AlternativeName;
+use crate::cert::extensions::Extensions;
+use crate::cert::types::DistinguishedName;
+use crate::cert::types::WellKnownGeneralName;
+use crate::encdec::rasn::Attribute;
+use crate::encdec::DecodingError;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+use rasn_pkix::Name;
+use rasn_pkix::SubjectPublicKeyInfo;
+use tyst::traits::se::PublicKey;
+use tyst::traits::se::ToPublicKey;
+use tyst::Tyst;
+
+/*
+CertificationRequest ::= SEQUENCE {
+     certificationRequestInfo CertificationRequestInfo,
+     signatureAlgorithm AlgorithmIdentifier{{ SignatureAlgorithms }},
+     signature          BIT STRING
+}
+
+CertificationRequestInfo ::= SEQUENCE {
+     version       INTEGER { v1(0) } (v1,...),
+     subject       Name,
+     subjectPKInfo SubjectPublicKeyInfo{{ PKInfoAlgorithms }},
+     attributes    [0] Attributes{{ CRIAttributes }}
+}
+
+Attribute { ATTRIBUTE:IOSet } ::= SEQUENCE {
+     type   ATTRIBUTE.&id({IOSet}),
+     values SET SIZE(1..MAX) OF ATTRIBUTE.&Type({IOSet}{@type})
+}
+*/
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct CertificationRequest {
+    certification_request_info: CertificationRequestInfo,
+    signature_algorithm: AlgorithmIdentifier,
+    signature: BitString,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct CertificationRequestInfo {
+    version: Integer,
+    subject: Name,
+    subject_pk_info: SubjectPublicKeyInfo,
+    #[rasn(tag(context, 0))]
+    attributes: SetOf<Attribute>,
+}
+
+/** Unsigned CertificationRequestInfo in preparation for signing.
+
+This allows assembly and encoding of all relevent pieces of information that
+goes into a PKCS #10 certification request except for the actual signing
+(proof of possession) by the requester's private key.
+
+Requested extensions are encoded as a [RFC2985 5.4.2](https://www.rfc-editor.org/rfc/rfc2985#section-5.4.2)
+`extensionRequest` attribute.
+*/
+pub struct NoSignatureCertificationRequestInfo {
+    certification_request_info: Vec<u8>,
+}
+
+impl NoSignatureCertificationRequestInfo {
+    /// `pkcs-9-at-extensionRequest` attribute.
+    pub const OID_EXTENSION_REQUEST: &'static [u32] = &[1, 2, 840, 113549, 1, 9, 14];
+
+    /// Return a new instance.
+    pub fn new(
+        subject: DistinguishedName,
+        subject_public_key: &dyn PublicKey,
+        requested_extensions: Extensions,
+    ) -> Self {
+        let subject_pk_info =
+            rasn::der::decode(&subject_public_key.try_as_spki().unwrap()).unwrap();
+        let mut attributes = SetOf::new();
+        if let Some(extensions) = requested_extensions.to_rasn_type() {
+            let mut values = SetOf::new();
+            values.insert(Any::new(rasn::der::encode(&extensions).unwrap()));
+            attributes.insert(Attribute {
+                r#type: ObjectIdentifier::new_unchecked(Self::OID_EXTENSION_REQUEST.into()),
+                values,
+            });
+        }
+        let certification_request_info = CertificationRequestInfo {
+            version: Integer::from(0),
+            subject: subject.as_rasn_type().unwrap(),
+            subject_pk_info,
+            attributes,
+        };
+        Self {
+            certification_request_info: rasn::der::encode(&certification_request_info).unwrap(),
+        }
+    }
+
+    /// Get the DER encoded CertificationRequestInfo.
+    ///
+    /// This is the data that the requester should sign.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.certification_request_info
+    }
+
+    /// Transform the CertificationRequestInfo into a DER encoded signed
+    /// CertificationRequest.
+    pub fn to_certification_request(
+        &self,
+        signature_algorithm: &[u8],
+        signature: Vec<u8>,
+    ) -> Vec<u8> {
+        let certification_request = CertificationRequest {
+            certification_request_info: rasn::der::decode(&self.certification_request_info)
+                .unwrap(),
+            signature_algorithm: rasn::der::decode(signature_algorithm).unwrap(),
+            signature: BitString::from_vec(signature),
+        };
+        rasn::der::encode(&certification_request).unwrap()
+    }
+}
+
+/// PKCS #10 Certification Request parser.
+#[derive(Clone)]
+pub struct CertificationRequestParser {
+    certification_request: CertificationRequest,
+}
+
+impl CertificationRequestParser {
+    /// Create a new instance from `encoded_certification_request` bytes.
+    pub fn from_bytes(encoded_certification_request: &[u8]) -> Result<Self, DecodingError> {
+        rasn::der::decode::<CertificationRequest>(encoded_certification_request)
+            .map_err(|e| {
+                let msg = format!(
+                    "Error while decoding certification request. kind: '{:?}', codec: '{:?}'",
+                    e.kind, e.codec
+                );
+                DecodingError::with_msg(&msg)
+            })
+            .map(|certification_request| Self {
+                certification_request,
+            })
+    }
+
+    /// Return the requested Subject (if only well-known attributes are used).
+    pub fn get_subject(&self) -> Result<DistinguishedName, DecodingError> {
+        DistinguishedName::from_rasn_type(
+            &self
+                .certification_request
+                .certification_request_info
+                .subject,
+        )
+        .map_err(|e| DecodingError::with_msg(&e.to_string()))
+    }
+
+    /// Return the requested Subject as DER encoded bytes.
+    pub fn get_encoded_subject(&self) -> Vec<u8> {
+        rasn::der::encode(
+            &self
+                .certification_request
+                .certification_request_info
+                .subject,
+        )
+        .unwrap()
+    }
+
+    /// Return the Subject Public Key Info as DER encoded bytes.
+    pub fn get_encoded_subject_public_key_info(&self) -> Vec<u8> {
+        rasn::der::encode(
+            &self
+                .certification_request
+                .certification_request_info
+                .subject_pk_info,
+        )
+        .unwrap()
+    }
+
+    /// Return all extensions from the `extensionRequest` attribute.
+    pub fn get_requested_extensions(&self) -> Vec<rasn_pkix::Extension> {
+        self.certification_request
+            .certification_request_info
+            .attributes
+            .to_vec()
+            .into_iter()
+            .filter(|attribute| {
+                ObjectIdentifier::new_unchecked(
+                    NoSignatureCertificationRequestInfo::OID_EXTENSION_REQUEST.into(),
+                )
+                .eq(&attribute.r#type)
+            })
+            .flat_map(|attribute| attribute.values.to_vec())
+            .filter_map(|value| rasn::der::decode::<rasn_pkix::Extensions>(value.as_bytes()).ok())
+            .flat_map(|extensions| extensions.to_vec())
+            .collect()
+    }
+
+    /// Return all requested Subject Alternative Names.
+    pub fn get_requested_subject_alternative_name(&self) -> Vec<(WellKnownGeneralName, String)> {
+        self.get_requested_extensions()
+            .iter()
+            .filter(|extension| {
+                ObjectIdentifier::new_unchecked(AlternativeName::OID_SUBJECT_AN.into())
+                    .eq(&extension.extn_id)
+            })
+            .filter_map(|extension| {
+                rasn::der::decode::<SequenceOf<rasn_pkix::GeneralName>>(&extension.extn_value).ok()
+            })
+            .flat_map(|general_names| AlternativeName::from_rasn_type(&general_names))
+            .collect()
+    }
+
+    /// Return `true` if the request is signed by the private key that
+    /// corresponds to the requested public key (proof of possession).
+    pub fn verify_signature(&self) -> bool {
+        let oid = crate::encdec::oid::as_string(
+            &self.certification_request.signature_algorithm.algorithm,
+        );
+        let public_key = self.get_encoded_subject_public_key_info().to_public_key();
+        let data =
+            rasn::der::encode(&self.certification_request.certification_request_info).unwrap();
+        Tyst::instance().ses().by_oid(&oid).is_some_and(|mut se| {
+            se.verify(
+                public_key.as_ref(),
+                self.certification_request.signature.as_raw_slice(),
+                &data,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::types::WellKnownAlgorithm;
+    use crate::test_utils::*;
+
+    #[test]
+    fn certification_request_round_trip() {
+        let cp = load_test_certificate("ec_ca.b64");
+        let subject = cp.get_subject().unwrap();
+        let sans = vec![(WellKnownGeneralName::DnsName, "example.com".to_string())];
+        let mut extensions = Extensions::default();
+        extensions.add_subject_alternative_name(&sans, false);
+        let public_key = cp.get_encoded_subject_public_key_info().to_public_key();
+        let request_info = NoSignatureCertificationRequestInfo::new(
+            subject.clone(),
+            public_key.as_ref(),
+            extensions,
+        );
+        let signature = sign_with_test_key(
+            "ec_ca_key.pem",
+            &WellKnownAlgorithm::EcdsaWithSha256,
+            request_info.as_bytes(),
+        );
+        let signature_algorithm = crate::cms::signature_algorithm_identifier(
+            WellKnownAlgorithm::EcdsaWithSha256.as_oid(),
+        );
+        let encoded =
+            request_info.to_certification_request(&signature_algorithm, signature.clone());
+        let csr = CertificationRequestParser::from_bytes(&encoded).unwrap();
+        assert!(csr.verify_signature());
+        assert_eq!(csr.get_encoded_subject(), cp.get_encoded_subject());
+        assert_eq!(
+            csr.get_subject().unwrap().to_rfc4514_string(),
+            subject.to_rfc4514_string()
+        );
+        assert_eq!(
+            csr.get_encoded_subject_public_key_info(),
+            cp.get_encoded_subject_public_key_info()
+        );
+        assert_eq!(csr.get_requested_extensions().len(), 1);
+        assert_eq!(csr.get_requested_subject_alternative_name(), sans);
+        // Proof of possession must fail for a signature over other data
+        let mut tampered = signature;
+        let last = tampered.len() - 1;
+        tampered[last] ^= 0x01;
+        let encoded = request_info.to_certification_request(&signature_algorithm, tampered);
+        assert!(!CertificationRequestParser::from_bytes(&encoded)
+            .unwrap()
+            .verify_signature());
+        assert!(CertificationRequestParser::from_bytes(&encoded[1..]).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! [RFC6962](https://www.rfc-editor.org/rfc/rfc6962) Certificate
+//! Transparency (CT).
+//!
+//! Verification of Signed Certificate Timestamps (SCTs) embedded in
+//! certificates against a caller-provided [CtLogList].
+
+mod ct_error;
+mod ct_log_list;
+mod precertificate;
+
+pub use self::ct_error::CtError;
+pub use self::ct_error::CtErrorKind;
+pub use self::ct_log_list::CtLog;
+pub use self::ct_log_list::CtLogList;
+pub use self::ct_log_list::CtLogState;
+pub use self::precertificate::check_precertificate_consistency;
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    //! Testing utilities for Certificate Transparency.
+
+    /// Return a log list with the test Certificate Transparency log
+    /// `ct_log_spki.b64` in the `state` (e.g. `usable`).
+    ///
+    /// The log accepts certificates that expire between 2026-07-01 and
+    /// 2027-07-01.
+    pub fn load_test_ct_log_list(state: &str) -> crate::ct::CtLogList {
+        use sha2::Digest;
+        let key = crate::test_utils::load_test_resource_b64("ct_log_spki.b64");
+        let json = format!(
+            r#"{{"operators":[{{"name":"Example","logs":[{{
+                "description":"Example test log","log_id":"{}","key":"{}",
+                "state":{{"{state}":{{"timestamp":"2026-01-01T00:00:00Z"}}}},
+                "temporal_interval":{{"start_inclusive":"2026-07-01T00:00:00Z","end_exclusive":"2027-07-01T00:00:00Z"}}
+            }}]}}]}}"#,
+            tyst::encdec::base64::encode(&sha2::Sha256::digest(&key)),
+            tyst::encdec::base64::encode(&key),
+        );
+        crate::ct::CtLogList::from_json(&json).unwrap()
+    }
+}
@@ -0,0 +1,94 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Certificate Transparency errors.
+
+use std::error::Error;
+use std::fmt;
+
+/// Cause of Certificate Transparency error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CtErrorKind {
+    /// Failure to decode the log list, SCT or certificate.
+    DecodingError,
+    /// The SCT was issued by a log that is not part of the log list.
+    UnknownLog,
+    /// The log state or temporal interval does not allow the SCT.
+    LogNotAcceptable,
+    /// The SCT uses an algorithm that is not supported.
+    UnsupportedAlgorithm,
+    /// The signature of the SCT could not be verified.
+    InvalidSignature,
+    /// The SCT timestamp is later than the validation time.
+    InvalidTimestamp,
+    /// The Precertificate Signing Certificate is not suitable.
+    InvalidPrecertificateSigningCertificate,
+    /// The final certificate does not match the precertificate.
+    PrecertificateMismatch,
+}
+
+impl CtErrorKind {
+    /// Create a new instance with an error message.
+    pub fn error_with_msg(self, msg: &str) -> CtError {
+        CtError {
+            kind: self,
+            msg: Some(msg.to_string()),
+        }
+    }
+
+    /// Create a new instance without an error message.
+    pub fn error(self) -> CtError {
+        CtError {
+            kind: self,
+            msg: None,
+        }
+    }
+}
+
+impl fmt::Display for CtErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/** Certificate Transparency error.
+
+Create a new instance via [CtErrorKind].
+*/
+#[derive(Debug)]
+pub struct CtError {
+    kind: CtErrorKind,
+    msg: Option<String>,
+}
+
+impl CtError {
+    /// Return the [CtErrorKind] type of this error.
+    pub fn kind(&self) -> &CtErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for CtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(msg) = &self.msg {
+            write!(f, "{} {}", self.kind, msg)
+        } else {
+            write!(f, "{}", self.kind)
+        }
+    }
+}
+
+impl Error for CtError {}
@@ -0,0 +1,497 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Certificate Transparency log list.
+
+use super::CtError;
+use super::CtErrorKind;
+use crate::cert::extensions::SignedCertificateTimestamp;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::Validity;
+use serde::Deserialize;
+use sha2::Digest;
+use std::collections::HashMap;
+use tyst::traits::se::ToPublicKey;
+use tyst::Tyst;
+
+/* Subset of https://www.gstatic.com/ct/log_list/v3/log_list_schema.json
+
+{
+  "operators": [
+    {
+      "name": "...",
+      "logs": [
+        {
+          "description": "...",
+          "log_id": "<base64 SHA-256 of key>",
+          "key": "<base64 DER SubjectPublicKeyInfo>",
+          "state": { "usable": { "timestamp": "2024-01-01T00:00:00Z" } },
+          "temporal_interval": {
+            "start_inclusive": "2025-01-01T00:00:00Z",
+            "end_exclusive": "2025-07-01T00:00:00Z"
+          }
+        }
+      ],
+      "tiled_logs": [ ... ]
+    }
+  ]
+}
+*/
+
+#[derive(Deserialize)]
+struct JsonLogList {
+    operators: Vec<JsonOperator>,
+}
+
+#[derive(Deserialize)]
+struct JsonOperator {
+    name: String,
+    #[serde(default)]
+    logs: Vec<JsonLog>,
+    #[serde(default)]
+    tiled_logs: Vec<JsonLog>,
+}
+
+#[derive(Deserialize)]
+struct JsonLog {
+    #[serde(default)]
+    description: String,
+    log_id: String,
+    key: String,
+    state: Option<HashMap<String, JsonState>>,
+    temporal_interval: Option<JsonTemporalInterval>,
+}
+
+#[derive(Deserialize)]
+struct JsonState {
+    timestamp: String,
+}
+
+#[derive(Deserialize)]
+struct JsonTemporalInterval {
+    start_inclusive: String,
+    end_exclusive: String,
+}
+
+/// State of a Certificate Transparency log with the epoch seconds it entered
+/// the state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CtLogState {
+    /// The log is not yet trusted.
+    Pending(u64),
+    /// The log is trusted and being monitored for compliance.
+    Qualified(u64),
+    /// The log is trusted.
+    Usable(u64),
+    /// The log no longer accepts new entries, but SCTs are still trusted.
+    ReadOnly(u64),
+    /// SCTs issued before the retirement are trusted.
+    Retired(u64),
+    /// The log was never trusted.
+    Rejected(u64),
+}
+
+impl CtLogState {
+    fn from_json(state: &HashMap<String, JsonState>) -> Result<Option<Self>, CtError> {
+        let Some((name, state)) = state.iter().next() else {
+            return Ok(None);
+        };
+        let timestamp = parse_rfc3339(&state.timestamp)?;
+        Ok(match name.as_str() {
+            "pending" => Some(Self::Pending(timestamp)),
+            "qualified" => Some(Self::Qualified(timestamp)),
+            "usable" => Some(Self::Usable(timestamp)),
+            "readonly" => Some(Self::ReadOnly(timestamp)),
+            "retired" => Some(Self::Retired(timestamp)),
+            "rejected" => Some(Self::Rejected(timestamp)),
+            _ => None,
+        })
+    }
+
+    /// Return `true` if an SCT issued at `timestamp_millis` by a log in this
+    /// state is acceptable.
+    pub fn is_acceptable_at(&self, timestamp_millis: u64) -> bool {
+        match self {
+            Self::Qualified(_) | Self::Usable(_) | Self::ReadOnly(_) => true,
+            Self::Retired(since) => timestamp_millis / 1000 < *since,
+            Self::Pending(_) | Self::Rejected(_) => false,
+        }
+    }
+}
+
+/// Certificate Transparency log from a [CtLogList].
+#[derive(Debug, Clone)]
+pub struct CtLog {
+    operator: String,
+    description: String,
+    log_id: Vec<u8>,
+    key: Vec<u8>,
+    state: Option<CtLogState>,
+    temporal_interval: Option<(u64, u64)>,
+}
+
+impl CtLog {
+    /// Return the name of the log operator.
+    pub fn get_operator(&self) -> &str {
+        &self.operator
+    }
+
+    /// Return the human readable description of the log.
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    /// Return the SHA-256 hash of the log's public key.
+    pub fn get_log_id(&self) -> &[u8] {
+        &self.log_id
+    }
+
+    /// Return the log's DER encoded SubjectPublicKeyInfo.
+    pub fn get_encoded_key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Return the state of the log if present.
+    pub fn get_state(&self) -> Option<CtLogState> {
+        self.state
+    }
+
+    /// Return the accepted certificate expiry interval as inclusive start and
+    /// exclusive end in epoch seconds if present.
+    pub fn get_temporal_interval(&self) -> Option<(u64, u64)> {
+        self.temporal_interval
+    }
+
+    fn from_json(operator: &str, log: &JsonLog) -> Result<Self, CtError> {
+        let log_id = decode_base64(&log.log_id)?;
+        let key = decode_base64(&log.key)?;
+        if log_id != sha2::Sha256::digest(&key).as_slice() {
+            return Err(CtErrorKind::DecodingError.error_with_msg(&format!(
+                "Log ID of '{}' does not match its key.",
+                log.description
+            )));
+        }
+        let state = match &log.state {
+            Some(state) => CtLogState::from_json(state)?,
+            None => None,
+        };
+        let temporal_interval = match &log.temporal_interval {
+            Some(interval) => Some((
+                parse_rfc3339(&interval.start_inclusive)?,
+                parse_rfc3339(&interval.end_exclusive)?,
+            )),
+            None => None,
+        };
+        Ok(Self {
+            operator: operator.to_string(),
+            description: log.description.to_string(),
+            log_id,
+            key,
+            state,
+            temporal_interval,
+        })
+    }
+}
+
+/** List of trusted Certificate Transparency logs.
+
+Parsed from the JSON format published for browsers, like
+`https://www.gstatic.com/ct/log_list/v3/log_list.json`. Both RFC6962 `logs`
+and static CT API `tiled_logs` are included.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct CtLogList {
+    logs: Vec<CtLog>,
+}
+
+impl CtLogList {
+    /// Parse a JSON log list.
+    pub fn from_json(json: &str) -> Result<Self, CtError> {
+        let log_list = serde_json::from_str::<JsonLogList>(json).map_err(|e| {
+            CtErrorKind::DecodingError.error_with_msg(&format!("Failed to parse log list: {e}"))
+        })?;
+        let mut logs = vec![];
+        for operator in &log_list.operators {
+            for log in operator.logs.iter().chain(operator.tiled_logs.iter()) {
+                logs.push(CtLog::from_json(&operator.name, log)?);
+            }
+        }
+        Ok(Self { logs })
+    }
+
+    /// Return all logs.
+    pub fn get_logs(&self) -> &[CtLog] {
+        &self.logs
+    }
+
+    /// Return the log with the `log_id` if present.
+    pub fn get_log(&self, log_id: &[u8]) -> Option<&CtLog> {
+        self.logs.iter().find(|log| log.log_id == log_id)
+    }
+
+    /** Verify an SCT embedded in `certificate` issued by `issuer`.
+
+    The log must be known, in an acceptable state when the SCT was issued and
+    the certificate expiry must be within the log's temporal interval.
+
+    Returns the [CtLog] that issued the SCT on success.
+    */
+    pub fn verify(
+        &self,
+        sct: &SignedCertificateTimestamp,
+        certificate: &CertificateParser,
+        issuer: &CertificateParser,
+    ) -> Result<&CtLog, CtError> {
+        self.verify_at(sct, certificate, issuer, Validity::now_epoch_seconds())
+    }
+
+    /** Verify an SCT like [Self::verify] at the point in time
+    `validation_time` (epoch seconds).
+
+    An SCT with a timestamp later than the validation time did not exist at
+    that time and is rejected.
+    */
+    pub fn verify_at(
+        &self,
+        sct: &SignedCertificateTimestamp,
+        certificate: &CertificateParser,
+        issuer: &CertificateParser,
+        validation_time: u64,
+    ) -> Result<&CtLog, CtError> {
+        if sct.get_timestamp_millis() / 1000 > validation_time {
+            return Err(CtErrorKind::InvalidTimestamp.error_with_msg(&format!(
+                "SCT timestamp {} ms is later than the validation time {validation_time}.",
+                sct.get_timestamp_millis()
+            )));
+        }
+        let log = self.get_log(sct.get_log_id()).ok_or_else(|| {
+            CtErrorKind::UnknownLog.error_with_msg(&format!(
+                "Log '{}' is not in the log list.",
+                tyst::encdec::base64::encode(sct.get_log_id())
+            ))
+        })?;
+        if !log
+            .state
+            .is_some_and(|state| state.is_acceptable_at(sct.get_timestamp_millis()))
+        {
+            return Err(CtErrorKind::LogNotAcceptable.error_with_msg(&format!(
+                "Log '{}' in state {:?} does not allow the SCT.",
+                log.description, log.state
+            )));
+        }
+        let not_after = certificate
+            .get_validity()
+            .map_err(|e| CtErrorKind::DecodingError.error_with_msg(&e.to_string()))?
+            .get_not_after();
+        if let Some((start_inclusive, end_exclusive)) = log.temporal_interval {
+            if not_after < start_inclusive || not_after >= end_exclusive {
+                return Err(CtErrorKind::LogNotAcceptable.error_with_msg(&format!(
+                    "Certificate expiry {not_after} is outside the temporal interval of log '{}'.",
+                    log.description
+                )));
+            }
+        }
+        let oid = match sct.get_signature_algorithm() {
+            (
+                SignedCertificateTimestamp::HASH_ALGORITHM_SHA256,
+                SignedCertificateTimestamp::SIGNATURE_ALGORITHM_ECDSA,
+            ) => "1.2.840.10045.4.3.2",
+            (
+                SignedCertificateTimestamp::HASH_ALGORITHM_SHA256,
+                SignedCertificateTimestamp::SIGNATURE_ALGORITHM_RSA,
+            ) => "1.2.840.113549.1.1.11",
+            (hash_algorithm, signature_algorithm) => {
+                return Err(CtErrorKind::UnsupportedAlgorithm.error_with_msg(&format!(
+                    "Hash algorithm {hash_algorithm} with signature algorithm {signature_algorithm} is not supported."
+                )));
+            }
+        };
+        let issuer_key_hash =
+            sha2::Sha256::digest(issuer.get_encoded_subject_public_key_info()).to_vec();
+        let tbs_certificate = certificate
+            .get_encoded_precertificate_tbs_certificate()
+            .map_err(|e| {
+                CtErrorKind::DecodingError.error_with_msg(&format!(
+                    "Failed to derive the precertificate TBSCertificate: {e}"
+                ))
+            })?;
+        let signed_data = sct
+            .get_precertificate_signed_data(&issuer_key_hash, &tbs_certificate)
+            .map_err(|e| {
+                CtErrorKind::DecodingError
+                    .error_with_msg(&format!("Failed to encode the signed data: {e}"))
+            })?;
+        let mut se = Tyst::instance().ses().by_oid(oid).ok_or_else(|| {
+            CtErrorKind::UnsupportedAlgorithm
+                .error_with_msg(&format!("Signature algorithm '{oid}' is not available."))
+        })?;
+        let public_key = log.key.to_public_key();
+        if !se.verify(public_key.as_ref(), sct.get_signature(), &signed_data) {
+            return Err(CtErrorKind::InvalidSignature.error_with_msg(&format!(
+                "SCT signature of log '{}' could not be verified.",
+                log.description
+            )));
+        }
+        Ok(log)
+    }
+}
+
+fn decode_base64(value: &str) -> Result<Vec<u8>, CtError> {
+    tyst::encdec::base64::decode(value).map_err(|e| {
+        CtErrorKind::DecodingError.error_with_msg(&format!("Invalid base64 '{value}': {e:?}"))
+    })
+}
+
+fn parse_rfc3339(value: &str) -> Result<u64, CtError> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .and_then(|date_time| u64::try_from(date_time.timestamp()).ok())
+        .ok_or_else(|| {
+            CtErrorKind::DecodingError.error_with_msg(&format!("Invalid timestamp '{value}'."))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ct::test_utils::*;
+    use crate::test_utils::*;
+
+    /// Timestamp of the SCT embedded in `ct_leaf.b64` in epoch seconds.
+    const SCT_TIMESTAMP: u64 = 1_792_337_400;
+
+    #[test]
+    fn parse_log_list() {
+        let key = vec![0x30, 0x03, 0x02, 0x01, 0x01];
+        let log_id = sha2::Sha256::digest(&key).to_vec();
+        let json = format!(
+            r#"{{"version":"1.0","operators":[{{"name":"Example","email":[],"logs":[{{
+                "description":"Example log","log_id":"{}","key":"{}","mmd":86400,
+                "state":{{"retired":{{"timestamp":"2025-01-01T00:00:00Z"}}}},
+                "temporal_interval":{{"start_inclusive":"2025-01-01T00:00:00Z","end_exclusive":"2025-07-01T00:00:00Z"}}
+            }}]}}]}}"#,
+            tyst::encdec::base64::encode(&log_id),
+            tyst::encdec::base64::encode(&key),
+        );
+        let log_list = CtLogList::from_json(&json).unwrap();
+        let log = log_list.get_log(&log_id).unwrap();
+        assert_eq!(log.get_operator(), "Example");
+        assert_eq!(log.get_state(), Some(CtLogState::Retired(1_735_689_600)));
+        assert_eq!(
+            log.get_temporal_interval(),
+            Some((1_735_689_600, 1_751_328_000))
+        );
+        assert!(log.get_state().unwrap().is_acceptable_at(1_735_689_599_000));
+        assert!(!log.get_state().unwrap().is_acceptable_at(1_735_689_600_000));
+    }
+
+    #[test]
+    fn precertificate_tbs_certificate() {
+        let der = load_test_certificate_der("ct_leaf.b64");
+        let certificate = CertificateParser::from_bytes(&der).unwrap();
+        let sct_oid =
+            rasn::types::ObjectIdentifier::new(SignedCertificateTimestamp::OID.to_vec()).unwrap();
+        let tbs_certificate = rasn::der::decode::<rasn_pkix::TbsCertificate>(
+            &certificate
+                .get_encoded_precertificate_tbs_certificate()
+                .unwrap(),
+        )
+        .unwrap();
+        let extensions = tbs_certificate.extensions.unwrap();
+        assert_eq!(extensions.len(), 6);
+        assert!(extensions
+            .iter()
+            .all(|extension| extension.extn_id != sct_oid));
+        // The extensions field is omitted when the SCT list was the only extension
+        let mut decoded = rasn::der::decode::<rasn_pkix::Certificate>(&der).unwrap();
+        if let Some(extensions) = decoded.tbs_certificate.extensions.as_mut() {
+            extensions.retain(|extension| extension.extn_id == sct_oid);
+        }
+        let certificate =
+            CertificateParser::from_bytes(&rasn::der::encode(&decoded).unwrap()).unwrap();
+        let tbs_certificate = rasn::der::decode::<rasn_pkix::TbsCertificate>(
+            &certificate
+                .get_encoded_precertificate_tbs_certificate()
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(tbs_certificate.extensions.is_none());
+    }
+
+    #[test]
+    fn verify_embedded_sct() {
+        init_logger();
+        let certificate = load_test_certificate("ct_leaf.b64");
+        let issuer = load_test_certificate("ec_ca.b64");
+        let scts = certificate.get_signed_certificate_timestamps().unwrap();
+        assert_eq!(scts.len(), 1);
+        let sct = &scts[0];
+        assert_eq!(sct.get_timestamp_millis(), SCT_TIMESTAMP * 1000);
+        let log_list = load_test_ct_log_list("usable");
+        let log = log_list
+            .verify_at(sct, &certificate, &issuer, SCT_TIMESTAMP + 60)
+            .unwrap();
+        assert_eq!(log.get_description(), "Example test log");
+        // The SCT did not exist yet
+        let e = log_list
+            .verify_at(sct, &certificate, &issuer, SCT_TIMESTAMP - 1)
+            .unwrap_err();
+        assert_eq!(e.kind(), &CtErrorKind::InvalidTimestamp);
+        // Unknown log
+        let e = CtLogList::default()
+            .verify_at(sct, &certificate, &issuer, SCT_TIMESTAMP + 60)
+            .unwrap_err();
+        assert_eq!(e.kind(), &CtErrorKind::UnknownLog);
+        // Log in a state that doesn't allow SCTs
+        let e = load_test_ct_log_list("pending")
+            .verify_at(sct, &certificate, &issuer, SCT_TIMESTAMP + 60)
+            .unwrap_err();
+        assert_eq!(e.kind(), &CtErrorKind::LogNotAcceptable);
+        // Other issuer
+        let e = log_list
+            .verify_at(
+                sct,
+                &certificate,
+                &load_test_certificate("ec_sub_ca.b64"),
+                SCT_TIMESTAMP + 60,
+            )
+            .unwrap_err();
+        assert_eq!(e.kind(), &CtErrorKind::InvalidSignature);
+    }
+
+    #[test]
+    fn tampered_sct_fails() {
+        let mut der = load_test_certificate_der("ct_leaf.b64");
+        // Move the embedded SCT timestamp one millisecond forward
+        let timestamp = (SCT_TIMESTAMP * 1000).to_be_bytes();
+        let position = der
+            .windows(timestamp.len())
+            .position(|window| window == timestamp)
+            .unwrap();
+        der[position + timestamp.len() - 1] += 1;
+        let certificate = CertificateParser::from_bytes(&der).unwrap();
+        let scts = certificate.get_signed_certificate_timestamps().unwrap();
+        let e = load_test_ct_log_list("usable")
+            .verify_at(
+                &scts[0],
+                &certificate,
+                &load_test_certificate("ec_ca.b64"),
+                SCT_TIMESTAMP + 60,
+            )
+            .unwrap_err();
+        assert_eq!(e.kind(), &CtErrorKind::InvalidSignature);
+    }
+}
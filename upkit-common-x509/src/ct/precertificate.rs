@@ -0,0 +1,460 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Precertificate consistency checks.
+
+use super::CtError;
+use super::CtErrorKind;
+use crate::cert::extensions::AuthorityKeyIdentifier;
+use crate::cert::extensions::ExtendedKeyUsage;
+use crate::cert::extensions::SignedCertificateTimestamp;
+use crate::cert::parse::CertificateParser;
+use crate::cert::parse::CertificateParsingError;
+use rasn::types::ObjectIdentifier;
+use rasn_pkix::TbsCertificate;
+use tyst::traits::se::ToPublicKey;
+use tyst::Tyst;
+
+/** Check that the final `certificate` matches the `precertificate` signed by
+the `signing_certificate`.
+
+The `signing_certificate` is either the issuer of the final certificate or a
+dedicated Precertificate Signing Certificate with the
+[ExtendedKeyUsage::CtPrecertificateSigning] EKU that was issued by the issuer
+of the final certificate.
+
+The only allowed differences are:
+
+* the precertificate poison extension of the precertificate,
+* the SignedCertificateTimestampList extension of the final certificate and
+* the issuer and AuthorityKeyIdentifier when the precertificate was signed by
+  a dedicated Precertificate Signing Certificate.
+
+See [RFC6962 3.1](https://www.rfc-editor.org/rfc/rfc6962#section-3.1).
+*/
+pub fn check_precertificate_consistency(
+    precertificate: &CertificateParser,
+    signing_certificate: &CertificateParser,
+    certificate: &CertificateParser,
+) -> Result<(), CtError> {
+    if !is_signed_by(precertificate, signing_certificate) {
+        return Err(
+            CtErrorKind::InvalidPrecertificateSigningCertificate.error_with_msg(&format!(
+                "Precertificate '{}' was not signed by '{}'.",
+                precertificate.fingerprint(),
+                signing_certificate.fingerprint(),
+            )),
+        );
+    }
+    let mut precertificate_tbs = decode_tbs_certificate(precertificate)?;
+    let mut certificate_tbs = decode_tbs_certificate(certificate)?;
+    let poison = oid(SignedCertificateTimestamp::OID_PRECERTIFICATE_POISON);
+    let has_poison = |tbs_certificate: &TbsCertificate, critical: bool| {
+        tbs_certificate
+            .extensions
+            .iter()
+            .flatten()
+            .any(|extension| extension.extn_id == poison && (!critical || extension.critical))
+    };
+    if !has_poison(&precertificate_tbs, true) {
+        return Err(CtErrorKind::PrecertificateMismatch
+            .error_with_msg("Precertificate is missing the critical poison extension."));
+    }
+    if has_poison(&certificate_tbs, false) {
+        return Err(CtErrorKind::PrecertificateMismatch
+            .error_with_msg("Final certificate contains the poison extension."));
+    }
+    let removed = [poison, oid(SignedCertificateTimestamp::OID)];
+    for tbs_certificate in [&mut precertificate_tbs, &mut certificate_tbs] {
+        if let Some(extensions) = tbs_certificate.extensions.as_mut() {
+            extensions.retain(|extension| !removed.contains(&extension.extn_id));
+        }
+    }
+    if !is_signed_by(certificate, signing_certificate) {
+        // Signed by a dedicated Precertificate Signing Certificate
+        check_precertificate_signing_certificate(signing_certificate, certificate)?;
+        precertificate_tbs.issuer = certificate_tbs.issuer.clone();
+        let aki = oid(AuthorityKeyIdentifier::OID);
+        let find_aki = |tbs_certificate: &TbsCertificate| {
+            tbs_certificate
+                .extensions
+                .iter()
+                .flatten()
+                .position(|extension| extension.extn_id == aki)
+        };
+        if let Some(position) = find_aki(&precertificate_tbs) {
+            let precertificate_kid = rasn::der::decode::<rasn_pkix::AuthorityKeyIdentifier>(
+                &precertificate_tbs.extensions.as_ref().unwrap()[position].extn_value,
+            )
+            .map_err(|e| {
+                CtErrorKind::DecodingError.error_with_msg(&format!(
+                    "Failed to decode precertificate AuthorityKeyIdentifier: {e:?}"
+                ))
+            })?
+            .key_identifier
+            .map(|kid| kid.to_vec());
+            if precertificate_kid != subject_key_identifier(signing_certificate)? {
+                return Err(CtErrorKind::PrecertificateMismatch.error_with_msg(
+                    "Precertificate AuthorityKeyIdentifier doesn't identify the Precertificate Signing Certificate.",
+                ));
+            }
+            // The log replaces it with the AuthorityKeyIdentifier of the final issuer
+            if let Some(certificate_aki) = find_aki(&certificate_tbs)
+                .map(|position| certificate_tbs.extensions.as_ref().unwrap()[position].clone())
+            {
+                precertificate_tbs.extensions.as_mut().unwrap()[position] = certificate_aki;
+            }
+        }
+    }
+    if precertificate_tbs != certificate_tbs {
+        let mut fields = vec![];
+        if precertificate_tbs.serial_number != certificate_tbs.serial_number {
+            fields.push("serialNumber");
+        }
+        if precertificate_tbs.signature != certificate_tbs.signature {
+            fields.push("signature");
+        }
+        if precertificate_tbs.issuer != certificate_tbs.issuer {
+            fields.push("issuer");
+        }
+        if precertificate_tbs.validity != certificate_tbs.validity {
+            fields.push("validity");
+        }
+        if precertificate_tbs.subject != certificate_tbs.subject {
+            fields.push("subject");
+        }
+        if precertificate_tbs.subject_public_key_info != certificate_tbs.subject_public_key_info {
+            fields.push("subjectPublicKeyInfo");
+        }
+        if precertificate_tbs.extensions != certificate_tbs.extensions {
+            fields.push("extensions");
+        }
+        return Err(CtErrorKind::PrecertificateMismatch.error_with_msg(&format!(
+            "Final certificate '{}' differs from precertificate '{}' in {fields:?}.",
+            certificate.fingerprint(),
+            precertificate.fingerprint(),
+        )));
+    }
+    Ok(())
+}
+
+/** Check that the Precertificate Signing Certificate `signing_certificate` is
+allowed to sign precertificates for the issuer of the final `certificate`.
+
+[RFC6962 3.1](https://www.rfc-editor.org/rfc/rfc6962#section-3.1):
+
+"This certificate MUST be directly certified by the CA certificate that will
+ultimately sign the end-entity TBSCertificate"
+*/
+fn check_precertificate_signing_certificate(
+    signing_certificate: &CertificateParser,
+    certificate: &CertificateParser,
+) -> Result<(), CtError> {
+    if !signing_certificate
+        .get_extended_key_usage()
+        .map_err(decoding_error)?
+        .contains(&ExtendedKeyUsage::CtPrecertificateSigning)
+    {
+        return Err(CtErrorKind::InvalidPrecertificateSigningCertificate.error_with_msg(
+            &format!(
+                "'{}' is neither the issuer of the final certificate nor a Precertificate Signing Certificate.",
+                signing_certificate.fingerprint(),
+            ),
+        ));
+    }
+    let issuer_kid = |certificate: &CertificateParser| {
+        certificate
+            .get_authority_key_identifier_kid()
+            .map_err(decoding_error)
+    };
+    let same_issuer_kid = match (issuer_kid(signing_certificate)?, issuer_kid(certificate)?) {
+        (Some(signing_certificate_kid), Some(certificate_kid)) => {
+            signing_certificate_kid == certificate_kid
+        }
+        _ => true,
+    };
+    if signing_certificate.as_encoded_issuer() != certificate.as_encoded_issuer()
+        || !same_issuer_kid
+    {
+        return Err(CtErrorKind::InvalidPrecertificateSigningCertificate.error_with_msg(
+            &format!(
+                "Precertificate Signing Certificate '{}' was not issued by the issuer of the final certificate '{}'.",
+                signing_certificate.fingerprint(),
+                certificate.fingerprint(),
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Return `true` if `certificate` was signed by the `issuer` certificate's key.
+fn is_signed_by(certificate: &CertificateParser, issuer: &CertificateParser) -> bool {
+    if certificate.as_encoded_issuer() != issuer.as_encoded_subject() {
+        return false;
+    }
+    let (oid, signature) = certificate.get_encoded_signature();
+    let public_key = issuer.get_encoded_subject_public_key_info().to_public_key();
+    Tyst::instance().ses().by_oid(&oid).is_some_and(|mut se| {
+        se.verify(
+            public_key.as_ref(),
+            signature,
+            certificate.as_encoded_tbs_certificate(),
+        )
+    })
+}
+
+fn subject_key_identifier(certificate: &CertificateParser) -> Result<Option<Vec<u8>>, CtError> {
+    certificate
+        .get_subject_key_identifier_kid()
+        .map_err(decoding_error)
+}
+
+fn decoding_error(e: CertificateParsingError) -> CtError {
+    CtErrorKind::DecodingError.error_with_msg(&format!("Failed to decode certificate: {e}"))
+}
+
+fn decode_tbs_certificate(certificate: &CertificateParser) -> Result<TbsCertificate, CtError> {
+    rasn::der::decode(&certificate.get_encoded_tbs_certificate()).map_err(|e| {
+        CtErrorKind::DecodingError.error_with_msg(&format!("Failed to decode certificate: {e:?}"))
+    })
+}
+
+fn oid(oid: &[u32]) -> ObjectIdentifier {
+    ObjectIdentifier::new_unchecked(oid.to_vec().into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::build::NoSignatureTbsCertificate;
+    use crate::cert::extensions::Extensions;
+    use crate::cert::types::DistinguishedName;
+    use crate::cert::types::SerialNumber;
+    use crate::cert::types::WellKnownAlgorithm;
+    use crate::cert::types::WellKnownGeneralName;
+    use crate::ct::test_utils::*;
+    use crate::test_utils::*;
+    use sha2::Digest;
+
+    /// Timestamp of the test SCTs in epoch seconds.
+    const SCT_TIMESTAMP: u64 = 1_792_337_400;
+
+    /// Return the TBSCertificate of a leaf issued by `issuer` that expires
+    /// within the temporal interval of the test log.
+    fn leaf_tbs_certificate(issuer: &CertificateParser) -> NoSignatureTbsCertificate {
+        let mut extensions = Extensions::default();
+        extensions.add_authority_key_identifier(
+            &AuthorityKeyIdentifier::from_issuers_subject_key_identifier(
+                &issuer.get_subject_key_identifier_kid().unwrap().unwrap(),
+            ),
+        );
+        extensions.add_extended_key_usage(&[ExtendedKeyUsage::PkixServerAuth]);
+        extensions.add_subject_alternative_name(
+            &[(WellKnownGeneralName::DnsName, "ct.example.com".to_string())],
+            false,
+        );
+        NoSignatureTbsCertificate::new(
+            issuer.get_subject().unwrap(),
+            1_798_761_600,
+            DistinguishedName::try_from(vec![vec![(
+                "common_name".to_string(),
+                "ct.example.com".to_string(),
+            )]])
+            .unwrap(),
+            load_test_certificate("ec_cdp_leaf.b64")
+                .get_encoded_subject_public_key_info()
+                .to_public_key()
+                .as_ref(),
+            extensions,
+        )
+    }
+
+    fn signature_algorithm() -> Vec<u8> {
+        crate::cms::signature_algorithm_identifier(WellKnownAlgorithm::EcdsaWithSha256.as_oid())
+    }
+
+    fn sign(tbs_certificate: &NoSignatureTbsCertificate, key: &str) -> CertificateParser {
+        let signature = sign_with_test_key(
+            key,
+            &WellKnownAlgorithm::EcdsaWithSha256,
+            &tbs_certificate.with_signature_algorithm_as_bytes(&signature_algorithm()),
+        );
+        let certificate = tbs_certificate.to_certificate(&signature_algorithm(), signature);
+        CertificateParser::from_bytes(&rasn::der::encode(&certificate).unwrap()).unwrap()
+    }
+
+    fn with_issuer(
+        tbs_certificate: &NoSignatureTbsCertificate,
+        issuer: &CertificateParser,
+    ) -> NoSignatureTbsCertificate {
+        let mut tbs_certificate = tbs_certificate.to_rasn_type();
+        tbs_certificate.issuer = rasn::der::decode(issuer.as_encoded_subject()).unwrap();
+        NoSignatureTbsCertificate::from_rasn_type(&tbs_certificate)
+    }
+
+    /// Return an SCT of the test log over the precertificate `tbs_certificate`
+    /// of the final `issuer`.
+    fn log_sct(issuer: &CertificateParser, tbs_certificate: &[u8]) -> SignedCertificateTimestamp {
+        let log_id = sha2::Sha256::digest(load_test_resource_b64("ct_log_spki.b64"));
+        let sct = |signature: &[u8]| {
+            SignedCertificateTimestamp::new(
+                &log_id,
+                SCT_TIMESTAMP * 1000,
+                &[],
+                SignedCertificateTimestamp::HASH_ALGORITHM_SHA256,
+                SignedCertificateTimestamp::SIGNATURE_ALGORITHM_ECDSA,
+                signature,
+            )
+            .unwrap()
+        };
+        let signed_data = sct(&[])
+            .get_precertificate_signed_data(
+                &sha2::Sha256::digest(issuer.get_encoded_subject_public_key_info()),
+                tbs_certificate,
+            )
+            .unwrap();
+        sct(&sign_with_test_key(
+            "ct_log_key.pem",
+            &WellKnownAlgorithm::EcdsaWithSha256,
+            &signed_data,
+        ))
+    }
+
+    #[test]
+    fn precertificate_signed_by_issuer() {
+        init_logger();
+        let issuer = load_test_certificate("ec_ca.b64");
+        let tbs_certificate = leaf_tbs_certificate(&issuer);
+        let precertificate = sign(
+            &tbs_certificate.to_precertificate(None).unwrap(),
+            "ec_ca_key.pem",
+        );
+        let log_tbs_certificate = precertificate
+            .get_encoded_precertificate_tbs_certificate()
+            .unwrap();
+        assert_eq!(
+            log_tbs_certificate,
+            tbs_certificate.with_signature_algorithm_as_bytes(&signature_algorithm())
+        );
+        let sct = log_sct(&issuer, &log_tbs_certificate);
+        let certificate = sign(
+            &tbs_certificate
+                .with_signed_certificate_timestamps(&[sct.clone()])
+                .unwrap(),
+            "ec_ca_key.pem",
+        );
+        check_precertificate_consistency(&precertificate, &issuer, &certificate).unwrap();
+        load_test_ct_log_list("usable")
+            .verify_at(&sct, &certificate, &issuer, SCT_TIMESTAMP + 60)
+            .unwrap();
+        // The final certificate differs from the precertificate
+        let other = sign(
+            &tbs_certificate
+                .with_serial_number(&SerialNumber::generate(None))
+                .with_signed_certificate_timestamps(&[sct])
+                .unwrap(),
+            "ec_ca_key.pem",
+        );
+        let e = check_precertificate_consistency(&precertificate, &issuer, &other).unwrap_err();
+        assert_eq!(e.kind(), &CtErrorKind::PrecertificateMismatch);
+        // The precertificate must be signed by the signing certificate
+        let e = check_precertificate_consistency(
+            &precertificate,
+            &load_test_certificate("ct_psc.b64"),
+            &certificate,
+        )
+        .unwrap_err();
+        assert_eq!(
+            e.kind(),
+            &CtErrorKind::InvalidPrecertificateSigningCertificate
+        );
+    }
+
+    #[test]
+    fn precertificate_signed_by_precertificate_signing_certificate() {
+        init_logger();
+        let issuer = load_test_certificate("ec_ca.b64");
+        let signing_certificate = load_test_certificate("ct_psc.b64");
+        let tbs_certificate = leaf_tbs_certificate(&issuer);
+        let precertificate = sign(
+            &tbs_certificate
+                .to_precertificate(Some(&signing_certificate))
+                .unwrap(),
+            "ct_psc_key.pem",
+        );
+        assert_eq!(
+            precertificate.as_encoded_issuer(),
+            signing_certificate.as_encoded_subject()
+        );
+        assert_eq!(
+            precertificate.get_authority_key_identifier_kid().unwrap(),
+            signing_certificate
+                .get_subject_key_identifier_kid()
+                .unwrap()
+        );
+        // The log restores the issuer and AuthorityKeyIdentifier of the final issuer
+        let sct = log_sct(
+            &issuer,
+            &tbs_certificate.with_signature_algorithm_as_bytes(&signature_algorithm()),
+        );
+        let certificate = sign(
+            &tbs_certificate
+                .with_signed_certificate_timestamps(&[sct.clone()])
+                .unwrap(),
+            "ec_ca_key.pem",
+        );
+        check_precertificate_consistency(&precertificate, &signing_certificate, &certificate)
+            .unwrap();
+        load_test_ct_log_list("usable")
+            .verify_at(&sct, &certificate, &issuer, SCT_TIMESTAMP + 60)
+            .unwrap();
+        // The final issuer did not sign the precertificate
+        let e =
+            check_precertificate_consistency(&precertificate, &issuer, &certificate).unwrap_err();
+        assert_eq!(
+            e.kind(),
+            &CtErrorKind::InvalidPrecertificateSigningCertificate
+        );
+        // The AuthorityKeyIdentifier must identify the signing certificate
+        let precertificate = sign(
+            &with_issuer(
+                &tbs_certificate.to_precertificate(None).unwrap(),
+                &signing_certificate,
+            ),
+            "ct_psc_key.pem",
+        );
+        let e =
+            check_precertificate_consistency(&precertificate, &signing_certificate, &certificate)
+                .unwrap_err();
+        assert_eq!(e.kind(), &CtErrorKind::PrecertificateMismatch);
+        // A certificate without the Precertificate Signing Certificate EKU
+        let signing_certificate = load_test_certificate("ec_cdp_leaf.b64");
+        let precertificate = sign(
+            &with_issuer(
+                &tbs_certificate.to_precertificate(None).unwrap(),
+                &signing_certificate,
+            ),
+            "ec_csr_key.pem",
+        );
+        let e =
+            check_precertificate_consistency(&precertificate, &signing_certificate, &certificate)
+                .unwrap_err();
+        assert_eq!(
+            e.kind(),
+            &CtErrorKind::InvalidPrecertificateSigningCertificate
+        );
+    }
+}
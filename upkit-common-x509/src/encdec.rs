@@ -5,5 +5,7 @@ pub mod oid;
 pub mod pem;
 pub mod puny_code;
 pub(crate) mod rasn;
+pub mod reader;
+pub(crate) mod tlv;
 
 pub use encdec_errors::DecodingError;
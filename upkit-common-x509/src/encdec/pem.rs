@@ -18,40 +18,43 @@
 //! [RFC7468](https://datatracker.ietf.org/doc/html/rfc7468) Textual Encodings of PKIX, PKCS, and CMS Structures
 
 mod pem_markers;
+mod pem_parser;
+mod pem_parsing_error;
 
 pub use self::pem_markers::Marker;
+pub use self::pem_parser::PemObject;
+pub use self::pem_parser::PemParser;
+pub use self::pem_parser::PemParsingMode;
+pub use self::pem_parsing_error::PemParsingError;
+pub use self::pem_parsing_error::PemParsingErrorKind;
 pub use super::DecodingError;
 
 /// Parse the text as PEM encoded objects
 ///
+/// This uses [PemParsingMode::Lax] and ignores any encapsulated headers.
+///
 /// The content is not vetted in any way to correspond to the claimed marker.
 pub fn parse(textual_encoding: &str) -> Result<Vec<(Marker, Vec<u8>)>, DecodingError> {
+    parse_objects(textual_encoding, PemParsingMode::Lax)
+        .map(|objects| objects.into_iter().map(PemObject::into_parts).collect())
+        .map_err(|e| DecodingError::with_msg(&e.to_string()))
+}
+
+/// Parse the text as PEM encoded objects using the [PemParsingMode].
+///
+/// The content is not vetted in any way to correspond to the claimed marker.
+pub fn parse_objects(
+    textual_encoding: &str,
+    mode: PemParsingMode,
+) -> Result<Vec<PemObject>, PemParsingError> {
+    let mut parser = PemParser::new(mode);
     let mut ret = vec![];
-    let lines = textual_encoding.lines();
-    let mut content = None;
-    for line in lines {
-        if content.is_none()
-            && line.starts_with(Marker::BEGIN_LINE_START)
-            && line.ends_with(Marker::BEGIN_LINE_FINISH)
-        {
-            content = Some((Marker::from_begin_line(line), Vec::<u8>::new()));
-        } else if line.starts_with(Marker::END_LINE_START)
-            && line.ends_with(Marker::END_LINE_FINISH)
-        {
-            ret.push(content.take().unwrap());
-        } else {
-            // There should be 64 base64-chars on each line except for the last one
-            let decoded = &tyst::encdec::base64::decode(line)
-                .map_err(|e| DecodingError::with_msg(&e.to_string()))?;
-            content.as_mut().unwrap().1.extend_from_slice(decoded);
+    for line in textual_encoding.lines() {
+        if let Some(pem_object) = parser.push_line(line)? {
+            ret.push(pem_object);
         }
     }
-    if content.is_some() {
-        // Missing end..
-        return Err(DecodingError::with_msg(
-            "Unable to find any content in PEM.",
-        ));
-    }
+    parser.finish()?;
     Ok(ret)
 }
 
@@ -18,6 +18,7 @@
 //! Common markers from [RFC7468 4](https://datatracker.ietf.org/doc/html/rfc7468#section-4)
 
 /// See [RFC7468 4](https://datatracker.ietf.org/doc/html/rfc7468#section-4).
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Marker {
     /// Data is of ASN.1 Type RFC5280 Certificate.
     Certificate,
@@ -66,12 +67,18 @@ impl Marker {
 
     /// Return a new instance from a PEM complete begin line
     pub fn from_begin_line(begin_line: &str) -> Self {
-        match begin_line
-            .split_at(begin_line.len() - Self::BEGIN_LINE_FINISH.len())
-            .0
-            .split_at(Self::BEGIN_LINE_START.len())
-            .1
-        {
+        Self::from_label(
+            begin_line
+                .split_at(begin_line.len() - Self::BEGIN_LINE_FINISH.len())
+                .0
+                .split_at(Self::BEGIN_LINE_START.len())
+                .1,
+        )
+    }
+
+    /// Return a new instance from the label of a `BEGIN` or `END` line
+    pub fn from_label(label: &str) -> Self {
+        match label {
             "CERTIFICATE" => Self::Certificate,
             "X509 CRL" => Self::CertificateRevocationList,
             "CERTIFICATE REQUEST" => Self::CertificationRequest,
@@ -0,0 +1,104 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PEM parsing errors.
+
+use std::error::Error;
+use std::fmt;
+
+/// Cause of PEM parsing error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PemParsingErrorKind {
+    /// The label of a `BEGIN` or `END` line contains invalid characters.
+    InvalidLabel,
+    /// The label of the `END` line does not match the label of the `BEGIN` line.
+    MismatchedLabel,
+    /// An `END` line without a preceding `BEGIN` line.
+    UnexpectedEndLine,
+    /// A `BEGIN` line inside an encapsulation boundary.
+    UnexpectedBeginLine,
+    /// Explanatory text outside of the encapsulation boundaries (strict mode).
+    UnexpectedText,
+    /// A malformed [RFC1421](https://www.rfc-editor.org/rfc/rfc1421) header
+    /// or headers where not allowed (strict mode).
+    InvalidHeader,
+    /// Invalid base64 data or line length.
+    InvalidBase64,
+    /// Missing `END` line at the end of the input.
+    MissingEndLine,
+}
+
+impl PemParsingErrorKind {
+    /// Create a new instance with an error message.
+    pub fn error_with_msg(self, line_number: usize, msg: &str) -> PemParsingError {
+        PemParsingError {
+            kind: self,
+            line_number,
+            msg: Some(msg.to_string()),
+        }
+    }
+
+    /// Create a new instance without an error message.
+    pub fn error(self, line_number: usize) -> PemParsingError {
+        PemParsingError {
+            kind: self,
+            line_number,
+            msg: None,
+        }
+    }
+}
+
+impl fmt::Display for PemParsingErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/** PEM parsing error.
+
+Create a new instance via [PemParsingErrorKind].
+*/
+#[derive(Debug)]
+pub struct PemParsingError {
+    kind: PemParsingErrorKind,
+    line_number: usize,
+    msg: Option<String>,
+}
+
+impl PemParsingError {
+    /// Return the [PemParsingErrorKind] type of this error.
+    pub fn kind(&self) -> &PemParsingErrorKind {
+        &self.kind
+    }
+
+    /// Return the (1-based) line number where the error was detected.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+}
+
+impl fmt::Display for PemParsingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(msg) = &self.msg {
+            write!(f, "{} at line {}: {}", self.kind, self.line_number, msg)
+        } else {
+            write!(f, "{} at line {}", self.kind, self.line_number)
+        }
+    }
+}
+
+impl Error for PemParsingError {}
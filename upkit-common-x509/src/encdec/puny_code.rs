@@ -42,17 +42,18 @@ pub fn encode(dns_name_utf8: &str) -> String {
     dns_name_punycode
 }
 
-/// [RFC 3492](https://www.rfc-editor.org/rfc/rfc3492) Puny code implementation
+/** [RFC 3492](https://www.rfc-editor.org/rfc/rfc3492) Puny code implementation
+
+Labels with an invalid puny code encoding are returned unmodified.
+*/
 pub fn decode(dns_name_punycode: &str) -> String {
     let dns_name_utf8 = dns_name_punycode
         .to_lowercase()
         .split('.')
         .map(|part| {
-            if part.starts_with("xn--") {
-                idna::punycode::decode_to_string(part.split_at(4).1).unwrap()
-            } else {
-                part.to_string()
-            }
+            part.strip_prefix("xn--")
+                .and_then(idna::punycode::decode_to_string)
+                .unwrap_or_else(|| part.to_string())
         })
         .collect::<Vec<_>>()
         .join(".");
@@ -77,4 +78,10 @@ mod test {
         let dns_name_utf8 = "übernice.fantastic.åäö";
         assert_eq!(dns_name_utf8, decode(&encode(dns_name_utf8)))
     }
+
+    #[test]
+    fn decode_invalid_puny_code() {
+        init_logger();
+        assert_eq!("xn--9.example.com", decode("xn--9.example.com"))
+    }
 }
@@ -1,12 +1,37 @@
+use rasn::types::Any;
+use rasn::types::BmpString;
+use rasn::types::Ia5String;
+use rasn::types::ObjectIdentifier;
+use rasn::types::PrintableString;
+use rasn::types::SetOf;
+use rasn::types::Utf8String;
+use rasn::AsnType;
+use rasn::Decode;
+use rasn::Encode;
 use rasn_pkix::DisplayText;
 
+/** Generic `Attribute` as used in e.g. RFC2986 `CRIAttributes` and RFC5958
+`Attributes`.
+
+```text
+Attribute ::= SEQUENCE {
+    type   OBJECT IDENTIFIER,
+    values SET SIZE(1..MAX) OF ANY }
+```
+*/
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Attribute {
+    pub r#type: ObjectIdentifier,
+    pub values: SetOf<Any>,
+}
+
 pub fn display_text_as_string(display_text: &DisplayText) -> String {
     match display_text {
         DisplayText::Ia5String(s) => s.to_string(),
         DisplayText::VisibleString(s) => s.to_string(),
         DisplayText::BmpString(s) => s
             .iter()
-            .map(|c| char::from_u32(*c as u32).unwrap())
+            .map(|c| char::from_u32(*c as u32).unwrap_or(char::REPLACEMENT_CHARACTER))
             .collect::<String>(),
         DisplayText::Utf8String(s) => s.to_string(),
     }
@@ -26,6 +51,16 @@ pub fn integer_as_usize(value: &rasn::types::Integer) -> usize {
     }
 }
 
+/// Return the value if it is non-negative and fits in an `u64`.
+///
+/// Prefer this over [integer_as_usize] for values from untrusted input.
+pub fn integer_try_as_u64(value: &rasn::types::Integer) -> Option<u64> {
+    match value {
+        rasn::types::Integer::Primitive(value) => u64::try_from(*value).ok(),
+        rasn::types::Integer::Variable(value) => u64::try_from(value.as_ref()).ok(),
+    }
+}
+
 pub fn integer_as_isize(value: &rasn::types::Integer) -> isize {
     match value {
         rasn::types::Integer::Primitive(value) => *value,
@@ -40,9 +75,66 @@ pub fn integer_as_isize(value: &rasn::types::Integer) -> isize {
     }
 }
 
+/// Return the value if it fits in an `isize`.
+///
+/// Prefer this over [integer_as_isize] for values from untrusted input.
+pub fn integer_try_as_isize(value: &rasn::types::Integer) -> Option<isize> {
+    match value {
+        rasn::types::Integer::Primitive(value) => Some(*value),
+        rasn::types::Integer::Variable(value) => isize::try_from(value.as_ref()).ok(),
+    }
+}
+
+/// Return the minimal big-endian two's complement encoding of the value (the
+/// content octets of the DER encoded INTEGER).
 pub fn integer_as_bytes_be(value: &rasn::types::Integer) -> Vec<u8> {
     match value {
-        rasn::types::Integer::Primitive(value) => (*value).to_be_bytes().to_vec(),
+        rasn::types::Integer::Primitive(value) => {
+            num_bigint::BigInt::from(*value).to_signed_bytes_be()
+        }
         rasn::types::Integer::Variable(value) => value.to_signed_bytes_be(),
     }
 }
+
+/// Decode a DER encoded string of any of the common string types.
+pub fn any_as_string(value: &Any) -> Option<String> {
+    let bytes = value.as_bytes();
+    if let Ok(s) = rasn::der::decode::<Utf8String>(bytes) {
+        return Some(s);
+    }
+    if let Ok(s) = rasn::der::decode::<PrintableString>(bytes) {
+        return String::from_utf8(s.to_vec()).ok();
+    }
+    if let Ok(s) = rasn::der::decode::<Ia5String>(bytes) {
+        return Some(s.to_string());
+    }
+    if let Ok(s) = rasn::der::decode::<BmpString>(bytes) {
+        return s
+            .iter()
+            .map(|c| char::from_u32(*c as u32))
+            .collect::<Option<String>>();
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integer_as_bytes_be_is_minimal() {
+        for (value, expected) in [
+            (0, vec![0x00]),
+            (1, vec![0x01]),
+            (0x80, vec![0x00, 0x80]),
+            (0x5a5a02, vec![0x5a, 0x5a, 0x02]),
+            (-1, vec![0xff]),
+            (-129, vec![0xff, 0x7f]),
+        ] {
+            assert_eq!(
+                integer_as_bytes_be(&rasn::types::Integer::Primitive(value)),
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,430 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Streaming reader of PEM or concatenated DER encoded objects.
+
+use super::pem::Marker;
+use super::pem::PemParser;
+use super::pem::PemParsingMode;
+use super::DecodingError;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::ErrorKind;
+use std::io::Read;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Pem,
+    Der,
+}
+
+/** Streaming reader of PEM or concatenated DER encoded objects.
+
+Objects are read from the underlying [BufRead] one at a time, so the input can
+be much larger than available memory (e.g. CT log exports).
+
+The input format is detected by peeking at the first byte: DER encoded objects
+always start with an ASN.1 `SEQUENCE` (`0x30`), while PEM is text. Since DER
+does not carry any type information, all DER objects are reported with the
+marker provided by [with_der_marker](Self::with_der_marker) (defaults to
+[Marker::Certificate]).
+
+Each item is a `Result`, so a malformed object does not abort the stream. A
+malformed DER header is the exception, since there is no way to find the start
+of the next object after it.
+*/
+pub struct EncodedObjectReader<R: BufRead> {
+    inner: R,
+    format: Option<Format>,
+    pem_parser: PemParser,
+    der_marker: Marker,
+    max_der_length: usize,
+    max_pem_line_length: usize,
+    offset: u64,
+    done: bool,
+}
+
+impl<R: Read> EncodedObjectReader<BufReader<R>> {
+    /// Return a new instance that wraps a [Read] in a [BufReader].
+    pub fn from_read(read: R, mode: PemParsingMode) -> Self {
+        Self::new(BufReader::new(read), mode)
+    }
+}
+
+impl<R: BufRead> EncodedObjectReader<R> {
+    /// Default maximum size of a single DER encoded object (64 MiB).
+    pub const DEFAULT_MAX_DER_LENGTH: usize = 64 * 1024 * 1024;
+    /// Default maximum length of a single PEM line (64 KiB).
+    pub const DEFAULT_MAX_PEM_LINE_LENGTH: usize = 64 * 1024;
+
+    /// Return a new instance. `mode` applies if the input is PEM encoded.
+    pub fn new(inner: R, mode: PemParsingMode) -> Self {
+        Self {
+            inner,
+            format: None,
+            pem_parser: PemParser::new(mode),
+            der_marker: Marker::Certificate,
+            max_der_length: Self::DEFAULT_MAX_DER_LENGTH,
+            max_pem_line_length: Self::DEFAULT_MAX_PEM_LINE_LENGTH,
+            offset: 0,
+            done: false,
+        }
+    }
+
+    /// Set the marker to report for DER encoded objects.
+    pub fn with_der_marker(mut self, der_marker: Marker) -> Self {
+        self.der_marker = der_marker;
+        self
+    }
+
+    /// Set the maximum accepted size of a single DER encoded object.
+    pub fn with_max_der_length(mut self, max_der_length: usize) -> Self {
+        self.max_der_length = max_der_length;
+        self
+    }
+
+    /// Set the maximum accepted length of a single PEM line including the
+    /// line break.
+    pub fn with_max_pem_line_length(mut self, max_pem_line_length: usize) -> Self {
+        self.max_pem_line_length = max_pem_line_length;
+        self
+    }
+
+    fn detect_format(&mut self) -> Result<Option<Format>, DecodingError> {
+        let buf = loop {
+            match self.inner.fill_buf() {
+                Ok(buf) => break buf,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(DecodingError::with_msg(&e.to_string())),
+            }
+        };
+        Ok(buf.first().map(|first| {
+            if *first == 0x30 {
+                Format::Der
+            } else {
+                Format::Pem
+            }
+        }))
+    }
+
+    fn next_pem(&mut self) -> Option<Result<(Marker, Vec<u8>), DecodingError>> {
+        let mut line = vec![];
+        loop {
+            line.clear();
+            let limit = u64::try_from(self.max_pem_line_length).unwrap_or(u64::MAX);
+            match (&mut self.inner).take(limit).read_until(b'\n', &mut line) {
+                Ok(0) => {
+                    self.done = true;
+                    return self
+                        .pem_parser
+                        .finish()
+                        .err()
+                        .map(|e| Err(DecodingError::with_msg(&e.to_string())));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(DecodingError::with_msg(&e.to_string())));
+                }
+            }
+            if line.len() >= self.max_pem_line_length && !line.ends_with(b"\n") {
+                if let Err(e) = self.skip_line() {
+                    self.done = true;
+                    return Some(Err(DecodingError::with_msg(&e.to_string())));
+                }
+                self.pem_parser.push_line("").ok();
+                self.pem_parser.discard_object();
+                let msg = format!(
+                    "Line {} exceeds the limit of {} bytes.",
+                    self.pem_parser.get_line_number(),
+                    self.max_pem_line_length
+                );
+                return Some(Err(DecodingError::with_msg(&msg)));
+            }
+            let Ok(text) = std::str::from_utf8(&line) else {
+                self.pem_parser.push_line("").ok();
+                self.pem_parser.discard_object();
+                let msg = format!(
+                    "Invalid UTF-8 at line {}.",
+                    self.pem_parser.get_line_number()
+                );
+                return Some(Err(DecodingError::with_msg(&msg)));
+            };
+            match self.pem_parser.push_line(text) {
+                Ok(Some(pem_object)) => return Some(Ok(pem_object.into_parts())),
+                Ok(None) => {}
+                Err(e) => {
+                    self.pem_parser.discard_object();
+                    return Some(Err(DecodingError::with_msg(&e.to_string())));
+                }
+            }
+        }
+    }
+
+    /// Discard input up to and including the next line break without
+    /// buffering it.
+    fn skip_line(&mut self) -> std::io::Result<()> {
+        loop {
+            let buf = match self.inner.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if buf.is_empty() {
+                return Ok(());
+            }
+            match buf.iter().position(|octet| *octet == b'\n') {
+                Some(position) => {
+                    self.inner.consume(position + 1);
+                    return Ok(());
+                }
+                None => {
+                    let length = buf.len();
+                    self.inner.consume(length);
+                }
+            }
+        }
+    }
+
+    fn next_der(&mut self) -> Option<Result<(Marker, Vec<u8>), DecodingError>> {
+        let start_offset = self.offset;
+        let ret = match self.read_tag() {
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Ok(Some(tag)) => self.read_der_after_tag(tag, start_offset),
+            Err(e) => Err(e),
+        };
+        if ret.is_err() {
+            // There is no reliable way to find the next object after this
+            self.done = true;
+        }
+        Some(ret)
+    }
+
+    /// Return the next tag octet or `None` at the end of the input.
+    fn read_tag(&mut self) -> Result<Option<u8>, DecodingError> {
+        let mut tag = [0u8; 1];
+        loop {
+            match self.inner.read(&mut tag) {
+                Ok(0) => return Ok(None),
+                Ok(_) => {
+                    self.offset += 1;
+                    return Ok(Some(tag[0]));
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(DecodingError::with_msg(&e.to_string())),
+            }
+        }
+    }
+
+    fn read_der_after_tag(
+        &mut self,
+        tag: u8,
+        start_offset: u64,
+    ) -> Result<(Marker, Vec<u8>), DecodingError> {
+        if tag != 0x30 {
+            let msg = format!(
+                "Expected ASN.1 SEQUENCE at byte offset {start_offset}, but found tag 0x{tag:02x}."
+            );
+            return Err(DecodingError::with_msg(&msg));
+        }
+        let mut der = vec![tag];
+        let mut length_octet = [0u8; 1];
+        self.read_exact(&mut length_octet, start_offset)?;
+        der.push(length_octet[0]);
+        let length = match length_octet[0] {
+            short if short < 0x80 => usize::from(short),
+            0x80 => {
+                let msg = format!(
+                    "Indefinite length is not allowed in DER (object at byte offset {start_offset})."
+                );
+                return Err(DecodingError::with_msg(&msg));
+            }
+            long => {
+                let count = usize::from(long & 0x7f);
+                if count > std::mem::size_of::<u32>() {
+                    let msg = format!(
+                        "Unsupported length of length {count} at byte offset {start_offset}."
+                    );
+                    return Err(DecodingError::with_msg(&msg));
+                }
+                let mut length_octets = vec![0u8; count];
+                self.read_exact(&mut length_octets, start_offset)?;
+                der.extend_from_slice(&length_octets);
+                length_octets
+                    .iter()
+                    .fold(0usize, |length, octet| (length << 8) | usize::from(*octet))
+            }
+        };
+        if length > self.max_der_length {
+            let msg = format!(
+                "Object at byte offset {start_offset} of {length} bytes exceeds the limit of {} bytes.",
+                self.max_der_length
+            );
+            return Err(DecodingError::with_msg(&msg));
+        }
+        let header_length = der.len();
+        der.resize(header_length + length, 0);
+        self.read_exact(&mut der[header_length..], start_offset)?;
+        Ok((self.der_marker.clone(), der))
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8], start_offset: u64) -> Result<(), DecodingError> {
+        self.inner.read_exact(buf).map_err(|e| {
+            let msg = format!("Truncated object at byte offset {start_offset}: {e}");
+            DecodingError::with_msg(&msg)
+        })?;
+        self.offset += buf.len() as u64;
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for EncodedObjectReader<R> {
+    type Item = Result<(Marker, Vec<u8>), DecodingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let format = match self.format {
+            Some(format) => format,
+            None => match self.detect_format() {
+                Ok(Some(format)) => {
+                    self.format = Some(format);
+                    format
+                }
+                Ok(None) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            },
+        };
+        match format {
+            Format::Pem => self.next_pem(),
+            Format::Der => self.next_der(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DER: &[u8] = &[0x30, 0x03, 0x02, 0x01, 0x01];
+
+    #[test]
+    fn read_concatenated_der() {
+        let mut input = DER.to_vec();
+        input.extend_from_slice(DER);
+        let items = EncodedObjectReader::from_read(input.as_slice(), PemParsingMode::Lax)
+            .collect::<Vec<_>>();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].as_ref().unwrap().1, DER);
+        // Truncated second object
+        input.pop();
+        let items = EncodedObjectReader::from_read(input.as_slice(), PemParsingMode::Lax)
+            .collect::<Vec<_>>();
+        assert_eq!(items.len(), 2);
+        assert!(items[1].is_err());
+    }
+
+    #[test]
+    fn read_pem_continues_after_error() {
+        let input = "-----BEGIN CERTIFICATE-----\r\nMAMCAQE=\r\n-----END CERTIFICATE-----\r\n\
+            -----BEGIN CERTIFICATE-----\r\n!!!!\r\nMAMCAQE=\r\n-----END CERTIFICATE-----\r\n\
+            -----BEGIN X509 CRL-----\r\nMAMCAQE=\r\n-----END X509 CRL-----\r\n";
+        let items = EncodedObjectReader::from_read(input.as_bytes(), PemParsingMode::Lax)
+            .collect::<Vec<_>>();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].as_ref().unwrap().1, DER);
+        assert!(items[1].is_err());
+        assert_eq!(
+            items[2].as_ref().unwrap().0,
+            Marker::CertificateRevocationList
+        );
+    }
+
+    #[test]
+    fn read_pem_keeps_object_after_truncated_object() {
+        let input = "-----BEGIN CERTIFICATE-----\r\nMAMC\r\n\
+            -----BEGIN CERTIFICATE-----\r\nMAMCAQE=\r\n-----END CERTIFICATE-----\r\n";
+        let items = EncodedObjectReader::from_read(input.as_bytes(), PemParsingMode::Lax)
+            .collect::<Vec<_>>();
+        assert_eq!(items.len(), 2);
+        let msg = items[0].as_ref().unwrap_err().to_string();
+        assert!(msg.contains("UnexpectedBeginLine at line 3"), "{msg}");
+        assert_eq!(items[1].as_ref().unwrap().1, DER);
+    }
+
+    #[test]
+    fn read_pem_rejects_overlong_line() {
+        let input = format!(
+            "-----BEGIN CERTIFICATE-----\r\n{}\r\n-----END CERTIFICATE-----\r\n\
+            -----BEGIN CERTIFICATE-----\r\nMAMCAQE=\r\n-----END CERTIFICATE-----\r\n",
+            "A".repeat(1000)
+        );
+        let items = EncodedObjectReader::from_read(input.as_bytes(), PemParsingMode::Lax)
+            .with_max_pem_line_length(100)
+            .collect::<Vec<_>>();
+        assert_eq!(items.len(), 2);
+        let msg = items[0].as_ref().unwrap_err().to_string();
+        assert!(msg.contains("exceeds the limit of 100 bytes"), "{msg}");
+        assert_eq!(items[1].as_ref().unwrap().1, DER);
+    }
+
+    /// Fails every other read with [ErrorKind::Interrupted] and returns at
+    /// most one octet per read.
+    struct InterruptingRead<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for InterruptingRead<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(std::io::Error::from(ErrorKind::Interrupted));
+            }
+            self.data.read(&mut buf[..buf.len().min(1)])
+        }
+    }
+
+    #[test]
+    fn der_offsets_include_tag_and_survive_interrupts() {
+        let mut input = DER.to_vec();
+        input.extend_from_slice(DER);
+        input.extend_from_slice(&[0x04, 0x00]);
+        let read = InterruptingRead {
+            data: &input,
+            interrupt: false,
+        };
+        let items =
+            EncodedObjectReader::new(BufReader::with_capacity(1, read), PemParsingMode::Lax)
+                .collect::<Vec<_>>();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].as_ref().unwrap().1, DER);
+        assert_eq!(items[1].as_ref().unwrap().1, DER);
+        let msg = items[2].as_ref().unwrap_err().to_string();
+        assert!(msg.contains("byte offset 10,"), "{msg}");
+    }
+}
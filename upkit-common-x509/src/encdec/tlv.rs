@@ -0,0 +1,104 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Minimal DER Tag-Length-Value walker for recording byte ranges.
+//!
+//! This does not interpret the content and only supports the low tag number
+//! form, which is sufficient for the structure of X.509 objects.
+
+use super::DecodingError;
+use std::ops::Range;
+
+/// Location of a DER encoded TLV within a larger buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Tlv {
+    /// Identifier octet.
+    pub(crate) tag: u8,
+    /// Range of the complete TLV including the header.
+    pub(crate) range: Range<usize>,
+    /// Range of the content octets.
+    pub(crate) content: Range<usize>,
+}
+
+impl Tlv {
+    /// Read the TLV that starts at `offset` of `data`.
+    pub(crate) fn read(data: &[u8], offset: usize) -> Result<Self, DecodingError> {
+        let truncated = || DecodingError::with_msg("Truncated DER encoding.");
+        let tag = *data.get(offset).ok_or_else(truncated)?;
+        if tag & 0x1f == 0x1f {
+            return Err(DecodingError::with_msg(
+                "High tag number form is not supported.",
+            ));
+        }
+        let first_length_octet = *data.get(offset + 1).ok_or_else(truncated)?;
+        let (header_len, content_len) = if first_length_octet & 0x80 == 0 {
+            (2, usize::from(first_length_octet))
+        } else {
+            let length_octets = usize::from(first_length_octet & 0x7f);
+            if length_octets == 0 || length_octets > std::mem::size_of::<usize>() {
+                return Err(DecodingError::with_msg("Unsupported DER length encoding."));
+            }
+            let length = data
+                .get(offset + 2..offset + 2 + length_octets)
+                .ok_or_else(truncated)?
+                .iter()
+                .fold(0usize, |length, octet| length << 8 | usize::from(*octet));
+            (2 + length_octets, length)
+        };
+        let content_start = offset + header_len;
+        let end = content_start
+            .checked_add(content_len)
+            .filter(|end| *end <= data.len())
+            .ok_or_else(truncated)?;
+        Ok(Self {
+            tag,
+            range: offset..end,
+            content: content_start..end,
+        })
+    }
+
+    /// Return all TLVs of the content of this constructed TLV.
+    pub(crate) fn children(&self, data: &[u8]) -> Result<Vec<Self>, DecodingError> {
+        let mut ret = vec![];
+        let mut offset = self.content.start;
+        while offset < self.content.end {
+            let child = Self::read(&data[..self.content.end], offset)?;
+            offset = child.range.end;
+            ret.push(child);
+        }
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_nested() {
+        // SEQUENCE { INTEGER 1, OCTET STRING (130 octets) }
+        let mut data = vec![0x30, 0x81, 0x88, 0x02, 0x01, 0x01, 0x04, 0x81, 0x82];
+        data.extend_from_slice(&[0xaa; 130]);
+        let sequence = Tlv::read(&data, 0).unwrap();
+        assert_eq!(sequence.range, 0..data.len());
+        let children = sequence.children(&data).unwrap();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].content, 5..6);
+        assert_eq!(children[1].tag, 0x04);
+        assert_eq!(children[1].content, 9..data.len());
+        assert!(Tlv::read(&data[..20], 0).is_err());
+    }
+}
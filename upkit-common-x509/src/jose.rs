@@ -0,0 +1,128 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! JSON Object Signing and Encryption (JOSE) integration.
+//!
+//! Export of public keys as [Jwk] and validation of the `x5c` certificate
+//! chain header of [RFC7515 4.1.6](https://www.rfc-editor.org/rfc/rfc7515#section-4.1.6).
+
+mod jose_error;
+mod jwk;
+
+pub use self::jose_error::JoseError;
+pub use self::jose_error::JoseErrorKind;
+pub use self::jwk::Jwk;
+use crate::cert::parse::CertificateParser;
+use crate::cert::validate::checkers::ExtensionChecker;
+use crate::cert::validate::CertificatePathValidator;
+use crate::cert::validate::CertificateValidationError;
+use crate::cert::validate::CertificateValidationErrorKind;
+use std::sync::Arc;
+
+/** Validate an `x5c` certificate chain and return the leaf certificate.
+
+Each entry of `x5c` is a base64 (not base64url) encoded DER certificate with
+the leaf certificate first.
+
+See [CertificatePathValidator::validate] for `at_epoch_seconds` and
+`additional_extension_checkers`.
+*/
+pub fn validate_x5c(
+    validator: &CertificatePathValidator,
+    x5c: &[String],
+    at_epoch_seconds: u64,
+    additional_extension_checkers: Vec<Arc<dyn ExtensionChecker>>,
+) -> Result<CertificateParser, CertificateValidationError> {
+    let chain = x5c
+        .iter()
+        .enumerate()
+        .map(|(i, encoded)| {
+            tyst::encdec::base64::decode(encoded).map_err(|e| {
+                CertificateValidationErrorKind::CertificateParsingError
+                    .error_with_msg(&format!("Invalid base64 in x5c[{i}]: {e:?}"))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let leaf = chain
+        .first()
+        .map(|leaf| CertificateParser::from_bytes(leaf))
+        .ok_or_else(|| CertificateValidationErrorKind::NotOneLeaf.error_with_msg("Empty x5c."))?
+        .map_err(|e| {
+            CertificateValidationErrorKind::CertificateParsingError.error_with_msg(&e.to_string())
+        })?;
+    validator.validate(chain, at_epoch_seconds, additional_extension_checkers)?;
+    Ok(leaf)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::*;
+
+    /// 2026-11-15, when the whole `ec_sub_ca_leaf.b64` chain is valid.
+    const AT_EPOCH_SECONDS: u64 = 1_794_700_800;
+
+    fn x5c(names: &[&str]) -> Vec<String> {
+        names
+            .iter()
+            .map(|name| tyst::encdec::base64::encode(&load_test_certificate_der(name)))
+            .collect()
+    }
+
+    fn validate(x5c: &[String]) -> Result<CertificateParser, CertificateValidationError> {
+        let validator = CertificatePathValidator::new(vec![load_test_certificate_der("ec_ca.b64")])
+            .unwrap()
+            .add_standard_extension_checkers();
+        validate_x5c(&validator, x5c, AT_EPOCH_SECONDS, vec![])
+    }
+
+    #[test]
+    fn valid_x5c() {
+        init_logger();
+        let leaf = validate(&x5c(&["ec_sub_ca_leaf.b64", "ec_sub_ca.b64"])).unwrap();
+        assert_eq!(
+            leaf.fingerprint(),
+            load_test_certificate("ec_sub_ca_leaf.b64").fingerprint()
+        );
+    }
+
+    #[test]
+    fn broken_x5c() {
+        init_logger();
+        // Missing intermediate
+        assert_kind(
+            validate(&x5c(&["ec_sub_ca_leaf.b64"])),
+            CertificateValidationErrorKind::NotTrusted,
+        );
+        // Intermediate with a broken signature
+        let mut sub_ca = load_test_certificate_der("ec_sub_ca.b64");
+        *sub_ca.last_mut().unwrap() ^= 0x01;
+        let mut broken = x5c(&["ec_sub_ca_leaf.b64"]);
+        broken.push(tyst::encdec::base64::encode(&sub_ca));
+        assert_kind(
+            validate(&broken),
+            CertificateValidationErrorKind::InvalidSignature,
+        );
+        // Not base64
+        let mut broken = x5c(&["ec_sub_ca_leaf.b64"]);
+        broken.push("not base64!".to_string());
+        assert_kind(
+            validate(&broken),
+            CertificateValidationErrorKind::CertificateParsingError,
+        );
+        assert_kind(validate(&[]), CertificateValidationErrorKind::NotOneLeaf);
+    }
+}
@@ -0,0 +1,82 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! JOSE errors.
+
+use std::error::Error;
+use std::fmt;
+
+/// Cause of JOSE error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JoseErrorKind {
+    /// Failure to decode the key, certificate or `x5c` entry.
+    DecodingError,
+    /// The key type has no JWK representation.
+    UnsupportedKeyType,
+}
+
+impl JoseErrorKind {
+    /// Create a new instance with an error message.
+    pub fn error_with_msg(self, msg: &str) -> JoseError {
+        JoseError {
+            kind: self,
+            msg: Some(msg.to_string()),
+        }
+    }
+
+    /// Create a new instance without an error message.
+    pub fn error(self) -> JoseError {
+        JoseError {
+            kind: self,
+            msg: None,
+        }
+    }
+}
+
+impl fmt::Display for JoseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/** JOSE error.
+
+Create a new instance via [JoseErrorKind].
+*/
+#[derive(Debug)]
+pub struct JoseError {
+    kind: JoseErrorKind,
+    msg: Option<String>,
+}
+
+impl JoseError {
+    /// Return the [JoseErrorKind] type of this error.
+    pub fn kind(&self) -> &JoseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for JoseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(msg) = &self.msg {
+            write!(f, "{} {}", self.kind, msg)
+        } else {
+            write!(f, "{}", self.kind)
+        }
+    }
+}
+
+impl Error for JoseError {}
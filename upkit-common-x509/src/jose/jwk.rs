@@ -0,0 +1,430 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! JSON Web Key (JWK).
+
+use super::JoseError;
+use super::JoseErrorKind;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::WellKnownAlgorithm;
+use rasn::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::base64::Base64;
+use serde_with::base64::UrlSafe;
+use serde_with::formats::Unpadded;
+use serde_with::serde_as;
+use serde_with::skip_serializing_none;
+use sha2::Digest;
+use std::collections::BTreeMap;
+
+/// `RSAPublicKey` from [RFC8017 A.1.1](https://www.rfc-editor.org/rfc/rfc8017#appendix-A.1.1).
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct RsaPublicKey {
+    modulus: Integer,
+    public_exponent: Integer,
+}
+
+/// Named curves of `id-ecPublicKey` with the JWK `crv` name and the size of a
+/// coordinate in octets.
+const EC_CURVES: &[(&[u32], &str, usize)] = &[
+    (&[1, 2, 840, 10045, 3, 1, 7], "P-256", 32),
+    (&[1, 3, 132, 0, 34], "P-384", 48),
+    (&[1, 3, 132, 0, 35], "P-521", 66),
+];
+
+/// Key algorithms of RFC8037 Octet Key Pairs with the JWK `crv` name.
+const OKP_CURVES: &[(&[u32], &str)] = &[
+    (&[1, 3, 101, 110], "X25519"),
+    (&[1, 3, 101, 111], "X448"),
+    (&[1, 3, 101, 112], "Ed25519"),
+    (&[1, 3, 101, 113], "Ed448"),
+];
+
+/** Public JSON Web Key.
+
+Supported key types are `RSA` and `EC` from
+[RFC7518 6](https://www.rfc-editor.org/rfc/rfc7518#section-6), `OKP` from
+[RFC8037 2](https://www.rfc-editor.org/rfc/rfc8037#section-2) and `AKP` for
+ML-DSA from
+[draft-ietf-cose-dilithium](https://datatracker.ietf.org/doc/draft-ietf-cose-dilithium/).
+*/
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Jwk {
+    kty: String,
+    alg: Option<String>,
+    crv: Option<String>,
+    #[serde_as(as = "Option<Base64<UrlSafe, Unpadded>>")]
+    n: Option<Vec<u8>>,
+    #[serde_as(as = "Option<Base64<UrlSafe, Unpadded>>")]
+    e: Option<Vec<u8>>,
+    #[serde_as(as = "Option<Base64<UrlSafe, Unpadded>>")]
+    x: Option<Vec<u8>>,
+    #[serde_as(as = "Option<Base64<UrlSafe, Unpadded>>")]
+    y: Option<Vec<u8>>,
+    #[serde(rename = "pub")]
+    #[serde_as(as = "Option<Base64<UrlSafe, Unpadded>>")]
+    public: Option<Vec<u8>>,
+    kid: Option<String>,
+    #[serde(rename = "x5t#S256")]
+    #[serde_as(as = "Option<Base64<UrlSafe, Unpadded>>")]
+    x5t_s256: Option<Vec<u8>>,
+}
+
+impl Jwk {
+    fn new(kty: &str) -> Self {
+        Self {
+            kty: kty.to_string(),
+            alg: None,
+            crv: None,
+            n: None,
+            e: None,
+            x: None,
+            y: None,
+            public: None,
+            kid: None,
+            x5t_s256: None,
+        }
+    }
+
+    /// Return a new instance from a DER encoded SubjectPublicKeyInfo.
+    pub fn from_subject_public_key_info(spki: &[u8]) -> Result<Self, JoseError> {
+        let spki = rasn::der::decode::<rasn_pkix::SubjectPublicKeyInfo>(spki).map_err(|e| {
+            JoseErrorKind::DecodingError
+                .error_with_msg(&format!("Failed to decode SubjectPublicKeyInfo: {e:?}"))
+        })?;
+        let key = spki.subject_public_key.as_raw_slice();
+        let algorithm_oid = spki.algorithm.algorithm.to_vec();
+        let algorithm = WellKnownAlgorithm::from_oid(&algorithm_oid).ok_or_else(|| {
+            JoseErrorKind::UnsupportedKeyType.error_with_msg(&format!(
+                "Unknown key algorithm '{}'.",
+                crate::encdec::oid::as_string(&algorithm_oid)
+            ))
+        })?;
+        let ret = match algorithm {
+            WellKnownAlgorithm::RsaEncryption => {
+                let rsa_public_key = rasn::der::decode::<RsaPublicKey>(key).map_err(|e| {
+                    JoseErrorKind::DecodingError
+                        .error_with_msg(&format!("Failed to decode RSAPublicKey: {e:?}"))
+                })?;
+                Self {
+                    n: Some(unsigned_bytes(&rsa_public_key.modulus)),
+                    e: Some(unsigned_bytes(&rsa_public_key.public_exponent)),
+                    ..Self::new("RSA")
+                }
+            }
+            WellKnownAlgorithm::EcPublicKey => {
+                let curve_oid = spki
+                    .algorithm
+                    .parameters
+                    .as_ref()
+                    .and_then(|parameters| {
+                        rasn::der::decode::<ObjectIdentifier>(parameters.as_bytes()).ok()
+                    })
+                    .ok_or_else(|| {
+                        JoseErrorKind::DecodingError.error_with_msg("Missing named curve.")
+                    })?;
+                let (_, crv, size) = EC_CURVES
+                    .iter()
+                    .find(|(oid, _, _)| curve_oid.to_vec().as_slice() == *oid)
+                    .ok_or_else(|| {
+                        JoseErrorKind::UnsupportedKeyType.error_with_msg(&format!(
+                            "Unsupported curve '{}'.",
+                            crate::encdec::oid::as_string(&curve_oid)
+                        ))
+                    })?;
+                // Only the uncompressed point form is allowed in JWK
+                if key.len() != 1 + 2 * size || key[0] != 0x04 {
+                    return Err(JoseErrorKind::UnsupportedKeyType
+                        .error_with_msg("Only uncompressed EC points are supported."));
+                }
+                Self {
+                    crv: Some(crv.to_string()),
+                    x: Some(key[1..1 + size].to_vec()),
+                    y: Some(key[1 + size..].to_vec()),
+                    ..Self::new("EC")
+                }
+            }
+            WellKnownAlgorithm::Ed25519
+            | WellKnownAlgorithm::Ed448
+            | WellKnownAlgorithm::X25519
+            | WellKnownAlgorithm::X448 => {
+                let (_, crv) = OKP_CURVES
+                    .iter()
+                    .find(|(oid, _)| algorithm_oid.as_slice() == *oid)
+                    .ok_or_else(|| {
+                        JoseErrorKind::UnsupportedKeyType.error_with_msg(&format!(
+                            "Unsupported OKP key algorithm '{}'.",
+                            algorithm.as_name()
+                        ))
+                    })?;
+                Self {
+                    crv: Some(crv.to_string()),
+                    x: Some(key.to_vec()),
+                    ..Self::new("OKP")
+                }
+            }
+            WellKnownAlgorithm::MlDsa44
+            | WellKnownAlgorithm::MlDsa65
+            | WellKnownAlgorithm::MlDsa87 => Self {
+                alg: Some(algorithm.as_name().to_string()),
+                public: Some(key.to_vec()),
+                ..Self::new("AKP")
+            },
+            _ => {
+                return Err(JoseErrorKind::UnsupportedKeyType.error_with_msg(&format!(
+                    "Key algorithm '{}' has no JWK representation.",
+                    algorithm.as_name()
+                )));
+            }
+        };
+        Ok(ret)
+    }
+
+    /** Return a new instance from the certificate's public key.
+
+    The `x5t#S256` certificate thumbprint is included and the `kid` is set to
+    the [RFC7638 JWK thumbprint](Self::get_thumbprint_sha256).
+    */
+    pub fn from_certificate(certificate: &CertificateParser) -> Result<Self, JoseError> {
+        let mut ret =
+            Self::from_subject_public_key_info(&certificate.get_encoded_subject_public_key_info())?;
+        ret.kid = Some(ret.get_thumbprint_sha256());
+        ret.x5t_s256 = Some(sha2::Sha256::digest(certificate.get_encoded_certificate()).to_vec());
+        Ok(ret)
+    }
+
+    /// Return the key type (`kty`).
+    pub fn get_key_type(&self) -> &str {
+        &self.kty
+    }
+
+    /// Return the key identifier (`kid`) if present.
+    pub fn get_key_id(&self) -> Option<&str> {
+        self.kid.as_deref()
+    }
+
+    /// Return the base64url encoded SHA-256 certificate thumbprint
+    /// (`x5t#S256`) if present.
+    pub fn get_x5t_s256(&self) -> Option<String> {
+        self.x5t_s256.as_deref().map(base64url)
+    }
+
+    /** Return the base64url encoded SHA-256
+    [RFC7638](https://www.rfc-editor.org/rfc/rfc7638) JWK thumbprint.
+
+    This is the hash of the required members of the key type in lexicographic
+    order without whitespace.
+    */
+    pub fn get_thumbprint_sha256(&self) -> String {
+        let required: &[&str] = match self.kty.as_str() {
+            "RSA" => &["e", "kty", "n"],
+            "EC" => &["crv", "kty", "x", "y"],
+            "OKP" => &["crv", "kty", "x"],
+            _ => &["alg", "kty", "pub"],
+        };
+        let members = self
+            .to_json_members()
+            .into_iter()
+            .filter(|(name, _)| required.contains(&name.as_str()))
+            .collect::<BTreeMap<_, _>>();
+        let canonical = serde_json::to_string(&members).unwrap();
+        base64url(&sha2::Sha256::digest(canonical.as_bytes()))
+    }
+
+    /// Return the JSON representation.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    fn to_json_members(&self) -> BTreeMap<String, serde_json::Value> {
+        match serde_json::to_value(self).unwrap() {
+            serde_json::Value::Object(map) => map.into_iter().collect(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Return the big-endian bytes of a positive integer without leading zeros.
+fn unsigned_bytes(value: &Integer) -> Vec<u8> {
+    let bytes = crate::encdec::rasn::integer_as_bytes_be(value);
+    let leading_zeros = bytes
+        .iter()
+        .take(bytes.len() - 1)
+        .take_while(|b| **b == 0)
+        .count();
+    bytes[leading_zeros..].to_vec()
+}
+
+fn base64url(data: &[u8]) -> String {
+    serde_json::to_value(Base64Url(data.to_vec()))
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap()
+}
+
+#[serde_as]
+#[derive(Serialize)]
+struct Base64Url(#[serde_as(as = "Base64<UrlSafe, Unpadded>")] Vec<u8>);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn encoded_spki(algorithm_oid: &[u32], key: &[u8]) -> Vec<u8> {
+        rasn::der::encode(&rasn_pkix::SubjectPublicKeyInfo {
+            algorithm: rasn_pkix::AlgorithmIdentifier {
+                algorithm: ObjectIdentifier::new(algorithm_oid.to_vec()).unwrap(),
+                parameters: None,
+            },
+            subject_public_key: BitString::from_slice(key),
+        })
+        .unwrap()
+    }
+
+    fn json(jwk: &Jwk) -> serde_json::Value {
+        serde_json::from_str(&jwk.to_json()).unwrap()
+    }
+
+    #[test]
+    fn ec_public_key() {
+        let certificate = load_test_certificate("ec_ca.b64");
+        let jwk = Jwk::from_certificate(&certificate).unwrap();
+        assert_eq!(jwk.get_key_type(), "EC");
+        assert_eq!(
+            json(&jwk),
+            serde_json::json!({
+                "kty": "EC",
+                "crv": "P-256",
+                "x": "F8dzjRDbMQ8bw5X4jEkPcLzsQ-79WFjD_Z0kmMGcb0M",
+                "y": "r2WB76iXkofyvq_XRZRlNztRiMDKmpQM0A1kbc-HrUs",
+                "kid": "i-EAWoepYOB5i3kKpu2Tp7lhdGOmqMw2cry7islVKrU",
+                "x5t#S256": "7Cqajg1Mh8eekSS7n7oYfmnyoSIOOGfleoFKapD1PUM",
+            })
+        );
+        assert_eq!(jwk.get_key_id(), Some(jwk.get_thumbprint_sha256().as_str()));
+        assert_eq!(
+            jwk.get_x5t_s256().as_deref(),
+            Some("7Cqajg1Mh8eekSS7n7oYfmnyoSIOOGfleoFKapD1PUM")
+        );
+        // Without the certificate there is no kid or x5t#S256
+        let jwk =
+            Jwk::from_subject_public_key_info(&certificate.get_encoded_subject_public_key_info())
+                .unwrap();
+        assert_eq!(jwk.get_key_id(), None);
+        assert_eq!(jwk.get_x5t_s256(), None);
+    }
+
+    #[test]
+    fn okp_public_key() {
+        // RFC8037 A.2 and A.3 example
+        let x =
+            tyst::encdec::base64::decode("11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo=").unwrap();
+        let jwk = Jwk::from_subject_public_key_info(&encoded_spki(&[1, 3, 101, 112], &x)).unwrap();
+        assert_eq!(
+            json(&jwk),
+            serde_json::json!({
+                "kty": "OKP",
+                "crv": "Ed25519",
+                "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo",
+            })
+        );
+        assert_eq!(
+            jwk.get_thumbprint_sha256(),
+            "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k"
+        );
+        for (oid, crv, len) in [
+            (&[1, 3, 101, 113], "Ed448", 57),
+            (&[1, 3, 101, 110], "X25519", 32),
+            (&[1, 3, 101, 111], "X448", 56),
+        ] {
+            let jwk =
+                Jwk::from_subject_public_key_info(&encoded_spki(oid, &vec![7u8; len])).unwrap();
+            assert_eq!(jwk.get_key_type(), "OKP");
+            assert_eq!(json(&jwk)["crv"], crv);
+        }
+    }
+
+    #[test]
+    fn akp_public_key() {
+        let public = vec![7u8; 1312];
+        let jwk = Jwk::from_subject_public_key_info(&encoded_spki(
+            WellKnownAlgorithm::MlDsa44.as_oid(),
+            &public,
+        ))
+        .unwrap();
+        assert_eq!(jwk.get_key_type(), "AKP");
+        assert_eq!(
+            json(&jwk),
+            serde_json::json!({
+                "kty": "AKP",
+                "alg": "ML-DSA-44",
+                "pub": base64url(&public),
+            })
+        );
+        // The thumbprint covers alg, kty and pub
+        let canonical = format!(
+            r#"{{"alg":"ML-DSA-44","kty":"AKP","pub":"{}"}}"#,
+            base64url(&public)
+        );
+        assert_eq!(
+            jwk.get_thumbprint_sha256(),
+            base64url(&sha2::Sha256::digest(canonical.as_bytes()))
+        );
+    }
+
+    #[test]
+    fn unsupported_public_keys() {
+        // Compressed P-256 point
+        let mut spki = rasn::der::decode::<rasn_pkix::SubjectPublicKeyInfo>(
+            &load_test_certificate("ec_ca.b64").get_encoded_subject_public_key_info(),
+        )
+        .unwrap();
+        let mut compressed = spki.subject_public_key.as_raw_slice()[..33].to_vec();
+        compressed[0] = 0x02;
+        spki.subject_public_key = BitString::from_vec(compressed);
+        let e = Jwk::from_subject_public_key_info(&rasn::der::encode(&spki).unwrap()).unwrap_err();
+        assert_eq!(e.kind(), &JoseErrorKind::UnsupportedKeyType);
+        // Unknown key algorithm
+        let e = Jwk::from_subject_public_key_info(&encoded_spki(&[1, 2, 3, 4], &[7u8; 32]))
+            .unwrap_err();
+        assert_eq!(e.kind(), &JoseErrorKind::UnsupportedKeyType);
+        let e = Jwk::from_subject_public_key_info(&[0x30, 0x00]).unwrap_err();
+        assert_eq!(e.kind(), &JoseErrorKind::DecodingError);
+    }
+
+    #[test]
+    fn rfc7638_thumbprint() {
+        // RFC7638 3.1 example
+        let jwk = serde_json::from_str::<Jwk>(
+            r#"{
+                "kty": "RSA",
+                "n": "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw",
+                "e": "AQAB",
+                "alg": "RS256",
+                "kid": "2011-04-29"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            jwk.get_thumbprint_sha256(),
+            "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs"
+        );
+    }
+}
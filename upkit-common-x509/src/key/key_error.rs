@@ -0,0 +1,87 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Private key errors.
+
+use std::error::Error;
+use std::fmt;
+
+/// Cause of private key error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyErrorKind {
+    /// Failure to decode the PEM or DER encoded key.
+    DecodingError,
+    /// The key or encryption uses an algorithm that is not supported.
+    UnsupportedAlgorithm,
+    /// Failure to decrypt the key. Usually due to a wrong password.
+    DecryptionFailure,
+    /// Failure to encrypt the key.
+    EncryptionFailure,
+}
+
+impl KeyErrorKind {
+    /// Create a new instance with an error message.
+    pub fn error_with_msg(self, msg: &str) -> KeyError {
+        KeyError {
+            kind: self,
+            msg: Some(msg.to_string()),
+        }
+    }
+
+    /// Create a new instance without an error message.
+    pub fn error(self) -> KeyError {
+        KeyError {
+            kind: self,
+            msg: None,
+        }
+    }
+}
+
+impl fmt::Display for KeyErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/** Private key error.
+
+Create a new instance via [KeyErrorKind].
+*/
+#[derive(Debug)]
+pub struct KeyError {
+    kind: KeyErrorKind,
+    msg: Option<String>,
+}
+
+impl KeyError {
+    /// Return the [KeyErrorKind] type of this error.
+    pub fn kind(&self) -> &KeyErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(msg) = &self.msg {
+            write!(f, "{} {}", self.kind, msg)
+        } else {
+            write!(f, "{}", self.kind)
+        }
+    }
+}
+
+impl Error for KeyError {}
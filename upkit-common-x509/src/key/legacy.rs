@@ -0,0 +1,108 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Legacy private key formats used by e.g. OpenSSL before PKCS #8 became the
+//! default.
+
+use super::KeyError;
+use super::KeyErrorKind;
+use super::OneAsymmetricKey;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+
+/*
+ECPrivateKey ::= SEQUENCE {
+  version        INTEGER { ecPrivkeyVer1(1) } (ecPrivkeyVer1),
+  privateKey     OCTET STRING,
+  parameters [0] ECParameters {{ NamedCurve }} OPTIONAL,
+  publicKey  [1] BIT STRING OPTIONAL
+}
+*/
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct EcPrivateKey {
+    version: Integer,
+    private_key: OctetString,
+    #[rasn(tag(explicit(context, 0)))]
+    parameters: Option<ObjectIdentifier>,
+    #[rasn(tag(explicit(context, 1)))]
+    public_key: Option<BitString>,
+}
+
+/// `rsaEncryption`
+const OID_RSA_ENCRYPTION: &[u32] = &[1, 2, 840, 113549, 1, 1, 1];
+/// `id-ecPublicKey`
+const OID_EC_PUBLIC_KEY: &[u32] = &[1, 2, 840, 10045, 2, 1];
+
+/// Wrap a [RFC8017 A.1.2](https://www.rfc-editor.org/rfc/rfc8017#appendix-A.1.2)
+/// `RSAPrivateKey` as PKCS #8.
+pub(crate) fn from_pkcs1_der(der: &[u8]) -> Result<OneAsymmetricKey, KeyError> {
+    // Sanity check that this is at least a SEQUENCE
+    rasn::der::decode::<Any>(der)
+        .map_err(|e| KeyErrorKind::DecodingError.error_with_msg(&e.to_string()))?;
+    Ok(OneAsymmetricKey {
+        version: Integer::from(0),
+        private_key_algorithm: AlgorithmIdentifier {
+            algorithm: ObjectIdentifier::new_unchecked(OID_RSA_ENCRYPTION.into()),
+            parameters: Some(Any::new(rasn::der::encode(&()).unwrap())),
+        },
+        private_key: OctetString::from(der.to_vec()),
+        attributes: None,
+        public_key: None,
+    })
+}
+
+/** Wrap a [RFC5915 3](https://www.rfc-editor.org/rfc/rfc5915#section-3)
+`ECPrivateKey` as PKCS #8.
+
+The named curve moves to the `AlgorithmIdentifier` parameters and is removed
+from the inner structure as recommended by RFC5915.
+*/
+pub(crate) fn from_sec1_der(der: &[u8]) -> Result<OneAsymmetricKey, KeyError> {
+    let mut ec_private_key = rasn::der::decode::<EcPrivateKey>(der)
+        .map_err(|e| KeyErrorKind::DecodingError.error_with_msg(&e.to_string()))?;
+    let named_curve = ec_private_key.parameters.take().ok_or_else(|| {
+        KeyErrorKind::UnsupportedAlgorithm
+            .error_with_msg("EC private key without a named curve is not supported.")
+    })?;
+    Ok(OneAsymmetricKey {
+        version: Integer::from(0),
+        private_key_algorithm: AlgorithmIdentifier {
+            algorithm: ObjectIdentifier::new_unchecked(OID_EC_PUBLIC_KEY.into()),
+            parameters: Some(Any::new(rasn::der::encode(&named_curve).unwrap())),
+        },
+        private_key: OctetString::from(rasn::der::encode(&ec_private_key).unwrap()),
+        attributes: None,
+        public_key: None,
+    })
+}
+
+/// Return the DER encoded `RSAPublicKey` of a DER encoded PKCS #1
+/// `RSAPrivateKey`.
+pub(crate) fn rsa_public_key_from_pkcs1_der(der: &[u8]) -> Option<Vec<u8>> {
+    // RSAPrivateKey ::= SEQUENCE { version, modulus, publicExponent, ... }
+    let fields = rasn::der::decode::<SequenceOf<Any>>(der).ok()?;
+    let public_key = fields.get(1..3)?.to_vec();
+    rasn::der::encode(&public_key).ok()
+}
+
+/// Return the public key of a DER encoded SEC1 `ECPrivateKey` if present.
+pub(crate) fn ec_public_key_from_sec1_der(der: &[u8]) -> Option<Vec<u8>> {
+    rasn::der::decode::<EcPrivateKey>(der)
+        .ok()?
+        .public_key
+        .map(|public_key| public_key.as_raw_slice().to_vec())
+}
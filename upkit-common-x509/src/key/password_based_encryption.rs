@@ -0,0 +1,725 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! [RFC8018 6.2](https://www.rfc-editor.org/rfc/rfc8018#section-6.2) PBES2
+//! password based encryption.
+
+use super::KeyError;
+use super::KeyErrorKind;
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::BlockCipher;
+use aes::cipher::BlockDecryptMut;
+use aes::cipher::BlockEncryptMut;
+use aes::cipher::KeyInit;
+use aes::cipher::KeyIvInit;
+use aes_gcm::aead::Aead;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+use serde_with::skip_serializing_none;
+use tyst::Tyst;
+
+/*
+PBES2-params ::= SEQUENCE {
+   keyDerivationFunc AlgorithmIdentifier {{PBES2-KDFs}},
+   encryptionScheme AlgorithmIdentifier {{PBES2-Encs}} }
+
+PBKDF2-params ::= SEQUENCE {
+    salt CHOICE {
+        specified OCTET STRING,
+        otherSource AlgorithmIdentifier {{PBKDF2-SaltSources}}
+    },
+    iterationCount INTEGER (1..MAX),
+    keyLength INTEGER (1..MAX) OPTIONAL,
+    prf AlgorithmIdentifier {{PBKDF2-PRFs}} DEFAULT algid-hmacWithSHA1 }
+
+scrypt-params ::= SEQUENCE {
+    salt OCTET STRING,
+    costParameter INTEGER (1..MAX),
+    blockSize INTEGER (1..MAX),
+    parallelizationParameter INTEGER (1..MAX),
+    keyLength INTEGER (1..MAX) OPTIONAL }
+
+GCMParameters ::= SEQUENCE {
+    aes-nonce        OCTET STRING, -- recommended size is 12 octets
+    aes-ICVlen       AES-GCM-ICVlen DEFAULT 12 }
+*/
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct Pbes2Params {
+    key_derivation_func: AlgorithmIdentifier,
+    encryption_scheme: AlgorithmIdentifier,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct Pbkdf2Params {
+    salt: OctetString,
+    iteration_count: Integer,
+    key_length: Option<Integer>,
+    /// `None` means `hmacWithSHA1`
+    prf: Option<AlgorithmIdentifier>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct ScryptParams {
+    salt: OctetString,
+    cost_parameter: Integer,
+    block_size: Integer,
+    parallelization_parameter: Integer,
+    key_length: Option<Integer>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct GcmParameters {
+    pub(crate) aes_nonce: OctetString,
+    /// `None` means 12
+    pub(crate) aes_icv_len: Option<Integer>,
+}
+
+/// Pseudo random function used by PBKDF2.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PbePrf {
+    /// `hmacWithSHA1` (only for decryption of legacy keys)
+    HmacWithSha1,
+    /// `hmacWithSHA256`
+    HmacWithSha256,
+    /// `hmacWithSHA384`
+    HmacWithSha384,
+    /// `hmacWithSHA512`
+    HmacWithSha512,
+}
+
+impl PbePrf {
+    /// Return the algorithm OID
+    pub fn as_oid(&self) -> &'static [u32] {
+        match self {
+            Self::HmacWithSha1 => &[1, 2, 840, 113549, 2, 7],
+            Self::HmacWithSha256 => &[1, 2, 840, 113549, 2, 9],
+            Self::HmacWithSha384 => &[1, 2, 840, 113549, 2, 10],
+            Self::HmacWithSha512 => &[1, 2, 840, 113549, 2, 11],
+        }
+    }
+
+    fn from_oid(oid: &[u32]) -> Option<Self> {
+        [
+            Self::HmacWithSha1,
+            Self::HmacWithSha256,
+            Self::HmacWithSha384,
+            Self::HmacWithSha512,
+        ]
+        .into_iter()
+        .find(|prf| prf.as_oid().eq(oid))
+    }
+}
+
+/// Key derivation function of PBES2.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PbeKdf {
+    /// [RFC8018 5.2](https://www.rfc-editor.org/rfc/rfc8018#section-5.2) PBKDF2.
+    Pbkdf2 {
+        /// Iteration count
+        iterations: u32,
+        /// Pseudo random function
+        prf: PbePrf,
+    },
+    /// [RFC7914](https://www.rfc-editor.org/rfc/rfc7914) scrypt.
+    Scrypt {
+        /// Base 2 logarithm of the CPU/memory cost parameter `N`.
+        log_n: u8,
+        /// Block size `r`.
+        block_size: u32,
+        /// Parallelization parameter `p`.
+        parallelization: u32,
+    },
+}
+
+impl PbeKdf {
+    const OID_PBKDF2: &'static [u32] = &[1, 2, 840, 113549, 1, 5, 12];
+    const OID_SCRYPT: &'static [u32] = &[1, 3, 6, 1, 4, 1, 11591, 4, 11];
+}
+
+/// Content encryption of PBES2.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PbeCipher {
+    /// `aes128-CBC-PAD`
+    Aes128Cbc,
+    /// `aes192-CBC-PAD`
+    Aes192Cbc,
+    /// `aes256-CBC-PAD`
+    Aes256Cbc,
+    /// `id-aes128-GCM`
+    Aes128Gcm,
+    /// `id-aes256-GCM`
+    Aes256Gcm,
+}
+
+impl PbeCipher {
+    /// Return the algorithm OID
+    pub fn as_oid(&self) -> &'static [u32] {
+        match self {
+            Self::Aes128Cbc => &[2, 16, 840, 1, 101, 3, 4, 1, 2],
+            Self::Aes192Cbc => &[2, 16, 840, 1, 101, 3, 4, 1, 22],
+            Self::Aes256Cbc => &[2, 16, 840, 1, 101, 3, 4, 1, 42],
+            Self::Aes128Gcm => &[2, 16, 840, 1, 101, 3, 4, 1, 6],
+            Self::Aes256Gcm => &[2, 16, 840, 1, 101, 3, 4, 1, 46],
+        }
+    }
+
+    pub(crate) fn from_oid(oid: &[u32]) -> Option<Self> {
+        [
+            Self::Aes128Cbc,
+            Self::Aes192Cbc,
+            Self::Aes256Cbc,
+            Self::Aes128Gcm,
+            Self::Aes256Gcm,
+        ]
+        .into_iter()
+        .find(|cipher| cipher.as_oid().eq(oid))
+    }
+
+    /// Return the key length in bytes.
+    pub fn key_length(&self) -> usize {
+        match self {
+            Self::Aes128Cbc | Self::Aes128Gcm => 16,
+            Self::Aes192Cbc => 24,
+            Self::Aes256Cbc | Self::Aes256Gcm => 32,
+        }
+    }
+
+    fn is_gcm(&self) -> bool {
+        matches!(self, Self::Aes128Gcm | Self::Aes256Gcm)
+    }
+}
+
+/** PBES2 password based encryption parameters.
+
+The default is PBKDF2 with HMAC-SHA256 (600 000 iterations as recommended by
+OWASP in 2023) and AES-256-CBC, which is the most widely supported
+combination.
+*/
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct PbeParameters {
+    kdf: PbeKdf,
+    cipher: PbeCipher,
+}
+
+impl Default for PbeParameters {
+    fn default() -> Self {
+        Self::new(
+            PbeKdf::Pbkdf2 {
+                iterations: 600_000,
+                prf: PbePrf::HmacWithSha256,
+            },
+            PbeCipher::Aes256Cbc,
+        )
+    }
+}
+
+impl PbeParameters {
+    /// `id-PBES2`
+    pub const OID_PBES2: &'static [u32] = &[1, 2, 840, 113549, 1, 5, 13];
+
+    /// Maximum accepted PBKDF2 iteration count.
+    pub const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+
+    /// Maximum accepted scrypt memory usage (`128 * r * N` bytes).
+    pub const MAX_SCRYPT_MEMORY: u64 = 1 << 30;
+
+    /// Maximum accepted scrypt parallelization parameter `p`.
+    pub const MAX_SCRYPT_PARALLELIZATION: u32 = 16;
+
+    /// Return a new instance.
+    pub fn new(kdf: PbeKdf, cipher: PbeCipher) -> Self {
+        Self { kdf, cipher }
+    }
+
+    /// Encrypt the `plaintext` and return the PBES2 `AlgorithmIdentifier` and
+    /// ciphertext.
+    pub(crate) fn encrypt(
+        &self,
+        password: &[u8],
+        plaintext: &[u8],
+    ) -> Result<(AlgorithmIdentifier, Vec<u8>), KeyError> {
+        let salt = Self::random(16);
+        let key_length = self.cipher.key_length();
+        let key = Self::derive_key(&self.kdf, password, &salt, key_length)?;
+        let key_derivation_func = match &self.kdf {
+            PbeKdf::Pbkdf2 { iterations, prf } => Self::algorithm_identifier(
+                PbeKdf::OID_PBKDF2,
+                &Pbkdf2Params {
+                    salt: OctetString::from(salt),
+                    iteration_count: Integer::from(*iterations),
+                    key_length: Some(Integer::from(key_length)),
+                    // DER: DEFAULT values must be absent
+                    prf: (*prf != PbePrf::HmacWithSha1).then(|| AlgorithmIdentifier {
+                        algorithm: ObjectIdentifier::new_unchecked(prf.as_oid().into()),
+                        parameters: Some(Any::new(rasn::der::encode(&()).unwrap())),
+                    }),
+                },
+            ),
+            PbeKdf::Scrypt {
+                log_n,
+                block_size,
+                parallelization,
+            } => Self::algorithm_identifier(
+                PbeKdf::OID_SCRYPT,
+                &ScryptParams {
+                    salt: OctetString::from(salt),
+                    cost_parameter: Integer::from(1u64 << log_n),
+                    block_size: Integer::from(*block_size),
+                    parallelization_parameter: Integer::from(*parallelization),
+                    key_length: Some(Integer::from(key_length)),
+                },
+            ),
+        };
+        let (encryption_scheme, ciphertext) = if self.cipher.is_gcm() {
+            let nonce = Self::random(12);
+            let ciphertext = match self.cipher {
+                PbeCipher::Aes128Gcm => {
+                    Self::gcm_encrypt::<aes_gcm::Aes128Gcm>(&key, &nonce, plaintext)
+                }
+                _ => Self::gcm_encrypt::<aes_gcm::Aes256Gcm>(&key, &nonce, plaintext),
+            }?;
+            let parameters = GcmParameters {
+                aes_nonce: OctetString::from(nonce),
+                aes_icv_len: Some(Integer::from(16)),
+            };
+            (
+                Self::algorithm_identifier(self.cipher.as_oid(), &parameters),
+                ciphertext,
+            )
+        } else {
+            let iv = Self::random(16);
+            let ciphertext = match self.cipher {
+                PbeCipher::Aes128Cbc => Self::cbc_encrypt::<aes::Aes128>(&key, &iv, plaintext),
+                PbeCipher::Aes192Cbc => Self::cbc_encrypt::<aes::Aes192>(&key, &iv, plaintext),
+                _ => Self::cbc_encrypt::<aes::Aes256>(&key, &iv, plaintext),
+            }?;
+            (
+                Self::algorithm_identifier(self.cipher.as_oid(), &OctetString::from(iv)),
+                ciphertext,
+            )
+        };
+        let pbes2 = Self::algorithm_identifier(
+            Self::OID_PBES2,
+            &Pbes2Params {
+                key_derivation_func,
+                encryption_scheme,
+            },
+        );
+        Ok((pbes2, ciphertext))
+    }
+
+    /// Decrypt the `ciphertext` using the PBES2 `AlgorithmIdentifier`.
+    pub(crate) fn decrypt(
+        algorithm: &AlgorithmIdentifier,
+        password: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, KeyError> {
+        if !algorithm.algorithm.to_vec().eq(Self::OID_PBES2) {
+            let msg = format!(
+                "Only PBES2 is supported, not '{}'.",
+                crate::encdec::oid::as_string(&algorithm.algorithm)
+            );
+            return Err(KeyErrorKind::UnsupportedAlgorithm.error_with_msg(&msg));
+        }
+        let pbes2 = Self::decode_parameters::<Pbes2Params>(algorithm)?;
+        let cipher_oid = pbes2.encryption_scheme.algorithm.to_vec();
+        let cipher = PbeCipher::from_oid(&cipher_oid).ok_or_else(|| {
+            let msg = format!(
+                "Unsupported PBES2 encryption scheme '{}'.",
+                crate::encdec::oid::as_string(&cipher_oid)
+            );
+            KeyErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+        })?;
+        let kdf_oid = pbes2.key_derivation_func.algorithm.to_vec();
+        let (kdf, salt, key_length) = if kdf_oid.eq(PbeKdf::OID_PBKDF2) {
+            let params = Self::decode_parameters::<Pbkdf2Params>(&pbes2.key_derivation_func)?;
+            let prf = match &params.prf {
+                None => PbePrf::HmacWithSha1,
+                Some(prf) => PbePrf::from_oid(&prf.algorithm).ok_or_else(|| {
+                    let msg = format!(
+                        "Unsupported PBKDF2 PRF '{}'.",
+                        crate::encdec::oid::as_string(&prf.algorithm)
+                    );
+                    KeyErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+                })?,
+            };
+            let kdf = PbeKdf::Pbkdf2 {
+                iterations: Self::decode_positive_u32(
+                    &params.iteration_count,
+                    "PBKDF2 iteration count",
+                )?,
+                prf,
+            };
+            (kdf, params.salt.to_vec(), params.key_length)
+        } else if kdf_oid.eq(PbeKdf::OID_SCRYPT) {
+            let params = Self::decode_parameters::<ScryptParams>(&pbes2.key_derivation_func)?;
+            let cost = Self::decode_positive_u32(&params.cost_parameter, "scrypt cost parameter")?;
+            if !cost.is_power_of_two() {
+                return Err(KeyErrorKind::DecodingError
+                    .error_with_msg("scrypt cost parameter must be a power of 2."));
+            }
+            let kdf = PbeKdf::Scrypt {
+                log_n: cost.trailing_zeros() as u8,
+                block_size: Self::decode_positive_u32(&params.block_size, "scrypt block size")?,
+                parallelization: Self::decode_positive_u32(
+                    &params.parallelization_parameter,
+                    "scrypt parallelization parameter",
+                )?,
+            };
+            (kdf, params.salt.to_vec(), params.key_length)
+        } else {
+            let msg = format!(
+                "Unsupported PBES2 key derivation function '{}'.",
+                crate::encdec::oid::as_string(&kdf_oid)
+            );
+            return Err(KeyErrorKind::UnsupportedAlgorithm.error_with_msg(&msg));
+        };
+        if let Some(key_length) = key_length {
+            if crate::encdec::rasn::integer_try_as_u64(&key_length)
+                != u64::try_from(cipher.key_length()).ok()
+            {
+                return Err(KeyErrorKind::DecodingError
+                    .error_with_msg("PBES2 key length does not match the encryption scheme."));
+            }
+        }
+        let key = Self::derive_key(&kdf, password, &salt, cipher.key_length())?;
+        if cipher.is_gcm() {
+            let params = Self::decode_parameters::<GcmParameters>(&pbes2.encryption_scheme)?;
+            let icv_len = params
+                .aes_icv_len
+                .as_ref()
+                .map_or(Some(12), crate::encdec::rasn::integer_try_as_u64);
+            if params.aes_nonce.len() != 12 || icv_len != Some(16) {
+                return Err(KeyErrorKind::UnsupportedAlgorithm.error_with_msg(
+                    "Only AES-GCM with 12 byte nonce and 16 byte tag is supported.",
+                ));
+            }
+            match cipher {
+                PbeCipher::Aes128Gcm => {
+                    Self::gcm_decrypt::<aes_gcm::Aes128Gcm>(&key, &params.aes_nonce, ciphertext)
+                }
+                _ => Self::gcm_decrypt::<aes_gcm::Aes256Gcm>(&key, &params.aes_nonce, ciphertext),
+            }
+        } else {
+            let iv = Self::decode_parameters::<OctetString>(&pbes2.encryption_scheme)?;
+            match cipher {
+                PbeCipher::Aes128Cbc => Self::cbc_decrypt::<aes::Aes128>(&key, &iv, ciphertext),
+                PbeCipher::Aes192Cbc => Self::cbc_decrypt::<aes::Aes192>(&key, &iv, ciphertext),
+                _ => Self::cbc_decrypt::<aes::Aes256>(&key, &iv, ciphertext),
+            }
+        }
+    }
+
+    fn derive_key(
+        kdf: &PbeKdf,
+        password: &[u8],
+        salt: &[u8],
+        key_length: usize,
+    ) -> Result<Vec<u8>, KeyError> {
+        Self::check_kdf_limits(kdf)?;
+        let mut key = vec![0u8; key_length];
+        match kdf {
+            PbeKdf::Pbkdf2 { iterations, prf } => match prf {
+                PbePrf::HmacWithSha1 => {
+                    pbkdf2::pbkdf2_hmac::<sha1::Sha1>(password, salt, *iterations, &mut key)
+                }
+                PbePrf::HmacWithSha256 => {
+                    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password, salt, *iterations, &mut key)
+                }
+                PbePrf::HmacWithSha384 => {
+                    pbkdf2::pbkdf2_hmac::<sha2::Sha384>(password, salt, *iterations, &mut key)
+                }
+                PbePrf::HmacWithSha512 => {
+                    pbkdf2::pbkdf2_hmac::<sha2::Sha512>(password, salt, *iterations, &mut key)
+                }
+            },
+            PbeKdf::Scrypt {
+                log_n,
+                block_size,
+                parallelization,
+            } => {
+                let params = scrypt::Params::new(*log_n, *block_size, *parallelization, key_length)
+                    .map_err(|e| {
+                        KeyErrorKind::UnsupportedAlgorithm.error_with_msg(&e.to_string())
+                    })?;
+                scrypt::scrypt(password, salt, &params, &mut key).map_err(|e| {
+                    KeyErrorKind::UnsupportedAlgorithm.error_with_msg(&e.to_string())
+                })?;
+            }
+        }
+        Ok(key)
+    }
+
+    /// Reject parameters that would make key derivation exhaust CPU or
+    /// memory.
+    fn check_kdf_limits(kdf: &PbeKdf) -> Result<(), KeyError> {
+        let exceeded = match kdf {
+            PbeKdf::Pbkdf2 { iterations, .. } => *iterations > Self::MAX_PBKDF2_ITERATIONS,
+            PbeKdf::Scrypt {
+                log_n,
+                block_size,
+                parallelization,
+            } => {
+                let memory = 1u64
+                    .checked_shl(u32::from(*log_n))
+                    .and_then(|n| n.checked_mul(128 * u64::from(*block_size)))
+                    .unwrap_or(u64::MAX);
+                *parallelization > Self::MAX_SCRYPT_PARALLELIZATION
+                    || memory > Self::MAX_SCRYPT_MEMORY
+            }
+        };
+        if exceeded {
+            let msg = format!("Key derivation parameters {kdf:?} exceed the supported limits.");
+            return Err(KeyErrorKind::UnsupportedAlgorithm.error_with_msg(&msg));
+        }
+        Ok(())
+    }
+
+    /// Decode an `INTEGER (1..MAX)` parameter that must fit in an `u32`.
+    fn decode_positive_u32(value: &Integer, name: &str) -> Result<u32, KeyError> {
+        crate::encdec::rasn::integer_try_as_u64(value)
+            .filter(|value| *value > 0)
+            .and_then(|value| u32::try_from(value).ok())
+            .ok_or_else(|| {
+                let msg = format!("The {name} must be a positive integer below 2^32.");
+                KeyErrorKind::DecodingError.error_with_msg(&msg)
+            })
+    }
+
+    pub(crate) fn cbc_encrypt<C>(
+        key: &[u8],
+        iv: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, KeyError>
+    where
+        C: BlockEncryptMut + BlockCipher + KeyInit,
+    {
+        cbc::Encryptor::<C>::new_from_slices(key, iv)
+            .map(|encryptor| encryptor.encrypt_padded_vec_mut::<Pkcs7>(plaintext))
+            .map_err(|e| KeyErrorKind::EncryptionFailure.error_with_msg(&e.to_string()))
+    }
+
+    pub(crate) fn cbc_decrypt<C>(
+        key: &[u8],
+        iv: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, KeyError>
+    where
+        C: BlockDecryptMut + BlockCipher + KeyInit,
+    {
+        cbc::Decryptor::<C>::new_from_slices(key, iv)
+            .map_err(|e| KeyErrorKind::DecryptionFailure.error_with_msg(&e.to_string()))?
+            .decrypt_padded_vec_mut::<Pkcs7>(ciphertext)
+            .map_err(|_| KeyErrorKind::DecryptionFailure.error_with_msg("Invalid padding."))
+    }
+
+    pub(crate) fn gcm_encrypt<A: KeyInit + Aead>(
+        key: &[u8],
+        nonce: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, KeyError> {
+        A::new_from_slice(key)
+            .map_err(|e| KeyErrorKind::EncryptionFailure.error_with_msg(&e.to_string()))?
+            .encrypt(aes_gcm::aead::Nonce::<A>::from_slice(nonce), plaintext)
+            .map_err(|e| KeyErrorKind::EncryptionFailure.error_with_msg(&e.to_string()))
+    }
+
+    pub(crate) fn gcm_decrypt<A: KeyInit + Aead>(
+        key: &[u8],
+        nonce: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, KeyError> {
+        A::new_from_slice(key)
+            .map_err(|e| KeyErrorKind::DecryptionFailure.error_with_msg(&e.to_string()))?
+            .decrypt(aes_gcm::aead::Nonce::<A>::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                KeyErrorKind::DecryptionFailure.error_with_msg("Authentication tag mismatch.")
+            })
+    }
+
+    pub(crate) fn algorithm_identifier<T: rasn::Encode>(
+        oid: &[u32],
+        parameters: &T,
+    ) -> AlgorithmIdentifier {
+        AlgorithmIdentifier {
+            algorithm: ObjectIdentifier::new_unchecked(oid.to_vec().into()),
+            parameters: Some(Any::new(rasn::der::encode(parameters).unwrap())),
+        }
+    }
+
+    pub(crate) fn decode_parameters<T: rasn::Decode>(
+        algorithm: &AlgorithmIdentifier,
+    ) -> Result<T, KeyError> {
+        algorithm
+            .parameters
+            .as_ref()
+            .and_then(|parameters| rasn::der::decode::<T>(parameters.as_bytes()).ok())
+            .ok_or_else(|| {
+                let msg = format!(
+                    "Invalid parameters for algorithm '{}'.",
+                    crate::encdec::oid::as_string(&algorithm.algorithm)
+                );
+                KeyErrorKind::DecodingError.error_with_msg(&msg)
+            })
+    }
+
+    pub(crate) fn random(len: usize) -> Vec<u8> {
+        let mut ret = vec![0u8; len];
+        Tyst::instance().prng_fill_with_random(None, &mut ret);
+        ret
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pbkdf2_pbes2(iteration_count: Integer, key_length: Option<Integer>) -> AlgorithmIdentifier {
+        let cipher = PbeCipher::Aes128Cbc;
+        PbeParameters::algorithm_identifier(
+            PbeParameters::OID_PBES2,
+            &Pbes2Params {
+                key_derivation_func: PbeParameters::algorithm_identifier(
+                    PbeKdf::OID_PBKDF2,
+                    &Pbkdf2Params {
+                        salt: OctetString::from(vec![0u8; 16]),
+                        iteration_count,
+                        key_length,
+                        prf: None,
+                    },
+                ),
+                encryption_scheme: PbeParameters::algorithm_identifier(
+                    cipher.as_oid(),
+                    &OctetString::from(vec![0u8; 16]),
+                ),
+            },
+        )
+    }
+
+    fn scrypt_pbes2(
+        cost: Integer,
+        block_size: Integer,
+        parallelization: Integer,
+    ) -> AlgorithmIdentifier {
+        PbeParameters::algorithm_identifier(
+            PbeParameters::OID_PBES2,
+            &Pbes2Params {
+                key_derivation_func: PbeParameters::algorithm_identifier(
+                    PbeKdf::OID_SCRYPT,
+                    &ScryptParams {
+                        salt: OctetString::from(vec![0u8; 16]),
+                        cost_parameter: cost,
+                        block_size,
+                        parallelization_parameter: parallelization,
+                        key_length: None,
+                    },
+                ),
+                encryption_scheme: PbeParameters::algorithm_identifier(
+                    PbeCipher::Aes128Cbc.as_oid(),
+                    &OctetString::from(vec![0u8; 16]),
+                ),
+            },
+        )
+    }
+
+    fn decrypt_error_kind(algorithm: &AlgorithmIdentifier) -> KeyErrorKind {
+        PbeParameters::decrypt(algorithm, b"secret", &[0u8; 16])
+            .err()
+            .unwrap()
+            .kind()
+            .clone()
+    }
+
+    #[test]
+    fn untrusted_kdf_parameters_are_rejected() {
+        let u32_overflow = Integer::from(1u64 << 32);
+        for iteration_count in [Integer::from(-1), Integer::from(0), u32_overflow.clone()] {
+            assert_eq!(
+                decrypt_error_kind(&pbkdf2_pbes2(iteration_count, None)),
+                KeyErrorKind::DecodingError
+            );
+        }
+        assert_eq!(
+            decrypt_error_kind(&pbkdf2_pbes2(
+                Integer::from(PbeParameters::MAX_PBKDF2_ITERATIONS + 1),
+                None
+            )),
+            KeyErrorKind::UnsupportedAlgorithm
+        );
+        // Key length must match the cipher without truncation
+        for key_length in [Integer::from(-16), Integer::from((1u64 << 32) + 16)] {
+            assert_eq!(
+                decrypt_error_kind(&pbkdf2_pbes2(Integer::from(1), Some(key_length))),
+                KeyErrorKind::DecodingError
+            );
+        }
+        assert_eq!(
+            decrypt_error_kind(&scrypt_pbes2(
+                Integer::from(-1024),
+                Integer::from(8),
+                Integer::from(1)
+            )),
+            KeyErrorKind::DecodingError
+        );
+        assert_eq!(
+            decrypt_error_kind(&scrypt_pbes2(
+                Integer::from(1024),
+                u32_overflow,
+                Integer::from(1)
+            )),
+            KeyErrorKind::DecodingError
+        );
+        // 128 * 8 * 2^21 bytes = 2 GiB
+        assert_eq!(
+            decrypt_error_kind(&scrypt_pbes2(
+                Integer::from(1 << 21),
+                Integer::from(8),
+                Integer::from(1)
+            )),
+            KeyErrorKind::UnsupportedAlgorithm
+        );
+        assert_eq!(
+            decrypt_error_kind(&scrypt_pbes2(
+                Integer::from(1024),
+                Integer::from(8),
+                Integer::from(17)
+            )),
+            KeyErrorKind::UnsupportedAlgorithm
+        );
+        // Encryption is subject to the same limits
+        let pbe_parameters = PbeParameters::new(
+            PbeKdf::Pbkdf2 {
+                iterations: PbeParameters::MAX_PBKDF2_ITERATIONS + 1,
+                prf: PbePrf::HmacWithSha256,
+            },
+            PbeCipher::Aes256Cbc,
+        );
+        assert!(pbe_parameters.encrypt(b"secret", b"plaintext").is_err());
+    }
+}
@@ -20,8 +20,21 @@
 #![doc = include_str!("../README.md")]
 
 pub mod cert;
+pub mod cms;
+pub mod crl;
+pub mod csr;
+pub mod ct;
 pub mod encdec;
+pub mod jose;
+pub mod key;
+pub mod lint;
 mod named_enum;
+pub mod pkcs12;
+#[cfg(feature = "x509-cert")]
+pub mod rustcrypto;
+#[cfg(feature = "rustls")]
+pub mod tls;
+pub mod tsp;
 
 use tyst::{encdec::hex::ToHex, Tyst};
 
@@ -47,4 +60,95 @@ pub mod test_utils {
             //.filter(Some("rustls"), log::LevelFilter::Info)
             .try_init();
     }
+
+    /// Return the decoded content of the Base64 encoded file `name` in
+    /// `resources/test`.
+    pub fn load_test_resource_b64(name: &str) -> Vec<u8> {
+        let b64 = std::fs::read_to_string(format!(
+            "{}/resources/test/{name}",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .unwrap();
+        tyst::encdec::base64::decode(&b64).unwrap()
+    }
+
+    /// Return the DER encoded certificate of the Base64 encoded file `name`
+    /// in `resources/test`.
+    pub fn load_test_certificate_der(name: &str) -> Vec<u8> {
+        load_test_resource_b64(name)
+    }
+
+    /// Return a parsed certificate of the Base64 encoded file `name` in
+    /// `resources/test`.
+    pub fn load_test_certificate(name: &str) -> crate::cert::parse::CertificateParser {
+        crate::cert::parse::CertificateParser::from_bytes(&load_test_certificate_der(name)).unwrap()
+    }
+
+    /// Return the DER encoded certificate of the Base64 encoded file `name`
+    /// in `resources/test` where the value of the extension `oid` no longer
+    /// decodes.
+    ///
+    /// The first octet of the `extnValue` content is replaced with the tag of
+    /// a `NULL`, so the outer structure of the certificate is still intact.
+    pub fn load_test_certificate_der_with_malformed_extension(name: &str, oid: &[u32]) -> Vec<u8> {
+        let mut der = load_test_certificate_der(name);
+        let encoded_oid =
+            rasn::der::encode(&rasn::types::ObjectIdentifier::new(oid.to_vec()).unwrap()).unwrap();
+        let mut offset = der
+            .windows(encoded_oid.len())
+            .position(|window| window == encoded_oid)
+            .unwrap()
+            + encoded_oid.len();
+        // critical BOOLEAN
+        if der[offset] == 0x01 {
+            offset += 3;
+        }
+        // extnValue OCTET STRING
+        assert_eq!(der[offset], 0x04);
+        offset += match der[offset + 1] {
+            length if length & 0x80 == 0 => 2,
+            length => 2 + usize::from(length & 0x7f),
+        };
+        der[offset] = 0x05;
+        der
+    }
+
+    /// Return the unencrypted PEM encoded PKCS #8 private key of the file
+    /// `name` in `resources/test`.
+    pub fn load_test_private_key(name: &str) -> crate::key::Pkcs8PrivateKey {
+        let pem = std::fs::read_to_string(format!(
+            "{}/resources/test/{name}",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .unwrap();
+        crate::key::Pkcs8PrivateKey::from_pem(&pem, None).unwrap()
+    }
+
+    /// Sign `data` with the private key of the file `name` in
+    /// `resources/test`.
+    pub fn sign_with_test_key(
+        name: &str,
+        signature_algorithm: &crate::cert::types::WellKnownAlgorithm,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let private_key = load_test_private_key(name).to_private_key();
+        let oid = crate::encdec::oid::as_string(signature_algorithm.as_oid());
+        let mut se = tyst::Tyst::instance().ses().by_oid(&oid).unwrap();
+        se.sign(private_key.as_ref(), data)
+    }
+
+    /// Assert that `result` failed with an error of the same kind as `kind`.
+    pub fn assert_kind<T>(
+        result: Result<T, crate::cert::validate::CertificateValidationError>,
+        kind: crate::cert::validate::CertificateValidationErrorKind,
+    ) {
+        let Err(e) = result else {
+            panic!("Expected an error.");
+        };
+        assert_eq!(
+            std::mem::discriminant(e.kind()),
+            std::mem::discriminant(&kind),
+            "{e}"
+        );
+    }
 }
@@ -0,0 +1,107 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Certificate linting.
+//!
+//! Lints report structured [LintFinding]s instead of failing, so a CA can
+//! decide which findings to act on before issuing a certificate.
+//!
+//! The [Linter] runs [LintRule]s derived from RFC5280 and the CA/Browser
+//! Forum requirements. Use
+//! [NoSignatureTbsCertificate::lint](crate::cert::build::NoSignatureTbsCertificate::lint)
+//! to lint a certificate before it is signed.
+
+mod key_usage_consistency;
+mod lint_finding;
+mod lint_rule;
+mod lint_source;
+mod linter;
+mod rules;
+
+pub use self::key_usage_consistency::analyze_key_usage_consistency;
+pub use self::lint_finding::LintFinding;
+pub use self::lint_finding::LintSeverity;
+pub use self::lint_rule::LintRule;
+pub use self::lint_source::LintSource;
+pub use self::linter::Linter;
+pub use self::rules::EmptySubjectWithoutCriticalSanRule;
+pub use self::rules::EvMissingSubjectAttributesRule;
+pub use self::rules::ExtensionCriticalityRule;
+pub use self::rules::KeyUsageConsistencyRule;
+pub use self::rules::SerialNumberLowEntropyRule;
+pub use self::rules::SerialNumberNotPositiveRule;
+pub use self::rules::SerialNumberTooLongRule;
+pub use self::rules::SmimeValidityTooLongRule;
+pub use self::rules::SubjectAttributeEncodingRule;
+pub use self::rules::SubjectAttributeTooLongRule;
+pub use self::rules::TlsServerCommonNameNotInSanRule;
+pub use self::rules::TlsServerMissingAuthorityInformationAccessRule;
+pub use self::rules::TlsServerMissingRevocationInformationRule;
+pub use self::rules::TlsServerMissingSanRule;
+pub use self::rules::TlsServerSanDisallowedTypeRule;
+pub use self::rules::TlsServerValidityTooLongRule;
+pub use self::rules::ValidityPeriodInvertedRule;
+
+#[cfg(test)]
+mod test_utils {
+    //! Testing utilities for building certificates to lint.
+
+    /// Return a TBSCertificate issued by the test EC CA for the public key of
+    /// the test certificate `name`.
+    pub fn build_test_tbs_certificate(
+        name: &str,
+        subject: crate::cert::types::DistinguishedName,
+        not_after_epoch_seconds: u64,
+        extensions: crate::cert::extensions::Extensions,
+    ) -> crate::cert::build::NoSignatureTbsCertificate {
+        use tyst::traits::se::ToPublicKey;
+        crate::cert::build::NoSignatureTbsCertificate::new(
+            crate::test_utils::load_test_certificate("ec_ca.b64")
+                .get_subject()
+                .unwrap(),
+            not_after_epoch_seconds,
+            subject,
+            crate::test_utils::load_test_certificate(name)
+                .get_encoded_subject_public_key_info()
+                .to_public_key()
+                .as_ref(),
+            extensions,
+        )
+    }
+
+    /// Return the parsed certificate of `tbs_certificate` with an empty
+    /// signature.
+    pub fn to_unsigned_test_certificate(
+        tbs_certificate: &crate::cert::build::NoSignatureTbsCertificate,
+    ) -> crate::cert::parse::CertificateParser {
+        let signature_algorithm = crate::cms::signature_algorithm_identifier(
+            crate::cert::types::WellKnownAlgorithm::EcdsaWithSha256.as_oid(),
+        );
+        let certificate = tbs_certificate.to_certificate(&signature_algorithm, vec![]);
+        crate::cert::parse::CertificateParser::from_bytes(&rasn::der::encode(&certificate).unwrap())
+            .unwrap()
+    }
+
+    /// Return the current time plus `days` in epoch seconds.
+    pub fn epoch_seconds_in_days(days: u64) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + days * 86_400
+    }
+}
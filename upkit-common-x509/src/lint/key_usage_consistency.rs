@@ -0,0 +1,289 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Consistency of the Key Usage extension with other parts of the certificate.
+
+use super::LintFinding;
+use super::LintSeverity;
+use crate::cert::extensions::ExtendedKeyUsage;
+use crate::cert::extensions::KeyUsage;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::WellKnownAlgorithm;
+
+/// Key usages allowed for signature-only public key algorithms.
+const SIGNATURE_KEY_USAGES: &[KeyUsage] = &[
+    KeyUsage::DigitalSignature,
+    KeyUsage::NonRepudiation,
+    KeyUsage::KeyCertSign,
+    KeyUsage::CRLSign,
+];
+
+/** Return the [KeyUsage]s allowed for the public key algorithm or `None` if
+there is no known restriction.
+
+See [RFC3279 2.3](https://www.rfc-editor.org/rfc/rfc3279#section-2.3),
+[RFC4055 1.2](https://www.rfc-editor.org/rfc/rfc4055#section-1.2),
+[RFC5480 3](https://www.rfc-editor.org/rfc/rfc5480#section-3),
+[RFC8410 5](https://www.rfc-editor.org/rfc/rfc8410#section-5) and the
+ML-DSA, SLH-DSA and ML-KEM certificate profiles.
+*/
+fn allowed_key_usages(algorithm: &WellKnownAlgorithm) -> Option<&'static [KeyUsage]> {
+    match algorithm {
+        WellKnownAlgorithm::RsaEncryption => Some(&[
+            KeyUsage::DigitalSignature,
+            KeyUsage::NonRepudiation,
+            KeyUsage::KeyEncipherment,
+            KeyUsage::DataEncipherment,
+            KeyUsage::KeyCertSign,
+            KeyUsage::CRLSign,
+        ]),
+        WellKnownAlgorithm::EcPublicKey => Some(&[
+            KeyUsage::DigitalSignature,
+            KeyUsage::NonRepudiation,
+            KeyUsage::KeyAgreement,
+            KeyUsage::KeyCertSign,
+            KeyUsage::CRLSign,
+            KeyUsage::EncipherOnly,
+            KeyUsage::DecipherOnly,
+        ]),
+        WellKnownAlgorithm::X25519 | WellKnownAlgorithm::X448 => Some(&[
+            KeyUsage::KeyAgreement,
+            KeyUsage::EncipherOnly,
+            KeyUsage::DecipherOnly,
+        ]),
+        WellKnownAlgorithm::RsaSsaPss
+        | WellKnownAlgorithm::Ed25519
+        | WellKnownAlgorithm::Ed448
+        | WellKnownAlgorithm::MlDsa44
+        | WellKnownAlgorithm::MlDsa65
+        | WellKnownAlgorithm::MlDsa87
+        | WellKnownAlgorithm::SlhDsaSha2_128s
+        | WellKnownAlgorithm::SlhDsaSha2_128f
+        | WellKnownAlgorithm::SlhDsaSha2_192s
+        | WellKnownAlgorithm::SlhDsaSha2_192f
+        | WellKnownAlgorithm::SlhDsaSha2_256s
+        | WellKnownAlgorithm::SlhDsaSha2_256f => Some(SIGNATURE_KEY_USAGES),
+        WellKnownAlgorithm::MlKem512
+        | WellKnownAlgorithm::MlKem768
+        | WellKnownAlgorithm::MlKem1024 => Some(&[KeyUsage::KeyEncipherment]),
+        _ => None,
+    }
+}
+
+/// Return the [KeyUsage]s consistent with the [ExtendedKeyUsage] according
+/// to [RFC5280 4.2.1.12](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.12)
+/// or `None` if not specified.
+fn consistent_key_usages(extended_key_usage: &ExtendedKeyUsage) -> Option<&'static [KeyUsage]> {
+    match extended_key_usage {
+        ExtendedKeyUsage::PkixServerAuth => Some(&[
+            KeyUsage::DigitalSignature,
+            KeyUsage::KeyEncipherment,
+            KeyUsage::KeyAgreement,
+        ]),
+        ExtendedKeyUsage::PkixClientAuth => {
+            Some(&[KeyUsage::DigitalSignature, KeyUsage::KeyAgreement])
+        }
+        ExtendedKeyUsage::PkixCodeSigning => Some(&[KeyUsage::DigitalSignature]),
+        ExtendedKeyUsage::PkixEmailProtection => Some(&[
+            KeyUsage::DigitalSignature,
+            KeyUsage::NonRepudiation,
+            KeyUsage::KeyEncipherment,
+            KeyUsage::KeyAgreement,
+        ]),
+        ExtendedKeyUsage::PkixTimeStamping | ExtendedKeyUsage::PkixOcspSigning => {
+            Some(&[KeyUsage::DigitalSignature, KeyUsage::NonRepudiation])
+        }
+        _ => None,
+    }
+}
+
+/** Analyze the consistency of the Key Usage extension with the rest of the
+certificate.
+
+The following is reported:
+
+* `e_key_usage_encipher_or_decipher_only_without_key_agreement`: The meaning
+  of encipherOnly and decipherOnly is undefined without keyAgreement
+  ([RFC5280 4.2.1.3](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.3)).
+* `e_key_usage_cert_sign_without_ca`: keyCertSign requires that the cA bit
+  of the Basic Constraints is asserted
+  ([RFC5280 4.2.1.9](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.9)).
+* `e_key_usage_incompatible_with_public_key_algorithm`: A key usage that the
+  public key algorithm can't perform (e.g. keyEncipherment with Ed25519).
+* `e_ext_key_usage_inconsistent_with_key_usage`: No asserted key usage is
+  consistent with an extended key usage
+  ([RFC5280 4.2.1.12](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.12)).
+*/
+pub fn analyze_key_usage_consistency(cp: &CertificateParser) -> Vec<LintFinding> {
+    let mut ret = vec![];
+    let Some(key_usages) = cp.get_key_usage().ok().flatten() else {
+        return ret;
+    };
+    if !key_usages.contains(&KeyUsage::KeyAgreement)
+        && (key_usages.contains(&KeyUsage::EncipherOnly)
+            || key_usages.contains(&KeyUsage::DecipherOnly))
+    {
+        ret.push(LintFinding::new(
+            "e_key_usage_encipher_or_decipher_only_without_key_agreement",
+            LintSeverity::Error,
+            "encipherOnly or decipherOnly is asserted without keyAgreement.",
+        ));
+    }
+    if key_usages.contains(&KeyUsage::KeyCertSign)
+        && !cp
+            .get_basic_constraints()
+            .ok()
+            .flatten()
+            .is_some_and(|basic_constraints| !basic_constraints.is_leaf())
+    {
+        ret.push(LintFinding::new(
+            "e_key_usage_cert_sign_without_ca",
+            LintSeverity::Error,
+            "keyCertSign is asserted, but Basic Constraints cA is not.",
+        ));
+    }
+    if let Some(algorithm) = cp.get_subject_public_key_algorithm() {
+        if let Some(allowed) = allowed_key_usages(&algorithm) {
+            let incompatible = key_usages
+                .iter()
+                .filter(|&key_usage| !allowed.contains(key_usage))
+                .collect::<Vec<_>>();
+            if !incompatible.is_empty() {
+                let msg = format!(
+                    "{incompatible:?} can't be used with public key algorithm '{}'.",
+                    algorithm.as_name()
+                );
+                ret.push(LintFinding::new(
+                    "e_key_usage_incompatible_with_public_key_algorithm",
+                    LintSeverity::Error,
+                    &msg,
+                ));
+            }
+        }
+    }
+    for extended_key_usage in cp.get_extended_key_usage().unwrap_or_default() {
+        if let Some(consistent) = consistent_key_usages(&extended_key_usage) {
+            if !key_usages
+                .iter()
+                .any(|key_usage| consistent.contains(key_usage))
+            {
+                let msg = format!(
+                    "{extended_key_usage:?} requires one of {consistent:?}, but only {key_usages:?} is asserted."
+                );
+                ret.push(LintFinding::new(
+                    "e_ext_key_usage_inconsistent_with_key_usage",
+                    LintSeverity::Error,
+                    &msg,
+                ));
+            }
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::extensions::BasicConstraints;
+    use crate::cert::extensions::Extensions;
+    use crate::cert::types::DistinguishedName;
+    use crate::lint::test_utils::*;
+
+    /// Return the rule identifiers of the findings for a certificate with the
+    /// public key of the test certificate `name` and the `extensions`.
+    fn analyze(name: &str, extensions: Extensions) -> Vec<String> {
+        let subject = DistinguishedName::try_from(vec![vec![(
+            "common_name".to_string(),
+            "ku.example.com".to_string(),
+        )]])
+        .unwrap();
+        let cp = to_unsigned_test_certificate(&build_test_tbs_certificate(
+            name,
+            subject,
+            epoch_seconds_in_days(90),
+            extensions,
+        ));
+        analyze_key_usage_consistency(&cp)
+            .iter()
+            .map(|finding| finding.get_rule_id().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn consistent_key_usage() {
+        let mut extensions = Extensions::default();
+        extensions.add_key_usage(&[KeyUsage::DigitalSignature]);
+        extensions.add_extended_key_usage(&[ExtendedKeyUsage::PkixServerAuth]);
+        assert!(analyze("ec_recipient.b64", extensions).is_empty());
+        let mut extensions = Extensions::default();
+        extensions.add_basic_constraints(&BasicConstraints::new_ca(None));
+        extensions.add_key_usage(&[KeyUsage::KeyCertSign, KeyUsage::CRLSign]);
+        assert!(analyze("ec_recipient.b64", extensions).is_empty());
+        let mut extensions = Extensions::default();
+        extensions.add_key_usage(&[KeyUsage::KeyEncipherment]);
+        assert!(analyze("mlkem_recipient.b64", extensions).is_empty());
+        // Without Key Usage there is nothing to compare
+        let mut extensions = Extensions::default();
+        extensions.add_extended_key_usage(&[ExtendedKeyUsage::PkixServerAuth]);
+        assert!(analyze("ec_recipient.b64", extensions).is_empty());
+    }
+
+    #[test]
+    fn inconsistent_key_usage() {
+        let mut extensions = Extensions::default();
+        extensions.add_key_usage(&[KeyUsage::DigitalSignature, KeyUsage::EncipherOnly]);
+        assert_eq!(
+            analyze("ec_recipient.b64", extensions),
+            vec!["e_key_usage_encipher_or_decipher_only_without_key_agreement"]
+        );
+        let mut extensions = Extensions::default();
+        extensions.add_key_usage(&[KeyUsage::KeyCertSign]);
+        assert_eq!(
+            analyze("ec_recipient.b64", extensions),
+            vec!["e_key_usage_cert_sign_without_ca"]
+        );
+        // EC keys can't be used for key transport and ML-KEM keys can't sign
+        let mut extensions = Extensions::default();
+        extensions.add_key_usage(&[KeyUsage::DigitalSignature, KeyUsage::KeyEncipherment]);
+        assert_eq!(
+            analyze("ec_recipient.b64", extensions),
+            vec!["e_key_usage_incompatible_with_public_key_algorithm"]
+        );
+        let mut extensions = Extensions::default();
+        extensions.add_key_usage(&[KeyUsage::DigitalSignature]);
+        assert_eq!(
+            analyze("mlkem_recipient.b64", extensions),
+            vec!["e_key_usage_incompatible_with_public_key_algorithm"]
+        );
+        let mut extensions = Extensions::default();
+        extensions.add_key_usage(&[KeyUsage::KeyAgreement]);
+        extensions.add_extended_key_usage(&[ExtendedKeyUsage::PkixCodeSigning]);
+        assert_eq!(
+            analyze("ec_recipient.b64", extensions),
+            vec!["e_ext_key_usage_inconsistent_with_key_usage"]
+        );
+    }
+
+    #[test]
+    fn signature_only_algorithms_reject_encipherment() {
+        let allowed = allowed_key_usages(&WellKnownAlgorithm::Ed25519).unwrap();
+        assert!(!allowed.contains(&KeyUsage::KeyEncipherment));
+        let allowed = allowed_key_usages(&WellKnownAlgorithm::MlDsa65).unwrap();
+        assert!(allowed.contains(&KeyUsage::DigitalSignature));
+        assert!(!allowed.contains(&KeyUsage::KeyAgreement));
+    }
+}
@@ -0,0 +1,89 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Result of a lint.
+
+use super::LintSource;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+use serde_with::skip_serializing_none;
+
+/// Severity of a [LintFinding].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    /// Allowed, but worth knowing about.
+    Notice,
+    /// Not recommended ("SHOULD" or "SHOULD NOT").
+    Warning,
+    /// Not compliant ("MUST" or "MUST NOT").
+    Error,
+}
+
+/// Structured finding of a lint.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct LintFinding {
+    rule_id: String,
+    severity: LintSeverity,
+    message: String,
+    source: Option<LintSource>,
+}
+
+impl LintFinding {
+    /// Return a new instance.
+    pub fn new(rule_id: &str, severity: LintSeverity, message: &str) -> Self {
+        Self {
+            rule_id: rule_id.to_string(),
+            severity,
+            message: message.to_string(),
+            source: None,
+        }
+    }
+
+    /// Return a copy with the document the rule is derived from.
+    pub fn with_source(self, source: LintSource) -> Self {
+        Self {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    /// Return the identifier of the rule that produced the finding.
+    pub fn get_rule_id(&self) -> &str {
+        &self.rule_id
+    }
+
+    /// Return the [LintSeverity].
+    pub fn get_severity(&self) -> LintSeverity {
+        self.severity
+    }
+
+    /// Return a human readable description of the finding.
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    /// Return the [LintSource] if the finding originates from a
+    /// [LintRule](super::LintRule).
+    pub fn get_source(&self) -> Option<LintSource> {
+        self.source
+    }
+}
@@ -0,0 +1,50 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Lint rule abstraction.
+
+use super::LintSeverity;
+use super::LintSource;
+use crate::cert::parse::CertificateParser;
+
+/** Single rule that a certificate is checked against by a
+[Linter](super::Linter).
+
+Rule identifiers follow the convention of prefixing the name with `e_`, `w_`
+or `n_` depending on the [LintSeverity].
+*/
+pub trait LintRule: Send + Sync {
+    /// Return the unique identifier of the rule.
+    fn get_rule_id(&self) -> &'static str;
+
+    /// Return the document the rule is derived from.
+    fn get_source(&self) -> LintSource;
+
+    /// Return the point in time (epoch seconds) when the rule came into effect.
+    ///
+    /// Certificates with a notBefore earlier than this are not checked.
+    fn get_effective_epoch_seconds(&self) -> u64;
+
+    /// Return the [LintSeverity] of a violation.
+    fn get_severity(&self) -> LintSeverity;
+
+    /// Return `true` if the rule applies to the certificate.
+    fn is_applicable(&self, cp: &CertificateParser) -> bool;
+
+    /// Return a description of each violation of the rule.
+    fn check(&self, cp: &CertificateParser) -> Vec<String>;
+}
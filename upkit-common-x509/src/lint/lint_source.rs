@@ -0,0 +1,39 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Origin of a lint rule.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Document that a [LintRule](super::LintRule) is derived from.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSource {
+    /// [RFC5280](https://www.rfc-editor.org/rfc/rfc5280) Internet X.509 Public
+    /// Key Infrastructure Certificate and CRL Profile.
+    Rfc5280,
+    /// CA/Browser Forum
+    /// [TLS Baseline Requirements](https://cabforum.org/working-groups/server/baseline-requirements/requirements/).
+    CabfBaselineRequirements,
+    /// CA/Browser Forum
+    /// [Extended Validation Guidelines](https://cabforum.org/working-groups/server/extended-validation/guidelines/).
+    CabfExtendedValidationGuidelines,
+    /// CA/Browser Forum
+    /// [S/MIME Baseline Requirements](https://cabforum.org/working-groups/smime/requirements/).
+    CabfSmimeBaselineRequirements,
+}
@@ -0,0 +1,152 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Rule engine for certificate linting.
+
+use super::rules;
+use super::LintFinding;
+use super::LintRule;
+use super::LintSource;
+use crate::cert::parse::CertificateParser;
+
+/** Runs a set of [LintRule]s over certificates.
+
+The default instance contains all built-in rules. A rule is only run when
+the certificate's notBefore is at or after the rule's effective date and the
+rule is applicable to the certificate.
+
+```ignore
+let findings = Linter::default()
+    .with_sources(&[LintSource::Rfc5280, LintSource::CabfBaselineRequirements])
+    .lint(&cp);
+```
+*/
+pub struct Linter {
+    rules: Vec<Box<dyn LintRule>>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self {
+            rules: rules::all(),
+        }
+    }
+}
+
+impl Linter {
+    /// Return a new instance without any rules.
+    pub fn without_rules() -> Self {
+        Self { rules: vec![] }
+    }
+
+    /// Return the instance with an additional rule.
+    pub fn with_rule(mut self, rule: Box<dyn LintRule>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Return the instance with only the rules from the provided `sources`.
+    pub fn with_sources(mut self, sources: &[LintSource]) -> Self {
+        self.rules
+            .retain(|rule| sources.contains(&rule.get_source()));
+        self
+    }
+
+    /// Return the instance without the rule identified by `rule_id`.
+    pub fn without_rule(mut self, rule_id: &str) -> Self {
+        self.rules.retain(|rule| rule.get_rule_id() != rule_id);
+        self
+    }
+
+    /// Return the identifiers of all rules.
+    pub fn get_rule_ids(&self) -> Vec<&'static str> {
+        self.rules.iter().map(|rule| rule.get_rule_id()).collect()
+    }
+
+    /// Return the findings of all effective and applicable rules.
+    pub fn lint(&self, cp: &CertificateParser) -> Vec<LintFinding> {
+        // A validity that fails to decode is reported by a rule, so don't
+        // skip any rule because of it
+        let not_before = cp
+            .get_validity()
+            .map_or(u64::MAX, |validity| validity.get_not_before());
+        self.rules
+            .iter()
+            .filter(|rule| not_before >= rule.get_effective_epoch_seconds())
+            .filter(|rule| rule.is_applicable(cp))
+            .flat_map(|rule| {
+                rule.check(cp).into_iter().map(move |message| {
+                    LintFinding::new(rule.get_rule_id(), rule.get_severity(), &message)
+                        .with_source(rule.get_source())
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::extensions::Extensions;
+    use crate::cert::extensions::KeyUsage;
+    use crate::cert::types::DistinguishedName;
+    use crate::lint::test_utils::*;
+    use crate::lint::LintSeverity;
+
+    #[test]
+    fn rule_ids_are_unique() {
+        let rule_ids = Linter::default().get_rule_ids();
+        let mut unique = rule_ids.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(rule_ids.len(), unique.len());
+        assert!(Linter::default()
+            .with_sources(&[LintSource::CabfSmimeBaselineRequirements])
+            .get_rule_ids()
+            .contains(&"e_smime_validity_period_too_long"));
+    }
+
+    #[test]
+    fn key_usage_consistency_findings() {
+        let mut extensions = Extensions::default();
+        extensions.add_key_usage(&[KeyUsage::KeyCertSign]);
+        let tbs_certificate = build_test_tbs_certificate(
+            "ec_recipient.b64",
+            DistinguishedName::try_from(vec![vec![(
+                "common_name".to_string(),
+                "ku.example.com".to_string(),
+            )]])
+            .unwrap(),
+            epoch_seconds_in_days(90),
+            extensions,
+        );
+        let rule_id = "e_key_usage_cert_sign_without_ca";
+        let findings = tbs_certificate.lint(&Linter::default()).unwrap();
+        let finding = findings
+            .iter()
+            .find(|finding| finding.get_rule_id() == rule_id)
+            .unwrap();
+        assert_eq!(finding.get_severity(), LintSeverity::Error);
+        assert_eq!(finding.get_source(), Some(LintSource::Rfc5280));
+        let findings = tbs_certificate
+            .lint(&Linter::default().without_rule(rule_id))
+            .unwrap();
+        assert!(findings
+            .iter()
+            .all(|finding| finding.get_rule_id() != rule_id));
+    }
+}
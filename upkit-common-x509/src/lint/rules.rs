@@ -0,0 +1,252 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Built-in [LintRule]s.
+
+mod distinguished_name_rules;
+mod extension_criticality_rules;
+mod extension_encoding_rules;
+mod key_usage_rules;
+mod revocation_information_rules;
+mod serial_number_rules;
+mod subject_alternative_name_rules;
+mod validity_rules;
+
+pub use self::distinguished_name_rules::EvMissingSubjectAttributesRule;
+pub use self::distinguished_name_rules::SubjectAttributeEncodingRule;
+pub use self::distinguished_name_rules::SubjectAttributeTooLongRule;
+pub use self::extension_criticality_rules::ExtensionCriticalityRule;
+pub use self::extension_encoding_rules::ExtensionNotDecodableRule;
+pub use self::key_usage_rules::KeyUsageConsistencyRule;
+pub use self::revocation_information_rules::TlsServerMissingAuthorityInformationAccessRule;
+pub use self::revocation_information_rules::TlsServerMissingRevocationInformationRule;
+pub use self::serial_number_rules::SerialNumberLowEntropyRule;
+pub use self::serial_number_rules::SerialNumberNotPositiveRule;
+pub use self::serial_number_rules::SerialNumberTooLongRule;
+pub use self::subject_alternative_name_rules::EmptySubjectWithoutCriticalSanRule;
+pub use self::subject_alternative_name_rules::TlsServerCommonNameNotInSanRule;
+pub use self::subject_alternative_name_rules::TlsServerMissingSanRule;
+pub use self::subject_alternative_name_rules::TlsServerSanDisallowedTypeRule;
+pub use self::validity_rules::SmimeValidityTooLongRule;
+pub use self::validity_rules::TlsServerValidityTooLongRule;
+pub use self::validity_rules::ValidityPeriodInvertedRule;
+use super::LintRule;
+use crate::cert::extensions::ExtendedKeyUsage;
+use crate::cert::extensions::WellKnownCertificatePolicy;
+use crate::cert::parse::CertificateParser;
+use crate::encdec::tlv::Tlv;
+
+/// [RFC5280](https://www.rfc-editor.org/rfc/rfc5280) publication (2008-05-01).
+const RFC5280_EFFECTIVE_EPOCH_SECONDS: u64 = 1_209_600_000;
+
+/// TLS Baseline Requirements 1.0 effective date (2012-07-01).
+const CABF_BR_EFFECTIVE_EPOCH_SECONDS: u64 = 1_341_100_800;
+
+/// Return all built-in rules.
+pub(super) fn all() -> Vec<Box<dyn LintRule>> {
+    let mut ret: Vec<Box<dyn LintRule>> = vec![
+        Box::new(ExtensionNotDecodableRule),
+        Box::new(SerialNumberTooLongRule),
+        Box::new(SerialNumberNotPositiveRule),
+        Box::new(SerialNumberLowEntropyRule),
+        Box::new(ValidityPeriodInvertedRule),
+        Box::new(TlsServerValidityTooLongRule),
+        Box::new(SmimeValidityTooLongRule),
+        Box::new(EmptySubjectWithoutCriticalSanRule),
+        Box::new(TlsServerMissingSanRule),
+        Box::new(TlsServerSanDisallowedTypeRule),
+        Box::new(TlsServerCommonNameNotInSanRule),
+        Box::new(SubjectAttributeTooLongRule),
+        Box::new(SubjectAttributeEncodingRule),
+        Box::new(EvMissingSubjectAttributesRule),
+        Box::new(TlsServerMissingAuthorityInformationAccessRule),
+        Box::new(TlsServerMissingRevocationInformationRule),
+    ];
+    for rule in ExtensionCriticalityRule::all() {
+        ret.push(Box::new(rule));
+    }
+    for rule in KeyUsageConsistencyRule::all() {
+        ret.push(Box::new(rule));
+    }
+    ret
+}
+
+/// Return `true` if the certificate is a CA certificate.
+///
+/// Extensions that fail to decode are treated as absent by the helpers of
+/// this module and are reported by [ExtensionNotDecodableRule] instead.
+fn is_ca(cp: &CertificateParser) -> bool {
+    cp.get_basic_constraints()
+        .ok()
+        .flatten()
+        .is_some_and(|basic_constraints| !basic_constraints.is_leaf())
+}
+
+/// Return `true` if the certificate asserts the certificate policy.
+fn has_certificate_policy(cp: &CertificateParser, policy: &WellKnownCertificatePolicy) -> bool {
+    cp.get_certificate_policies()
+        .unwrap_or_default()
+        .iter()
+        .any(|certificate_policy| certificate_policy.get_oid().eq(policy.as_oid()))
+}
+
+/// Return `true` if the certificate is a TLS server subscriber certificate in
+/// scope of the TLS Baseline Requirements.
+fn is_tls_server_subscriber(cp: &CertificateParser) -> bool {
+    !is_ca(cp)
+        && (cp
+            .get_extended_key_usage()
+            .unwrap_or_default()
+            .contains(&ExtendedKeyUsage::PkixServerAuth)
+            || [
+                WellKnownCertificatePolicy::CabfExtendedValidation,
+                WellKnownCertificatePolicy::CabfDomainValidated,
+                WellKnownCertificatePolicy::CabfOrganizationValidated,
+                WellKnownCertificatePolicy::CabfIndividualValidated,
+            ]
+            .iter()
+            .any(|policy| has_certificate_policy(cp, policy)))
+}
+
+/// Return `true` if the certificate is an S/MIME subscriber certificate.
+fn is_smime_subscriber(cp: &CertificateParser) -> bool {
+    !is_ca(cp)
+        && cp
+            .get_extended_key_usage()
+            .unwrap_or_default()
+            .contains(&ExtendedKeyUsage::PkixEmailProtection)
+}
+
+/// Return the object identifier and DER encoded value of each subject
+/// attribute in order of appearance.
+fn subject_attributes(cp: &CertificateParser) -> Vec<(Vec<u32>, Vec<u8>)> {
+    match rasn::der::decode::<rasn_pkix::Name>(cp.as_encoded_subject()) {
+        Ok(rasn_pkix::Name::RdnSequence(rdns)) => rdns
+            .iter()
+            .flat_map(|rdn| rdn.to_vec())
+            .map(|atav| (atav.r#type.to_vec(), atav.value.as_bytes().to_vec()))
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// Return the tag and the decoded value of a DER encoded string.
+fn decode_string_value(encoded: &[u8]) -> Option<(u8, String)> {
+    let tlv = Tlv::read(encoded, 0).ok()?;
+    let content = &encoded[tlv.content];
+    let value = match tlv.tag {
+        // BMPString
+        0x1e => String::from_utf16(
+            &content
+                .chunks_exact(2)
+                .map(|chunk| u16::from_be_bytes([chunk[0], chunk[1]]))
+                .collect::<Vec<_>>(),
+        )
+        .ok()?,
+        // UniversalString
+        0x1c => content
+            .chunks_exact(4)
+            .map(|chunk| {
+                char::from_u32(u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            })
+            .collect::<Option<String>>()?,
+        _ => String::from_utf8_lossy(content).to_string(),
+    };
+    Some((tlv.tag, value))
+}
+
+/// Return the messages of the `rule` for the certificate that the
+/// `tbs_certificate` will become.
+#[cfg(test)]
+fn check_tbs_certificate(
+    rule: impl LintRule + 'static,
+    tbs_certificate: &crate::cert::build::NoSignatureTbsCertificate,
+) -> Vec<String> {
+    tbs_certificate
+        .lint(&super::Linter::without_rules().with_rule(Box::new(rule)))
+        .unwrap()
+        .iter()
+        .map(|finding| finding.get_message().to_string())
+        .collect()
+}
+
+/// Return a TBSCertificate for the `subject` with the `extensions` that is
+/// valid for `days`.
+#[cfg(test)]
+fn build_tbs_certificate(
+    subject: &[(crate::cert::types::WellKnownAttribute, &str)],
+    days: u64,
+    extensions: crate::cert::extensions::Extensions,
+) -> crate::cert::build::NoSignatureTbsCertificate {
+    let subject = subject
+        .iter()
+        .map(|(attribute, value)| vec![attribute.with_value(value)])
+        .collect();
+    super::test_utils::build_test_tbs_certificate(
+        "ec_recipient.b64",
+        crate::cert::types::DistinguishedName::new_unchecked(subject),
+        super::test_utils::epoch_seconds_in_days(days),
+        extensions,
+    )
+}
+
+/// Return the extensions of a TLS server subscriber certificate.
+#[cfg(test)]
+fn tls_server_extensions(
+    subject_alternative_names: &[(crate::cert::types::WellKnownGeneralName, &str)],
+    access_descriptions: &[crate::cert::extensions::AuthorityInfoAccessDescription],
+    crl_distribution_point_uri: Option<&str>,
+) -> crate::cert::extensions::Extensions {
+    let mut extensions = crate::cert::extensions::Extensions::default();
+    extensions.add_extended_key_usage(&[ExtendedKeyUsage::PkixServerAuth]);
+    extensions.add_subject_alternative_name(
+        &subject_alternative_names
+            .iter()
+            .map(|(general_name, value)| (general_name.to_owned(), value.to_string()))
+            .collect::<Vec<_>>(),
+        false,
+    );
+    extensions.add_authority_information_access(access_descriptions);
+    if let Some(crl_distribution_point_uri) = crl_distribution_point_uri {
+        extensions.add_crl_distribution_points(crl_distribution_point_uri);
+    }
+    extensions
+}
+
+/// Return the extensions of a conforming TLS server subscriber certificate
+/// for `lint.example.com`.
+#[cfg(test)]
+fn conforming_tls_server_extensions() -> crate::cert::extensions::Extensions {
+    use crate::cert::extensions::AuthorityInfoAccessDescription;
+    use crate::cert::types::WellKnownGeneralName;
+
+    tls_server_extensions(
+        &[(WellKnownGeneralName::DnsName, "lint.example.com")],
+        &[
+            AuthorityInfoAccessDescription::Ocsp {
+                uri: "http://ocsp.example.com".to_string(),
+            },
+            AuthorityInfoAccessDescription::CaIssuers {
+                access_location: (
+                    WellKnownGeneralName::Uri,
+                    "http://ca.example.com/ca.crt".to_string(),
+                ),
+            },
+        ],
+        Some("http://crl.example.com/ca.crl"),
+    )
+}
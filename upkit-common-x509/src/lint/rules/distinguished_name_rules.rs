@@ -0,0 +1,292 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Distinguished Name attribute rules.
+
+use super::CABF_BR_EFFECTIVE_EPOCH_SECONDS;
+use super::RFC5280_EFFECTIVE_EPOCH_SECONDS;
+use crate::cert::extensions::WellKnownCertificatePolicy;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::Asn1EncodingType;
+use crate::cert::types::WellKnownAttribute;
+use crate::lint::LintRule;
+use crate::lint::LintSeverity;
+use crate::lint::LintSource;
+
+const TAG_UTF8_STRING: u8 = 0x0c;
+const TAG_PRINTABLE_STRING: u8 = 0x13;
+const TAG_IA5_STRING: u8 = 0x16;
+
+/// Subject attribute values MUST NOT exceed the upper bounds of
+/// [RFC5280 Appendix A.1](https://www.rfc-editor.org/rfc/rfc5280#appendix-A.1).
+pub struct SubjectAttributeTooLongRule;
+
+impl LintRule for SubjectAttributeTooLongRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_subject_attribute_too_long"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::Rfc5280
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        RFC5280_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, _cp: &CertificateParser) -> bool {
+        true
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let mut ret = vec![];
+        for (oid, encoded_value) in super::subject_attributes(cp) {
+            let Ok(attribute) = WellKnownAttribute::by_oid(&oid) else {
+                continue;
+            };
+            let name = attribute.as_name();
+            let Ok(atavi) = WellKnownAttribute::meta_data_by_name(&name) else {
+                continue;
+            };
+            let Some((_, value)) = super::decode_string_value(&encoded_value) else {
+                continue;
+            };
+            let len = value.chars().count();
+            if len > atavi.max_char_len {
+                ret.push(format!(
+                    "Attribute '{name}' is {len} chars, but the upper bound is {}.",
+                    atavi.max_char_len
+                ));
+            }
+        }
+        ret
+    }
+}
+
+/** Subject attribute values MUST use the encoding allowed for the attribute.
+
+DirectoryString values MUST be encoded as PrintableString or UTF8String,
+while attributes like countryName and serialNumber are PrintableString and
+emailAddress and domainComponent are IA5String.
+
+See [RFC5280 4.1.2.4](https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.4)
+and [RFC5280 Appendix A.1](https://www.rfc-editor.org/rfc/rfc5280#appendix-A.1).
+*/
+pub struct SubjectAttributeEncodingRule;
+
+impl LintRule for SubjectAttributeEncodingRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_subject_attribute_invalid_encoding"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::Rfc5280
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        RFC5280_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, _cp: &CertificateParser) -> bool {
+        true
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let mut ret = vec![];
+        for (oid, encoded_value) in super::subject_attributes(cp) {
+            let Ok(attribute) = WellKnownAttribute::by_oid(&oid) else {
+                continue;
+            };
+            let name = attribute.as_name();
+            let Ok(atavi) = WellKnownAttribute::meta_data_by_name(&name) else {
+                continue;
+            };
+            let allowed_tags: &[u8] = match atavi.encoding {
+                Asn1EncodingType::IA5String => &[TAG_IA5_STRING],
+                Asn1EncodingType::PrintableString => &[TAG_PRINTABLE_STRING],
+                Asn1EncodingType::Utf8String => &[TAG_UTF8_STRING, TAG_PRINTABLE_STRING],
+            };
+            let tag = encoded_value.first().copied().unwrap_or_default();
+            if !allowed_tags.contains(&tag) {
+                ret.push(format!(
+                    "Attribute '{name}' is encoded with tag 0x{tag:02x}, but {:?} is required.",
+                    atavi.encoding
+                ));
+            }
+        }
+        ret
+    }
+}
+
+/// EV certificates MUST contain the subject attributes required by the
+/// [EV Guidelines 9.2](https://cabforum.org/working-groups/server/extended-validation/guidelines/).
+pub struct EvMissingSubjectAttributesRule;
+
+impl EvMissingSubjectAttributesRule {
+    const REQUIRED: &[WellKnownAttribute] = &[
+        WellKnownAttribute::OrganizationName,
+        WellKnownAttribute::BusinessCategory,
+        WellKnownAttribute::JurisdictionCountry,
+        WellKnownAttribute::SerialNumber,
+        WellKnownAttribute::CountryName,
+    ];
+}
+
+impl LintRule for EvMissingSubjectAttributesRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_ev_missing_subject_attributes"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::CabfExtendedValidationGuidelines
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        CABF_BR_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        super::is_tls_server_subscriber(cp)
+            && super::has_certificate_policy(
+                cp,
+                &WellKnownCertificatePolicy::CabfExtendedValidation,
+            )
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let present = super::subject_attributes(cp)
+            .iter()
+            .filter_map(|(oid, _)| WellKnownAttribute::by_oid(oid).ok())
+            .collect::<Vec<_>>();
+        Self::REQUIRED
+            .iter()
+            .filter(|&attribute| !present.contains(attribute))
+            .map(|attribute| format!("Required attribute '{}' is missing.", attribute.as_name()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::build::NoSignatureTbsCertificate;
+    use crate::cert::extensions::CertificatePolicy;
+    use crate::lint::rules::build_tbs_certificate;
+    use crate::lint::rules::check_tbs_certificate;
+    use crate::lint::rules::conforming_tls_server_extensions;
+
+    #[test]
+    fn attribute_encoding_of_test_certificate() {
+        let cp = crate::test_utils::load_test_certificate("h1_leaf.b64");
+        assert!(SubjectAttributeEncodingRule.check(&cp).is_empty());
+        assert!(SubjectAttributeTooLongRule.check(&cp).is_empty());
+    }
+
+    #[test]
+    fn subject_attribute_too_long() {
+        let common_name = "a".repeat(64);
+        let tbs_certificate = build_tbs_certificate(
+            &[(WellKnownAttribute::CommonName, &common_name)],
+            90,
+            Default::default(),
+        );
+        assert!(check_tbs_certificate(SubjectAttributeTooLongRule, &tbs_certificate).is_empty());
+        let common_name = "a".repeat(65);
+        let tbs_certificate = build_tbs_certificate(
+            &[(WellKnownAttribute::CommonName, &common_name)],
+            90,
+            Default::default(),
+        );
+        assert_eq!(
+            check_tbs_certificate(SubjectAttributeTooLongRule, &tbs_certificate).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn subject_attribute_encoding() {
+        let tbs_certificate = build_tbs_certificate(
+            &[(WellKnownAttribute::CountryName, "SE")],
+            90,
+            Default::default(),
+        );
+        assert!(check_tbs_certificate(SubjectAttributeEncodingRule, &tbs_certificate).is_empty());
+        // countryName as UTF8String
+        let mut tbs_certificate = tbs_certificate.to_rasn_type();
+        let mut rdn = rasn::types::SetOf::new();
+        rdn.insert(rasn_pkix::AttributeTypeAndValue {
+            r#type: rasn::types::ObjectIdentifier::new_unchecked(vec![2, 5, 4, 6].into()),
+            value: rasn::types::Any::new(rasn::der::encode(&"SE".to_string()).unwrap()),
+        });
+        tbs_certificate.subject = rasn_pkix::Name::RdnSequence(vec![rdn.into()]);
+        let tbs_certificate = NoSignatureTbsCertificate::from_rasn_type(&tbs_certificate);
+        assert_eq!(
+            check_tbs_certificate(SubjectAttributeEncodingRule, &tbs_certificate).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn ev_missing_subject_attributes() {
+        let extensions = || {
+            let mut extensions = conforming_tls_server_extensions();
+            extensions.add_certificate_policies(&[CertificatePolicy::OidPolicy {
+                oid: WellKnownCertificatePolicy::CabfExtendedValidation
+                    .as_oid()
+                    .to_vec(),
+            }]);
+            extensions
+        };
+        let tbs_certificate = build_tbs_certificate(
+            &[
+                (WellKnownAttribute::CountryName, "SE"),
+                (WellKnownAttribute::JurisdictionCountry, "SE"),
+                (WellKnownAttribute::BusinessCategory, "Private Organization"),
+                (WellKnownAttribute::SerialNumber, "5560000000"),
+                (WellKnownAttribute::OrganizationName, "Example"),
+                (WellKnownAttribute::CommonName, "lint.example.com"),
+            ],
+            90,
+            extensions(),
+        );
+        assert!(check_tbs_certificate(EvMissingSubjectAttributesRule, &tbs_certificate).is_empty());
+        let tbs_certificate = build_tbs_certificate(
+            &[
+                (WellKnownAttribute::OrganizationName, "Example"),
+                (WellKnownAttribute::CommonName, "lint.example.com"),
+            ],
+            90,
+            extensions(),
+        );
+        assert_eq!(
+            check_tbs_certificate(EvMissingSubjectAttributesRule, &tbs_certificate).len(),
+            4
+        );
+    }
+}
@@ -0,0 +1,276 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Extension criticality rules.
+
+use super::RFC5280_EFFECTIVE_EPOCH_SECONDS;
+use crate::cert::extensions::AuthorityInfoAccessDescription;
+use crate::cert::extensions::AuthorityKeyIdentifier;
+use crate::cert::extensions::BasicConstraints;
+use crate::cert::extensions::CrlDistributionPoint;
+use crate::cert::extensions::KeyUsage;
+use crate::cert::extensions::SubjectKeyIdentifier;
+use crate::cert::parse::CertificateParser;
+use crate::lint::LintRule;
+use crate::lint::LintSeverity;
+use crate::lint::LintSource;
+
+/** An extension, when present, MUST (or SHOULD) be marked with the expected
+criticality.
+
+Use [Self::all] for the rules of
+[RFC5280 4.2](https://www.rfc-editor.org/rfc/rfc5280#section-4.2).
+*/
+pub struct ExtensionCriticalityRule {
+    rule_id: &'static str,
+    source: LintSource,
+    effective_epoch_seconds: u64,
+    severity: LintSeverity,
+    oid: &'static [u32],
+    critical: bool,
+    ca_only: bool,
+}
+
+impl ExtensionCriticalityRule {
+    /// Return a new instance.
+    ///
+    /// When `ca_only` is `true`, the rule only applies to CA certificates.
+    pub fn new(
+        rule_id: &'static str,
+        source: LintSource,
+        effective_epoch_seconds: u64,
+        severity: LintSeverity,
+        oid: &'static [u32],
+        critical: bool,
+        ca_only: bool,
+    ) -> Self {
+        Self {
+            rule_id,
+            source,
+            effective_epoch_seconds,
+            severity,
+            oid,
+            critical,
+            ca_only,
+        }
+    }
+
+    /// Return the extension criticality rules of RFC5280.
+    pub fn all() -> Vec<Self> {
+        [
+            // 4.2.1.9: Conforming CAs MUST include this extension in all CA
+            // certificates [...] and MUST mark the extension as critical
+            (
+                "e_basic_constraints_not_critical_in_ca",
+                LintSeverity::Error,
+                BasicConstraints::OID,
+                true,
+                true,
+            ),
+            // 4.2.1.3: When present, conforming CAs SHOULD mark this extension as critical.
+            (
+                "w_key_usage_not_critical",
+                LintSeverity::Warning,
+                KeyUsage::OID,
+                true,
+                false,
+            ),
+            // 4.2.1.1: Conforming CAs MUST mark this extension as non-critical.
+            (
+                "e_authority_key_identifier_critical",
+                LintSeverity::Error,
+                AuthorityKeyIdentifier::OID,
+                false,
+                false,
+            ),
+            // 4.2.1.2: Conforming CAs MUST mark this extension as non-critical.
+            (
+                "e_subject_key_identifier_critical",
+                LintSeverity::Error,
+                SubjectKeyIdentifier::OID,
+                false,
+                false,
+            ),
+            // 4.2.2.1: Conforming CAs MUST mark this extension as non-critical.
+            (
+                "e_authority_information_access_critical",
+                LintSeverity::Error,
+                AuthorityInfoAccessDescription::OID,
+                false,
+                false,
+            ),
+            // 4.2.1.13: The extension SHOULD be non-critical
+            (
+                "w_crl_distribution_points_critical",
+                LintSeverity::Warning,
+                CrlDistributionPoint::OID,
+                false,
+                false,
+            ),
+        ]
+        .into_iter()
+        .map(|(rule_id, severity, oid, critical, ca_only)| {
+            Self::new(
+                rule_id,
+                LintSource::Rfc5280,
+                RFC5280_EFFECTIVE_EPOCH_SECONDS,
+                severity,
+                oid,
+                critical,
+                ca_only,
+            )
+        })
+        .collect()
+    }
+}
+
+impl LintRule for ExtensionCriticalityRule {
+    fn get_rule_id(&self) -> &'static str {
+        self.rule_id
+    }
+
+    fn get_source(&self) -> LintSource {
+        self.source
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        self.effective_epoch_seconds
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        self.severity
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        (!self.ca_only || super::is_ca(cp)) && cp.as_encoded_extension_value(self.oid).is_some()
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let critical = cp
+            .get_critical_extension_oids()
+            .contains(&self.oid.to_vec());
+        if critical == self.critical {
+            vec![]
+        } else {
+            let oid = self
+                .oid
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(".");
+            let expected = if self.critical {
+                "critical"
+            } else {
+                "non-critical"
+            };
+            vec![format!("Extension '{oid}' is not marked as {expected}.")]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::build::NoSignatureTbsCertificate;
+    use crate::cert::extensions::Extensions;
+    use crate::cert::types::WellKnownAttribute;
+    use crate::cert::types::WellKnownGeneralName;
+    use crate::lint::rules::build_tbs_certificate;
+    use crate::lint::rules::check_tbs_certificate;
+
+    /// Return a CA certificate with each extension of the rules.
+    fn ca_tbs_certificate() -> NoSignatureTbsCertificate {
+        let ca = crate::test_utils::load_test_certificate("ec_ca.b64");
+        let mut extensions = Extensions::default();
+        extensions.add_basic_constraints(&BasicConstraints::new_ca(None));
+        extensions.add_key_usage(&[KeyUsage::KeyCertSign, KeyUsage::CRLSign]);
+        extensions.add_authority_key_identifier(
+            &AuthorityKeyIdentifier::from_issuers_subject_key_identifier(
+                &ca.get_subject_key_identifier_kid().unwrap().unwrap(),
+            ),
+        );
+        extensions.add_subject_key_identifier(&SubjectKeyIdentifier::from_public_key(
+            tyst::traits::se::ToPublicKey::to_public_key(&ca.get_encoded_subject_public_key_info())
+                .as_ref(),
+        ));
+        extensions.add_authority_information_access(&[AuthorityInfoAccessDescription::CaIssuers {
+            access_location: (
+                WellKnownGeneralName::Uri,
+                "http://ca.example.com/ca.crt".to_string(),
+            ),
+        }]);
+        extensions.add_crl_distribution_points("http://crl.example.com/ca.crl");
+        build_tbs_certificate(
+            &[(WellKnownAttribute::CommonName, "Lint Sub CA")],
+            365,
+            extensions,
+        )
+    }
+
+    /// Return a copy with the criticality of the extension `oid` inverted.
+    fn with_inverted_criticality(
+        tbs_certificate: &NoSignatureTbsCertificate,
+        oid: &[u32],
+    ) -> NoSignatureTbsCertificate {
+        let mut tbs_certificate = tbs_certificate.to_rasn_type();
+        tbs_certificate
+            .extensions
+            .iter_mut()
+            .flatten()
+            .filter(|extension| extension.extn_id.to_vec() == oid)
+            .for_each(|extension| extension.critical = !extension.critical);
+        NoSignatureTbsCertificate::from_rasn_type(&tbs_certificate)
+    }
+
+    #[test]
+    fn extension_criticality() {
+        let tbs_certificate = ca_tbs_certificate();
+        for rule in ExtensionCriticalityRule::all() {
+            let rule_id = rule.get_rule_id();
+            assert!(
+                check_tbs_certificate(rule, &tbs_certificate).is_empty(),
+                "{rule_id}"
+            );
+        }
+        for rule in ExtensionCriticalityRule::all() {
+            let rule_id = rule.get_rule_id();
+            let tbs_certificate = with_inverted_criticality(&tbs_certificate, rule.oid);
+            assert_eq!(
+                check_tbs_certificate(rule, &tbs_certificate).len(),
+                1,
+                "{rule_id}"
+            );
+        }
+    }
+
+    #[test]
+    fn ca_only_extension_criticality() {
+        // A non-critical Basic Constraints is allowed in leaf certificates
+        let mut extensions = Extensions::default();
+        extensions.add_basic_constraints(&BasicConstraints::new_leaf());
+        let tbs_certificate = build_tbs_certificate(
+            &[(WellKnownAttribute::CommonName, "lint.example.com")],
+            90,
+            extensions,
+        );
+        let rule = ExtensionCriticalityRule::all()
+            .into_iter()
+            .find(|rule| rule.get_rule_id() == "e_basic_constraints_not_critical_in_ca")
+            .unwrap();
+        assert!(check_tbs_certificate(rule, &tbs_certificate).is_empty());
+    }
+}
@@ -0,0 +1,95 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Extension encoding rules.
+
+use super::RFC5280_EFFECTIVE_EPOCH_SECONDS;
+use crate::cert::parse::CertificateParser;
+use crate::cert::parse::CertificateParsingError;
+use crate::lint::LintRule;
+use crate::lint::LintSeverity;
+use crate::lint::LintSource;
+
+/** The value of every extension known by this library MUST decode as the
+ASN.1 type defined for it.
+
+Other rules treat an extension that fails to decode as absent, so this rule
+is the one that reports it.
+
+See [RFC5280 4.2](https://www.rfc-editor.org/rfc/rfc5280#section-4.2).
+*/
+pub struct ExtensionNotDecodableRule;
+
+impl LintRule for ExtensionNotDecodableRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_extension_not_decodable"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::Rfc5280
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        RFC5280_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, _cp: &CertificateParser) -> bool {
+        true
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        [
+            cp.get_basic_constraints().err(),
+            cp.get_key_usage().err(),
+            cp.get_extended_key_usage().err(),
+            cp.get_authority_key_identifier().err(),
+            cp.get_subject_key_identifier_kid().err(),
+            cp.get_certificate_policies().err(),
+            cp.get_authority_information_access().err(),
+            cp.get_crl_distribution_point().err(),
+            cp.get_subject_alternative_name().err(),
+            cp.get_issuer_alternative_name().err(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|e: CertificateParsingError| e.to_string())
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::extensions::ExtendedKeyUsage;
+
+    #[test]
+    fn extension_not_decodable() {
+        let cp = crate::test_utils::load_test_certificate("h1_leaf.b64");
+        assert!(ExtensionNotDecodableRule.check(&cp).is_empty());
+        let der = crate::test_utils::load_test_certificate_der_with_malformed_extension(
+            "h1_leaf.b64",
+            ExtendedKeyUsage::OID,
+        );
+        let cp = CertificateParser::from_bytes(&der).unwrap();
+        let findings = ExtensionNotDecodableRule.check(&cp);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].contains("2.5.29.37"));
+    }
+}
@@ -0,0 +1,81 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Key usage consistency rules.
+
+use super::RFC5280_EFFECTIVE_EPOCH_SECONDS;
+use crate::cert::extensions::KeyUsage;
+use crate::cert::parse::CertificateParser;
+use crate::lint::analyze_key_usage_consistency;
+use crate::lint::LintRule;
+use crate::lint::LintSeverity;
+use crate::lint::LintSource;
+
+/** The Key Usage extension MUST be consistent with the Basic Constraints,
+the public key algorithm and the Extended Key Usage.
+
+Use [Self::all] for one rule per finding of
+[analyze_key_usage_consistency].
+*/
+pub struct KeyUsageConsistencyRule {
+    rule_id: &'static str,
+}
+
+impl KeyUsageConsistencyRule {
+    /// Return the key usage consistency rules.
+    pub fn all() -> Vec<Self> {
+        [
+            "e_key_usage_encipher_or_decipher_only_without_key_agreement",
+            "e_key_usage_cert_sign_without_ca",
+            "e_key_usage_incompatible_with_public_key_algorithm",
+            "e_ext_key_usage_inconsistent_with_key_usage",
+        ]
+        .into_iter()
+        .map(|rule_id| Self { rule_id })
+        .collect()
+    }
+}
+
+impl LintRule for KeyUsageConsistencyRule {
+    fn get_rule_id(&self) -> &'static str {
+        self.rule_id
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::Rfc5280
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        RFC5280_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        cp.as_encoded_extension_value(KeyUsage::OID).is_some()
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        analyze_key_usage_consistency(cp)
+            .into_iter()
+            .filter(|finding| finding.get_rule_id() == self.rule_id)
+            .map(|finding| finding.get_message().to_string())
+            .collect()
+    }
+}
@@ -0,0 +1,179 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Authority Information Access and CRL Distribution Points rules.
+
+use crate::cert::extensions::AuthorityInfoAccessDescription;
+use crate::cert::parse::CertificateParser;
+use crate::lint::LintRule;
+use crate::lint::LintSeverity;
+use crate::lint::LintSource;
+
+/// TLS server subscriber certificates MUST contain the Authority Information
+/// Access extension.
+///
+/// See [TLS BR 7.1.2.7.7](https://cabforum.org/working-groups/server/baseline-requirements/requirements/).
+pub struct TlsServerMissingAuthorityInformationAccessRule;
+
+impl LintRule for TlsServerMissingAuthorityInformationAccessRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_tls_server_missing_authority_information_access"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::CabfBaselineRequirements
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        // Ballot SC-062 (2023-09-15)
+        1_694_736_000
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        super::is_tls_server_subscriber(cp)
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        if cp
+            .get_authority_information_access()
+            .unwrap_or_default()
+            .is_empty()
+        {
+            vec!["Authority Information Access is missing.".to_string()]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// TLS server subscriber certificates without an OCSP responder URI MUST
+/// contain the CRL Distribution Points extension.
+///
+/// See [TLS BR 7.1.2.11.2](https://cabforum.org/working-groups/server/baseline-requirements/requirements/).
+pub struct TlsServerMissingRevocationInformationRule;
+
+impl LintRule for TlsServerMissingRevocationInformationRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_tls_server_missing_revocation_information"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::CabfBaselineRequirements
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        // Ballot SC-063 (2024-03-15)
+        1_710_460_800
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        super::is_tls_server_subscriber(cp)
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let has_ocsp = cp
+            .get_authority_information_access()
+            .unwrap_or_default()
+            .iter()
+            .any(|description| matches!(description, AuthorityInfoAccessDescription::Ocsp { .. }));
+        if has_ocsp || cp.get_crl_distribution_point().ok().flatten().is_some() {
+            vec![]
+        } else {
+            vec![
+                "Neither an OCSP responder URI nor a CRL Distribution Point is present."
+                    .to_string(),
+            ]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::types::WellKnownAttribute;
+    use crate::cert::types::WellKnownGeneralName;
+    use crate::lint::rules::build_tbs_certificate;
+    use crate::lint::rules::check_tbs_certificate;
+    use crate::lint::rules::conforming_tls_server_extensions;
+    use crate::lint::rules::tls_server_extensions;
+
+    const CN: (WellKnownAttribute, &str) = (WellKnownAttribute::CommonName, "lint.example.com");
+    const SAN: (WellKnownGeneralName, &str) = (WellKnownGeneralName::DnsName, "lint.example.com");
+
+    fn ca_issuers() -> AuthorityInfoAccessDescription {
+        AuthorityInfoAccessDescription::CaIssuers {
+            access_location: (
+                WellKnownGeneralName::Uri,
+                "http://ca.example.com/ca.crt".to_string(),
+            ),
+        }
+    }
+
+    #[test]
+    fn tls_server_missing_authority_information_access() {
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, conforming_tls_server_extensions());
+        assert!(check_tbs_certificate(
+            TlsServerMissingAuthorityInformationAccessRule,
+            &tbs_certificate
+        )
+        .is_empty());
+        let extensions = tls_server_extensions(&[SAN], &[], Some("http://crl.example.com/ca.crl"));
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, extensions);
+        assert_eq!(
+            check_tbs_certificate(
+                TlsServerMissingAuthorityInformationAccessRule,
+                &tbs_certificate
+            ),
+            vec!["Authority Information Access is missing."]
+        );
+    }
+
+    #[test]
+    fn tls_server_missing_revocation_information() {
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, conforming_tls_server_extensions());
+        assert!(
+            check_tbs_certificate(TlsServerMissingRevocationInformationRule, &tbs_certificate)
+                .is_empty()
+        );
+        // Either OCSP or a CRL Distribution Point is sufficient
+        let extensions = tls_server_extensions(
+            &[SAN],
+            &[ca_issuers()],
+            Some("http://crl.example.com/ca.crl"),
+        );
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, extensions);
+        assert!(
+            check_tbs_certificate(TlsServerMissingRevocationInformationRule, &tbs_certificate)
+                .is_empty()
+        );
+        let extensions = tls_server_extensions(&[SAN], &[ca_issuers()], None);
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, extensions);
+        assert_eq!(
+            check_tbs_certificate(TlsServerMissingRevocationInformationRule, &tbs_certificate)
+                .len(),
+            1
+        );
+    }
+}
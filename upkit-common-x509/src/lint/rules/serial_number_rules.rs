@@ -0,0 +1,196 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Serial number rules.
+
+use super::RFC5280_EFFECTIVE_EPOCH_SECONDS;
+use crate::cert::parse::CertificateParser;
+use crate::lint::LintRule;
+use crate::lint::LintSeverity;
+use crate::lint::LintSource;
+
+/// CAs MUST NOT use serial numbers longer than 20 octets.
+///
+/// See [RFC5280 4.1.2.2](https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.2).
+pub struct SerialNumberTooLongRule;
+
+impl LintRule for SerialNumberTooLongRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_serial_number_longer_than_20_octets"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::Rfc5280
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        RFC5280_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, _cp: &CertificateParser) -> bool {
+        true
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let len = cp.get_serial_number().as_bytes().len();
+        if len > 20 {
+            vec![format!("Serial number is {len} octets long.")]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// The serial number MUST be a positive integer.
+///
+/// See [RFC5280 4.1.2.2](https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.2).
+pub struct SerialNumberNotPositiveRule;
+
+impl LintRule for SerialNumberNotPositiveRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_serial_number_not_positive"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::Rfc5280
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        RFC5280_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, _cp: &CertificateParser) -> bool {
+        true
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let serial_number = cp.get_serial_number();
+        if serial_number.is_positive() {
+            vec![]
+        } else {
+            vec![format!("Serial number '{serial_number}' is not positive.")]
+        }
+    }
+}
+
+/** Serial numbers MUST contain at least 64 bits of CSPRNG output.
+
+A positive serial number with 64 random bits will almost always be at least
+8 octets long, so shorter serial numbers are reported as a warning.
+
+See [TLS BR 7.1](https://cabforum.org/working-groups/server/baseline-requirements/requirements/).
+*/
+pub struct SerialNumberLowEntropyRule;
+
+impl LintRule for SerialNumberLowEntropyRule {
+    fn get_rule_id(&self) -> &'static str {
+        "w_serial_number_low_entropy"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::CabfBaselineRequirements
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        // Ballot 164 (2016-09-30)
+        1_475_193_600
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        super::is_tls_server_subscriber(cp)
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let len = cp.get_serial_number().as_bytes().len();
+        if len < 8 {
+            vec![format!(
+                "Serial number is only {len} octets long and can't hold 64 bits of CSPRNG output."
+            )]
+        } else {
+            vec![]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::types::SerialNumber;
+    use crate::cert::types::WellKnownAttribute;
+    use crate::lint::rules::build_tbs_certificate;
+    use crate::lint::rules::check_tbs_certificate;
+    use crate::lint::rules::conforming_tls_server_extensions;
+
+    const CN: (WellKnownAttribute, &str) = (WellKnownAttribute::CommonName, "lint.example.com");
+
+    #[test]
+    fn serial_number_too_long() {
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, Default::default())
+            .with_serial_number(&SerialNumber::from_bytes_be(&[0x7f; 20]));
+        assert!(check_tbs_certificate(SerialNumberTooLongRule, &tbs_certificate).is_empty());
+        let tbs_certificate =
+            tbs_certificate.with_serial_number(&SerialNumber::from_bytes_be(&[0x7f; 21]));
+        assert_eq!(
+            check_tbs_certificate(SerialNumberTooLongRule, &tbs_certificate),
+            vec!["Serial number is 21 octets long."]
+        );
+    }
+
+    #[test]
+    fn serial_number_not_positive() {
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, Default::default());
+        assert!(check_tbs_certificate(SerialNumberNotPositiveRule, &tbs_certificate).is_empty());
+        for serial_number in [
+            SerialNumber::from_bytes_be(&[0x00]),
+            SerialNumber::from_signed_bytes_be(&[0xff]),
+        ] {
+            let tbs_certificate = tbs_certificate.with_serial_number(&serial_number);
+            assert_eq!(
+                check_tbs_certificate(SerialNumberNotPositiveRule, &tbs_certificate).len(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn serial_number_low_entropy() {
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, conforming_tls_server_extensions());
+        assert!(check_tbs_certificate(SerialNumberLowEntropyRule, &tbs_certificate).is_empty());
+        let tbs_certificate =
+            tbs_certificate.with_serial_number(&SerialNumber::from_bytes_be(&[0x01, 0x02]));
+        assert_eq!(
+            check_tbs_certificate(SerialNumberLowEntropyRule, &tbs_certificate).len(),
+            1
+        );
+        // Only applies to TLS server subscriber certificates
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, Default::default())
+            .with_serial_number(&SerialNumber::from_bytes_be(&[0x01, 0x02]));
+        assert!(check_tbs_certificate(SerialNumberLowEntropyRule, &tbs_certificate).is_empty());
+    }
+}
@@ -0,0 +1,294 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Subject Alternative Name rules.
+
+use super::CABF_BR_EFFECTIVE_EPOCH_SECONDS;
+use super::RFC5280_EFFECTIVE_EPOCH_SECONDS;
+use crate::cert::extensions::AlternativeName;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::WellKnownAttribute;
+use crate::cert::types::WellKnownGeneralName;
+use crate::lint::LintRule;
+use crate::lint::LintSeverity;
+use crate::lint::LintSource;
+
+/** When the subject is empty, the identity MUST be in a critical Subject
+Alternative Name extension.
+
+See [RFC5280 4.1.2.6](https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.6)
+and [RFC5280 4.2.1.6](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.6).
+*/
+pub struct EmptySubjectWithoutCriticalSanRule;
+
+impl LintRule for EmptySubjectWithoutCriticalSanRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_empty_subject_without_critical_subject_alternative_name"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::Rfc5280
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        RFC5280_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        super::subject_attributes(cp).is_empty()
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        if cp
+            .get_subject_alternative_name()
+            .unwrap_or_default()
+            .is_empty()
+        {
+            vec!["Subject is empty and there is no Subject Alternative Name.".to_string()]
+        } else if !cp
+            .get_critical_extension_oids()
+            .contains(&AlternativeName::OID_SUBJECT_AN.to_vec())
+        {
+            vec!["Subject is empty, but the Subject Alternative Name is not critical.".to_string()]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// TLS server subscriber certificates MUST contain a Subject Alternative
+/// Name.
+///
+/// See [TLS BR 7.1.2.7.12](https://cabforum.org/working-groups/server/baseline-requirements/requirements/).
+pub struct TlsServerMissingSanRule;
+
+impl LintRule for TlsServerMissingSanRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_tls_server_missing_subject_alternative_name"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::CabfBaselineRequirements
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        CABF_BR_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        super::is_tls_server_subscriber(cp)
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        if cp
+            .get_subject_alternative_name()
+            .unwrap_or_default()
+            .is_empty()
+        {
+            vec!["Subject Alternative Name is missing.".to_string()]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// The Subject Alternative Name of TLS server subscriber certificates MUST
+/// only contain dNSName and iPAddress entries.
+///
+/// See [TLS BR 7.1.2.7.12](https://cabforum.org/working-groups/server/baseline-requirements/requirements/).
+pub struct TlsServerSanDisallowedTypeRule;
+
+impl LintRule for TlsServerSanDisallowedTypeRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_tls_server_subject_alternative_name_disallowed_type"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::CabfBaselineRequirements
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        CABF_BR_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        super::is_tls_server_subscriber(cp)
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        cp.get_subject_alternative_name()
+            .unwrap_or_default()
+            .iter()
+            .filter(|(general_name, _)| {
+                !matches!(
+                    general_name,
+                    WellKnownGeneralName::DnsName | WellKnownGeneralName::IpAddress
+                )
+            })
+            .map(|(general_name, value)| {
+                format!(
+                    "Subject Alternative Name '{value}' of type '{}' is not allowed.",
+                    general_name.as_name()
+                )
+            })
+            .collect()
+    }
+}
+
+/// A commonName in a TLS server subscriber certificate MUST also be present
+/// in the Subject Alternative Name.
+///
+/// See [TLS BR 7.1.4.3](https://cabforum.org/working-groups/server/baseline-requirements/requirements/).
+pub struct TlsServerCommonNameNotInSanRule;
+
+impl LintRule for TlsServerCommonNameNotInSanRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_tls_server_common_name_not_in_subject_alternative_name"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::CabfBaselineRequirements
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        CABF_BR_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        super::is_tls_server_subscriber(cp)
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let subject_alternative_names = cp.get_subject_alternative_name().unwrap_or_default();
+        super::subject_attributes(cp)
+            .iter()
+            .filter(|(oid, _)| {
+                WellKnownAttribute::by_oid(oid)
+                    .is_ok_and(|attribute| attribute == WellKnownAttribute::CommonName)
+            })
+            .filter_map(|(_, encoded_value)| super::decode_string_value(encoded_value))
+            .filter(|(_, common_name)| {
+                !subject_alternative_names
+                    .iter()
+                    .any(|(_, value)| value.eq_ignore_ascii_case(common_name))
+            })
+            .map(|(_, common_name)| {
+                format!("commonName '{common_name}' is not in the Subject Alternative Name.")
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::extensions::Extensions;
+    use crate::lint::rules::build_tbs_certificate;
+    use crate::lint::rules::check_tbs_certificate;
+    use crate::lint::rules::conforming_tls_server_extensions;
+    use crate::lint::rules::tls_server_extensions;
+
+    const CN: (WellKnownAttribute, &str) = (WellKnownAttribute::CommonName, "lint.example.com");
+
+    #[test]
+    fn empty_subject_without_critical_san() {
+        let san = |critical| {
+            let mut extensions = Extensions::default();
+            extensions.add_subject_alternative_name(
+                &[(
+                    WellKnownGeneralName::DnsName,
+                    "lint.example.com".to_string(),
+                )],
+                critical,
+            );
+            extensions
+        };
+        let tbs_certificate = build_tbs_certificate(&[], 90, san(true));
+        assert!(
+            check_tbs_certificate(EmptySubjectWithoutCriticalSanRule, &tbs_certificate).is_empty()
+        );
+        let tbs_certificate = build_tbs_certificate(&[], 90, san(false));
+        assert_eq!(
+            check_tbs_certificate(EmptySubjectWithoutCriticalSanRule, &tbs_certificate),
+            vec!["Subject is empty, but the Subject Alternative Name is not critical."]
+        );
+    }
+
+    #[test]
+    fn tls_server_missing_san() {
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, conforming_tls_server_extensions());
+        assert!(check_tbs_certificate(TlsServerMissingSanRule, &tbs_certificate).is_empty());
+        let extensions = tls_server_extensions(&[], &[], Some("http://crl.example.com/ca.crl"));
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, extensions);
+        assert_eq!(
+            check_tbs_certificate(TlsServerMissingSanRule, &tbs_certificate),
+            vec!["Subject Alternative Name is missing."]
+        );
+    }
+
+    #[test]
+    fn tls_server_san_disallowed_type() {
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, conforming_tls_server_extensions());
+        assert!(check_tbs_certificate(TlsServerSanDisallowedTypeRule, &tbs_certificate).is_empty());
+        let extensions = tls_server_extensions(
+            &[
+                (WellKnownGeneralName::DnsName, "lint.example.com"),
+                (WellKnownGeneralName::Rfc822Name, "lint@example.com"),
+            ],
+            &[],
+            Some("http://crl.example.com/ca.crl"),
+        );
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, extensions);
+        assert_eq!(
+            check_tbs_certificate(TlsServerSanDisallowedTypeRule, &tbs_certificate).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn tls_server_common_name_not_in_san() {
+        let tbs_certificate = build_tbs_certificate(&[CN], 90, conforming_tls_server_extensions());
+        assert!(
+            check_tbs_certificate(TlsServerCommonNameNotInSanRule, &tbs_certificate).is_empty()
+        );
+        let tbs_certificate = build_tbs_certificate(
+            &[(WellKnownAttribute::CommonName, "other.example.com")],
+            90,
+            conforming_tls_server_extensions(),
+        );
+        assert_eq!(
+            check_tbs_certificate(TlsServerCommonNameNotInSanRule, &tbs_certificate),
+            vec!["commonName 'other.example.com' is not in the Subject Alternative Name."]
+        );
+    }
+}
@@ -0,0 +1,226 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Validity period rules.
+
+use super::RFC5280_EFFECTIVE_EPOCH_SECONDS;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::Validity;
+use crate::lint::LintRule;
+use crate::lint::LintSeverity;
+use crate::lint::LintSource;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Return the validity period in seconds including both notBefore and
+/// notAfter as defined by the CA/Browser Forum requirements.
+fn validity_period_seconds(validity: &Validity) -> u64 {
+    (validity.get_not_after() + 1).saturating_sub(validity.get_not_before())
+}
+
+/// The validity of a certificate MUST decode and the notAfter can't be before
+/// the notBefore.
+///
+/// Other rules ignore a validity that fails to decode, so this rule is the one
+/// that reports it.
+///
+/// See [RFC5280 4.1.2.5](https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.5).
+pub struct ValidityPeriodInvertedRule;
+
+impl LintRule for ValidityPeriodInvertedRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_validity_not_after_before_not_before"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::Rfc5280
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        RFC5280_EFFECTIVE_EPOCH_SECONDS
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, _cp: &CertificateParser) -> bool {
+        true
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let validity = match cp.get_validity() {
+            Ok(validity) => validity,
+            Err(e) => return vec![e.to_string()],
+        };
+        if validity.get_not_after() < validity.get_not_before() {
+            vec![format!(
+                "notAfter {} is before notBefore {}.",
+                validity.get_not_after(),
+                validity.get_not_before()
+            )]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/** Maximum validity period of TLS server subscriber certificates.
+
+The limit depends on when the certificate was issued:
+
+| notBefore on or after | Maximum days |
+|-----------------------|--------------|
+| 2020-09-01            | 398          |
+| 2026-03-15            | 200          |
+| 2027-03-15            | 100          |
+| 2029-03-15            | 47           |
+
+See [TLS BR 6.3.2](https://cabforum.org/working-groups/server/baseline-requirements/requirements/).
+*/
+pub struct TlsServerValidityTooLongRule;
+
+impl TlsServerValidityTooLongRule {
+    /// Start of each limit (epoch seconds) and maximum days, latest first.
+    const MAX_DAYS: &[(u64, u64)] = &[
+        (1_868_227_200, 47),
+        (1_805_068_800, 100),
+        (1_773_532_800, 200),
+        (1_598_918_400, 398),
+    ];
+
+    /// Return the maximum number of days allowed for a certificate issued at
+    /// `not_before` (epoch seconds).
+    pub fn max_validity_days(not_before: u64) -> Option<u64> {
+        Self::MAX_DAYS
+            .iter()
+            .find(|(start, _)| not_before >= *start)
+            .map(|(_, max_days)| *max_days)
+    }
+}
+
+impl LintRule for TlsServerValidityTooLongRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_tls_server_validity_period_too_long"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::CabfBaselineRequirements
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        Self::MAX_DAYS.last().unwrap().0
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        super::is_tls_server_subscriber(cp)
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let Ok(validity) = cp.get_validity() else {
+            return vec![];
+        };
+        let Some(max_days) = Self::max_validity_days(validity.get_not_before()) else {
+            return vec![];
+        };
+        let validity_period = validity_period_seconds(&validity);
+        if validity_period > max_days * SECONDS_PER_DAY {
+            vec![format!(
+                "Validity period of {validity_period} seconds exceeds {max_days} days."
+            )]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// S/MIME subscriber certificates MUST NOT have a validity period greater
+/// than 825 days.
+///
+/// See [S/MIME BR 6.3.2](https://cabforum.org/working-groups/smime/requirements/).
+pub struct SmimeValidityTooLongRule;
+
+impl SmimeValidityTooLongRule {
+    const MAX_DAYS: u64 = 825;
+}
+
+impl LintRule for SmimeValidityTooLongRule {
+    fn get_rule_id(&self) -> &'static str {
+        "e_smime_validity_period_too_long"
+    }
+
+    fn get_source(&self) -> LintSource {
+        LintSource::CabfSmimeBaselineRequirements
+    }
+
+    fn get_effective_epoch_seconds(&self) -> u64 {
+        // S/MIME BR 1.0.0 (2023-09-01)
+        1_693_526_400
+    }
+
+    fn get_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn is_applicable(&self, cp: &CertificateParser) -> bool {
+        super::is_smime_subscriber(cp)
+    }
+
+    fn check(&self, cp: &CertificateParser) -> Vec<String> {
+        let Ok(validity) = cp.get_validity() else {
+            return vec![];
+        };
+        let validity_period = validity_period_seconds(&validity);
+        if validity_period > Self::MAX_DAYS * SECONDS_PER_DAY {
+            vec![format!(
+                "Validity period of {validity_period} seconds exceeds {} days.",
+                Self::MAX_DAYS
+            )]
+        } else {
+            vec![]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tls_server_max_validity_days() {
+        assert_eq!(
+            TlsServerValidityTooLongRule::max_validity_days(1_598_918_399),
+            None
+        );
+        assert_eq!(
+            TlsServerValidityTooLongRule::max_validity_days(1_598_918_400),
+            Some(398)
+        );
+        assert_eq!(
+            TlsServerValidityTooLongRule::max_validity_days(1_790_000_000),
+            Some(200)
+        );
+        assert_eq!(
+            TlsServerValidityTooLongRule::max_validity_days(1_900_000_000),
+            Some(47)
+        );
+    }
+}
@@ -0,0 +1,586 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! [RFC7292](https://www.rfc-editor.org/rfc/rfc7292) PKCS #12 (PFX) import
+//! and export of a private key with its certificate chain.
+
+/*
+PFX ::= SEQUENCE {
+    version     INTEGER {v3(3)}(v3,...),
+    authSafe    ContentInfo,
+    macData     MacData OPTIONAL
+}
+
+MacData ::= SEQUENCE {
+    mac         DigestInfo,
+    macSalt     OCTET STRING,
+    iterations  INTEGER DEFAULT 1
+}
+
+AuthenticatedSafe ::= SEQUENCE OF ContentInfo
+    -- Data if unencrypted
+    -- EncryptedData if password-encrypted
+
+SafeContents ::= SEQUENCE OF SafeBag
+
+SafeBag ::= SEQUENCE {
+    bagId          BAG-TYPE.&id ({PKCS12BagSet}),
+    bagValue       [0] EXPLICIT BAG-TYPE.&Type({PKCS12BagSet}{@bagId}),
+    bagAttributes  SET OF PKCS12Attribute OPTIONAL
+}
+
+CertBag ::= SEQUENCE {
+    certId      BAG-TYPE.&id   ({CertTypes}),
+    certValue   [0] EXPLICIT BAG-TYPE.&Type ({CertTypes}{@certId})
+}
+
+EncryptedData ::= SEQUENCE {
+    version               CMSVersion,
+    encryptedContentInfo  EncryptedContentInfo,
+    unprotectedAttrs  [1] IMPLICIT UnprotectedAttributes OPTIONAL }
+
+EncryptedContentInfo ::= SEQUENCE {
+    contentType                 ContentType,
+    contentEncryptionAlgorithm  ContentEncryptionAlgorithmIdentifier,
+    encryptedContent        [0] IMPLICIT EncryptedContent OPTIONAL }
+*/
+
+mod pkcs12_builder;
+mod pkcs12_error;
+mod pkcs12_pbe;
+
+pub use self::pkcs12_builder::Pkcs12Builder;
+pub use self::pkcs12_error::Pkcs12Error;
+pub use self::pkcs12_error::Pkcs12ErrorKind;
+pub use self::pkcs12_pbe::Pkcs12MacAlgorithm;
+use crate::cert::parse::CertificateParser;
+use crate::cert::validate::CertificatePathValidator;
+use crate::encdec::rasn::Attribute;
+use crate::key::EncryptedPrivateKeyInfo;
+use crate::key::Pkcs8PrivateKey;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct Pfx {
+    version: Integer,
+    auth_safe: ContentInfo,
+    mac_data: Option<MacData>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct ContentInfo {
+    content_type: ObjectIdentifier,
+    #[rasn(tag(explicit(context, 0)))]
+    content: Option<Any>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct MacData {
+    mac: DigestInfo,
+    mac_salt: OctetString,
+    /// `None` means 1
+    iterations: Option<Integer>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct DigestInfo {
+    digest_algorithm: AlgorithmIdentifier,
+    digest: OctetString,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct EncryptedData {
+    version: Integer,
+    encrypted_content_info: EncryptedContentInfo,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct EncryptedContentInfo {
+    content_type: ObjectIdentifier,
+    content_encryption_algorithm: AlgorithmIdentifier,
+    #[rasn(tag(context, 0))]
+    encrypted_content: Option<OctetString>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct SafeBag {
+    bag_id: ObjectIdentifier,
+    #[rasn(tag(explicit(context, 0)))]
+    bag_value: Any,
+    bag_attributes: Option<SetOf<Attribute>>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct CertBag {
+    cert_id: ObjectIdentifier,
+    #[rasn(tag(explicit(context, 0)))]
+    cert_value: OctetString,
+}
+
+/// `id-data`
+const OID_DATA: &[u32] = &[1, 2, 840, 113549, 1, 7, 1];
+/// `id-encryptedData`
+const OID_ENCRYPTED_DATA: &[u32] = &[1, 2, 840, 113549, 1, 7, 6];
+/// `keyBag`
+const OID_KEY_BAG: &[u32] = &[1, 2, 840, 113549, 1, 12, 10, 1, 1];
+/// `pkcs8ShroudedKeyBag`
+const OID_PKCS8_SHROUDED_KEY_BAG: &[u32] = &[1, 2, 840, 113549, 1, 12, 10, 1, 2];
+/// `certBag`
+const OID_CERT_BAG: &[u32] = &[1, 2, 840, 113549, 1, 12, 10, 1, 3];
+/// `x509Certificate`
+const OID_X509_CERTIFICATE: &[u32] = &[1, 2, 840, 113549, 1, 9, 22, 1];
+/// `pkcs-9-at-friendlyName`
+const OID_FRIENDLY_NAME: &[u32] = &[1, 2, 840, 113549, 1, 9, 20];
+/// `pkcs-9-at-localKeyId`
+const OID_LOCAL_KEY_ID: &[u32] = &[1, 2, 840, 113549, 1, 9, 21];
+
+/// Attributes of a PKCS #12 key or certificate bag.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pkcs12BagAttributes {
+    friendly_name: Option<String>,
+    local_key_id: Option<Vec<u8>>,
+}
+
+impl Pkcs12BagAttributes {
+    /// Return a new instance.
+    pub fn new(friendly_name: Option<String>, local_key_id: Option<Vec<u8>>) -> Self {
+        Self {
+            friendly_name,
+            local_key_id,
+        }
+    }
+
+    /// Return the `friendlyName` used by e.g. key stores as the alias.
+    pub fn get_friendly_name(&self) -> Option<&str> {
+        self.friendly_name.as_deref()
+    }
+
+    /// Return the `localKeyId` that links a certificate to its private key.
+    pub fn get_local_key_id(&self) -> Option<&[u8]> {
+        self.local_key_id.as_deref()
+    }
+
+    fn from_rasn_type(bag_attributes: &Option<SetOf<Attribute>>) -> Self {
+        let mut ret = Self::default();
+        for attribute in bag_attributes.iter().flat_map(|set| set.to_vec()) {
+            let value = attribute.values.to_vec().into_iter().next();
+            match attribute.r#type.to_vec().as_slice() {
+                OID_FRIENDLY_NAME => {
+                    ret.friendly_name = value.and_then(crate::encdec::rasn::any_as_string);
+                }
+                OID_LOCAL_KEY_ID => {
+                    ret.local_key_id = value
+                        .and_then(|value| rasn::der::decode::<OctetString>(value.as_bytes()).ok())
+                        .map(|local_key_id| local_key_id.to_vec());
+                }
+                _ => {}
+            }
+        }
+        ret
+    }
+
+    fn to_rasn_type(&self) -> Result<Option<SetOf<Attribute>>, Pkcs12Error> {
+        let mut ret = SetOf::new();
+        if let Some(friendly_name) = &self.friendly_name {
+            let friendly_name = BmpString::try_from(friendly_name.as_str()).map_err(|e| {
+                let msg = format!("Friendly name '{friendly_name}' is not a BMPString: {e}");
+                Pkcs12ErrorKind::EncodingError.error_with_msg(&msg)
+            })?;
+            let mut values = SetOf::new();
+            values.insert(Any::new(rasn::der::encode(&friendly_name).unwrap()));
+            ret.insert(Attribute {
+                r#type: ObjectIdentifier::new_unchecked(OID_FRIENDLY_NAME.into()),
+                values,
+            });
+        }
+        if let Some(local_key_id) = &self.local_key_id {
+            let mut values = SetOf::new();
+            values.insert(Any::new(
+                rasn::der::encode(&OctetString::from(local_key_id.to_owned())).unwrap(),
+            ));
+            ret.insert(Attribute {
+                r#type: ObjectIdentifier::new_unchecked(OID_LOCAL_KEY_ID.into()),
+                values,
+            });
+        }
+        Ok((!ret.to_vec().is_empty()).then_some(ret))
+    }
+}
+
+/** PKCS #12 (PFX) parser.
+
+The whole PFX is decrypted when parsed. The integrity MAC (when present) is
+verified before anything is decrypted. BER encoded input (as produced by some
+Windows and Java versions) is accepted.
+*/
+#[derive(Clone)]
+pub struct Pkcs12Parser {
+    private_key: Option<(Pkcs8PrivateKey, Pkcs12BagAttributes)>,
+    certificates: Vec<(CertificateParser, Pkcs12BagAttributes)>,
+}
+
+impl Pkcs12Parser {
+    /// Create a new instance from `encoded_pfx` bytes.
+    pub fn from_bytes(encoded_pfx: &[u8], password: &str) -> Result<Self, Pkcs12Error> {
+        let pfx = Self::decode::<Pfx>(encoded_pfx)?;
+        let auth_safe = Self::content_as_data(&pfx.auth_safe)?;
+        if let Some(mac_data) = &pfx.mac_data {
+            Self::verify_mac(mac_data, password, &auth_safe)?;
+        } else {
+            log::debug!("PFX without integrity MAC.");
+        }
+        let mut ret = Self {
+            private_key: None,
+            certificates: vec![],
+        };
+        for content_info in Self::decode::<SequenceOf<ContentInfo>>(&auth_safe)? {
+            let safe_contents = if content_info.content_type.to_vec().eq(OID_ENCRYPTED_DATA) {
+                Self::decrypt_content(&content_info, password)?
+            } else {
+                Self::content_as_data(&content_info)?
+            };
+            for safe_bag in Self::decode::<SequenceOf<SafeBag>>(&safe_contents)? {
+                ret.add_safe_bag(&safe_bag, password)?;
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Return the private key (if any) and its attributes.
+    pub fn get_private_key(&self) -> Option<&(Pkcs8PrivateKey, Pkcs12BagAttributes)> {
+        self.private_key.as_ref()
+    }
+
+    /// Return all certificates and their attributes in the order they were
+    /// found.
+    pub fn get_certificates(&self) -> &[(CertificateParser, Pkcs12BagAttributes)] {
+        &self.certificates
+    }
+
+    /** Return the certificate chain ordered from leaf to root.
+
+    When the private key has a `localKeyId`, certificates with a different
+    `localKeyId` are not considered a leaf. The chain is ordered using
+    [CertificatePathValidator::order_leaf_chain].
+    */
+    pub fn get_certificate_chain(&self) -> Result<Vec<CertificateParser>, Pkcs12Error> {
+        let key_local_key_id = self
+            .private_key
+            .as_ref()
+            .and_then(|(_, attributes)| attributes.get_local_key_id());
+        let certificates = self
+            .certificates
+            .iter()
+            .filter(|(_, attributes)| {
+                key_local_key_id.is_none()
+                    || attributes.get_local_key_id().is_none()
+                    || attributes.get_local_key_id().eq(&key_local_key_id)
+            })
+            .map(|(certificate, _)| certificate.to_owned())
+            .collect();
+        CertificatePathValidator::order_leaf_chain(certificates)
+            .map_err(|e| Pkcs12ErrorKind::InvalidCertificateChain.error_with_msg(&e.to_string()))
+    }
+
+    fn add_safe_bag(&mut self, safe_bag: &SafeBag, password: &str) -> Result<(), Pkcs12Error> {
+        let attributes = Pkcs12BagAttributes::from_rasn_type(&safe_bag.bag_attributes);
+        let bag_value = safe_bag.bag_value.as_bytes();
+        match safe_bag.bag_id.to_vec().as_slice() {
+            OID_KEY_BAG => {
+                let private_key =
+                    Pkcs8PrivateKey::from_der(bag_value).map_err(pkcs12_pbe::from_key_error)?;
+                self.set_private_key(private_key, attributes)?;
+            }
+            OID_PKCS8_SHROUDED_KEY_BAG => {
+                let encrypted_private_key_info =
+                    Self::decode::<EncryptedPrivateKeyInfo>(bag_value)?;
+                let der = pkcs12_pbe::decrypt(
+                    &encrypted_private_key_info.encryption_algorithm,
+                    password,
+                    &encrypted_private_key_info.encrypted_data,
+                )?;
+                let private_key = Pkcs8PrivateKey::from_der(&der).map_err(|_| {
+                    Pkcs12ErrorKind::DecryptionFailure
+                        .error_with_msg("Decrypted data is not a private key.")
+                })?;
+                self.set_private_key(private_key, attributes)?;
+            }
+            OID_CERT_BAG => {
+                let cert_bag = Self::decode::<CertBag>(bag_value)?;
+                if cert_bag.cert_id.to_vec().eq(OID_X509_CERTIFICATE) {
+                    let certificate =
+                        CertificateParser::from_bytes(&cert_bag.cert_value).map_err(|e| {
+                            Pkcs12ErrorKind::DecodingError.error_with_msg(&e.to_string())
+                        })?;
+                    self.certificates.push((certificate, attributes));
+                } else {
+                    log::debug!(
+                        "Ignoring certificate bag of type '{}'.",
+                        crate::encdec::oid::as_string(&cert_bag.cert_id)
+                    );
+                }
+            }
+            other => {
+                log::debug!(
+                    "Ignoring PKCS #12 bag of type '{}'.",
+                    crate::encdec::oid::as_string(other)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn set_private_key(
+        &mut self,
+        private_key: Pkcs8PrivateKey,
+        attributes: Pkcs12BagAttributes,
+    ) -> Result<(), Pkcs12Error> {
+        if self.private_key.is_some() {
+            return Err(Pkcs12ErrorKind::DecodingError
+                .error_with_msg("PFX with more than one private key is not supported."));
+        }
+        self.private_key = Some((private_key, attributes));
+        Ok(())
+    }
+
+    fn verify_mac(mac_data: &MacData, password: &str, auth_safe: &[u8]) -> Result<(), Pkcs12Error> {
+        let digest_oid = mac_data.mac.digest_algorithm.algorithm.to_vec();
+        let mac_algorithm = Pkcs12MacAlgorithm::from_oid(&digest_oid).ok_or_else(|| {
+            let msg = format!(
+                "Unsupported MAC digest algorithm '{}'.",
+                crate::encdec::oid::as_string(&digest_oid)
+            );
+            Pkcs12ErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+        })?;
+        let iterations = mac_data
+            .iterations
+            .as_ref()
+            .map_or(Ok(1), pkcs12_pbe::decode_iterations)?;
+        if !mac_algorithm.verify(
+            password,
+            &mac_data.mac_salt,
+            iterations,
+            auth_safe,
+            &mac_data.mac.digest,
+        ) {
+            return Err(Pkcs12ErrorKind::MacVerificationFailure.error());
+        }
+        Ok(())
+    }
+
+    fn decrypt_content(content_info: &ContentInfo, password: &str) -> Result<Vec<u8>, Pkcs12Error> {
+        let encrypted_data = content_info
+            .content
+            .as_ref()
+            .ok_or_else(|| Pkcs12ErrorKind::DecodingError.error_with_msg("Missing content."))
+            .and_then(|content| Self::decode::<EncryptedData>(content.as_bytes()))?;
+        let encrypted_content_info = encrypted_data.encrypted_content_info;
+        let ciphertext = encrypted_content_info.encrypted_content.ok_or_else(|| {
+            Pkcs12ErrorKind::DecodingError.error_with_msg("Missing encrypted content.")
+        })?;
+        pkcs12_pbe::decrypt(
+            &encrypted_content_info.content_encryption_algorithm,
+            password,
+            &ciphertext,
+        )
+    }
+
+    fn content_as_data(content_info: &ContentInfo) -> Result<Vec<u8>, Pkcs12Error> {
+        if !content_info.content_type.to_vec().eq(OID_DATA) {
+            let msg = format!(
+                "Unsupported content type '{}'.",
+                crate::encdec::oid::as_string(&content_info.content_type)
+            );
+            return Err(Pkcs12ErrorKind::UnsupportedAlgorithm.error_with_msg(&msg));
+        }
+        content_info
+            .content
+            .as_ref()
+            .ok_or_else(|| Pkcs12ErrorKind::DecodingError.error_with_msg("Missing content."))
+            .and_then(|content| Self::decode::<OctetString>(content.as_bytes()))
+            .map(|data| data.to_vec())
+    }
+
+    fn decode<T: rasn::Decode>(encoded: &[u8]) -> Result<T, Pkcs12Error> {
+        rasn::ber::decode::<T>(encoded)
+            .map_err(|e| Pkcs12ErrorKind::DecodingError.error_with_msg(&e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::types::WellKnownAlgorithm;
+    use crate::key::PbeCipher;
+    use crate::key::PbeKdf;
+    use crate::key::PbeParameters;
+    use crate::key::PbePrf;
+    use crate::test_utils::*;
+    use tyst::traits::se::ToPublicKey;
+    use tyst::Tyst;
+
+    /// Assert that the PFX holds the `ec_ca` key and certificate.
+    fn assert_ec_ca_pfx(pfx: &Pkcs12Parser, friendly_name: Option<&str>) {
+        let (private_key, key_attributes) = pfx.get_private_key().unwrap();
+        assert_eq!(key_attributes.get_friendly_name(), friendly_name);
+        assert!(key_attributes.get_local_key_id().is_some());
+        let (_, certificate_attributes) = &pfx.get_certificates()[0];
+        assert_eq!(
+            certificate_attributes.get_local_key_id(),
+            key_attributes.get_local_key_id()
+        );
+        let chain = pfx.get_certificate_chain().unwrap();
+        assert_eq!(chain.len(), 1);
+        assert_eq!(
+            chain[0].get_encoded_certificate(),
+            load_test_certificate_der("ec_ca.b64")
+        );
+        // The private key must match the public key of the certificate
+        let oid = crate::encdec::oid::as_string(WellKnownAlgorithm::EcdsaWithSha256.as_oid());
+        let mut se = Tyst::instance().ses().by_oid(&oid).unwrap();
+        let signature = se.sign(private_key.to_private_key().as_ref(), b"data");
+        let public_key = chain[0]
+            .get_encoded_subject_public_key_info()
+            .to_public_key();
+        assert!(se.verify(public_key.as_ref(), &signature, b"data"));
+    }
+
+    fn error_kind(result: Result<Pkcs12Parser, Pkcs12Error>) -> Pkcs12ErrorKind {
+        result.err().unwrap().kind().clone()
+    }
+
+    #[test]
+    fn import_openssl_pfx() {
+        // PBES2 with AES-256-CBC, and legacy RC2-40 and 3DES with SHA-1 MAC
+        for name in ["ec_ca_pbes2.p12.b64", "ec_ca_legacy.p12.b64"] {
+            let encoded = load_test_resource_b64(name);
+            let pfx = Pkcs12Parser::from_bytes(&encoded, "secret").unwrap();
+            assert_ec_ca_pfx(&pfx, Some("Test EC CA"));
+            assert_eq!(
+                error_kind(Pkcs12Parser::from_bytes(&encoded, "wrong")),
+                Pkcs12ErrorKind::MacVerificationFailure
+            );
+        }
+    }
+
+    #[test]
+    fn export_and_import() {
+        let private_key = load_test_private_key("ec_ca_key.pem");
+        let chain = vec![load_test_certificate_der("ec_ca.b64")];
+        let pbe_parameters = PbeParameters::new(
+            PbeKdf::Pbkdf2 {
+                iterations: 1000,
+                prf: PbePrf::HmacWithSha512,
+            },
+            PbeCipher::Aes128Gcm,
+        );
+        for (builder, friendly_name) in [
+            (
+                Pkcs12Builder::new(private_key.clone(), &chain)
+                    .unwrap()
+                    .with_pbe_parameters(pbe_parameters),
+                None,
+            ),
+            (
+                Pkcs12Builder::new(private_key.clone(), &chain)
+                    .unwrap()
+                    .with_legacy_compatibility()
+                    .with_friendly_name("Test EC CA"),
+                Some("Test EC CA"),
+            ),
+        ] {
+            let encoded = builder.build("secret").unwrap();
+            let pfx = Pkcs12Parser::from_bytes(&encoded, "secret").unwrap();
+            assert_ec_ca_pfx(&pfx, friendly_name);
+        }
+        // Friendly names outside the Basic Multilingual Plane can't be encoded
+        let err = Pkcs12Builder::new(private_key.clone(), &chain)
+            .unwrap()
+            .with_friendly_name("\u{1F511}")
+            .build("secret")
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), &Pkcs12ErrorKind::EncodingError);
+        let err = Pkcs12Builder::new(private_key, &chain)
+            .unwrap()
+            .with_mac_iterations(0)
+            .build("secret")
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), &Pkcs12ErrorKind::UnsupportedAlgorithm);
+    }
+
+    #[test]
+    fn private_key_must_match_leaf() {
+        let recipient_chain = vec![
+            load_test_certificate_der("rsa_recipient.b64"),
+            load_test_certificate_der("ec_ca.b64"),
+        ];
+        assert!(Pkcs12Builder::new(
+            load_test_private_key("rsa_recipient_key.pem"),
+            &recipient_chain
+        )
+        .is_ok());
+        let ca_chain = vec![load_test_certificate_der("ec_ca.b64")];
+        for (private_key, chain) in [
+            // Same curve, different key
+            ("ec_recipient_key.pem", &ca_chain),
+            // Different algorithm
+            ("rsa_recipient_key.pem", &ca_chain),
+            // The issuer's key
+            ("ec_ca_key.pem", &recipient_chain),
+        ] {
+            let err = Pkcs12Builder::new(load_test_private_key(private_key), chain)
+                .err()
+                .unwrap();
+            assert_eq!(err.kind(), &Pkcs12ErrorKind::PrivateKeyMismatch);
+        }
+    }
+
+    #[test]
+    fn untrusted_mac_iterations_are_rejected() {
+        let encoded = load_test_resource_b64("ec_ca_pbes2.p12.b64");
+        let pfx = Pkcs12Parser::decode::<Pfx>(&encoded).unwrap();
+        for (iterations, expected) in [
+            (Integer::from(-1), Pkcs12ErrorKind::DecodingError),
+            (Integer::from(0), Pkcs12ErrorKind::UnsupportedAlgorithm),
+            (
+                Integer::from(1u64 << 32),
+                Pkcs12ErrorKind::UnsupportedAlgorithm,
+            ),
+            (
+                Integer::from(num_bigint::BigInt::from(u64::MAX) + 1),
+                Pkcs12ErrorKind::DecodingError,
+            ),
+            (
+                Integer::from(PbeParameters::MAX_PBKDF2_ITERATIONS + 1),
+                Pkcs12ErrorKind::UnsupportedAlgorithm,
+            ),
+        ] {
+            let mut tampered = pfx.clone();
+            tampered.mac_data.as_mut().unwrap().iterations = Some(iterations);
+            assert_eq!(
+                error_kind(Pkcs12Parser::from_bytes(
+                    &rasn::der::encode(&tampered).unwrap(),
+                    "secret"
+                )),
+                expected
+            );
+        }
+    }
+}
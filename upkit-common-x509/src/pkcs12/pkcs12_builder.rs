@@ -0,0 +1,243 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PKCS #12 (PFX) builder.
+
+use super::pkcs12_pbe;
+use super::pkcs12_pbe::Pkcs12LegacyCipher;
+use super::CertBag;
+use super::ContentInfo;
+use super::DigestInfo;
+use super::EncryptedContentInfo;
+use super::EncryptedData;
+use super::MacData;
+use super::Pfx;
+use super::Pkcs12BagAttributes;
+use super::Pkcs12Error;
+use super::Pkcs12ErrorKind;
+use super::Pkcs12MacAlgorithm;
+use super::SafeBag;
+use super::OID_CERT_BAG;
+use super::OID_DATA;
+use super::OID_ENCRYPTED_DATA;
+use super::OID_PKCS8_SHROUDED_KEY_BAG;
+use super::OID_X509_CERTIFICATE;
+use crate::cert::parse::CertificateParser;
+use crate::cert::validate::CertificatePathValidator;
+use crate::key::EncryptedPrivateKeyInfo;
+use crate::key::PbeParameters;
+use crate::key::Pkcs8PrivateKey;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+use sha1::Digest;
+
+/** PKCS #12 (PFX) builder.
+
+By default the private key and the certificates are protected with the
+default [PbeParameters] (PBES2 with PBKDF2-HMAC-SHA256 and AES-256-CBC) and
+the integrity MAC uses HMAC-SHA256.
+
+[Self::with_legacy_compatibility] switches to `pbeWithSHAAnd3-KeyTripleDES-CBC`
+and HMAC-SHA1 for consumers like older Windows and Java versions that can't
+handle PBES2.
+*/
+pub struct Pkcs12Builder {
+    private_key: Pkcs8PrivateKey,
+    certificate_chain: Vec<CertificateParser>,
+    friendly_name: Option<String>,
+    pbe_parameters: PbeParameters,
+    legacy_compatibility: bool,
+    mac_iterations: u32,
+}
+
+impl Pkcs12Builder {
+    /// Iteration count of the integrity MAC and legacy encryption.
+    pub const DEFAULT_ITERATIONS: u32 = 2048;
+
+    /** Return a new instance.
+
+    The DER encoded certificates may be in any order and are ordered from leaf
+    to root using [CertificatePathValidator::order_leaf_chain].
+
+    The private key must belong to the leaf certificate.
+    */
+    pub fn new(
+        private_key: Pkcs8PrivateKey,
+        certificate_chain_der: &[Vec<u8>],
+    ) -> Result<Self, Pkcs12Error> {
+        let certificates = certificate_chain_der
+            .iter()
+            .map(|der| CertificateParser::from_bytes(der))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Pkcs12ErrorKind::DecodingError.error_with_msg(&e.to_string()))?;
+        let certificate_chain = CertificatePathValidator::order_leaf_chain(certificates)
+            .map_err(|e| Pkcs12ErrorKind::InvalidCertificateChain.error_with_msg(&e.to_string()))?;
+        let leaf = certificate_chain.first().ok_or_else(|| {
+            Pkcs12ErrorKind::InvalidCertificateChain.error_with_msg("No leaf certificate.")
+        })?;
+        if !private_key.matches_subject_public_key_info(leaf.as_encoded_subject_public_key_info()) {
+            let msg = format!(
+                "The private key does not match the public key of the leaf certificate with fingerprint '{}'.",
+                leaf.fingerprint()
+            );
+            return Err(Pkcs12ErrorKind::PrivateKeyMismatch.error_with_msg(&msg));
+        }
+        Ok(Self {
+            private_key,
+            certificate_chain,
+            friendly_name: None,
+            pbe_parameters: PbeParameters::default(),
+            legacy_compatibility: false,
+            mac_iterations: Self::DEFAULT_ITERATIONS,
+        })
+    }
+
+    /// Set the `friendlyName` (alias) of the private key and leaf certificate.
+    pub fn with_friendly_name(mut self, friendly_name: &str) -> Self {
+        self.friendly_name = Some(friendly_name.to_string());
+        self
+    }
+
+    /// Use non-default PBES2 parameters.
+    pub fn with_pbe_parameters(mut self, pbe_parameters: PbeParameters) -> Self {
+        self.pbe_parameters = pbe_parameters;
+        self
+    }
+
+    /// Use legacy algorithms that are understood by older consumers.
+    pub fn with_legacy_compatibility(mut self) -> Self {
+        self.legacy_compatibility = true;
+        self
+    }
+
+    /// Use a non-default integrity MAC iteration count.
+    pub fn with_mac_iterations(mut self, mac_iterations: u32) -> Self {
+        self.mac_iterations = mac_iterations;
+        self
+    }
+
+    /// Return the DER encoded PFX protected by the `password`.
+    pub fn build(&self, password: &str) -> Result<Vec<u8>, Pkcs12Error> {
+        // Like OpenSSL: the localKeyId is the SHA-1 fingerprint of the leaf
+        let leaf = self.certificate_chain.first().unwrap();
+        let local_key_id = sha1::Sha1::digest(leaf.get_encoded_certificate()).to_vec();
+        let leaf_attributes =
+            Pkcs12BagAttributes::new(self.friendly_name.to_owned(), Some(local_key_id))
+                .to_rasn_type()?;
+        pkcs12_pbe::check_iterations(u64::from(self.mac_iterations))?;
+        // Private key in a shrouded key bag
+        let (encryption_algorithm, encrypted_data) =
+            self.encrypt(password, &self.private_key.to_der())?;
+        let key_bag = SafeBag {
+            bag_id: ObjectIdentifier::new_unchecked(OID_PKCS8_SHROUDED_KEY_BAG.into()),
+            bag_value: Any::new(
+                rasn::der::encode(&EncryptedPrivateKeyInfo {
+                    encryption_algorithm,
+                    encrypted_data: OctetString::from(encrypted_data),
+                })
+                .unwrap(),
+            ),
+            bag_attributes: leaf_attributes.clone(),
+        };
+        // Certificates in cert bags inside EncryptedData
+        let cert_bags = self
+            .certificate_chain
+            .iter()
+            .enumerate()
+            .map(|(index, certificate)| SafeBag {
+                bag_id: ObjectIdentifier::new_unchecked(OID_CERT_BAG.into()),
+                bag_value: Any::new(
+                    rasn::der::encode(&CertBag {
+                        cert_id: ObjectIdentifier::new_unchecked(OID_X509_CERTIFICATE.into()),
+                        cert_value: OctetString::from(certificate.get_encoded_certificate()),
+                    })
+                    .unwrap(),
+                ),
+                bag_attributes: if index == 0 {
+                    leaf_attributes.clone()
+                } else {
+                    None
+                },
+            })
+            .collect::<Vec<_>>();
+        let (content_encryption_algorithm, encrypted_content) =
+            self.encrypt(password, &rasn::der::encode(&cert_bags).unwrap())?;
+        let encrypted_data = EncryptedData {
+            version: Integer::from(0),
+            encrypted_content_info: EncryptedContentInfo {
+                content_type: ObjectIdentifier::new_unchecked(OID_DATA.into()),
+                content_encryption_algorithm,
+                encrypted_content: Some(OctetString::from(encrypted_content)),
+            },
+        };
+        let auth_safe = rasn::der::encode(&vec![
+            ContentInfo {
+                content_type: ObjectIdentifier::new_unchecked(OID_ENCRYPTED_DATA.into()),
+                content: Some(Any::new(rasn::der::encode(&encrypted_data).unwrap())),
+            },
+            Self::data_content_info(&rasn::der::encode(&vec![key_bag]).unwrap()),
+        ])
+        .unwrap();
+        // Integrity MAC over the AuthenticatedSafe
+        let mac_algorithm = if self.legacy_compatibility {
+            Pkcs12MacAlgorithm::Sha1
+        } else {
+            Pkcs12MacAlgorithm::Sha256
+        };
+        let mac_salt = PbeParameters::random(16);
+        let mac = mac_algorithm.mac(password, &mac_salt, self.mac_iterations, &auth_safe);
+        let pfx = Pfx {
+            version: Integer::from(3),
+            auth_safe: Self::data_content_info(&auth_safe),
+            mac_data: Some(MacData {
+                mac: DigestInfo {
+                    digest_algorithm: AlgorithmIdentifier {
+                        algorithm: ObjectIdentifier::new_unchecked(mac_algorithm.as_oid().into()),
+                        parameters: Some(Any::new(rasn::der::encode(&()).unwrap())),
+                    },
+                    digest: OctetString::from(mac),
+                },
+                mac_salt: OctetString::from(mac_salt),
+                iterations: Some(Integer::from(self.mac_iterations)),
+            }),
+        };
+        Ok(rasn::der::encode(&pfx).unwrap())
+    }
+
+    fn encrypt(
+        &self,
+        password: &str,
+        plaintext: &[u8],
+    ) -> Result<(AlgorithmIdentifier, Vec<u8>), Pkcs12Error> {
+        if self.legacy_compatibility {
+            Pkcs12LegacyCipher::TripleDesCbc.encrypt(password, self.mac_iterations, plaintext)
+        } else {
+            self.pbe_parameters
+                .encrypt(password.as_bytes(), plaintext)
+                .map_err(pkcs12_pbe::from_key_error)
+        }
+    }
+
+    fn data_content_info(data: &[u8]) -> ContentInfo {
+        ContentInfo {
+            content_type: ObjectIdentifier::new_unchecked(OID_DATA.into()),
+            content: Some(Any::new(
+                rasn::der::encode(&OctetString::from(data.to_vec())).unwrap(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PKCS #12 errors.
+
+use std::error::Error;
+use std::fmt;
+
+/// Cause of PKCS #12 error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pkcs12ErrorKind {
+    /// Failure to decode the BER or DER encoded PFX or one of its parts.
+    DecodingError,
+    /// The PFX uses an algorithm that is not supported.
+    UnsupportedAlgorithm,
+    /// The integrity MAC does not match. Usually due to a wrong password.
+    MacVerificationFailure,
+    /// Failure to decrypt a bag. Usually due to a wrong password.
+    DecryptionFailure,
+    /// Failure to encrypt a bag.
+    EncryptionFailure,
+    /// Failure to encode a bag attribute (e.g. a `friendlyName` outside the
+    /// Basic Multilingual Plane).
+    EncodingError,
+    /// The certificates do not form a single chain.
+    InvalidCertificateChain,
+    /// The private key does not belong to the leaf certificate.
+    PrivateKeyMismatch,
+}
+
+impl Pkcs12ErrorKind {
+    /// Create a new instance with an error message.
+    pub fn error_with_msg(self, msg: &str) -> Pkcs12Error {
+        Pkcs12Error {
+            kind: self,
+            msg: Some(msg.to_string()),
+        }
+    }
+
+    /// Create a new instance without an error message.
+    pub fn error(self) -> Pkcs12Error {
+        Pkcs12Error {
+            kind: self,
+            msg: None,
+        }
+    }
+}
+
+impl fmt::Display for Pkcs12ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/** PKCS #12 error.
+
+Create a new instance via [Pkcs12ErrorKind].
+*/
+#[derive(Debug)]
+pub struct Pkcs12Error {
+    kind: Pkcs12ErrorKind,
+    msg: Option<String>,
+}
+
+impl Pkcs12Error {
+    /// Return the [Pkcs12ErrorKind] type of this error.
+    pub fn kind(&self) -> &Pkcs12ErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for Pkcs12Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(msg) = &self.msg {
+            write!(f, "{} {}", self.kind, msg)
+        } else {
+            write!(f, "{}", self.kind)
+        }
+    }
+}
+
+impl Error for Pkcs12Error {}
@@ -0,0 +1,364 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! [RFC7292 Appendix B](https://www.rfc-editor.org/rfc/rfc7292#appendix-B)
+//! key derivation with the integrity MAC and legacy encryption schemes built
+//! on top of it.
+
+use super::Pkcs12Error;
+use super::Pkcs12ErrorKind;
+use crate::key::KeyError;
+use crate::key::PbeParameters;
+use hmac::digest::KeyInit;
+use hmac::Mac;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+use sha2::Digest;
+
+/*
+pkcs-12PbeParams ::= SEQUENCE {
+    salt        OCTET STRING,
+    iterations  INTEGER
+}
+*/
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+struct Pkcs12PbeParams {
+    salt: OctetString,
+    iterations: Integer,
+}
+
+/// Purpose of derived key material.
+#[derive(Debug, Clone, Copy)]
+enum Pkcs12KeyId {
+    Key = 1,
+    Iv = 2,
+    Mac = 3,
+}
+
+/// Digest algorithm used for the PFX integrity MAC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pkcs12MacAlgorithm {
+    /// HMAC-SHA1 (legacy compatibility)
+    Sha1,
+    /// HMAC-SHA256
+    Sha256,
+    /// HMAC-SHA384
+    Sha384,
+    /// HMAC-SHA512
+    Sha512,
+}
+
+impl Pkcs12MacAlgorithm {
+    /// Return the digest algorithm OID
+    pub fn as_oid(&self) -> &'static [u32] {
+        match self {
+            Self::Sha1 => &[1, 3, 14, 3, 2, 26],
+            Self::Sha256 => &[2, 16, 840, 1, 101, 3, 4, 2, 1],
+            Self::Sha384 => &[2, 16, 840, 1, 101, 3, 4, 2, 2],
+            Self::Sha512 => &[2, 16, 840, 1, 101, 3, 4, 2, 3],
+        }
+    }
+
+    /// Return a new instance from the digest algorithm OID
+    pub fn from_oid(oid: &[u32]) -> Option<Self> {
+        [Self::Sha1, Self::Sha256, Self::Sha384, Self::Sha512]
+            .into_iter()
+            .find(|alg| alg.as_oid().eq(oid))
+    }
+
+    /// Return the MAC of the `data`.
+    pub(crate) fn mac(&self, password: &str, salt: &[u8], iterations: u32, data: &[u8]) -> Vec<u8> {
+        let bmp_password = bmp_password(password);
+        match self {
+            Self::Sha1 => {
+                let key =
+                    kdf::<sha1::Sha1>(&bmp_password, salt, Pkcs12KeyId::Mac, iterations, 20, 64);
+                hmac::<hmac::Hmac<sha1::Sha1>>(&key, data)
+            }
+            Self::Sha256 => {
+                let key =
+                    kdf::<sha2::Sha256>(&bmp_password, salt, Pkcs12KeyId::Mac, iterations, 32, 64);
+                hmac::<hmac::Hmac<sha2::Sha256>>(&key, data)
+            }
+            Self::Sha384 => {
+                let key =
+                    kdf::<sha2::Sha384>(&bmp_password, salt, Pkcs12KeyId::Mac, iterations, 48, 128);
+                hmac::<hmac::Hmac<sha2::Sha384>>(&key, data)
+            }
+            Self::Sha512 => {
+                let key =
+                    kdf::<sha2::Sha512>(&bmp_password, salt, Pkcs12KeyId::Mac, iterations, 64, 128);
+                hmac::<hmac::Hmac<sha2::Sha512>>(&key, data)
+            }
+        }
+    }
+
+    /// Return `true` if the `expected` MAC of the `data` matches.
+    pub(crate) fn verify(
+        &self,
+        password: &str,
+        salt: &[u8],
+        iterations: u32,
+        data: &[u8],
+        expected: &[u8],
+    ) -> bool {
+        let mac = self.mac(password, salt, iterations, data);
+        // Constant time comparison
+        mac.len() == expected.len()
+            && mac
+                .iter()
+                .zip(expected)
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+}
+
+/// Legacy PKCS #12 password based encryption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Pkcs12LegacyCipher {
+    /// `pbeWithSHAAnd3-KeyTripleDES-CBC`
+    TripleDesCbc,
+    /// `pbeWithSHAAnd128BitRC2-CBC`
+    Rc2Cbc128,
+    /// `pbeWithSHAAnd40BitRC2-CBC`
+    Rc2Cbc40,
+}
+
+impl Pkcs12LegacyCipher {
+    fn as_oid(&self) -> &'static [u32] {
+        match self {
+            Self::TripleDesCbc => &[1, 2, 840, 113549, 1, 12, 1, 3],
+            Self::Rc2Cbc128 => &[1, 2, 840, 113549, 1, 12, 1, 5],
+            Self::Rc2Cbc40 => &[1, 2, 840, 113549, 1, 12, 1, 6],
+        }
+    }
+
+    fn from_oid(oid: &[u32]) -> Option<Self> {
+        [Self::TripleDesCbc, Self::Rc2Cbc128, Self::Rc2Cbc40]
+            .into_iter()
+            .find(|cipher| cipher.as_oid().eq(oid))
+    }
+
+    fn key_length(&self) -> usize {
+        match self {
+            Self::TripleDesCbc => 24,
+            Self::Rc2Cbc128 => 16,
+            Self::Rc2Cbc40 => 5,
+        }
+    }
+
+    fn derive_key_and_iv(
+        &self,
+        password: &str,
+        salt: &[u8],
+        iterations: u32,
+    ) -> (Vec<u8>, Vec<u8>) {
+        let bmp_password = bmp_password(password);
+        let key = kdf::<sha1::Sha1>(
+            &bmp_password,
+            salt,
+            Pkcs12KeyId::Key,
+            iterations,
+            self.key_length(),
+            64,
+        );
+        let iv = kdf::<sha1::Sha1>(&bmp_password, salt, Pkcs12KeyId::Iv, iterations, 8, 64);
+        (key, iv)
+    }
+
+    /// Encrypt the `plaintext` and return the `AlgorithmIdentifier` and
+    /// ciphertext.
+    pub(crate) fn encrypt(
+        &self,
+        password: &str,
+        iterations: u32,
+        plaintext: &[u8],
+    ) -> Result<(AlgorithmIdentifier, Vec<u8>), Pkcs12Error> {
+        let salt = PbeParameters::random(8);
+        let (key, iv) = self.derive_key_and_iv(password, &salt, iterations);
+        let ciphertext = match self {
+            Self::TripleDesCbc => PbeParameters::cbc_encrypt::<des::TdesEde3>(&key, &iv, plaintext),
+            Self::Rc2Cbc128 | Self::Rc2Cbc40 => {
+                PbeParameters::cbc_encrypt::<rc2::Rc2>(&key, &iv, plaintext)
+            }
+        }
+        .map_err(from_key_error)?;
+        let algorithm = PbeParameters::algorithm_identifier(
+            self.as_oid(),
+            &Pkcs12PbeParams {
+                salt: OctetString::from(salt),
+                iterations: Integer::from(iterations),
+            },
+        );
+        Ok((algorithm, ciphertext))
+    }
+}
+
+/** Decrypt the `ciphertext` using either PBES2 or a legacy PKCS #12 scheme.
+
+PBES2 uses the UTF-8 encoded password as done by OpenSSL, while legacy schemes
+use the `BMPString` encoding mandated by RFC7292.
+*/
+pub(crate) fn decrypt(
+    algorithm: &AlgorithmIdentifier,
+    password: &str,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, Pkcs12Error> {
+    let oid = algorithm.algorithm.to_vec();
+    if oid.eq(PbeParameters::OID_PBES2) {
+        return PbeParameters::decrypt(algorithm, password.as_bytes(), ciphertext)
+            .map_err(from_key_error);
+    }
+    let cipher = Pkcs12LegacyCipher::from_oid(&oid).ok_or_else(|| {
+        let msg = format!(
+            "Unsupported encryption algorithm '{}'.",
+            crate::encdec::oid::as_string(&oid)
+        );
+        Pkcs12ErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+    })?;
+    let params =
+        PbeParameters::decode_parameters::<Pkcs12PbeParams>(algorithm).map_err(from_key_error)?;
+    let iterations = decode_iterations(&params.iterations)?;
+    let (key, iv) = cipher.derive_key_and_iv(password, &params.salt, iterations);
+    match cipher {
+        Pkcs12LegacyCipher::TripleDesCbc => {
+            PbeParameters::cbc_decrypt::<des::TdesEde3>(&key, &iv, ciphertext)
+        }
+        Pkcs12LegacyCipher::Rc2Cbc128 | Pkcs12LegacyCipher::Rc2Cbc40 => {
+            PbeParameters::cbc_decrypt::<rc2::Rc2>(&key, &iv, ciphertext)
+        }
+    }
+    .map_err(from_key_error)
+}
+
+/** Decode an iteration count from untrusted input.
+
+The PKCS #12 KDF is at least as costly per iteration as PBKDF2, so the same
+[PbeParameters::MAX_PBKDF2_ITERATIONS] limit applies.
+*/
+pub(crate) fn decode_iterations(iterations: &Integer) -> Result<u32, Pkcs12Error> {
+    let iterations = crate::encdec::rasn::integer_try_as_u64(iterations).ok_or_else(|| {
+        Pkcs12ErrorKind::DecodingError
+            .error_with_msg("The iteration count must be a non-negative integer below 2^64.")
+    })?;
+    check_iterations(iterations)?;
+    Ok(iterations as u32)
+}
+
+/// Reject iteration counts outside `1..=`[PbeParameters::MAX_PBKDF2_ITERATIONS].
+pub(crate) fn check_iterations(iterations: u64) -> Result<(), Pkcs12Error> {
+    if iterations == 0 || iterations > u64::from(PbeParameters::MAX_PBKDF2_ITERATIONS) {
+        let msg = format!(
+            "Iteration count {iterations} is outside the supported range 1..={}.",
+            PbeParameters::MAX_PBKDF2_ITERATIONS
+        );
+        return Err(Pkcs12ErrorKind::UnsupportedAlgorithm.error_with_msg(&msg));
+    }
+    Ok(())
+}
+
+/// Map [KeyError] from the shared PBES2 implementation.
+pub(crate) fn from_key_error(key_error: KeyError) -> Pkcs12Error {
+    let kind = match key_error.kind() {
+        crate::key::KeyErrorKind::DecodingError => Pkcs12ErrorKind::DecodingError,
+        crate::key::KeyErrorKind::UnsupportedAlgorithm => Pkcs12ErrorKind::UnsupportedAlgorithm,
+        crate::key::KeyErrorKind::DecryptionFailure => Pkcs12ErrorKind::DecryptionFailure,
+        crate::key::KeyErrorKind::EncryptionFailure => Pkcs12ErrorKind::EncryptionFailure,
+    };
+    kind.error_with_msg(&key_error.to_string())
+}
+
+/// Password as big endian UTF-16 with a two zero byte terminator.
+fn bmp_password(password: &str) -> Vec<u8> {
+    password
+        .encode_utf16()
+        .chain([0u16])
+        .flat_map(u16::to_be_bytes)
+        .collect()
+}
+
+/// [RFC7292 B.2](https://www.rfc-editor.org/rfc/rfc7292#appendix-B.2) with
+/// `v` as the digest block size in bytes.
+fn kdf<D: Digest>(
+    bmp_password: &[u8],
+    salt: &[u8],
+    id: Pkcs12KeyId,
+    iterations: u32,
+    len: usize,
+    v: usize,
+) -> Vec<u8> {
+    let fill = |data: &[u8]| -> Vec<u8> {
+        let len = v * data.len().div_ceil(v);
+        data.iter().cycle().take(len).copied().collect()
+    };
+    let diversifier = vec![id as u8; v];
+    let mut i = fill(salt);
+    i.extend(fill(bmp_password));
+    let mut ret = Vec::with_capacity(len);
+    while ret.len() < len {
+        let mut a = D::new()
+            .chain_update(&diversifier)
+            .chain_update(&i)
+            .finalize()
+            .to_vec();
+        for _ in 1..iterations {
+            a = D::digest(&a).to_vec();
+        }
+        let b = fill(&a);
+        // I_j = (I_j + B + 1) mod 2^(v*8) for each v-byte block
+        for block in i.chunks_mut(v) {
+            let mut carry = 1u16;
+            for (ij, bj) in block.iter_mut().zip(b.iter()).rev() {
+                let sum = *ij as u16 + *bj as u16 + carry;
+                *ij = sum as u8;
+                carry = sum >> 8;
+            }
+        }
+        ret.extend(a);
+    }
+    ret.truncate(len);
+    ret
+}
+
+fn hmac<M: Mac + KeyInit>(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = <M as Mac>::new_from_slice(key).unwrap();
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tyst::encdec::hex::ToHex;
+
+    #[test]
+    fn kdf_known_answer() {
+        // Test vector from the BouncyCastle PKCS12 KDF test suite
+        let key = kdf::<sha1::Sha1>(
+            &bmp_password("smeg"),
+            &[0x0A, 0x58, 0xCF, 0x64, 0x53, 0x0D, 0x82, 0x3F],
+            Pkcs12KeyId::Key,
+            1,
+            24,
+            64,
+        );
+        assert_eq!(
+            key.to_hex().to_lowercase(),
+            "8aaae6297b6cb04642ab5b077851284eb7128f1a2a7fbca3"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Conversions to and from [RustCrypto](https://github.com/RustCrypto/formats)
+//! `x509-cert`, `spki` and `der` types.
+//!
+//! All conversions go through the DER encoding, so that no semantics are lost
+//! between the `rasn` based types of this crate and the RustCrypto types.
+//!
+//! Requires the `x509-cert` feature.
+
+use crate::cert::extensions::Extensions;
+use crate::cert::parse::CertificateParser;
+use crate::cert::parse::CertificateParsingError;
+use crate::cert::parse::CertificateParsingErrorKind;
+use crate::cert::types::DistinguishedName;
+use crate::encdec::DecodingError;
+use der::Decode;
+use der::Encode;
+use spki::SubjectPublicKeyInfoOwned;
+use tyst::traits::se::PublicKey;
+use tyst::traits::se::ToPublicKey;
+
+impl CertificateParser {
+    /// Return a new instance from a RustCrypto [x509_cert::Certificate].
+    pub fn from_x509_cert_type(
+        certificate: &x509_cert::Certificate,
+    ) -> Result<Self, CertificateParsingError> {
+        let der = to_der(certificate).map_err(|e| {
+            CertificateParsingErrorKind::CertificateDecodingError.error_with_msg(&e.to_string())
+        })?;
+        Self::from_bytes(&der)
+    }
+
+    /// Return the certificate as a RustCrypto [x509_cert::Certificate].
+    pub fn to_x509_cert_type(&self) -> Result<x509_cert::Certificate, DecodingError> {
+        from_der(&self.get_encoded_certificate())
+    }
+
+    /// Return the Subject Public Key Info as a RustCrypto
+    /// [SubjectPublicKeyInfoOwned].
+    pub fn get_subject_public_key_info_x509_cert_type(
+        &self,
+    ) -> Result<SubjectPublicKeyInfoOwned, DecodingError> {
+        from_der(&self.get_encoded_subject_public_key_info())
+    }
+}
+
+impl DistinguishedName {
+    /// Return a new instance from a RustCrypto [x509_cert::name::Name].
+    pub fn from_x509_cert_type(name: &x509_cert::name::Name) -> Result<Self, DecodingError> {
+        let name = rasn::der::decode::<rasn_pkix::Name>(&to_der(name)?)
+            .map_err(|e| DecodingError::with_msg(&format!("Failed to decode Name: {e:?}")))?;
+        Self::from_rasn_type(&name).map_err(|e| DecodingError::with_msg(&e.to_string()))
+    }
+
+    /// Return the distinguished name as a RustCrypto [x509_cert::name::Name].
+    pub fn to_x509_cert_type(&self) -> Result<x509_cert::name::Name, DecodingError> {
+        from_der(&self.to_der())
+    }
+}
+
+impl Extensions {
+    /// Return a new instance from RustCrypto [x509_cert::ext::Extensions].
+    pub fn from_x509_cert_type(
+        extensions: &x509_cert::ext::Extensions,
+    ) -> Result<Self, DecodingError> {
+        let extensions = rasn::der::decode::<rasn_pkix::Extensions>(&to_der(extensions)?)
+            .map_err(|e| DecodingError::with_msg(&format!("Failed to decode Extensions: {e:?}")))?;
+        Ok(Self::from_rasn_type(&extensions))
+    }
+
+    /// Return the extensions as RustCrypto [x509_cert::ext::Extensions].
+    pub fn to_x509_cert_type(&self) -> Result<x509_cert::ext::Extensions, DecodingError> {
+        match self.to_rasn_type() {
+            Some(extensions) => from_der(&rasn::der::encode(&extensions).unwrap()),
+            None => Ok(x509_cert::ext::Extensions::default()),
+        }
+    }
+}
+
+/// Return the public key as a RustCrypto [SubjectPublicKeyInfoOwned].
+pub fn public_key_to_x509_cert_type(
+    public_key: &dyn PublicKey,
+) -> Result<SubjectPublicKeyInfoOwned, DecodingError> {
+    let spki = public_key
+        .try_as_spki()
+        .ok_or_else(|| DecodingError::with_msg("Public key has no SubjectPublicKeyInfo form."))?;
+    from_der(&spki)
+}
+
+/// Return a TYST [PublicKey] from a RustCrypto [SubjectPublicKeyInfoOwned].
+pub fn public_key_from_x509_cert_type(
+    subject_public_key_info: &SubjectPublicKeyInfoOwned,
+) -> Result<Box<dyn PublicKey>, DecodingError> {
+    Ok(to_der(subject_public_key_info)?.to_public_key())
+}
+
+fn to_der<T: Encode>(value: &T) -> Result<Vec<u8>, DecodingError> {
+    value
+        .to_der()
+        .map_err(|e| DecodingError::with_msg(&format!("Failed to encode DER: {e}")))
+}
+
+fn from_der<T: der::DecodeOwned>(der: &[u8]) -> Result<T, DecodingError> {
+    T::from_der(der).map_err(|e| DecodingError::with_msg(&format!("Failed to decode DER: {e}")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn certificate_round_trip() {
+        let der = crate::test_utils::load_test_certificate_der("h1_leaf.b64");
+        let parser = CertificateParser::from_bytes(&der).unwrap();
+        let certificate = parser.to_x509_cert_type().unwrap();
+        assert_eq!(certificate.to_der().unwrap(), der);
+        let subject =
+            DistinguishedName::from_x509_cert_type(&certificate.tbs_certificate.subject).unwrap();
+        assert_eq!(subject.to_der(), parser.get_encoded_subject());
+        let extensions = Extensions::from_x509_cert_type(
+            certificate.tbs_certificate.extensions.as_ref().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            extensions.to_x509_cert_type().unwrap(),
+            certificate.tbs_certificate.extensions.clone().unwrap()
+        );
+        let spki = parser.get_subject_public_key_info_x509_cert_type().unwrap();
+        assert_eq!(
+            spki.to_der().unwrap(),
+            parser.get_encoded_subject_public_key_info()
+        );
+        let round_trip = CertificateParser::from_x509_cert_type(&certificate).unwrap();
+        assert_eq!(round_trip.fingerprint(), parser.fingerprint());
+    }
+}
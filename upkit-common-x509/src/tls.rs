@@ -0,0 +1,609 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! [rustls](https://docs.rs/rustls) certificate verifiers backed by
+//! [CertificatePathValidator](crate::cert::validate::CertificatePathValidator).
+//!
+//! This allows TLS connections to use the same trust rules (extension checkers
+//! and algorithms provided by [TYST](https://github.com/mydriatech/tyst)) as
+//! any other validation.
+//!
+//! Requires the `rustls` feature.
+
+mod client_cert_verifier;
+mod rsa_pss;
+mod server_cert_verifier;
+
+pub use self::client_cert_verifier::PathValidatingClientCertVerifier;
+pub use self::server_cert_verifier::PathValidatingServerCertVerifier;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::WellKnownAlgorithm;
+use crate::cert::types::WellKnownNamedCurve;
+use crate::cert::validate::CertificateValidationError;
+use crate::cert::validate::CertificateValidationErrorKind;
+use rasn::types::ObjectIdentifier;
+use rasn_pkix::AlgorithmIdentifier;
+use rasn_pkix::SubjectPublicKeyInfo;
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::pki_types::CertificateDer;
+use rustls::CertificateError;
+use rustls::SignatureScheme;
+use tyst::traits::se::ToPublicKey;
+use tyst::Tyst;
+
+/// TLS `SignatureScheme` code points and the corresponding signature
+/// algorithms.
+const SIGNATURE_SCHEMES: &[(u16, WellKnownAlgorithm)] = &[
+    (0x0401, WellKnownAlgorithm::Sha256WithRsaEncryption),
+    (0x0501, WellKnownAlgorithm::Sha384WithRsaEncryption),
+    (0x0601, WellKnownAlgorithm::Sha512WithRsaEncryption),
+    (0x0403, WellKnownAlgorithm::EcdsaWithSha256),
+    (0x0503, WellKnownAlgorithm::EcdsaWithSha384),
+    (0x0603, WellKnownAlgorithm::EcdsaWithSha512),
+    (0x0807, WellKnownAlgorithm::Ed25519),
+    (0x0808, WellKnownAlgorithm::Ed448),
+    // draft-ietf-tls-mldsa
+    (0x0904, WellKnownAlgorithm::MlDsa44),
+    (0x0905, WellKnownAlgorithm::MlDsa65),
+    (0x0906, WellKnownAlgorithm::MlDsa87),
+];
+
+/// TLS `rsa_pss_rsae_*` code points and the digest used for both the message
+/// and MGF1.
+const RSA_PSS_SIGNATURE_SCHEMES: &[(u16, &str)] = &[
+    (0x0804, "SHA-256"),
+    (0x0805, "SHA-384"),
+    (0x0806, "SHA-512"),
+];
+
+/// TLS 1.3 `CertificateVerify` signature schemes.
+///
+/// [RFC8446 4.2.3](https://www.rfc-editor.org/rfc/rfc8446#section-4.2.3):
+/// "RSASSA-PKCS1-v1_5 algorithms refer solely to signatures which appear in
+/// certificates [...] and are not defined for use in signed TLS handshake
+/// messages".
+const TLS13_SIGNATURE_SCHEMES: &[u16] = &[
+    0x0403, 0x0503, 0x0603, 0x0804, 0x0805, 0x0806, 0x0807, 0x0808, 0x0904, 0x0905, 0x0906,
+];
+
+/// Curves that TLS 1.3 binds to the `ecdsa_secp*r1_*` signature schemes.
+const TLS13_ECDSA_CURVES: &[(u16, WellKnownNamedCurve)] = &[
+    (0x0403, WellKnownNamedCurve::Secp256r1),
+    (0x0503, WellKnownNamedCurve::Secp384r1),
+    (0x0603, WellKnownNamedCurve::Secp521r1),
+];
+
+/// Return the DER encoded chain in the form expected by
+/// [CertificatePathValidator::validate](crate::cert::validate::CertificatePathValidator::validate).
+fn to_leaf_certificate_chain(
+    end_entity: &CertificateDer<'_>,
+    intermediates: &[CertificateDer<'_>],
+) -> Vec<Vec<u8>> {
+    std::iter::once(end_entity)
+        .chain(intermediates)
+        .map(|certificate| certificate.as_ref().to_vec())
+        .collect()
+}
+
+/// Map [CertificateValidationErrorKind] to the closest rustls
+/// [CertificateError].
+fn to_rustls_error(e: CertificateValidationError) -> rustls::Error {
+    log::debug!("TLS peer certificate validation failed: {e}");
+    let certificate_error = match e.kind() {
+        CertificateValidationErrorKind::CertificateParsingError => CertificateError::BadEncoding,
+        CertificateValidationErrorKind::InvalidSignature
+        | CertificateValidationErrorKind::UnknownSignature => CertificateError::BadSignature,
+        CertificateValidationErrorKind::InvalidLifeSpan => CertificateError::Expired,
+        CertificateValidationErrorKind::NotOneLeaf | CertificateValidationErrorKind::NotTrusted => {
+            CertificateError::UnknownIssuer
+        }
+        CertificateValidationErrorKind::UnhandledCriticalExtensions => {
+            CertificateError::UnhandledCriticalExtension
+        }
+        CertificateValidationErrorKind::ExtensionHandlingFailure => {
+            CertificateError::ApplicationVerificationFailure
+        }
+        CertificateValidationErrorKind::Revoked => CertificateError::Revoked,
+        CertificateValidationErrorKind::RevocationStatusUnknown => {
+            CertificateError::UnknownRevocationStatus
+        }
+    };
+    rustls::Error::InvalidCertificate(certificate_error)
+}
+
+/// Return all [SIGNATURE_SCHEMES] and [RSA_PSS_SIGNATURE_SCHEMES] that are
+/// available in TYST.
+fn supported_verify_schemes() -> Vec<SignatureScheme> {
+    let signature_schemes = SIGNATURE_SCHEMES
+        .iter()
+        .filter(|(_, algorithm)| {
+            Tyst::instance()
+                .ses()
+                .by_oid(&crate::encdec::oid::as_string(algorithm.as_oid()))
+                .is_some()
+        })
+        .map(|(code_point, _)| *code_point);
+    let rsa_pss_signature_schemes = RSA_PSS_SIGNATURE_SCHEMES
+        .iter()
+        .filter(|(_, digest_name)| Tyst::instance().digests().by_name(digest_name).is_some())
+        .map(|(code_point, _)| *code_point);
+    signature_schemes
+        .chain(rsa_pss_signature_schemes)
+        .map(SignatureScheme::from)
+        .collect()
+}
+
+/// Verify a TLS 1.2 handshake signature of `message` using the public key of
+/// `certificate`.
+fn verify_tls12_signature(
+    message: &[u8],
+    certificate: &CertificateDer<'_>,
+    scheme: SignatureScheme,
+    signature: &[u8],
+) -> Result<HandshakeSignatureValid, rustls::Error> {
+    verify_signature(message, certificate, u16::from(scheme), signature)
+}
+
+/** Verify a TLS 1.3 `CertificateVerify` signature of `message` using the
+public key of `certificate`.
+
+Only [TLS13_SIGNATURE_SCHEMES] are allowed and ECDSA keys must be on the curve
+of the scheme.
+*/
+fn verify_tls13_signature(
+    message: &[u8],
+    certificate: &CertificateDer<'_>,
+    scheme: SignatureScheme,
+    signature: &[u8],
+) -> Result<HandshakeSignatureValid, rustls::Error> {
+    let code_point = u16::from(scheme);
+    if !TLS13_SIGNATURE_SCHEMES.contains(&code_point) {
+        return Err(rustls::Error::PeerIncompatible(
+            rustls::PeerIncompatible::NoSignatureSchemesInCommon,
+        ));
+    }
+    if let Some((_, curve)) = TLS13_ECDSA_CURVES
+        .iter()
+        .find(|(scheme, _)| *scheme == code_point)
+    {
+        let spki = rasn::der::decode::<SubjectPublicKeyInfo>(
+            &parse_certificate(certificate)?.get_encoded_subject_public_key_info(),
+        )
+        .map_err(|_| rustls::Error::InvalidCertificate(CertificateError::BadEncoding))?;
+        if named_curve(&spki.algorithm) != Some(*curve) {
+            log::debug!("TLS 1.3 signature scheme {scheme:?} requires a {curve:?} key.");
+            return Err(rustls::Error::InvalidCertificate(
+                CertificateError::BadSignature,
+            ));
+        }
+    }
+    verify_signature(message, certificate, code_point, signature)
+}
+
+/// Verify a TLS handshake signature of `message` using the public key of
+/// `certificate`.
+fn verify_signature(
+    message: &[u8],
+    certificate: &CertificateDer<'_>,
+    code_point: u16,
+    signature: &[u8],
+) -> Result<HandshakeSignatureValid, rustls::Error> {
+    let no_signature_schemes_in_common =
+        rustls::Error::PeerIncompatible(rustls::PeerIncompatible::NoSignatureSchemesInCommon);
+    let encoded_spki = parse_certificate(certificate)?.get_encoded_subject_public_key_info();
+    let valid = if let Some((_, digest_name)) = RSA_PSS_SIGNATURE_SCHEMES
+        .iter()
+        .find(|(scheme, _)| *scheme == code_point)
+    {
+        if Tyst::instance().digests().by_name(digest_name).is_none() {
+            return Err(no_signature_schemes_in_common);
+        }
+        rsa_pss::verify(&encoded_spki, digest_name, signature, message)
+    } else {
+        let oid = SIGNATURE_SCHEMES
+            .iter()
+            .find(|(scheme, _)| *scheme == code_point)
+            .map(|(_, algorithm)| crate::encdec::oid::as_string(algorithm.as_oid()))
+            .ok_or(no_signature_schemes_in_common.clone())?;
+        let mut se = Tyst::instance()
+            .ses()
+            .by_oid(&oid)
+            .ok_or(no_signature_schemes_in_common)?;
+        se.verify(encoded_spki.to_public_key().as_ref(), signature, message)
+    };
+    if valid {
+        Ok(HandshakeSignatureValid::assertion())
+    } else {
+        Err(rustls::Error::InvalidCertificate(
+            CertificateError::BadSignature,
+        ))
+    }
+}
+
+fn parse_certificate(certificate: &CertificateDer<'_>) -> Result<CertificateParser, rustls::Error> {
+    CertificateParser::from_bytes(certificate.as_ref())
+        .map_err(|_| rustls::Error::InvalidCertificate(CertificateError::BadEncoding))
+}
+
+/// Return the named curve of an `id-ecPublicKey` key.
+fn named_curve(algorithm_identifier: &AlgorithmIdentifier) -> Option<WellKnownNamedCurve> {
+    if !WellKnownAlgorithm::EcPublicKey
+        .as_oid()
+        .eq(algorithm_identifier.algorithm.to_vec().as_slice())
+    {
+        return None;
+    }
+    algorithm_identifier
+        .parameters
+        .as_ref()
+        .and_then(|parameters| rasn::der::decode::<ObjectIdentifier>(parameters.as_bytes()).ok())
+        .and_then(|curve_oid| WellKnownNamedCurve::from_oid(&curve_oid))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::build::NoSignatureTbsCertificate;
+    use crate::cert::extensions::AuthorityKeyIdentifier;
+    use crate::cert::extensions::BasicConstraints;
+    use crate::cert::extensions::ExtendedKeyUsage;
+    use crate::cert::extensions::Extensions;
+    use crate::cert::extensions::KeyUsage;
+    use crate::cert::extensions::SubjectKeyIdentifier;
+    use crate::cert::types::DistinguishedName;
+    use crate::cert::types::WellKnownGeneralName;
+    use crate::cert::validate::CertificatePathValidator;
+    use crate::key::Pkcs8PrivateKey;
+    use crate::test_utils::*;
+    use p256::pkcs8::EncodePrivateKey;
+    use p256::pkcs8::EncodePublicKey;
+    use rasn::types::Any;
+    use rustls::pki_types::PrivateKeyDer;
+    use rustls::pki_types::PrivatePkcs8KeyDer;
+    use std::io::Read;
+    use std::io::Write;
+    use std::sync::Arc;
+
+    struct TestIdentity {
+        certificate: Vec<u8>,
+        pkcs8: Vec<u8>,
+    }
+
+    fn issue(
+        subject: &str,
+        issuer: Option<(&str, &TestIdentity)>,
+        ekus: &[ExtendedKeyUsage],
+    ) -> TestIdentity {
+        let mut secret = [0u8; 32];
+        Tyst::instance().prng_fill_with_random(None, &mut secret);
+        let secret_key = p256::SecretKey::from_slice(&secret).unwrap();
+        let pkcs8 = secret_key.to_pkcs8_der().unwrap().as_bytes().to_vec();
+        let spki = secret_key
+            .public_key()
+            .to_public_key_der()
+            .unwrap()
+            .as_bytes()
+            .to_vec();
+        let public_key = spki.to_public_key();
+        let mut extensions = Extensions::default();
+        extensions.add_basic_constraints(&if issuer.is_none() {
+            BasicConstraints::new_ca(None)
+        } else {
+            BasicConstraints::new_leaf()
+        });
+        extensions.add_key_usage(&if issuer.is_none() {
+            vec![KeyUsage::KeyCertSign]
+        } else {
+            vec![KeyUsage::DigitalSignature]
+        });
+        extensions.add_extended_key_usage(ekus);
+        extensions.add_subject_key_identifier(&SubjectKeyIdentifier::from_public_key(
+            public_key.as_ref(),
+        ));
+        let (issuer_name, signer) = match issuer {
+            Some((issuer_name, issuer)) => {
+                let issuer_public_key = CertificateParser::from_bytes(&issuer.certificate)
+                    .unwrap()
+                    .get_encoded_subject_public_key_info()
+                    .to_public_key();
+                extensions.add_authority_key_identifier(&AuthorityKeyIdentifier::from_public_key(
+                    issuer_public_key.as_ref(),
+                ));
+                extensions.add_subject_alternative_name(
+                    &[(WellKnownGeneralName::DnsName, subject.to_string())],
+                    false,
+                );
+                (issuer_name, issuer.pkcs8.clone())
+            }
+            None => (subject, pkcs8.clone()),
+        };
+        let tbs_certificate = NoSignatureTbsCertificate::new(
+            common_name(issuer_name),
+            crate::cert::types::Validity::now_epoch_seconds() + 3600,
+            common_name(subject),
+            public_key.as_ref(),
+            extensions,
+        );
+        let signature_algorithm = rasn::der::encode(&rasn_pkix::AlgorithmIdentifier {
+            algorithm: ObjectIdentifier::new_unchecked(
+                WellKnownAlgorithm::EcdsaWithSha256.as_oid().into(),
+            ),
+            parameters: None::<Any>,
+        })
+        .unwrap();
+        let signature = Tyst::instance()
+            .ses()
+            .by_oid("1.2.840.10045.4.3.2")
+            .unwrap()
+            .sign(
+                Pkcs8PrivateKey::from_der(&signer)
+                    .unwrap()
+                    .to_private_key()
+                    .as_ref(),
+                &tbs_certificate.with_signature_algorithm_as_bytes(&signature_algorithm),
+            );
+        let certificate = tbs_certificate.to_certificate(&signature_algorithm, signature);
+        TestIdentity {
+            certificate: rasn::der::encode(&certificate).unwrap(),
+            pkcs8,
+        }
+    }
+
+    fn common_name(value: &str) -> DistinguishedName {
+        DistinguishedName::try_from(vec![vec![("common_name".to_string(), value.to_string())]])
+            .unwrap()
+    }
+
+    fn handshake(
+        client: &mut rustls::ClientConnection,
+        server: &mut rustls::ServerConnection,
+    ) -> Result<(), rustls::Error> {
+        let mut buffer = Vec::new();
+        while client.is_handshaking() || server.is_handshaking() {
+            buffer.clear();
+            client.write_tls(&mut buffer).unwrap();
+            server.read_tls(&mut buffer.as_slice()).unwrap();
+            server.process_new_packets()?;
+            buffer.clear();
+            server.write_tls(&mut buffer).unwrap();
+            client.read_tls(&mut buffer.as_slice()).unwrap();
+            client.process_new_packets()?;
+        }
+        Ok(())
+    }
+
+    fn connect(
+        server_name: &str,
+        ca: &TestIdentity,
+        server: &TestIdentity,
+        client: Option<&TestIdentity>,
+    ) -> Result<(), rustls::Error> {
+        let validator = Arc::new(
+            CertificatePathValidator::new(vec![ca.certificate.clone()])
+                .unwrap()
+                .add_standard_extension_checkers(),
+        );
+        let server_config = rustls::ServerConfig::builder();
+        let server_config = if client.is_some() {
+            server_config.with_client_cert_verifier(Arc::new(
+                PathValidatingClientCertVerifier::new(Arc::clone(&validator)),
+            ))
+        } else {
+            server_config.with_no_client_auth()
+        }
+        .with_single_cert(
+            vec![CertificateDer::from(server.certificate.clone())],
+            PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(server.pkcs8.clone())),
+        )
+        .unwrap();
+        let client_config = rustls::ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(PathValidatingServerCertVerifier::new(
+                validator,
+            )));
+        let client_config = if let Some(client) = client {
+            client_config
+                .with_client_auth_cert(
+                    vec![CertificateDer::from(client.certificate.clone())],
+                    PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(client.pkcs8.clone())),
+                )
+                .unwrap()
+        } else {
+            client_config.with_no_client_auth()
+        };
+        let mut client_connection = rustls::ClientConnection::new(
+            Arc::new(client_config),
+            server_name.to_string().try_into().unwrap(),
+        )
+        .unwrap();
+        let mut server_connection = rustls::ServerConnection::new(Arc::new(server_config)).unwrap();
+        handshake(&mut client_connection, &mut server_connection)?;
+        client_connection.writer().write_all(b"ping").unwrap();
+        let mut buffer = Vec::new();
+        client_connection.write_tls(&mut buffer).unwrap();
+        server_connection.read_tls(&mut buffer.as_slice()).unwrap();
+        server_connection.process_new_packets()?;
+        let mut received = [0u8; 4];
+        server_connection
+            .reader()
+            .read_exact(&mut received)
+            .unwrap();
+        assert_eq!(&received, b"ping");
+        Ok(())
+    }
+
+    #[test]
+    fn in_memory_tls_connection() {
+        crate::test_utils::init_logger();
+        let ca = issue("Test CA", None, &[]);
+        let server = issue(
+            "server.example.com",
+            Some(("Test CA", &ca)),
+            &[ExtendedKeyUsage::PkixServerAuth],
+        );
+        let client = issue(
+            "client.example.com",
+            Some(("Test CA", &ca)),
+            &[ExtendedKeyUsage::PkixClientAuth],
+        );
+        connect("server.example.com", &ca, &server, None).unwrap();
+        connect("server.example.com", &ca, &server, Some(&client)).unwrap();
+        assert!(matches!(
+            connect("other.example.com", &ca, &server, None),
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName
+            ))
+        ));
+        // Same name, but another key
+        let untrusted_ca = issue("Test CA", None, &[]);
+        assert!(matches!(
+            connect("server.example.com", &untrusted_ca, &server, None),
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::BadSignature
+            ))
+        ));
+        let other_ca = issue("Other CA", None, &[]);
+        assert!(matches!(
+            connect("server.example.com", &other_ca, &server, None),
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::UnknownIssuer
+            ))
+        ));
+    }
+
+    /// Message signed by `rsa_recipient_key.pem` in the
+    /// `rsa_recipient_*_sha*.b64` signatures.
+    const SIGNED_MESSAGE: &[u8] = b"TLS 1.3, server CertificateVerify test message";
+
+    fn certificate_der(name: &str) -> CertificateDer<'static> {
+        CertificateDer::from(load_test_certificate_der(name))
+    }
+
+    #[test]
+    fn rsa_pss_signature_schemes() {
+        let certificate = certificate_der("rsa_recipient.b64");
+        for (scheme, name) in [
+            (
+                SignatureScheme::RSA_PSS_SHA256,
+                "rsa_recipient_pss_sha256.b64",
+            ),
+            (
+                SignatureScheme::RSA_PSS_SHA384,
+                "rsa_recipient_pss_sha384.b64",
+            ),
+        ] {
+            let signature = load_test_resource_b64(name);
+            verify_tls12_signature(SIGNED_MESSAGE, &certificate, scheme, &signature).unwrap();
+            verify_tls13_signature(SIGNED_MESSAGE, &certificate, scheme, &signature).unwrap();
+            assert!(matches!(
+                verify_tls13_signature(b"Other message", &certificate, scheme, &signature),
+                Err(rustls::Error::InvalidCertificate(
+                    CertificateError::BadSignature
+                ))
+            ));
+        }
+        let signature = load_test_resource_b64("rsa_recipient_pss_sha256.b64");
+        // The digest of the scheme doesn't match the signature
+        assert!(matches!(
+            verify_tls13_signature(
+                SIGNED_MESSAGE,
+                &certificate,
+                SignatureScheme::RSA_PSS_SHA384,
+                &signature
+            ),
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::BadSignature
+            ))
+        ));
+        // Not an RSA key
+        assert!(matches!(
+            verify_tls13_signature(
+                SIGNED_MESSAGE,
+                &certificate_der("ec_ca.b64"),
+                SignatureScheme::RSA_PSS_SHA256,
+                &signature
+            ),
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::BadSignature
+            ))
+        ));
+    }
+
+    #[test]
+    fn tls13_rejects_pkcs1_signature_schemes() {
+        let certificate = certificate_der("rsa_recipient.b64");
+        let signature = load_test_resource_b64("rsa_recipient_pkcs1_sha256.b64");
+        verify_tls12_signature(
+            SIGNED_MESSAGE,
+            &certificate,
+            SignatureScheme::RSA_PKCS1_SHA256,
+            &signature,
+        )
+        .unwrap();
+        for scheme in [
+            SignatureScheme::RSA_PKCS1_SHA256,
+            SignatureScheme::RSA_PKCS1_SHA384,
+            SignatureScheme::RSA_PKCS1_SHA512,
+        ] {
+            assert!(matches!(
+                verify_tls13_signature(SIGNED_MESSAGE, &certificate, scheme, &signature),
+                Err(rustls::Error::PeerIncompatible(
+                    rustls::PeerIncompatible::NoSignatureSchemesInCommon
+                ))
+            ));
+        }
+    }
+
+    #[test]
+    fn tls13_ecdsa_signature_scheme_must_match_curve() {
+        // P-256 key
+        let certificate = certificate_der("ec_ca.b64");
+        let signature = sign_with_test_key(
+            "ec_ca_key.pem",
+            &WellKnownAlgorithm::EcdsaWithSha256,
+            SIGNED_MESSAGE,
+        );
+        verify_tls13_signature(
+            SIGNED_MESSAGE,
+            &certificate,
+            SignatureScheme::ECDSA_NISTP256_SHA256,
+            &signature,
+        )
+        .unwrap();
+        for scheme in [
+            SignatureScheme::ECDSA_NISTP384_SHA384,
+            SignatureScheme::ECDSA_NISTP521_SHA512,
+        ] {
+            assert!(matches!(
+                verify_tls13_signature(SIGNED_MESSAGE, &certificate, scheme, &signature),
+                Err(rustls::Error::InvalidCertificate(
+                    CertificateError::BadSignature
+                ))
+            ));
+        }
+        assert!(matches!(
+            verify_tls13_signature(
+                SIGNED_MESSAGE,
+                &certificate_der("rsa_recipient.b64"),
+                SignatureScheme::ECDSA_NISTP256_SHA256,
+                &signature
+            ),
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::BadSignature
+            ))
+        ));
+    }
+}
@@ -0,0 +1,141 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! rustls client certificate verifier.
+
+use crate::cert::extensions::ExtendedKeyUsage;
+use crate::cert::validate::checkers::ExtendedKeyUsageChecker;
+use crate::cert::validate::checkers::ExtensionChecker;
+use crate::cert::validate::CertificatePathValidator;
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::UnixTime;
+use rustls::server::danger::ClientCertVerified;
+use rustls::server::danger::ClientCertVerifier;
+use rustls::DigitallySignedStruct;
+use rustls::DistinguishedName;
+use rustls::SignatureScheme;
+use std::fmt;
+use std::sync::Arc;
+
+/** rustls [ClientCertVerifier] that validates the client's certificate chain
+using a [CertificatePathValidator].
+
+The chain is validated at the current time with the
+[ExtendedKeyUsage::PkixClientAuth] requirement on the leaf. The subjects of
+the trust anchors are sent as hints to the client. Handshake signatures are
+verified using TYST.
+*/
+pub struct PathValidatingClientCertVerifier {
+    validator: Arc<CertificatePathValidator>,
+    extension_checkers: Vec<Arc<dyn ExtensionChecker>>,
+    root_hint_subjects: Vec<DistinguishedName>,
+    mandatory: bool,
+}
+
+impl PathValidatingClientCertVerifier {
+    /// Return a new instance that requires client authentication.
+    pub fn new(validator: Arc<CertificatePathValidator>) -> Self {
+        let root_hint_subjects = validator
+            .get_trust_anchor_subjects()
+            .into_iter()
+            .map(DistinguishedName::from)
+            .collect();
+        Self {
+            validator,
+            extension_checkers: vec![Arc::new(ExtendedKeyUsageChecker::new(&[
+                ExtendedKeyUsage::PkixClientAuth,
+            ]))],
+            root_hint_subjects,
+            mandatory: true,
+        }
+    }
+
+    /// Allow anonymous clients. Certificates presented by the client are still
+    /// validated.
+    pub fn allow_unauthenticated(mut self) -> Self {
+        self.mandatory = false;
+        self
+    }
+
+    /// Add extension checkers that apply to client certificates in addition to
+    /// the ones of the [CertificatePathValidator].
+    pub fn with_extension_checkers(
+        mut self,
+        extension_checkers: Vec<Arc<dyn ExtensionChecker>>,
+    ) -> Self {
+        self.extension_checkers.extend(extension_checkers);
+        self
+    }
+}
+
+impl fmt::Debug for PathValidatingClientCertVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PathValidatingClientCertVerifier")
+            .field("extension_checkers", &self.extension_checkers.len())
+            .field("root_hint_subjects", &self.root_hint_subjects)
+            .field("mandatory", &self.mandatory)
+            .finish()
+    }
+}
+
+impl ClientCertVerifier for PathValidatingClientCertVerifier {
+    fn client_auth_mandatory(&self) -> bool {
+        self.mandatory
+    }
+
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        &self.root_hint_subjects
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> Result<ClientCertVerified, rustls::Error> {
+        self.validator
+            .validate(
+                super::to_leaf_certificate_chain(end_entity, intermediates),
+                now.as_secs(),
+                self.extension_checkers.clone(),
+            )
+            .map_err(super::to_rustls_error)?;
+        Ok(ClientCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        super::verify_tls12_signature(message, cert, dss.scheme, dss.signature())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        super::verify_tls13_signature(message, cert, dss.scheme, dss.signature())
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        super::supported_verify_schemes()
+    }
+}
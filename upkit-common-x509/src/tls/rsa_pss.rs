@@ -0,0 +1,130 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! RSASSA-PSS signature verification for the TLS `rsa_pss_rsae_*` schemes.
+//!
+//! TYST signature engines are selected by OID and `id-RSASSA-PSS` alone
+//! doesn't convey the parameters implied by a TLS `SignatureScheme`, so the
+//! EMSA-PSS encoding is verified here using digests provided by TYST.
+
+use crate::cert::types::WellKnownAlgorithm;
+use num_bigint::BigUint;
+use rasn::types::Integer;
+use rasn::types::SequenceOf;
+use rasn_pkix::SubjectPublicKeyInfo;
+use tyst::Tyst;
+
+/** Return `true` if `signature` is a valid RSASSA-PSS signature of `message`
+by the `rsaEncryption` key in the DER encoded `encoded_spki`.
+
+MGF1 uses the same digest `digest_name` as the message and the salt length is
+the length of the digest as required by
+[RFC8446 4.2.3](https://www.rfc-editor.org/rfc/rfc8446#section-4.2.3).
+*/
+pub(super) fn verify(
+    encoded_spki: &[u8],
+    digest_name: &str,
+    signature: &[u8],
+    message: &[u8],
+) -> bool {
+    if Tyst::instance().digests().by_name(digest_name).is_none() {
+        return false;
+    }
+    let hash = |data: &[u8]| {
+        Tyst::instance()
+            .digests()
+            .by_name(digest_name)
+            .unwrap()
+            .hash(data)
+    };
+    let Some((modulus, public_exponent)) = rsa_public_key(encoded_spki) else {
+        return false;
+    };
+    // RFC 8017 8.1.2 RSASSA-PSS-VERIFY
+    let k = modulus.to_bytes_be().len();
+    let s = BigUint::from_bytes_be(signature);
+    if signature.len() != k || s >= modulus {
+        return false;
+    }
+    let em_bits = usize::try_from(modulus.bits() - 1).unwrap();
+    let em_len = em_bits.div_ceil(8);
+    let m = s.modpow(&public_exponent, &modulus).to_bytes_be();
+    if m.len() > em_len {
+        return false;
+    }
+    let mut em = vec![0u8; em_len - m.len()];
+    em.extend_from_slice(&m);
+    // RFC 8017 9.1.2 EMSA-PSS-VERIFY
+    let m_hash = hash(message);
+    let h_len = m_hash.len();
+    let s_len = h_len;
+    if em_len < h_len + s_len + 2 || em[em_len - 1] != 0xbc {
+        return false;
+    }
+    let (masked_db, h) = em[..em_len - 1].split_at(em_len - h_len - 1);
+    let unused_bits_mask = !(0xffu8 >> (8 * em_len - em_bits));
+    if masked_db[0] & unused_bits_mask != 0 {
+        return false;
+    }
+    let mut db = mgf1(&hash, h, masked_db.len());
+    db.iter_mut()
+        .zip(masked_db)
+        .for_each(|(octet, masked)| *octet ^= masked);
+    db[0] &= !unused_bits_mask;
+    let padding_len = em_len - h_len - s_len - 2;
+    if db[..padding_len].iter().any(|octet| *octet != 0) || db[padding_len] != 0x01 {
+        return false;
+    }
+    let salt = &db[db.len() - s_len..];
+    let mut m_prime = vec![0u8; 8];
+    m_prime.extend_from_slice(&m_hash);
+    m_prime.extend_from_slice(salt);
+    hash(&m_prime) == h
+}
+
+/// Return the modulus and public exponent of an `rsaEncryption` key.
+fn rsa_public_key(encoded_spki: &[u8]) -> Option<(BigUint, BigUint)> {
+    let spki = rasn::der::decode::<SubjectPublicKeyInfo>(encoded_spki).ok()?;
+    if !WellKnownAlgorithm::RsaEncryption
+        .as_oid()
+        .eq(spki.algorithm.algorithm.to_vec().as_slice())
+    {
+        return None;
+    }
+    // RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+    let rsa_public_key =
+        rasn::der::decode::<SequenceOf<Integer>>(spki.subject_public_key.as_raw_slice()).ok()?;
+    let [modulus, public_exponent] = rsa_public_key.as_slice() else {
+        return None;
+    };
+    let unsigned =
+        |value: &Integer| BigUint::from_bytes_be(&crate::encdec::rasn::integer_as_bytes_be(value));
+    Some((unsigned(modulus), unsigned(public_exponent)))
+}
+
+/// RFC 8017 B.2.1 MGF1 mask generation function.
+fn mgf1(hash: &dyn Fn(&[u8]) -> Vec<u8>, seed: &[u8], mask_len: usize) -> Vec<u8> {
+    let mut mask = vec![];
+    let mut counter = 0u32;
+    while mask.len() < mask_len {
+        let mut data = seed.to_vec();
+        data.extend_from_slice(&counter.to_be_bytes());
+        mask.extend(hash(&data));
+        counter += 1;
+    }
+    mask.truncate(mask_len);
+    mask
+}
@@ -0,0 +1,184 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! rustls server certificate verifier.
+
+use crate::cert::extensions::ExtendedKeyUsage;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::WellKnownGeneralName;
+use crate::cert::validate::checkers::ExtendedKeyUsageChecker;
+use crate::cert::validate::checkers::ExtensionChecker;
+use crate::cert::validate::CertificatePathValidator;
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::client::danger::ServerCertVerified;
+use rustls::client::danger::ServerCertVerifier;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::ServerName;
+use rustls::pki_types::UnixTime;
+use rustls::CertificateError;
+use rustls::DigitallySignedStruct;
+use rustls::SignatureScheme;
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+/** rustls [ServerCertVerifier] that validates the server's certificate chain
+using a [CertificatePathValidator].
+
+The chain is validated at the current time with the
+[ExtendedKeyUsage::PkixServerAuth] requirement on the leaf and the server name
+is matched against the subject alternative names of the leaf. Handshake
+signatures are verified using TYST.
+*/
+pub struct PathValidatingServerCertVerifier {
+    validator: Arc<CertificatePathValidator>,
+    extension_checkers: Vec<Arc<dyn ExtensionChecker>>,
+}
+
+impl PathValidatingServerCertVerifier {
+    /// Return a new instance.
+    pub fn new(validator: Arc<CertificatePathValidator>) -> Self {
+        Self {
+            validator,
+            extension_checkers: vec![Arc::new(ExtendedKeyUsageChecker::new(&[
+                ExtendedKeyUsage::PkixServerAuth,
+            ]))],
+        }
+    }
+
+    /// Add extension checkers that apply to server certificates in addition to
+    /// the ones of the [CertificatePathValidator].
+    pub fn with_extension_checkers(
+        mut self,
+        extension_checkers: Vec<Arc<dyn ExtensionChecker>>,
+    ) -> Self {
+        self.extension_checkers.extend(extension_checkers);
+        self
+    }
+
+    /** Return `true` if the server name matches a subject alternative name of
+    the leaf.
+
+    DNS names support a wildcard as the complete left-most label, see
+    [RFC6125 6.4.3](https://www.rfc-editor.org/rfc/rfc6125#section-6.4.3).
+    */
+    fn matches_server_name(leaf: &CertificateParser, server_name: &ServerName<'_>) -> bool {
+        let Ok(subject_alternative_names) = leaf.get_subject_alternative_name() else {
+            return false;
+        };
+        match server_name {
+            ServerName::DnsName(dns_name) => {
+                let dns_name = crate::encdec::puny_code::decode(dns_name.as_ref());
+                subject_alternative_names
+                    .iter()
+                    .filter(|(general_name, _)| *general_name == WellKnownGeneralName::DnsName)
+                    .any(|(_, value)| Self::matches_dns_name(value, &dns_name))
+            }
+            ServerName::IpAddress(ip_address) => {
+                let ip_address = IpAddr::from(*ip_address);
+                subject_alternative_names
+                    .iter()
+                    .filter(|(general_name, _)| *general_name == WellKnownGeneralName::IpAddress)
+                    .any(|(_, value)| value.parse::<IpAddr>().is_ok_and(|ip| ip == ip_address))
+            }
+            _ => false,
+        }
+    }
+
+    fn matches_dns_name(pattern: &str, dns_name: &str) -> bool {
+        let pattern = pattern.trim_end_matches('.');
+        let dns_name = dns_name.trim_end_matches('.');
+        if let Some(parent) = pattern.strip_prefix("*.") {
+            dns_name.split_once('.').is_some_and(|(label, remaining)| {
+                !label.is_empty() && remaining.eq_ignore_ascii_case(parent)
+            })
+        } else {
+            pattern.eq_ignore_ascii_case(dns_name)
+        }
+    }
+}
+
+impl fmt::Debug for PathValidatingServerCertVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PathValidatingServerCertVerifier")
+            .field("extension_checkers", &self.extension_checkers.len())
+            .finish()
+    }
+}
+
+impl ServerCertVerifier for PathValidatingServerCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        self.validator
+            .validate(
+                super::to_leaf_certificate_chain(end_entity, intermediates),
+                now.as_secs(),
+                self.extension_checkers.clone(),
+            )
+            .map_err(super::to_rustls_error)?;
+        let leaf = CertificateParser::from_bytes(end_entity.as_ref())
+            .map_err(|_| rustls::Error::InvalidCertificate(CertificateError::BadEncoding))?;
+        if !Self::matches_server_name(&leaf, server_name) {
+            return Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName,
+            ));
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        super::verify_tls12_signature(message, cert, dss.scheme, dss.signature())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        super::verify_tls13_signature(message, cert, dss.scheme, dss.signature())
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        super::supported_verify_schemes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dns_name_matching() {
+        let matches = PathValidatingServerCertVerifier::matches_dns_name;
+        assert!(matches("www.example.com", "WWW.example.com"));
+        assert!(matches("*.example.com", "www.example.com"));
+        assert!(!matches("*.example.com", "example.com"));
+        assert!(!matches("*.example.com", "a.www.example.com"));
+        assert!(!matches("www.example.com", "example.com"));
+    }
+}
@@ -0,0 +1,306 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! [RFC3161](https://www.rfc-editor.org/rfc/rfc3161) Time-Stamp Protocol (TSP).
+//!
+//! Time-stamp tokens are CMS SignedData with encapsulated `TSTInfo` content
+//! and are both created and verified using the [crate::cms] module.
+
+/*
+TimeStampReq ::= SEQUENCE  {
+    version                      INTEGER  { v1(1) },
+    messageImprint               MessageImprint,
+    reqPolicy             TSAPolicyId              OPTIONAL,
+    nonce                 INTEGER                  OPTIONAL,
+    certReq               BOOLEAN                  DEFAULT FALSE,
+    extensions            [0] IMPLICIT Extensions  OPTIONAL  }
+
+MessageImprint ::= SEQUENCE  {
+    hashAlgorithm                AlgorithmIdentifier,
+    hashedMessage                OCTET STRING  }
+
+TimeStampResp ::= SEQUENCE  {
+    status                  PKIStatusInfo,
+    timeStampToken          TimeStampToken     OPTIONAL  }
+
+PKIStatusInfo ::= SEQUENCE {
+    status        PKIStatus,
+    statusString  PKIFreeText     OPTIONAL,
+    failInfo      PKIFailureInfo  OPTIONAL  }
+
+TSTInfo ::= SEQUENCE  {
+    version                      INTEGER  { v1(1) },
+    policy                       TSAPolicyId,
+    messageImprint               MessageImprint,
+    serialNumber                 INTEGER,
+    genTime                      GeneralizedTime,
+    accuracy                     Accuracy                 OPTIONAL,
+    ordering                     BOOLEAN             DEFAULT FALSE,
+    nonce                        INTEGER                  OPTIONAL,
+    tsa                          [0] GeneralName          OPTIONAL,
+    extensions                   [1] IMPLICIT Extensions   OPTIONAL  }
+
+Accuracy ::= SEQUENCE {
+    seconds        INTEGER              OPTIONAL,
+    millis     [0] INTEGER  (1..999)    OPTIONAL,
+    micros     [1] INTEGER  (1..999)    OPTIONAL  }
+*/
+
+mod time_stamp_request;
+mod time_stamp_response_parser;
+mod time_stamp_token_builder;
+mod tsp_error;
+
+pub use self::time_stamp_request::TimeStampRequestBuilder;
+pub use self::time_stamp_request::TimeStampRequestParser;
+pub use self::time_stamp_response_parser::TimeStampResponseParser;
+pub use self::time_stamp_response_parser::TimeStampTokenParser;
+pub use self::time_stamp_token_builder::TimeStampTokenBuilder;
+pub use self::tsp_error::TspError;
+pub use self::tsp_error::TspErrorKind;
+use crate::cms::CmsDigestAlgorithm;
+use num_bigint::BigInt;
+use num_bigint::Sign;
+use rasn::prelude::*;
+use rasn_pkix::AlgorithmIdentifier;
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct TimeStampReq {
+    pub(crate) version: Integer,
+    pub(crate) message_imprint: MessageImprint,
+    pub(crate) req_policy: Option<ObjectIdentifier>,
+    pub(crate) nonce: Option<Integer>,
+    /// `None` means `false`
+    pub(crate) cert_req: Option<bool>,
+    #[rasn(tag(context, 0))]
+    pub(crate) extensions: Option<rasn_pkix::Extensions>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct MessageImprint {
+    pub(crate) hash_algorithm: AlgorithmIdentifier,
+    pub(crate) hashed_message: OctetString,
+}
+
+impl MessageImprint {
+    fn new(digest_algorithm: CmsDigestAlgorithm, hashed_message: &[u8]) -> Self {
+        Self {
+            hash_algorithm: digest_algorithm.to_algorithm_identifier(),
+            hashed_message: OctetString::from(hashed_message.to_vec()),
+        }
+    }
+
+    /// Return `true` if both imprints use the same hash algorithm and value.
+    ///
+    /// Absent and `NULL` hash algorithm parameters are treated as equal.
+    fn matches(&self, other: &Self) -> bool {
+        self.hash_algorithm
+            .algorithm
+            .eq(&other.hash_algorithm.algorithm)
+            && self.hashed_message.eq(&other.hashed_message)
+    }
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct TimeStampResp {
+    pub(crate) status: PkiStatusInfo,
+    pub(crate) time_stamp_token: Option<Any>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct PkiStatusInfo {
+    pub(crate) status: Integer,
+    pub(crate) status_string: Option<SequenceOf<Utf8String>>,
+    pub(crate) fail_info: Option<BitString>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct TstInfo {
+    pub(crate) version: Integer,
+    pub(crate) policy: ObjectIdentifier,
+    pub(crate) message_imprint: MessageImprint,
+    pub(crate) serial_number: Integer,
+    pub(crate) gen_time: GeneralizedTime,
+    pub(crate) accuracy: Option<Accuracy>,
+    /// `None` means `false`
+    pub(crate) ordering: Option<bool>,
+    pub(crate) nonce: Option<Integer>,
+    #[rasn(tag(explicit(context, 0)))]
+    pub(crate) tsa: Option<rasn_pkix::GeneralName>,
+    #[rasn(tag(context, 1))]
+    pub(crate) extensions: Option<rasn_pkix::Extensions>,
+}
+
+#[derive(AsnType, Decode, Encode, Debug, Clone, PartialEq)]
+pub(crate) struct Accuracy {
+    pub(crate) seconds: Option<Integer>,
+    #[rasn(tag(context, 0))]
+    pub(crate) millis: Option<Integer>,
+    #[rasn(tag(context, 1))]
+    pub(crate) micros: Option<Integer>,
+}
+
+impl Accuracy {
+    fn from_millis(accuracy_millis: u64) -> Self {
+        let seconds = accuracy_millis / 1000;
+        let millis = accuracy_millis % 1000;
+        Self {
+            seconds: (seconds > 0).then(|| Integer::from(seconds)),
+            millis: (millis > 0).then(|| Integer::from(millis)),
+            micros: None,
+        }
+    }
+
+    /// Return the accuracy in microseconds or `None` if any of the values is
+    /// out of range.
+    fn as_micros(&self) -> Option<u64> {
+        let sub_second = |value: &Option<Integer>| {
+            value.as_ref().map_or(Some(0), |value| {
+                crate::encdec::rasn::integer_try_as_u64(value)
+                    .filter(|value| (1..=999).contains(value))
+            })
+        };
+        let seconds = self
+            .seconds
+            .as_ref()
+            .map_or(Some(0), crate::encdec::rasn::integer_try_as_u64)?;
+        seconds
+            .checked_mul(1_000_000)?
+            .checked_add(sub_second(&self.millis)? * 1000)?
+            .checked_add(sub_second(&self.micros)?)
+    }
+}
+
+/// `id-ct-TSTInfo`
+const OID_CT_TST_INFO: &[u32] = &[1, 2, 840, 113549, 1, 9, 16, 1, 4];
+
+/// `PKIStatus` of a [TimeStampResponseParser].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PkiStatus {
+    /// A time-stamp token is present as requested.
+    Granted,
+    /// A time-stamp token is present with modifications of the request.
+    GrantedWithMods,
+    /// The request was rejected.
+    Rejection,
+    /// The request has not yet been processed.
+    Waiting,
+    /// A revocation is imminent.
+    RevocationWarning,
+    /// A revocation has occurred.
+    RevocationNotification,
+}
+
+impl PkiStatus {
+    /// Return the ASN.1 `INTEGER` value.
+    pub fn value(&self) -> u8 {
+        match self {
+            Self::Granted => 0,
+            Self::GrantedWithMods => 1,
+            Self::Rejection => 2,
+            Self::Waiting => 3,
+            Self::RevocationWarning => 4,
+            Self::RevocationNotification => 5,
+        }
+    }
+
+    /// Return a new instance from the ASN.1 `INTEGER` value.
+    pub fn from_value(value: usize) -> Option<Self> {
+        [
+            Self::Granted,
+            Self::GrantedWithMods,
+            Self::Rejection,
+            Self::Waiting,
+            Self::RevocationWarning,
+            Self::RevocationNotification,
+        ]
+        .into_iter()
+        .find(|status| usize::from(status.value()) == value)
+    }
+}
+
+/// `PKIFailureInfo` bits used by a TSA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PkiFailureInfo {
+    /// Unrecognized or unsupported algorithm identifier.
+    BadAlg,
+    /// Transaction not permitted or supported.
+    BadRequest,
+    /// The data submitted has the wrong format.
+    BadDataFormat,
+    /// The TSA's time source is not available.
+    TimeNotAvailable,
+    /// The requested TSA policy is not supported by the TSA.
+    UnacceptedPolicy,
+    /// The requested extension is not supported by the TSA.
+    UnacceptedExtension,
+    /// The additional information requested could not be understood or is
+    /// not available.
+    AddInfoNotAvailable,
+    /// The request cannot be handled due to system failure.
+    SystemFailure,
+}
+
+impl PkiFailureInfo {
+    const ALL: [Self; 8] = [
+        Self::BadAlg,
+        Self::BadRequest,
+        Self::BadDataFormat,
+        Self::TimeNotAvailable,
+        Self::UnacceptedPolicy,
+        Self::UnacceptedExtension,
+        Self::AddInfoNotAvailable,
+        Self::SystemFailure,
+    ];
+
+    /// Return the bit position in the `PKIFailureInfo` `BIT STRING`.
+    pub fn index(&self) -> usize {
+        match self {
+            Self::BadAlg => 0,
+            Self::BadRequest => 2,
+            Self::BadDataFormat => 5,
+            Self::TimeNotAvailable => 14,
+            Self::UnacceptedPolicy => 15,
+            Self::UnacceptedExtension => 16,
+            Self::AddInfoNotAvailable => 17,
+            Self::SystemFailure => 25,
+        }
+    }
+
+    fn to_rasn_type(self) -> BitString {
+        let mut ret = BitString::repeat(false, self.index() + 1);
+        ret.set(self.index(), true);
+        ret
+    }
+
+    fn from_rasn_type(fail_info: &BitString) -> Vec<Self> {
+        Self::ALL
+            .into_iter()
+            .filter(|failure_info| fail_info.get(failure_info.index()).is_some_and(|bit| *bit))
+            .collect()
+    }
+}
+
+/// Return the big-endian nonce as ASN.1 `INTEGER`.
+fn nonce_to_rasn_type(nonce: &[u8]) -> Integer {
+    // The nonce is a positive integer, even if the first octet is >= 0x80
+    Integer::from(BigInt::from_bytes_be(Sign::Plus, nonce))
+}
+
+fn decode<T: rasn::Decode>(encoded: &[u8]) -> Result<T, TspError> {
+    rasn::ber::decode::<T>(encoded)
+        .map_err(|e| TspErrorKind::DecodingError.error_with_msg(&e.to_string()))
+}
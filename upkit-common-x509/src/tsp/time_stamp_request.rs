@@ -0,0 +1,204 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Time-stamp request creation and parsing.
+
+use super::MessageImprint;
+use super::TimeStampReq;
+use super::TspError;
+use super::TspErrorKind;
+use crate::cms::CmsDigestAlgorithm;
+use rasn::prelude::*;
+use tyst::Tyst;
+
+/** Time-stamp request (`TimeStampReq`) builder.
+
+A random 64-bit nonce is included and the TSA certificate is requested by
+default, since the certificate is needed to verify the resulting token.
+*/
+pub struct TimeStampRequestBuilder {
+    digest_algorithm: CmsDigestAlgorithm,
+    hashed_message: Vec<u8>,
+    policy: Option<Vec<u32>>,
+    nonce: Option<Vec<u8>>,
+    certificate_request: bool,
+}
+
+impl TimeStampRequestBuilder {
+    /// Return a new instance for time-stamping the `message` using the
+    /// `digest_algorithm`.
+    pub fn new(message: &[u8], digest_algorithm: CmsDigestAlgorithm) -> Self {
+        Self::from_hashed_message(digest_algorithm, &digest_algorithm.digest(message))
+    }
+
+    /// Return a new instance for time-stamping an already hashed message.
+    pub fn from_hashed_message(
+        digest_algorithm: CmsDigestAlgorithm,
+        hashed_message: &[u8],
+    ) -> Self {
+        let mut nonce = vec![0u8; 8];
+        Tyst::instance().prng_fill_with_random(None, &mut nonce);
+        // Ensure that the nonce is positive
+        nonce[0] &= 0x7f;
+        Self {
+            digest_algorithm,
+            hashed_message: hashed_message.to_vec(),
+            policy: None,
+            nonce: Some(nonce),
+            certificate_request: true,
+        }
+    }
+
+    /// Request a specific TSA policy.
+    pub fn with_policy(mut self, policy: &[u32]) -> Self {
+        self.policy = Some(policy.to_vec());
+        self
+    }
+
+    /// Use the big-endian `nonce` instead of a random one or `None` to omit
+    /// the nonce.
+    pub fn with_nonce(mut self, nonce: Option<&[u8]>) -> Self {
+        self.nonce = nonce.map(<[u8]>::to_vec);
+        self
+    }
+
+    /// Don't ask the TSA to include its certificate in the token.
+    pub fn without_certificate_request(mut self) -> Self {
+        self.certificate_request = false;
+        self
+    }
+
+    /// Return the DER encoded `TimeStampReq`.
+    pub fn build(&self) -> Vec<u8> {
+        rasn::der::encode(&TimeStampReq {
+            version: Integer::from(1),
+            message_imprint: MessageImprint::new(self.digest_algorithm, &self.hashed_message),
+            req_policy: self
+                .policy
+                .as_ref()
+                .map(|policy| ObjectIdentifier::new_unchecked(policy.to_vec().into())),
+            nonce: self
+                .nonce
+                .as_ref()
+                .map(|nonce| super::nonce_to_rasn_type(nonce)),
+            cert_req: self.certificate_request.then_some(true),
+            extensions: None,
+        })
+        .unwrap()
+    }
+}
+
+/// Time-stamp request (`TimeStampReq`) parser.
+pub struct TimeStampRequestParser {
+    pub(crate) time_stamp_req: TimeStampReq,
+}
+
+impl TimeStampRequestParser {
+    /// Create a new instance from a BER or DER encoded `TimeStampReq`.
+    ///
+    /// Requests with unsupported hash algorithms, a hash of the wrong length or
+    /// any extensions are rejected.
+    pub fn from_bytes(encoded_request: &[u8]) -> Result<Self, TspError> {
+        let time_stamp_req = super::decode::<TimeStampReq>(encoded_request)?;
+        if !time_stamp_req.version.eq(&Integer::from(1)) {
+            return Err(TspErrorKind::DecodingError.error_with_msg("Unsupported version."));
+        }
+        let digest_algorithm = CmsDigestAlgorithm::from_algorithm_identifier(
+            &time_stamp_req.message_imprint.hash_algorithm,
+        )?;
+        if digest_algorithm.digest(&[]).len() != time_stamp_req.message_imprint.hashed_message.len()
+        {
+            return Err(TspErrorKind::DecodingError
+                .error_with_msg("Hashed message length does not match the hash algorithm."));
+        }
+        if time_stamp_req.extensions.is_some() {
+            return Err(TspErrorKind::DecodingError.error_with_msg("Extensions are not supported."));
+        }
+        Ok(Self { time_stamp_req })
+    }
+
+    /// Return the hash algorithm of the message imprint.
+    pub fn get_digest_algorithm(&self) -> CmsDigestAlgorithm {
+        CmsDigestAlgorithm::from_algorithm_identifier(
+            &self.time_stamp_req.message_imprint.hash_algorithm,
+        )
+        .unwrap()
+    }
+
+    /// Return the hashed message of the message imprint.
+    pub fn get_hashed_message(&self) -> &[u8] {
+        self.time_stamp_req.message_imprint.hashed_message.as_ref()
+    }
+
+    /// Return the requested TSA policy (if any).
+    pub fn get_policy(&self) -> Option<Vec<u32>> {
+        self.time_stamp_req
+            .req_policy
+            .as_ref()
+            .map(|policy| policy.to_vec())
+    }
+
+    /// Return the big-endian nonce (if any).
+    pub fn get_nonce(&self) -> Option<Vec<u8>> {
+        self.time_stamp_req
+            .nonce
+            .as_ref()
+            .map(crate::encdec::rasn::integer_as_bytes_be)
+    }
+
+    /// Return `true` if the TSA certificate should be included in the token.
+    pub fn is_certificate_requested(&self) -> bool {
+        self.time_stamp_req.cert_req.unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn request_round_trip() {
+        let encoded = TimeStampRequestBuilder::new(b"message", CmsDigestAlgorithm::Sha384)
+            .with_policy(&[1, 2, 3, 4])
+            .with_nonce(Some(&[1, 2, 3, 4, 5, 6, 7, 8]))
+            .build();
+        let request = TimeStampRequestParser::from_bytes(&encoded).unwrap();
+        assert_eq!(request.get_digest_algorithm(), CmsDigestAlgorithm::Sha384);
+        assert_eq!(
+            request.get_hashed_message(),
+            CmsDigestAlgorithm::Sha384.digest(b"message")
+        );
+        assert_eq!(request.get_policy(), Some(vec![1, 2, 3, 4]));
+        assert_eq!(request.get_nonce(), Some(vec![1, 2, 3, 4, 5, 6, 7, 8]));
+        assert!(request.is_certificate_requested());
+    }
+
+    #[test]
+    fn nonce_is_positive() {
+        let encoded = TimeStampRequestBuilder::new(b"message", CmsDigestAlgorithm::Sha256)
+            .with_nonce(Some(&[0x80, 0x01]))
+            .build();
+        let request = TimeStampRequestParser::from_bytes(&encoded).unwrap();
+        assert_eq!(
+            request
+                .time_stamp_req
+                .nonce
+                .as_ref()
+                .and_then(crate::encdec::rasn::integer_try_as_u64),
+            Some(0x8001)
+        );
+    }
+}
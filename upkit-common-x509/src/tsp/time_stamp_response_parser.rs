@@ -0,0 +1,505 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Time-stamp response parsing and token verification.
+
+use super::PkiFailureInfo;
+use super::PkiStatus;
+use super::TimeStampRequestParser;
+use super::TimeStampResp;
+use super::TspError;
+use super::TspErrorKind;
+use super::TstInfo;
+use crate::cert::extensions::ExtendedKeyUsage;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::SerialNumber;
+use crate::cert::validate::CertificatePathValidator;
+use crate::cms::CmsDigestAlgorithm;
+use crate::cms::SignedDataParser;
+use rasn::prelude::*;
+
+/// Time-stamp response (`TimeStampResp`) parser.
+pub struct TimeStampResponseParser {
+    time_stamp_resp: TimeStampResp,
+    status: PkiStatus,
+}
+
+impl TimeStampResponseParser {
+    /// Create a new instance from a BER or DER encoded `TimeStampResp`.
+    pub fn from_bytes(encoded_response: &[u8]) -> Result<Self, TspError> {
+        let time_stamp_resp = super::decode::<TimeStampResp>(encoded_response)?;
+        let status = crate::encdec::rasn::integer_try_as_u64(&time_stamp_resp.status.status)
+            .and_then(|value| usize::try_from(value).ok())
+            .and_then(PkiStatus::from_value)
+            .ok_or_else(|| TspErrorKind::DecodingError.error_with_msg("Unknown PKIStatus."))?;
+        Ok(Self {
+            time_stamp_resp,
+            status,
+        })
+    }
+
+    /// Return the status of the response.
+    pub fn get_status(&self) -> PkiStatus {
+        self.status
+    }
+
+    /// Return the free text status strings of the response.
+    pub fn get_status_strings(&self) -> Vec<String> {
+        self.time_stamp_resp
+            .status
+            .status_string
+            .clone()
+            .unwrap_or_default()
+    }
+
+    /// Return the reasons for a rejection.
+    pub fn get_failure_info(&self) -> Vec<PkiFailureInfo> {
+        self.time_stamp_resp
+            .status
+            .fail_info
+            .as_ref()
+            .map(PkiFailureInfo::from_rasn_type)
+            .unwrap_or_default()
+    }
+
+    /// Return the time-stamp token of a granted response.
+    pub fn get_time_stamp_token(&self) -> Result<TimeStampTokenParser, TspError> {
+        if !matches!(self.status, PkiStatus::Granted | PkiStatus::GrantedWithMods) {
+            let msg = format!(
+                "Status: {:?} Failure info: {:?} Status string: {}",
+                self.status,
+                self.get_failure_info(),
+                self.get_status_strings().join(" ")
+            );
+            return Err(TspErrorKind::Rejected.error_with_msg(&msg));
+        }
+        let time_stamp_token = self
+            .time_stamp_resp
+            .time_stamp_token
+            .as_ref()
+            .ok_or_else(|| {
+                TspErrorKind::DecodingError.error_with_msg("Missing time-stamp token.")
+            })?;
+        TimeStampTokenParser::from_bytes(time_stamp_token.as_bytes())
+    }
+
+    /** Return the verified time-stamp token of a granted response.
+
+    The token must match the `request` (see [TimeStampTokenParser::check_request])
+    and the TSA certificate must be valid (see [TimeStampTokenParser::validate]).
+    */
+    pub fn validate(
+        &self,
+        request: &TimeStampRequestParser,
+        certificate_path_validator: &CertificatePathValidator,
+        at_epoch_seconds: u64,
+    ) -> Result<TimeStampTokenParser, TspError> {
+        let time_stamp_token = self.get_time_stamp_token()?;
+        time_stamp_token.check_request(request)?;
+        time_stamp_token.validate(certificate_path_validator, at_epoch_seconds)?;
+        Ok(time_stamp_token)
+    }
+}
+
+/// Time-stamp token (`TimeStampToken`) parser.
+pub struct TimeStampTokenParser {
+    signed_data_parser: SignedDataParser,
+    tst_info: TstInfo,
+    gen_time: u64,
+    accuracy_micros: Option<u64>,
+}
+
+impl TimeStampTokenParser {
+    /// Create a new instance from a BER or DER encoded `TimeStampToken`.
+    pub fn from_bytes(encoded_token: &[u8]) -> Result<Self, TspError> {
+        let signed_data_parser = SignedDataParser::from_bytes(encoded_token)?;
+        if !signed_data_parser
+            .get_content_type()
+            .eq(super::OID_CT_TST_INFO)
+        {
+            return Err(
+                TspErrorKind::DecodingError.error_with_msg("Encapsulated content is not TSTInfo.")
+            );
+        }
+        let tst_info = super::decode::<TstInfo>(
+            signed_data_parser
+                .get_content()
+                .ok_or_else(|| TspErrorKind::DecodingError.error_with_msg("Missing TSTInfo."))?,
+        )?;
+        if !tst_info.version.eq(&Integer::from(1)) {
+            return Err(TspErrorKind::DecodingError.error_with_msg("Unsupported version."));
+        }
+        let gen_time = u64::try_from(tst_info.gen_time.timestamp()).map_err(|_| {
+            TspErrorKind::DecodingError.error_with_msg("Time-stamp is before 1970-01-01.")
+        })?;
+        let accuracy_micros = tst_info
+            .accuracy
+            .as_ref()
+            .map(|accuracy| {
+                accuracy
+                    .as_micros()
+                    .ok_or_else(|| TspErrorKind::DecodingError.error_with_msg("Invalid accuracy."))
+            })
+            .transpose()?;
+        Ok(Self {
+            signed_data_parser,
+            tst_info,
+            gen_time,
+            accuracy_micros,
+        })
+    }
+
+    /// Return the time of the time-stamp as seconds since 1970-01-01 00:00:00.
+    pub fn get_gen_time(&self) -> u64 {
+        self.gen_time
+    }
+
+    /// Return the accuracy of [Self::get_gen_time] in microseconds (if
+    /// present).
+    pub fn get_accuracy_micros(&self) -> Option<u64> {
+        self.accuracy_micros
+    }
+
+    /// Return the TSA policy.
+    pub fn get_policy(&self) -> Vec<u32> {
+        self.tst_info.policy.to_vec()
+    }
+
+    /// Return the serial number assigned by the TSA.
+    pub fn get_serial_number(&self) -> SerialNumber {
+        SerialNumber::from_rasn_type(&self.tst_info.serial_number)
+    }
+
+    /// Return the big-endian nonce (if any).
+    pub fn get_nonce(&self) -> Option<Vec<u8>> {
+        self.tst_info
+            .nonce
+            .as_ref()
+            .map(crate::encdec::rasn::integer_as_bytes_be)
+    }
+
+    /// Return the hashed message of the message imprint.
+    pub fn get_hashed_message(&self) -> &[u8] {
+        self.tst_info.message_imprint.hashed_message.as_ref()
+    }
+
+    /// Return the certificates included in the token.
+    pub fn get_certificates(&self) -> &[CertificateParser] {
+        self.signed_data_parser.get_certificates()
+    }
+
+    /** Check that message imprint, nonce and policy match the `request`.
+
+    When the request asked for the TSA certificate, it must be included in
+    the token.
+    */
+    pub fn check_request(&self, request: &TimeStampRequestParser) -> Result<(), TspError> {
+        if !self
+            .tst_info
+            .message_imprint
+            .matches(&request.time_stamp_req.message_imprint)
+        {
+            return Err(TspErrorKind::MessageImprintMismatch.error());
+        }
+        if let Some(nonce) = &request.time_stamp_req.nonce {
+            if !self.tst_info.nonce.as_ref().is_some_and(|n| n.eq(nonce)) {
+                return Err(TspErrorKind::NonceMismatch.error());
+            }
+        }
+        if let Some(policy) = &request.time_stamp_req.req_policy {
+            if !self.tst_info.policy.eq(policy) {
+                return Err(TspErrorKind::PolicyMismatch.error());
+            }
+        }
+        if request.is_certificate_requested()
+            && !self.signed_data_parser.includes_signer_certificates()
+        {
+            return Err(TspErrorKind::InvalidTsaCertificate
+                .error_with_msg("The requested TSA certificate is not included in the token."));
+        }
+        Ok(())
+    }
+
+    /// Check that the message imprint is the digest of the `message`.
+    pub fn check_message(&self, message: &[u8]) -> Result<(), TspError> {
+        let digest_algorithm = CmsDigestAlgorithm::from_algorithm_identifier(
+            &self.tst_info.message_imprint.hash_algorithm,
+        )?;
+        if digest_algorithm
+            .digest(message)
+            .eq(self.get_hashed_message())
+        {
+            Ok(())
+        } else {
+            Err(TspErrorKind::MessageImprintMismatch.error())
+        }
+    }
+
+    /** Verify the signature and validate the TSA certificate chain.
+
+    The token must have exactly one signer and an ESS signing-certificate or
+    signing-certificate-v2 signed attribute that references the TSA
+    certificate ([RFC3161 2.4.1](https://www.rfc-editor.org/rfc/rfc3161#section-2.4.1)
+    and [RFC5816](https://www.rfc-editor.org/rfc/rfc5816)).
+
+    The TSA certificate must have a critical Extended Key Usage extension with
+    `id-kp-timeStamping` as the only purpose ([RFC3161 2.3](https://www.rfc-editor.org/rfc/rfc3161#section-2.3))
+    and is validated with the `certificate_path_validator` at
+    `at_epoch_seconds` together with the CA certificates in the token.
+    */
+    pub fn validate(
+        &self,
+        certificate_path_validator: &CertificatePathValidator,
+        at_epoch_seconds: u64,
+    ) -> Result<(), TspError> {
+        if !self.signed_data_parser.has_signing_certificate_attributes() {
+            return Err(TspErrorKind::InvalidSignature
+                .error_with_msg("Missing ESS signing certificate attribute."));
+        }
+        let signer_certificates = self.signed_data_parser.verify_signatures(None)?;
+        let [tsa_certificate] = signer_certificates.as_slice() else {
+            return Err(TspErrorKind::InvalidSignature
+                .error_with_msg("Time-stamp token must have exactly one signer."));
+        };
+        check_tsa_certificate(tsa_certificate)?;
+        self.signed_data_parser.validate_signer_certificate(
+            tsa_certificate,
+            certificate_path_validator,
+            at_epoch_seconds,
+            &[ExtendedKeyUsage::PkixTimeStamping],
+        )?;
+        Ok(())
+    }
+}
+
+/// Check the RFC3161 2.3 Extended Key Usage requirements of the TSA
+/// certificate.
+pub(crate) fn check_tsa_certificate(tsa_certificate: &CertificateParser) -> Result<(), TspError> {
+    let extended_key_usage = tsa_certificate.get_extended_key_usage().map_err(|e| {
+        TspErrorKind::InvalidTsaCertificate
+            .error_with_msg(&format!("Invalid Extended Key Usage: {e}"))
+    })?;
+    if !matches!(
+        extended_key_usage.as_slice(),
+        [ExtendedKeyUsage::PkixTimeStamping]
+    ) {
+        return Err(TspErrorKind::InvalidTsaCertificate
+            .error_with_msg("Extended Key Usage must be id-kp-timeStamping only."));
+    }
+    if !tsa_certificate
+        .get_critical_extension_oids()
+        .iter()
+        .any(|oid| oid.eq(ExtendedKeyUsage::OID))
+    {
+        return Err(TspErrorKind::InvalidTsaCertificate
+            .error_with_msg("Extended Key Usage extension must be critical."));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cert::types::Validity;
+    use crate::cert::types::WellKnownAlgorithm;
+    use crate::test_utils::*;
+    use crate::tsp::Accuracy;
+    use crate::tsp::PkiStatusInfo;
+    use crate::tsp::TimeStampRequestBuilder;
+    use crate::tsp::TimeStampTokenBuilder;
+
+    const MESSAGE: &[u8] = b"Time-stamped message";
+    const POLICY: &[u32] = &[1, 3, 6, 1, 4, 1, 99999, 1];
+
+    fn request(builder: TimeStampRequestBuilder) -> TimeStampRequestParser {
+        TimeStampRequestParser::from_bytes(&builder.build()).unwrap()
+    }
+
+    fn respond(request: &TimeStampRequestParser, gen_time: u64) -> TimeStampResponseParser {
+        let private_key = load_test_private_key("tsa_key.pem").to_private_key();
+        let encoded = TimeStampTokenBuilder::with_details(
+            request,
+            POLICY,
+            &load_test_certificate_der("tsa.b64"),
+            SerialNumber::from_bytes_be(&[0x01, 0x02]),
+            gen_time,
+            Some(1500),
+        )
+        .unwrap()
+        .sign(
+            private_key.as_ref(),
+            WellKnownAlgorithm::EcdsaWithSha256.as_oid(),
+        )
+        .unwrap();
+        TimeStampResponseParser::from_bytes(&encoded).unwrap()
+    }
+
+    #[test]
+    fn time_stamp_and_validate() {
+        let now = Validity::now_epoch_seconds();
+        let request = request(
+            TimeStampRequestBuilder::new(MESSAGE, CmsDigestAlgorithm::Sha256)
+                .with_policy(POLICY)
+                .with_nonce(Some(&[0x01, 0x23, 0x45])),
+        );
+        let response = respond(&request, now);
+        assert_eq!(response.get_status(), PkiStatus::Granted);
+        let validator = CertificatePathValidator::new(vec![load_test_certificate_der("ec_ca.b64")])
+            .unwrap()
+            .add_standard_extension_checkers();
+        let token = response.validate(&request, &validator, now).unwrap();
+        assert_eq!(token.get_gen_time(), now);
+        assert_eq!(token.get_accuracy_micros(), Some(1_500_000));
+        assert_eq!(token.get_policy(), POLICY);
+        assert_eq!(token.get_nonce(), Some(vec![0x01, 0x23, 0x45]));
+        assert_eq!(
+            token.get_serial_number(),
+            SerialNumber::from_bytes_be(&[0x01, 0x02])
+        );
+        token.check_message(MESSAGE).unwrap();
+        assert_eq!(
+            token.check_message(b"Other message").err().unwrap().kind(),
+            &TspErrorKind::MessageImprintMismatch
+        );
+        // The TSA certificate must be trusted
+        let untrusted_validator =
+            CertificatePathValidator::new(vec![load_test_certificate_der("h1_root_ca.b64")])
+                .unwrap();
+        assert_eq!(
+            token
+                .validate(&untrusted_validator, now)
+                .err()
+                .unwrap()
+                .kind(),
+            &TspErrorKind::InvalidTsaCertificate
+        );
+    }
+
+    #[test]
+    fn token_must_match_request() {
+        let request_with_nonce = |nonce: &[u8]| {
+            request(
+                TimeStampRequestBuilder::new(MESSAGE, CmsDigestAlgorithm::Sha256)
+                    .with_nonce(Some(nonce)),
+            )
+        };
+        let token = respond(&request_with_nonce(&[0x01]), Validity::now_epoch_seconds())
+            .get_time_stamp_token()
+            .unwrap();
+        let mismatch = |request: TimeStampRequestParser| {
+            token.check_request(&request).err().unwrap().kind().clone()
+        };
+        token.check_request(&request_with_nonce(&[0x01])).unwrap();
+        assert_eq!(
+            mismatch(request_with_nonce(&[0x02])),
+            TspErrorKind::NonceMismatch
+        );
+        // A nonce in the token is fine when none was requested
+        token
+            .check_request(&request(
+                TimeStampRequestBuilder::new(MESSAGE, CmsDigestAlgorithm::Sha256).with_nonce(None),
+            ))
+            .unwrap();
+        assert_eq!(
+            mismatch(request(
+                TimeStampRequestBuilder::new(b"Other message", CmsDigestAlgorithm::Sha256)
+                    .with_nonce(Some(&[0x01]))
+            )),
+            TspErrorKind::MessageImprintMismatch
+        );
+        assert_eq!(
+            mismatch(request(
+                TimeStampRequestBuilder::new(MESSAGE, CmsDigestAlgorithm::Sha384)
+                    .with_nonce(Some(&[0x01]))
+            )),
+            TspErrorKind::MessageImprintMismatch
+        );
+        assert_eq!(
+            mismatch(request(
+                TimeStampRequestBuilder::new(MESSAGE, CmsDigestAlgorithm::Sha256)
+                    .with_nonce(Some(&[0x01]))
+                    .with_policy(&[1, 3, 6, 1, 4, 1, 99999, 2])
+            )),
+            TspErrorKind::PolicyMismatch
+        );
+        // A requested TSA certificate must be included
+        let request_without_certificate = request(
+            TimeStampRequestBuilder::new(MESSAGE, CmsDigestAlgorithm::Sha256)
+                .with_nonce(Some(&[0x01]))
+                .without_certificate_request(),
+        );
+        let token_without_certificate =
+            respond(&request_without_certificate, Validity::now_epoch_seconds())
+                .get_time_stamp_token()
+                .unwrap();
+        assert!(token_without_certificate.get_certificates().is_empty());
+        token_without_certificate
+            .check_request(&request_without_certificate)
+            .unwrap();
+        assert_eq!(
+            token_without_certificate
+                .check_request(&request_with_nonce(&[0x01]))
+                .err()
+                .unwrap()
+                .kind(),
+            &TspErrorKind::InvalidTsaCertificate
+        );
+        // The TSA refuses to issue tokens for other policies
+        let err = TimeStampTokenBuilder::new(
+            &request(
+                TimeStampRequestBuilder::new(MESSAGE, CmsDigestAlgorithm::Sha256)
+                    .with_policy(&[1, 3, 6, 1, 4, 1, 99999, 2]),
+            ),
+            POLICY,
+            &load_test_certificate_der("tsa.b64"),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err.kind(), &TspErrorKind::PolicyMismatch);
+    }
+
+    #[test]
+    fn untrusted_values_are_rejected() {
+        let integer = |value: i64| Some(Integer::from(value));
+        let accuracy = |seconds, millis, micros| Accuracy {
+            seconds,
+            millis,
+            micros,
+        };
+        assert_eq!(
+            accuracy(integer(2), integer(3), integer(4)).as_micros(),
+            Some(2_003_004)
+        );
+        assert_eq!(accuracy(None, None, None).as_micros(), Some(0));
+        assert_eq!(accuracy(integer(-1), None, None).as_micros(), None);
+        assert_eq!(accuracy(None, integer(1000), None).as_micros(), None);
+        assert_eq!(accuracy(None, None, integer(0)).as_micros(), None);
+        assert_eq!(accuracy(integer(i64::MAX), None, None).as_micros(), None);
+        for status in [-1, 6, i64::MAX] {
+            let encoded = rasn::der::encode(&TimeStampResp {
+                status: PkiStatusInfo {
+                    status: Integer::from(status),
+                    status_string: None,
+                    fail_info: None,
+                },
+                time_stamp_token: None,
+            })
+            .unwrap();
+            let err = TimeStampResponseParser::from_bytes(&encoded).err().unwrap();
+            assert_eq!(err.kind(), &TspErrorKind::DecodingError);
+        }
+    }
+}
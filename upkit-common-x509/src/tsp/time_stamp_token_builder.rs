@@ -0,0 +1,197 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Time-stamp token creation by a TSA.
+
+use super::Accuracy;
+use super::PkiFailureInfo;
+use super::PkiStatus;
+use super::PkiStatusInfo;
+use super::TimeStampRequestParser;
+use super::TimeStampResp;
+use super::TspError;
+use super::TspErrorKind;
+use super::TstInfo;
+use crate::cert::parse::CertificateParser;
+use crate::cert::types::SerialNumber;
+use crate::cert::types::Validity;
+use crate::cms::SignedDataBuilder;
+use chrono::TimeZone;
+use rasn::prelude::*;
+use tyst::traits::se::PrivateKey;
+use tyst::Tyst;
+
+/** Time-stamp token builder for a local TSA.
+
+Signing is done in two phases like for [SignedDataBuilder]: first the
+[Self::get_signed_attributes_to_be_signed] are signed by the holder of the TSA
+private key and then [Self::to_time_stamp_response] assembles the response.
+For keys available through `tyst`, [Self::sign] does both.
+
+The TSA certificate must have `id-kp-timeStamping` as its only Extended Key
+Usage in a critical extension as required by
+[RFC3161 2.3](https://www.rfc-editor.org/rfc/rfc3161#section-2.3).
+*/
+pub struct TimeStampTokenBuilder {
+    signed_data_builder: SignedDataBuilder,
+}
+
+impl TimeStampTokenBuilder {
+    /** Return a new instance that will answer the `request`.
+
+    `policy` is the TSA policy. A request for any other policy is rejected.
+    The time of the token is the creation time of this builder.
+    */
+    pub fn new(
+        request: &TimeStampRequestParser,
+        policy: &[u32],
+        tsa_certificate_der: &[u8],
+    ) -> Result<Self, TspError> {
+        Self::with_details(
+            request,
+            policy,
+            tsa_certificate_der,
+            SerialNumber::default(),
+            Validity::now_epoch_seconds(),
+            None,
+        )
+    }
+
+    /** Return a new instance that will answer the `request`.
+
+    The TSA is responsible for the uniqueness of the `serial_number`.
+    `gen_time_epoch_seconds` is the time of the token and `accuracy_millis`
+    optionally the accuracy of the time source.
+    */
+    pub fn with_details(
+        request: &TimeStampRequestParser,
+        policy: &[u32],
+        tsa_certificate_der: &[u8],
+        serial_number: SerialNumber,
+        gen_time_epoch_seconds: u64,
+        accuracy_millis: Option<u64>,
+    ) -> Result<Self, TspError> {
+        if request
+            .get_policy()
+            .is_some_and(|requested_policy| !requested_policy.eq(policy))
+        {
+            return Err(TspErrorKind::PolicyMismatch
+                .error_with_msg("The requested policy is not supported by this TSA."));
+        }
+        let tsa_certificate = CertificateParser::from_bytes(tsa_certificate_der)
+            .map_err(|e| TspErrorKind::InvalidTsaCertificate.error_with_msg(&e.to_string()))?;
+        super::time_stamp_response_parser::check_tsa_certificate(&tsa_certificate)?;
+        let gen_time = chrono::Utc
+            .timestamp_opt(i64::try_from(gen_time_epoch_seconds).unwrap(), 0)
+            .unwrap()
+            .fixed_offset();
+        let tst_info = TstInfo {
+            version: Integer::from(1),
+            policy: ObjectIdentifier::new_unchecked(policy.to_vec().into()),
+            message_imprint: request.time_stamp_req.message_imprint.clone(),
+            serial_number: serial_number.to_rasn_type(),
+            gen_time,
+            accuracy: accuracy_millis.map(Accuracy::from_millis),
+            ordering: None,
+            nonce: request.time_stamp_req.nonce.clone(),
+            tsa: None,
+            extensions: None,
+        };
+        let mut signed_data_builder =
+            SignedDataBuilder::new(&rasn::der::encode(&tst_info).unwrap(), tsa_certificate_der)?
+                .with_content_type(super::OID_CT_TST_INFO)
+                .with_digest_algorithm(request.get_digest_algorithm())
+                .with_signing_time(gen_time_epoch_seconds);
+        if !request.is_certificate_requested() {
+            signed_data_builder = signed_data_builder.without_certificates();
+        }
+        Ok(Self {
+            signed_data_builder,
+        })
+    }
+
+    /// Return the DER encoded signed attributes that should be signed.
+    pub fn get_signed_attributes_to_be_signed(&self) -> Vec<u8> {
+        self.signed_data_builder
+            .get_signed_attributes_to_be_signed()
+    }
+
+    /** Return the DER encoded `TimeStampToken`.
+
+    `signature_algorithm` is a DER encoded `AlgorithmIdentifier` (see
+    [crate::cms::signature_algorithm_identifier]) and `signature` is the
+    signature of [Self::get_signed_attributes_to_be_signed].
+    */
+    pub fn to_time_stamp_token(
+        &self,
+        signature_algorithm: &[u8],
+        signature: Vec<u8>,
+    ) -> Result<Vec<u8>, TspError> {
+        Ok(self
+            .signed_data_builder
+            .to_content_info(signature_algorithm, signature)?)
+    }
+
+    /// Return the DER encoded `TimeStampResp` with the granted token.
+    pub fn to_time_stamp_response(
+        &self,
+        signature_algorithm: &[u8],
+        signature: Vec<u8>,
+    ) -> Result<Vec<u8>, TspError> {
+        let time_stamp_token = self.to_time_stamp_token(signature_algorithm, signature)?;
+        Ok(rasn::der::encode(&TimeStampResp {
+            status: PkiStatusInfo {
+                status: Integer::from(PkiStatus::Granted.value()),
+                status_string: None,
+                fail_info: None,
+            },
+            time_stamp_token: Some(Any::new(time_stamp_token)),
+        })
+        .unwrap())
+    }
+
+    /// Sign using a `tyst` private key and return the DER encoded
+    /// `TimeStampResp`.
+    pub fn sign(
+        &self,
+        private_key: &dyn PrivateKey,
+        signature_algorithm_oid: &[u32],
+    ) -> Result<Vec<u8>, TspError> {
+        let oid = crate::encdec::oid::as_string(signature_algorithm_oid);
+        let mut se = Tyst::instance().ses().by_oid(&oid).ok_or_else(|| {
+            let msg = format!("Unsupported signature algorithm '{oid}'.");
+            TspErrorKind::UnsupportedAlgorithm.error_with_msg(&msg)
+        })?;
+        let signature = se.sign(private_key, &self.get_signed_attributes_to_be_signed());
+        self.to_time_stamp_response(
+            &crate::cms::signature_algorithm_identifier(signature_algorithm_oid),
+            signature,
+        )
+    }
+
+    /// Return a DER encoded `TimeStampResp` that rejects a request.
+    pub fn to_rejection_response(failure_info: PkiFailureInfo, status_string: &str) -> Vec<u8> {
+        rasn::der::encode(&TimeStampResp {
+            status: PkiStatusInfo {
+                status: Integer::from(PkiStatus::Rejection.value()),
+                status_string: Some(vec![status_string.to_string()]),
+                fail_info: Some(failure_info.to_rasn_type()),
+            },
+            time_stamp_token: None,
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,119 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Time-Stamp Protocol errors.
+
+use crate::cms::CmsError;
+use crate::cms::CmsErrorKind;
+use std::error::Error;
+use std::fmt;
+
+/// Cause of Time-Stamp Protocol error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TspErrorKind {
+    /// Failure to decode the BER or DER encoded message or one of its parts.
+    DecodingError,
+    /// The message uses an algorithm that is not supported.
+    UnsupportedAlgorithm,
+    /// The TSA did not grant the request.
+    Rejected,
+    /// The message imprint of the token does not match the request or message.
+    MessageImprintMismatch,
+    /// The nonce of the token does not match the request.
+    NonceMismatch,
+    /// The policy of the token does not match the request.
+    PolicyMismatch,
+    /// The signature of the token could not be verified.
+    InvalidSignature,
+    /// The TSA certificate is not suitable for time-stamping or failed path
+    /// validation.
+    InvalidTsaCertificate,
+    /// Failure to create the time-stamp token.
+    SigningFailure,
+}
+
+impl TspErrorKind {
+    /// Create a new instance with an error message.
+    pub fn error_with_msg(self, msg: &str) -> TspError {
+        TspError {
+            kind: self,
+            msg: Some(msg.to_string()),
+        }
+    }
+
+    /// Create a new instance without an error message.
+    pub fn error(self) -> TspError {
+        TspError {
+            kind: self,
+            msg: None,
+        }
+    }
+}
+
+impl fmt::Display for TspErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/** Time-Stamp Protocol error.
+
+Create a new instance via [TspErrorKind].
+*/
+#[derive(Debug)]
+pub struct TspError {
+    kind: TspErrorKind,
+    msg: Option<String>,
+}
+
+impl TspError {
+    /// Return the [TspErrorKind] type of this error.
+    pub fn kind(&self) -> &TspErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for TspError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(msg) = &self.msg {
+            write!(f, "{} {}", self.kind, msg)
+        } else {
+            write!(f, "{}", self.kind)
+        }
+    }
+}
+
+impl Error for TspError {}
+
+impl From<CmsError> for TspError {
+    fn from(value: CmsError) -> Self {
+        let kind = match value.kind() {
+            CmsErrorKind::DecodingError => TspErrorKind::DecodingError,
+            CmsErrorKind::UnsupportedAlgorithm => TspErrorKind::UnsupportedAlgorithm,
+            CmsErrorKind::InvalidSignerCertificate | CmsErrorKind::InvalidKeyUsage => {
+                TspErrorKind::InvalidTsaCertificate
+            }
+            CmsErrorKind::EncryptionFailure | CmsErrorKind::DecryptionFailure => {
+                TspErrorKind::SigningFailure
+            }
+            CmsErrorKind::SignerNotFound
+            | CmsErrorKind::RecipientNotFound
+            | CmsErrorKind::InvalidAttributes
+            | CmsErrorKind::InvalidSignature => TspErrorKind::InvalidSignature,
+        };
+        kind.error_with_msg(&value.to_string())
+    }
+}
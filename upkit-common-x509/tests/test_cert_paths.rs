@@ -24,6 +24,10 @@ use upkit_common_x509::cert::parse::CertificateParser;
 use upkit_common_x509::cert::validate::checkers::CertificatePoliciesChecker;
 use upkit_common_x509::cert::validate::checkers::ExtendedKeyUsageChecker;
 use upkit_common_x509::cert::validate::CertificatePathValidator;
+use upkit_common_x509::cert::validate::CertificateValidationErrorKind;
+use upkit_common_x509::cert::validate::ValidationModel;
+use upkit_common_x509::cert::validate::ValidationTimePolicy;
+use upkit_common_x509::cert::validate::ValidationWarning;
 
 pub fn init_logger() {
     let _ = env_logger::builder()
@@ -95,6 +99,150 @@ fn test_cert_path_real_world() {
         .unwrap();
 }
 
+#[test]
+fn test_cert_path_batch_with_cache() {
+    init_logger();
+    // Hardcode a time where this should succeed
+    let at_epoch_seconds = 1739555555;
+    let chain = vec![load_b64_file("h1_leaf.b64"), load_b64_file("h1_sub_ca.b64")];
+    let results = CertificatePathValidator::new(vec![load_b64_file("h1_root_ca.b64")])
+        .unwrap()
+        .add_standard_extension_checkers()
+        .with_cache(16)
+        .validate_batch(
+            vec![chain.clone(), vec![chain[1].clone()], chain],
+            at_epoch_seconds,
+            vec![],
+        );
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results[0].as_ref().unwrap().get_validation_model(),
+        ValidationModel::Shell
+    );
+    // A chain without leaf must still fail with a warm cache
+    assert!(results[1].is_err());
+    assert!(results[2].is_ok());
+}
+
+#[test]
+fn test_cert_path_clock_skew() {
+    init_logger();
+    let leaf = load_b64_file("h1_leaf.b64");
+    let not_before = CertificateParser::from_bytes(&leaf)
+        .unwrap()
+        .get_validity()
+        .unwrap()
+        .get_not_before();
+    let chain = vec![leaf, load_b64_file("h1_sub_ca.b64")];
+    let validator = CertificatePathValidator::new(vec![load_b64_file("h1_root_ca.b64")])
+        .unwrap()
+        .add_standard_extension_checkers();
+    assert!(validator
+        .validate(chain.clone(), not_before - 30, vec![])
+        .is_err());
+    let result = validator
+        .with_time_policy(ValidationTimePolicy::default().with_clock_skew_tolerance(60))
+        .validate_with_model(chain, not_before - 30, ValidationModel::Shell, vec![])
+        .unwrap();
+    assert!(matches!(
+        result.get_warnings().first(),
+        Some(ValidationWarning::WithinClockSkew { .. })
+    ));
+}
+
+#[test]
+fn test_cert_path_intermediate_trust_anchor() {
+    init_logger();
+    let at_epoch_seconds = 1739555555;
+    let leaf = load_b64_file("h1_leaf.b64");
+    let validator = CertificatePathValidator::new(vec![load_b64_file("h1_sub_ca.b64")])
+        .unwrap()
+        .add_standard_extension_checkers();
+    // The trusted sub CA is part of the leaf chain
+    let result = validator
+        .validate_with_model(
+            vec![leaf.clone(), load_b64_file("h1_sub_ca.b64")],
+            at_epoch_seconds,
+            ValidationModel::Shell,
+            vec![],
+        )
+        .unwrap();
+    assert_eq!(result.get_validation_times().len(), 2);
+    // The signature of the leaf must still be verified using the trust anchor
+    let mut forged_leaf = leaf;
+    let last = forged_leaf.len() - 1;
+    forged_leaf[last] ^= 0x01;
+    let err = validator
+        .validate(
+            vec![forged_leaf, load_b64_file("h1_sub_ca.b64")],
+            at_epoch_seconds,
+            vec![],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        CertificateValidationErrorKind::InvalidSignature
+    ));
+}
+
+#[test]
+fn test_cert_path_chain_model() {
+    init_logger();
+    // The sub CA expires 2027-01-01, but was valid when it issued the leaf
+    let leaf = load_b64_file("ec_sub_ca_leaf.b64");
+    let leaf_not_before = 1793491200;
+    let sub_ca_not_before = 1792368000;
+    let chain = vec![leaf, load_b64_file("ec_sub_ca.b64")];
+    let validator = CertificatePathValidator::new(vec![load_b64_file("ec_ca.b64")])
+        .unwrap()
+        .add_standard_extension_checkers();
+    // 2027-06-01
+    let at_epoch_seconds = 1811808000;
+    let err = validator
+        .validate_with_model(
+            chain.clone(),
+            at_epoch_seconds,
+            ValidationModel::Shell,
+            vec![],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        CertificateValidationErrorKind::InvalidLifeSpan
+    ));
+    let result = validator
+        .validate_with_model(
+            chain.clone(),
+            at_epoch_seconds,
+            ValidationModel::Chain,
+            vec![],
+        )
+        .unwrap();
+    assert_eq!(result.get_validation_model(), ValidationModel::Chain);
+    assert_eq!(result.get_at_epoch_seconds(), at_epoch_seconds);
+    assert_eq!(
+        result
+            .get_validation_times()
+            .iter()
+            .map(|(_fingerprint, validation_time)| *validation_time)
+            .collect::<Vec<_>>(),
+        vec![at_epoch_seconds, leaf_not_before, sub_ca_not_before]
+    );
+    // The leaf itself must still be valid at the point in time of validation
+    let err = validator
+        .validate_with_model(
+            chain,
+            leaf_not_before - 86400,
+            ValidationModel::Chain,
+            vec![],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        CertificateValidationErrorKind::InvalidLifeSpan
+    ));
+}
+
 fn load_b64_file(relative_path: &str) -> Vec<u8> {
     let contents = load_test_resource_file(relative_path);
     let bytes = tyst::encdec::base64::decode(&contents).unwrap();
@@ -0,0 +1,93 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Command failure and exit code mapping.
+
+use std::fmt;
+use upkit_common_x509::cert::validate::CertificateValidationError;
+use upkit_common_x509::cert::validate::CertificateValidationErrorKind;
+use upkit_common_x509::key::KeyError;
+
+/// Failure of a command with the process exit code to use.
+#[derive(Debug)]
+pub struct CommandError {
+    exit_code: u8,
+    msg: String,
+}
+
+impl CommandError {
+    /// Generic failure like I/O errors or malformed input.
+    pub const EXIT_CODE_FAILURE: u8 = 1;
+
+    /// Create a new generic failure with an error message.
+    pub fn with_msg(msg: &str) -> Self {
+        Self {
+            exit_code: Self::EXIT_CODE_FAILURE,
+            msg: msg.to_string(),
+        }
+    }
+
+    /// Return the process exit code.
+    pub fn exit_code(&self) -> u8 {
+        self.exit_code
+    }
+
+    /// Map each [CertificateValidationErrorKind] to a distinct exit code.
+    pub fn exit_code_from_validation_error_kind(kind: &CertificateValidationErrorKind) -> u8 {
+        match kind {
+            CertificateValidationErrorKind::CertificateParsingError => 10,
+            CertificateValidationErrorKind::InvalidSignature => 11,
+            CertificateValidationErrorKind::UnknownSignature => 12,
+            CertificateValidationErrorKind::InvalidLifeSpan => 13,
+            CertificateValidationErrorKind::NotOneLeaf => 14,
+            CertificateValidationErrorKind::NotTrusted => 15,
+            CertificateValidationErrorKind::UnhandledCriticalExtensions => 16,
+            CertificateValidationErrorKind::ExtensionHandlingFailure => 17,
+            CertificateValidationErrorKind::Revoked => 18,
+            CertificateValidationErrorKind::RevocationStatusUnknown => 19,
+        }
+    }
+}
+
+impl From<CertificateValidationError> for CommandError {
+    fn from(value: CertificateValidationError) -> Self {
+        Self {
+            exit_code: Self::exit_code_from_validation_error_kind(value.kind()),
+            msg: value.to_string(),
+        }
+    }
+}
+
+impl From<KeyError> for CommandError {
+    fn from(value: KeyError) -> Self {
+        Self::with_msg(&value.to_string())
+    }
+}
+
+impl From<std::io::Error> for CommandError {
+    fn from(value: std::io::Error) -> Self {
+        Self::with_msg(&value.to_string())
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl std::error::Error for CommandError {}
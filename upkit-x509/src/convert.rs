@@ -0,0 +1,110 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Conversion between PEM and DER and splitting of PEM bundles.
+
+use crate::input;
+use crate::CommandError;
+use clap::Args;
+use clap::ValueEnum;
+use std::path::PathBuf;
+use upkit_common_x509::encdec::pem::Marker;
+
+/// Target encoding of `convert`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum TargetEncoding {
+    /// RFC7468 textual encoding.
+    Pem,
+    /// Binary ASN.1 DER encoding.
+    Der,
+}
+
+impl TargetEncoding {
+    fn file_suffix(&self) -> &'static str {
+        match self {
+            Self::Pem => "pem",
+            Self::Der => "der",
+        }
+    }
+}
+
+/// Arguments of `convert`.
+#[derive(Args)]
+pub struct ConvertArgs {
+    /// Target encoding.
+    #[arg(long, value_enum)]
+    to: TargetEncoding,
+    /// PEM label to use for DER encoded input.
+    #[arg(long, default_value = "CERTIFICATE")]
+    der_label: String,
+    /// Write each object to a separate file named `<SPLIT><index>.<pem|der>`.
+    #[arg(long)]
+    split: Option<String>,
+    /// Output file. Writes to stdout if omitted.
+    #[arg(long, conflicts_with = "split")]
+    out: Option<PathBuf>,
+    /// PEM or DER encoded input file. Reads stdin if omitted.
+    file: Option<PathBuf>,
+}
+
+/// Convert the input to the target encoding.
+pub fn run(args: ConvertArgs) -> Result<(), CommandError> {
+    let der_marker = Marker::from_begin_line(&format!(
+        "{}{}{}",
+        Marker::BEGIN_LINE_START,
+        args.der_label,
+        Marker::BEGIN_LINE_FINISH
+    ));
+    let objects = input::decode_objects(&input::read_bytes(args.file.as_deref())?, der_marker)?;
+    if let Some(prefix) = args.split {
+        for (index, (marker, der)) in objects.into_iter().enumerate() {
+            let file = PathBuf::from(format!("{prefix}{index}.{}", args.to.file_suffix()));
+            write_object(args.to, Some(file), marker, &der)?;
+        }
+        return Ok(());
+    }
+    match args.to {
+        TargetEncoding::Der if objects.len() != 1 => Err(CommandError::with_msg(&format!(
+            "DER can only hold a single object, but the input contained {}. Use --split.",
+            objects.len()
+        ))),
+        TargetEncoding::Der => {
+            let (marker, der) = objects.into_iter().next().unwrap();
+            write_object(args.to, args.out, marker, &der)
+        }
+        TargetEncoding::Pem => {
+            let objects = objects
+                .iter()
+                .map(|(marker, der)| (marker.clone(), der.as_slice()))
+                .collect::<Vec<_>>();
+            let pem = upkit_common_x509::encdec::pem::encode(&objects);
+            input::write_bytes(args.out.as_deref(), pem.as_bytes())
+        }
+    }
+}
+
+fn write_object(
+    to: TargetEncoding,
+    file: Option<PathBuf>,
+    marker: Marker,
+    der: &[u8],
+) -> Result<(), CommandError> {
+    match to {
+        TargetEncoding::Pem => input::write_pem(file.as_deref(), marker, der),
+        TargetEncoding::Der => input::write_bytes(file.as_deref(), der),
+    }
+}
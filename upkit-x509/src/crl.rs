@@ -0,0 +1,151 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Listing and creation of Certificate Revocation Lists.
+
+use crate::input;
+use crate::signer::Signer;
+use crate::CommandError;
+use clap::Args;
+use clap::Subcommand;
+use std::path::PathBuf;
+use upkit_common_x509::cert::parse::CertificateParser;
+use upkit_common_x509::cert::types::Validity;
+use upkit_common_x509::crl::CrlNumber;
+use upkit_common_x509::crl::CrlParser;
+use upkit_common_x509::crl::NoSignatureTbsCertList;
+use upkit_common_x509::crl::RevokedCertificateEntry;
+use upkit_common_x509::encdec::pem::Marker;
+
+/// Subcommands of `crl`.
+#[derive(Subcommand)]
+pub enum CrlCommand {
+    /// List the content of a CRL as JSON.
+    List(CrlListArgs),
+    /// Create and sign a CRL.
+    Create(CrlCreateArgs),
+}
+
+/// Arguments of `crl list`.
+#[derive(Args)]
+pub struct CrlListArgs {
+    /// PEM or DER encoded issuer certificate used to verify the CRL signature.
+    #[arg(long)]
+    issuer_cert: Option<PathBuf>,
+    /// PEM or DER encoded CRL. Reads stdin if omitted.
+    file: Option<PathBuf>,
+}
+
+/// Arguments of `crl create`.
+#[derive(Args)]
+pub struct CrlCreateArgs {
+    /// PEM or DER encoded issuer certificate.
+    #[arg(long)]
+    issuer_cert: PathBuf,
+    /// PEM or DER encoded PKCS #8 private key of the issuer.
+    #[arg(long)]
+    issuer_key: PathBuf,
+    /// Signature algorithm name (e.g. `ecdsa-with-SHA256`) or OID.
+    #[arg(long)]
+    signature_algorithm: String,
+    /// Monotonically increasing CRL number in decimal notation (up to 20 octets).
+    #[arg(long)]
+    crl_number: CrlNumber,
+    /// Seconds from now until the next CRL will be issued.
+    #[arg(long)]
+    next_update_seconds: Option<u64>,
+    /// JSON array of revoked certificate entries like
+    /// `[{"serial_number":{"serial_number_hex":"01ab"},"revocation_epoch_seconds":1739555555,"reason":"key_compromise"}]`.
+    #[arg(long)]
+    revoked: Option<PathBuf>,
+    /// Output file. Writes PEM to stdout if omitted.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+/// Run the `crl` subcommand.
+pub fn run(command: CrlCommand) -> Result<(), CommandError> {
+    match command {
+        CrlCommand::List(args) => list(args),
+        CrlCommand::Create(args) => create(args),
+    }
+}
+
+fn list(args: CrlListArgs) -> Result<(), CommandError> {
+    let der = if let Some(file) = &args.file {
+        input::read_object(file, Marker::CertificateRevocationList)?
+    } else {
+        let mut objects = input::read_objects(&[], Marker::CertificateRevocationList)?;
+        if objects.len() != 1 {
+            return Err(CommandError::with_msg("Expected exactly one CRL."));
+        }
+        objects.remove(0)
+    };
+    let crl = CrlParser::from_bytes(&der).map_err(|e| CommandError::with_msg(&e.to_string()))?;
+    let crl_number = crl
+        .get_crl_number()
+        .map_err(|e| CommandError::with_msg(&e.to_string()))?;
+    let signature_valid = if let Some(issuer_cert) = &args.issuer_cert {
+        let issuer = load_certificate(issuer_cert)?;
+        Some(crl.verify_signature(&issuer))
+    } else {
+        None
+    };
+    let listing = serde_json::json!({
+        "issuer": crl.get_issuer_rfc4514_string(),
+        "this_update": crl.get_this_update(),
+        "next_update": crl.get_next_update(),
+        "crl_number": crl_number,
+        "signature_valid": signature_valid,
+        "revoked_certificates": crl.get_revoked_certificates(),
+    });
+    let output = serde_json::to_string_pretty(&listing).unwrap() + "\n";
+    input::write_bytes(None, output.as_bytes())?;
+    if signature_valid == Some(false) {
+        return Err(CommandError::with_msg("CRL signature verification failed."));
+    }
+    Ok(())
+}
+
+fn create(args: CrlCreateArgs) -> Result<(), CommandError> {
+    let issuer = load_certificate(&args.issuer_cert)?;
+    let signer = Signer::from_file(&args.issuer_key, &args.signature_algorithm)?;
+    let revoked = if let Some(revoked) = &args.revoked {
+        serde_json::from_slice::<Vec<RevokedCertificateEntry>>(&input::read_bytes(Some(revoked))?)
+            .map_err(|e| CommandError::with_msg(&format!("Invalid revoked entries: {e}")))?
+    } else {
+        vec![]
+    };
+    let now = Validity::now_epoch_seconds();
+    let tbs_cert_list = NoSignatureTbsCertList::new(
+        &issuer,
+        now,
+        args.next_update_seconds.map(|seconds| now + seconds),
+        &args.crl_number,
+        &revoked,
+    );
+    let signature_algorithm = signer.signature_algorithm();
+    let signature =
+        signer.sign(&tbs_cert_list.with_signature_algorithm_as_bytes(&signature_algorithm))?;
+    let crl = tbs_cert_list.to_certificate_list(&signature_algorithm, signature);
+    input::write_pem(args.out.as_deref(), Marker::CertificateRevocationList, &crl)
+}
+
+fn load_certificate(file: &std::path::Path) -> Result<CertificateParser, CommandError> {
+    CertificateParser::from_bytes(&input::read_object(file, Marker::Certificate)?)
+        .map_err(|e| CommandError::with_msg(&e.to_string()))
+}
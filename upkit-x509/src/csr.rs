@@ -0,0 +1,71 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Creation of PKCS #10 certification requests.
+
+use crate::input;
+use crate::signer;
+use crate::signer::Signer;
+use crate::values;
+use crate::CommandError;
+use clap::Args;
+use std::path::PathBuf;
+use upkit_common_x509::cert::extensions::Extensions;
+use upkit_common_x509::csr::NoSignatureCertificationRequestInfo;
+use upkit_common_x509::encdec::pem::Marker;
+
+/// Arguments of `csr`.
+#[derive(Args)]
+pub struct CsrArgs {
+    /// Requested subject like `country_name=SE,common_name=An entity`.
+    #[arg(long)]
+    subject: String,
+    /// Requested Subject Alternative Name like `dns_name=www.example.com`.
+    #[arg(long)]
+    san: Vec<String>,
+    /// PEM or DER encoded public key (SubjectPublicKeyInfo).
+    #[arg(long)]
+    public_key: PathBuf,
+    /// PEM or DER encoded PKCS #8 private key that matches the public key.
+    #[arg(long)]
+    key: PathBuf,
+    /// Signature algorithm name (e.g. `ecdsa-with-SHA256`) or OID.
+    #[arg(long)]
+    signature_algorithm: String,
+    /// Output file. Writes PEM to stdout if omitted.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+/// Create and sign a certification request.
+pub fn run(args: CsrArgs) -> Result<(), CommandError> {
+    let subject = values::parse_subject(&args.subject)?;
+    let sans = args
+        .san
+        .iter()
+        .map(|san| values::parse_general_name(san))
+        .collect::<Result<Vec<_>, _>>()?;
+    let public_key = signer::load_public_key(&args.public_key)?;
+    let signer = Signer::from_file(&args.key, &args.signature_algorithm)?;
+    let mut extensions = Extensions::default();
+    extensions.add_subject_alternative_name(&sans, subject.is_empty());
+    let request_info =
+        NoSignatureCertificationRequestInfo::new(subject, public_key.as_ref(), extensions);
+    let signature = signer.sign(request_info.as_bytes())?;
+    let csr = request_info.to_certification_request(&signer.signature_algorithm(), signature);
+    input::write_pem(args.out.as_deref(), Marker::CertificationRequest, &csr)
+}
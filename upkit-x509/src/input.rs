@@ -0,0 +1,116 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Reading PEM or DER encoded input and writing output.
+
+use crate::CommandError;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use upkit_common_x509::encdec::pem;
+use upkit_common_x509::encdec::pem::Marker;
+
+/// Read all bytes from the file or stdin if no file was provided.
+pub fn read_bytes(file: Option<&Path>) -> Result<Vec<u8>, CommandError> {
+    let mut bytes = vec![];
+    if let Some(file) = file {
+        bytes = std::fs::read(file).map_err(|e| {
+            CommandError::with_msg(&format!("Unable to read '{}': {e}", file.display()))
+        })?;
+    } else {
+        std::io::stdin().read_to_end(&mut bytes)?;
+    }
+    Ok(bytes)
+}
+
+/** Decode all objects of PEM or DER encoded data.
+
+A single DER encoded object is assumed to be of type `der_marker` since DER
+does not carry any type information on its own.
+*/
+pub fn decode_objects(
+    bytes: &[u8],
+    der_marker: Marker,
+) -> Result<Vec<(Marker, Vec<u8>)>, CommandError> {
+    // A DER encoded SEQUENCE always starts with 0x30
+    if bytes.first().is_some_and(|first| *first == 0x30) {
+        return Ok(vec![(der_marker, bytes.to_vec())]);
+    }
+    let text = std::str::from_utf8(bytes)
+        .map_err(|_| CommandError::with_msg("Input is neither DER nor PEM encoded."))?;
+    pem::parse(text).map_err(|e| CommandError::with_msg(&e.to_string()))
+}
+
+/// Read and decode all objects with the expected marker from each file (or
+/// stdin if no files were provided).
+pub fn read_objects(files: &[PathBuf], expected: Marker) -> Result<Vec<Vec<u8>>, CommandError> {
+    let mut ret = vec![];
+    if files.is_empty() {
+        let objects = decode_objects(&read_bytes(None)?, expected.clone())?;
+        ret.extend(filter_objects(objects, &expected));
+    }
+    for file in files {
+        let objects = decode_objects(&read_bytes(Some(file))?, expected.clone())?;
+        ret.extend(filter_objects(objects, &expected));
+    }
+    Ok(ret)
+}
+
+/// Read exactly one object with the expected marker.
+pub fn read_object(file: &Path, expected: Marker) -> Result<Vec<u8>, CommandError> {
+    let mut objects = read_objects(&[file.to_path_buf()], expected.clone())?;
+    if objects.len() != 1 {
+        return Err(CommandError::with_msg(&format!(
+            "Expected exactly one '{}' in '{}', but found {}.",
+            expected.as_str(),
+            file.display(),
+            objects.len()
+        )));
+    }
+    Ok(objects.remove(0))
+}
+
+fn filter_objects(objects: Vec<(Marker, Vec<u8>)>, expected: &Marker) -> Vec<Vec<u8>> {
+    objects
+        .into_iter()
+        .filter_map(|(marker, data)| {
+            if marker.eq(expected) {
+                Some(data)
+            } else {
+                log::debug!("Ignoring PEM object of type '{}'.", marker.as_str());
+                None
+            }
+        })
+        .collect()
+}
+
+/// Write the output to the file or stdout if no file was provided.
+pub fn write_bytes(file: Option<&Path>, bytes: &[u8]) -> Result<(), CommandError> {
+    if let Some(file) = file {
+        std::fs::write(file, bytes).map_err(|e| {
+            CommandError::with_msg(&format!("Unable to write '{}': {e}", file.display()))
+        })
+    } else {
+        Ok(std::io::stdout().write_all(bytes)?)
+    }
+}
+
+/// Write the DER encoded object as PEM to the file or stdout.
+pub fn write_pem(file: Option<&Path>, marker: Marker, der: &[u8]) -> Result<(), CommandError> {
+    write_bytes(file, pem::encode(&[(marker, der)]).as_bytes())
+}
@@ -0,0 +1,113 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Show the content of certificates.
+
+use crate::input;
+use crate::CommandError;
+use clap::Args;
+use clap::ValueEnum;
+use std::io::BufRead;
+use std::path::PathBuf;
+use upkit_common_x509::cert::parse::CertificateReader;
+
+/// Output format of `inspect`.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum InspectFormat {
+    /// Human readable text.
+    #[default]
+    Text,
+    /// JSON array of certificate summaries.
+    Json,
+}
+
+/// Arguments of `inspect`.
+#[derive(Args)]
+pub struct InspectArgs {
+    /// Output format.
+    #[arg(long, value_enum, default_value_t)]
+    format: InspectFormat,
+    /// PEM or DER encoded certificate files. Reads stdin if omitted.
+    files: Vec<PathBuf>,
+}
+
+/** Print every certificate found in the input.
+
+Certificates are streamed, so that large bundles can be inspected. Malformed
+certificates are reported and skipped.
+*/
+pub fn run(args: InspectArgs) -> Result<(), CommandError> {
+    let mut state = InspectState::default();
+    if matches!(args.format, InspectFormat::Json) {
+        input::write_bytes(None, b"[")?;
+    }
+    if args.files.is_empty() {
+        let reader = CertificateReader::from_read(std::io::stdin().lock());
+        inspect_all(reader, args.format, &mut state)?;
+    }
+    for file in &args.files {
+        let file = std::fs::File::open(file).map_err(|e| {
+            CommandError::with_msg(&format!("Unable to read '{}': {e}", file.display()))
+        })?;
+        inspect_all(CertificateReader::from_read(file), args.format, &mut state)?;
+    }
+    if matches!(args.format, InspectFormat::Json) {
+        input::write_bytes(None, b"]\n")?;
+    }
+    if state.failures > 0 {
+        return Err(CommandError::with_msg(&format!(
+            "{} object(s) could not be parsed.",
+            state.failures
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct InspectState {
+    written: usize,
+    failures: usize,
+}
+
+fn inspect_all<R: BufRead>(
+    reader: CertificateReader<R>,
+    format: InspectFormat,
+    state: &mut InspectState,
+) -> Result<(), CommandError> {
+    for certificate in reader {
+        let certificate = match certificate {
+            Ok(certificate) => certificate,
+            Err(e) => {
+                eprintln!("{e}");
+                state.failures += 1;
+                continue;
+            }
+        };
+        let separator = match format {
+            _ if state.written == 0 => "",
+            InspectFormat::Text => "\n",
+            InspectFormat::Json => ",",
+        };
+        let output = match format {
+            InspectFormat::Text => certificate.to_text(),
+            InspectFormat::Json => serde_json::to_string_pretty(&certificate.to_summary()).unwrap(),
+        };
+        input::write_bytes(None, (separator.to_string() + &output).as_bytes())?;
+        state.written += 1;
+    }
+    Ok(())
+}
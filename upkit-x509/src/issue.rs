@@ -0,0 +1,217 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Certificate issuance from a local (test) CA.
+
+use crate::input;
+use crate::signer;
+use crate::signer::Signer;
+use crate::values;
+use crate::CommandError;
+use clap::Args;
+use serde::Deserialize;
+use std::path::PathBuf;
+use tyst::traits::se::ToPublicKey;
+use upkit_common_x509::cert::build::NoSignatureTbsCertificate;
+use upkit_common_x509::cert::extensions::AuthorityInfoAccessDescription;
+use upkit_common_x509::cert::extensions::AuthorityKeyIdentifier;
+use upkit_common_x509::cert::extensions::BasicConstraints;
+use upkit_common_x509::cert::extensions::CertificatePolicy;
+use upkit_common_x509::cert::extensions::ExtendedKeyUsage;
+use upkit_common_x509::cert::extensions::Extensions;
+use upkit_common_x509::cert::extensions::KeyUsage;
+use upkit_common_x509::cert::extensions::SubjectKeyIdentifier;
+use upkit_common_x509::cert::parse::CertificateParser;
+use upkit_common_x509::cert::types::Validity;
+use upkit_common_x509::cert::types::WellKnownGeneralName;
+use upkit_common_x509::csr::CertificationRequestParser;
+use upkit_common_x509::encdec::pem::Marker;
+use upkit_common_x509::lint::LintSeverity;
+use upkit_common_x509::lint::Linter;
+
+/// Issuance profile as read from a JSON file.
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+struct IssuanceProfile {
+    /// Validity of the issued certificate from now.
+    validity_seconds: u64,
+    /// Signature algorithm name (e.g. `ecdsa-with-SHA256`) or OID.
+    signature_algorithm: String,
+    basic_constraints: Option<BasicConstraints>,
+    #[serde(default)]
+    key_usages: Vec<KeyUsage>,
+    #[serde(default)]
+    extended_key_usages: Vec<ExtendedKeyUsage>,
+    #[serde(default)]
+    certificate_policies: Vec<CertificatePolicy>,
+    #[serde(default)]
+    authority_information_access: Vec<AuthorityInfoAccessDescription>,
+    crl_distribution_point: Option<String>,
+    /// Subject Alternative Names added in addition to the requested ones.
+    #[serde(default)]
+    subject_alternative_names: Vec<(WellKnownGeneralName, String)>,
+}
+
+/// Arguments of `issue`.
+#[derive(Args)]
+pub struct IssueArgs {
+    /// JSON encoded issuance profile.
+    #[arg(long)]
+    profile: PathBuf,
+    /// PEM or DER encoded PKCS #8 private key of the issuer.
+    #[arg(long)]
+    issuer_key: PathBuf,
+    /// PEM or DER encoded issuer certificate. The certificate will be
+    /// self-signed when omitted.
+    #[arg(long)]
+    issuer_cert: Option<PathBuf>,
+    /// PEM or DER encoded PKCS #10 certification request.
+    #[arg(long, conflicts_with_all = ["subject", "public_key"])]
+    csr: Option<PathBuf>,
+    /// Subject like `country_name=SE,common_name=An entity` (when no CSR is used).
+    #[arg(long, requires = "public_key")]
+    subject: Option<String>,
+    /// PEM or DER encoded subject public key (when no CSR is used).
+    #[arg(long, requires = "subject")]
+    public_key: Option<PathBuf>,
+    /// Output file. Writes PEM to stdout if omitted.
+    #[arg(long)]
+    out: Option<PathBuf>,
+    /// Refuse to sign when linting reports errors.
+    #[arg(long)]
+    deny_lint_errors: bool,
+}
+
+/// Issue a certificate according to the profile.
+pub fn run(args: IssueArgs) -> Result<(), CommandError> {
+    let profile =
+        serde_json::from_slice::<IssuanceProfile>(&input::read_bytes(Some(&args.profile))?)
+            .map_err(|e| CommandError::with_msg(&format!("Invalid profile: {e}")))?;
+    let signer = Signer::from_file(&args.issuer_key, &profile.signature_algorithm)?;
+    // Subject and public key
+    let mut subject_alternative_names = profile.subject_alternative_names.clone();
+    let (subject, subject_public_key) = if let Some(csr) = &args.csr {
+        let csr = CertificationRequestParser::from_bytes(&input::read_object(
+            csr,
+            Marker::CertificationRequest,
+        )?)
+        .map_err(|e| CommandError::with_msg(&e.to_string()))?;
+        if !csr.verify_signature() {
+            return Err(CommandError::with_msg(
+                "Certification request signature verification failed.",
+            ));
+        }
+        subject_alternative_names.extend(csr.get_requested_subject_alternative_name());
+        (
+            csr.get_subject()
+                .map_err(|e| CommandError::with_msg(&e.to_string()))?,
+            csr.get_encoded_subject_public_key_info().to_public_key(),
+        )
+    } else if let (Some(subject), Some(public_key)) = (&args.subject, &args.public_key) {
+        (
+            values::parse_subject(subject)?,
+            signer::load_public_key(public_key)?,
+        )
+    } else {
+        return Err(CommandError::with_msg(
+            "Either --csr or --subject and --public-key is required.",
+        ));
+    };
+    // Issuer
+    let (issuer, authority_key_identifier) = if let Some(issuer_cert) = &args.issuer_cert {
+        let issuer =
+            CertificateParser::from_bytes(&input::read_object(issuer_cert, Marker::Certificate)?)
+                .map_err(|e| CommandError::with_msg(&e.to_string()))?;
+        let aki = if let Some(kid) = issuer
+            .get_subject_key_identifier_kid()
+            .map_err(|e| CommandError::with_msg(&e.to_string()))?
+        {
+            AuthorityKeyIdentifier::from_issuers_subject_key_identifier(&kid)
+        } else {
+            let spki = issuer.get_encoded_subject_public_key_info();
+            AuthorityKeyIdentifier::from_public_key(spki.to_public_key().as_ref())
+        };
+        (
+            issuer
+                .get_subject()
+                .map_err(|e| CommandError::with_msg(&e.to_string()))?,
+            aki,
+        )
+    } else {
+        (
+            subject.clone(),
+            AuthorityKeyIdentifier::from_public_key(subject_public_key.as_ref()),
+        )
+    };
+    // Extensions
+    let mut extensions = Extensions::default();
+    extensions.add_basic_constraints(
+        &profile
+            .basic_constraints
+            .unwrap_or_else(BasicConstraints::new_leaf),
+    );
+    extensions.add_key_usage(&profile.key_usages);
+    extensions.add_extended_key_usage(&profile.extended_key_usages);
+    extensions.add_certificate_policies(&profile.certificate_policies);
+    extensions.add_authority_information_access(&profile.authority_information_access);
+    if let Some(crl_distribution_point) = &profile.crl_distribution_point {
+        extensions.add_crl_distribution_points(crl_distribution_point);
+    }
+    extensions.add_subject_alternative_name(&subject_alternative_names, subject.is_empty());
+    extensions.add_subject_key_identifier(&SubjectKeyIdentifier::from_public_key(
+        subject_public_key.as_ref(),
+    ));
+    extensions.add_authority_key_identifier(&authority_key_identifier);
+    // Sign
+    let tbs_certificate = NoSignatureTbsCertificate::new(
+        issuer,
+        Validity::now_epoch_seconds() + profile.validity_seconds,
+        subject,
+        subject_public_key.as_ref(),
+        extensions,
+    );
+    // Lint
+    let findings = tbs_certificate
+        .lint(&Linter::default())
+        .map_err(|e| CommandError::with_msg(&e.to_string()))?;
+    for finding in &findings {
+        eprintln!(
+            "{:?} {}: {}",
+            finding.get_severity(),
+            finding.get_rule_id(),
+            finding.get_message()
+        );
+    }
+    if args.deny_lint_errors
+        && findings
+            .iter()
+            .any(|finding| finding.get_severity() == LintSeverity::Error)
+    {
+        return Err(CommandError::with_msg(
+            "Linting reported errors. Refusing to sign.",
+        ));
+    }
+    let signature_algorithm = signer.signature_algorithm();
+    let signature =
+        signer.sign(&tbs_certificate.with_signature_algorithm_as_bytes(&signature_algorithm))?;
+    let certificate = tbs_certificate.to_certificate(&signature_algorithm, signature);
+    input::write_pem(
+        args.out.as_deref(),
+        Marker::Certificate,
+        &rasn::der::encode(&certificate).unwrap(),
+    )
+}
@@ -0,0 +1,81 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! UPKIT X.509 command-line tool.
+
+#![forbid(unsafe_code)]
+
+mod command_error;
+mod convert;
+mod crl;
+mod csr;
+mod input;
+mod inspect;
+mod issue;
+mod signer;
+mod validate;
+mod values;
+
+use self::command_error::CommandError;
+use clap::Parser;
+use clap::Subcommand;
+use std::process::ExitCode;
+
+/// UPKIT X.509 command-line tool.
+#[derive(Parser)]
+#[command(name = "upkit-x509", version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show the content of PEM or DER encoded certificates.
+    Inspect(inspect::InspectArgs),
+    /// Validate a certificate chain against a trust bundle.
+    Validate(validate::ValidateArgs),
+    /// Convert between PEM and DER and split certificate bundles.
+    Convert(convert::ConvertArgs),
+    /// Create a PKCS #10 certification request.
+    Csr(csr::CsrArgs),
+    /// Issue a certificate from a local (test) CA.
+    Issue(issue::IssueArgs),
+    /// List or create Certificate Revocation Lists.
+    #[command(subcommand)]
+    Crl(crl::CrlCommand),
+}
+
+fn main() -> ExitCode {
+    env_logger::init();
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Inspect(args) => inspect::run(args),
+        Command::Validate(args) => validate::run(args),
+        Command::Convert(args) => convert::run(args),
+        Command::Csr(args) => csr::run(args),
+        Command::Issue(args) => issue::run(args),
+        Command::Crl(command) => crl::run(command),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
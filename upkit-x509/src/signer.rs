@@ -0,0 +1,118 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Key loading and signing.
+
+use crate::input;
+use crate::values;
+use crate::CommandError;
+use rasn::types::Any;
+use rasn::types::ObjectIdentifier;
+use rasn_pkix::AlgorithmIdentifier;
+use std::path::Path;
+use tyst::traits::se::PrivateKey;
+use tyst::traits::se::PublicKey;
+use tyst::traits::se::ToPublicKey;
+use tyst::Tyst;
+use upkit_common_x509::cert::types::WellKnownAlgorithm;
+use upkit_common_x509::encdec::oid;
+use upkit_common_x509::encdec::pem::Marker;
+use upkit_common_x509::key::Pkcs8PrivateKey;
+
+/// Private key and signature algorithm used to sign objects.
+pub struct Signer {
+    private_key: Box<dyn PrivateKey>,
+    signature_algorithm_oid: Vec<u32>,
+}
+
+impl Signer {
+    /// Environment variable holding the password of an encrypted private key.
+    pub const ENV_KEY_PASSWORD: &str = "UPKIT_KEY_PASSWORD";
+
+    /** Return a new instance from a private key file.
+
+    The file may be a PEM encoded PKCS #8 (optionally encrypted), PKCS #1 or
+    SEC1 private key, or a DER encoded PKCS #8 private key. The password of an
+    encrypted key is read from the [Self::ENV_KEY_PASSWORD] environment
+    variable.
+
+    `signature_algorithm` is either a well-known algorithm name like
+    `ecdsa-with-SHA256` or `ML-DSA-65`, or a dotted OID.
+    */
+    pub fn from_file(key_file: &Path, signature_algorithm: &str) -> Result<Self, CommandError> {
+        let private_key = Self::load_private_key(key_file)?;
+        let signature_algorithm_oid = WellKnownAlgorithm::from_name(signature_algorithm)
+            .map(|alg| Ok(alg.as_oid().to_vec()))
+            .unwrap_or_else(|| values::parse_oid(signature_algorithm))?;
+        Ok(Self {
+            private_key: private_key.to_private_key(),
+            signature_algorithm_oid,
+        })
+    }
+
+    fn load_private_key(key_file: &Path) -> Result<Pkcs8PrivateKey, CommandError> {
+        let bytes = input::read_bytes(Some(key_file))?;
+        if bytes.first() == Some(&0x30) {
+            return Ok(Pkcs8PrivateKey::from_der(&bytes)?);
+        }
+        let password = std::env::var(Self::ENV_KEY_PASSWORD).ok();
+        let textual_encoding = String::from_utf8(bytes)
+            .map_err(|_| CommandError::with_msg("Private key file is neither PEM nor DER."))?;
+        Ok(Pkcs8PrivateKey::from_pem(
+            &textual_encoding,
+            password.as_ref().map(String::as_bytes),
+        )?)
+    }
+
+    /** Return the DER encoded `AlgorithmIdentifier` of the signature algorithm.
+
+    [RFC4055 5](https://www.rfc-editor.org/rfc/rfc4055#section-5) requires
+    `NULL` parameters for the PKCS #1 v1.5 algorithms. All other supported
+    signature algorithms have absent parameters.
+    */
+    pub fn signature_algorithm(&self) -> Vec<u8> {
+        let parameters = match WellKnownAlgorithm::from_oid(&self.signature_algorithm_oid) {
+            Some(
+                WellKnownAlgorithm::Sha1WithRsaEncryption
+                | WellKnownAlgorithm::Sha256WithRsaEncryption
+                | WellKnownAlgorithm::Sha384WithRsaEncryption
+                | WellKnownAlgorithm::Sha512WithRsaEncryption,
+            ) => Some(Any::new(rasn::der::encode(&()).unwrap())),
+            _ => None,
+        };
+        rasn::der::encode(&AlgorithmIdentifier {
+            algorithm: ObjectIdentifier::new_unchecked(self.signature_algorithm_oid.clone().into()),
+            parameters,
+        })
+        .unwrap()
+    }
+
+    /// Sign the data.
+    pub fn sign(&self, data: &[u8]) -> Result<Vec<u8>, CommandError> {
+        let oid = oid::as_string(&self.signature_algorithm_oid);
+        let mut se = Tyst::instance().ses().by_oid(&oid).ok_or_else(|| {
+            CommandError::with_msg(&format!("Unsupported signature algorithm '{oid}'."))
+        })?;
+        Ok(se.sign(self.private_key.as_ref(), data))
+    }
+}
+
+/// Load a SubjectPublicKeyInfo (PEM or DER) public key file.
+pub fn load_public_key(public_key_file: &Path) -> Result<Box<dyn PublicKey>, CommandError> {
+    let spki = input::read_object(public_key_file, Marker::PublicKey)?;
+    Ok(spki.to_public_key())
+}
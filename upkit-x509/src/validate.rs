@@ -0,0 +1,119 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Certificate chain validation.
+
+use crate::input;
+use crate::values;
+use crate::CommandError;
+use clap::Args;
+use clap::ValueEnum;
+use std::path::PathBuf;
+use std::sync::Arc;
+use upkit_common_x509::cert::extensions::CertificatePolicy;
+use upkit_common_x509::cert::types::Validity;
+use upkit_common_x509::cert::validate::checkers::CertificatePoliciesChecker;
+use upkit_common_x509::cert::validate::checkers::CrlRevocationChecker;
+use upkit_common_x509::cert::validate::checkers::ExtendedKeyUsageChecker;
+use upkit_common_x509::cert::validate::checkers::ExtensionChecker;
+use upkit_common_x509::cert::validate::CertificatePathValidator;
+use upkit_common_x509::cert::validate::ValidationModel;
+use upkit_common_x509::encdec::pem::Marker;
+
+/// Validation model of `validate`.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum ValidateModel {
+    /// Validate every certificate at the same point in time.
+    #[default]
+    Shell,
+    /// Validate each issuer at the time it issued the certificate below it.
+    Chain,
+}
+
+/// Arguments of `validate`.
+#[derive(Args)]
+pub struct ValidateArgs {
+    /// Trusted certificates (trust anchors). May be repeated or contain a
+    /// PEM bundle.
+    #[arg(long, required = true)]
+    trust: Vec<PathBuf>,
+    /// Point in time of validation in Unix Epoch seconds. Defaults to now.
+    #[arg(long)]
+    at: Option<u64>,
+    /// Required leaf Extended Key Usage by name (e.g. `pkix_server_auth`) or
+    /// OID.
+    #[arg(long)]
+    eku: Vec<String>,
+    /// Required leaf Certificate Policy OID.
+    #[arg(long)]
+    policy: Vec<String>,
+    /// CRLs used for revocation checking of every non-trust anchor
+    /// certificate. May be repeated or contain a PEM bundle.
+    #[arg(long)]
+    crl: Vec<PathBuf>,
+    /// How `--at` is applied to the certificates of the chain.
+    #[arg(long, value_enum, default_value_t)]
+    model: ValidateModel,
+    /// The leaf certificate and intermediate CAs in any order.
+    #[arg(required = true)]
+    chain: Vec<PathBuf>,
+}
+
+/// Validate the chain with the library's [CertificatePathValidator].
+pub fn run(args: ValidateArgs) -> Result<(), CommandError> {
+    let trusted = input::read_objects(&args.trust, Marker::Certificate)?;
+    let chain = input::read_objects(&args.chain, Marker::Certificate)?;
+    let mut additional_checkers: Vec<Arc<dyn ExtensionChecker>> = vec![];
+    if !args.eku.is_empty() {
+        let ekus = args
+            .eku
+            .iter()
+            .map(|eku| values::parse_extended_key_usage(eku))
+            .collect::<Result<Vec<_>, _>>()?;
+        additional_checkers.push(Arc::new(ExtendedKeyUsageChecker::new(&ekus)));
+    }
+    if !args.policy.is_empty() {
+        let policies = args
+            .policy
+            .iter()
+            .map(|policy| values::parse_oid(policy).map(|oid| CertificatePolicy::OidPolicy { oid }))
+            .collect::<Result<Vec<_>, _>>()?;
+        additional_checkers.push(Arc::new(CertificatePoliciesChecker::new(&policies)));
+    }
+    if !args.crl.is_empty() {
+        let crls = input::read_objects(&args.crl, Marker::CertificateRevocationList)?;
+        additional_checkers.push(Arc::new(CrlRevocationChecker::new(&crls)?));
+    }
+    let validation_model = match args.model {
+        ValidateModel::Shell => ValidationModel::Shell,
+        ValidateModel::Chain => ValidationModel::Chain,
+    };
+    let at_epoch_seconds = args.at.unwrap_or_else(Validity::now_epoch_seconds);
+    let validation_result = CertificatePathValidator::new(trusted)?
+        .add_standard_extension_checkers()
+        .validate_with_model(
+            chain,
+            at_epoch_seconds,
+            validation_model,
+            additional_checkers,
+        )?;
+    for (fingerprint, validation_time) in validation_result.get_validation_times() {
+        log::debug!("Validated cert with fp '{fingerprint}' at {validation_time} epoch seconds.");
+    }
+    println!("OK");
+    Ok(())
+}
@@ -0,0 +1,85 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of command-line values.
+
+use crate::CommandError;
+use upkit_common_x509::cert::extensions::ExtendedKeyUsage;
+use upkit_common_x509::cert::types::DistinguishedName;
+use upkit_common_x509::cert::types::WellKnownAttribute;
+use upkit_common_x509::cert::types::WellKnownGeneralName;
+use upkit_common_x509::encdec::oid;
+
+/// Parse a dotted OID like `1.2.3.4`.
+pub fn parse_oid(value: &str) -> Result<Vec<u32>, CommandError> {
+    if value.is_empty()
+        || !value
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+    {
+        return Err(CommandError::with_msg(&format!(
+            "'{value}' is neither a known name nor an OID."
+        )));
+    }
+    oid::from_string(value).map_err(|e| CommandError::with_msg(&e.to_string()))
+}
+
+/// Parse the EKU as a well-known name (e.g. `pkix_server_auth`) or a dotted OID.
+pub fn parse_extended_key_usage(value: &str) -> Result<ExtendedKeyUsage, CommandError> {
+    if let Ok(eku) =
+        serde_json::from_value::<ExtendedKeyUsage>(serde_json::Value::String(value.to_string()))
+    {
+        return Ok(eku);
+    }
+    parse_oid(value).map(|oid| ExtendedKeyUsage::from_oid(&oid))
+}
+
+/** Parse a subject like `country_name=SE,common_name=An entity`.
+
+Attributes use the well-known attribute names and are encoded in the order
+provided. A `+` joins attributes into a multi-valued RDN. Escaping is not
+supported.
+*/
+pub fn parse_subject(value: &str) -> Result<DistinguishedName, CommandError> {
+    let mut rdns = vec![];
+    for rdn in value.split(',').filter(|rdn| !rdn.trim().is_empty()) {
+        let mut atavs = vec![];
+        for atav in rdn.split('+') {
+            let (name, value) = split_name_value(atav)?;
+            WellKnownAttribute::by_name(name)
+                .map_err(|e| CommandError::with_msg(&e.to_string()))?;
+            atavs.push((name.to_string(), value.to_string()));
+        }
+        rdns.push(atavs);
+    }
+    DistinguishedName::try_from(rdns).map_err(|e| CommandError::with_msg(&e.to_string()))
+}
+
+/// Parse a GeneralName like `dns_name=www.example.com`.
+pub fn parse_general_name(value: &str) -> Result<(WellKnownGeneralName, String), CommandError> {
+    let (name, value) = split_name_value(value)?;
+    WellKnownGeneralName::by_name(name)
+        .map(|general_name| (general_name, value.to_string()))
+        .ok_or_else(|| CommandError::with_msg(&format!("Unknown GeneralName type '{name}'.")))
+}
+
+fn split_name_value(value: &str) -> Result<(&str, &str), CommandError> {
+    value
+        .split_once('=')
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .ok_or_else(|| CommandError::with_msg(&format!("Expected 'name=value', got '{value}'.")))
+}
@@ -0,0 +1,63 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+use std::ffi::OsStr;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Output;
+
+pub fn test_resource(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../upkit-common-x509/resources/test")
+        .join(name)
+}
+
+pub fn upkit_x509(args: &[&OsStr]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_upkit-x509"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Return a new empty temporary directory unique to this process and `name`.
+pub fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("upkit-x509-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Write the Base64 encoded test certificate `name` as DER to `dir`.
+pub fn write_test_certificate(dir: &Path, name: &str) -> PathBuf {
+    let b64 = std::fs::read_to_string(test_resource(name)).unwrap();
+    let path = dir.join(name).with_extension("der");
+    std::fs::write(&path, tyst::encdec::base64::decode(&b64).unwrap()).unwrap();
+    path
+}
+
+/// Assert that the command succeeded and show stderr otherwise.
+pub fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,128 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Integration tests of the `crl` subcommand.
+
+mod common;
+
+use self::common::*;
+use std::ffi::OsStr;
+
+#[test]
+fn crl_create_and_list() {
+    let dir = test_dir("crl");
+    let issuer_cert = write_test_certificate(&dir, "ec_ca.b64");
+    let other_cert = write_test_certificate(&dir, "h1_leaf.b64");
+    let issuer_key = test_resource("ec_ca_key.pem");
+    let revoked = dir.join("revoked.json");
+    std::fs::write(
+        &revoked,
+        r#"[{"serial_number":{"serial_number_hex":"1267"},"revocation_epoch_seconds":1700000000,"reason":"key_compromise"}]"#,
+    )
+    .unwrap();
+    let crl = dir.join("crl.pem");
+    let create_args = [
+        OsStr::new("crl"),
+        OsStr::new("create"),
+        OsStr::new("--issuer-cert"),
+        issuer_cert.as_os_str(),
+        OsStr::new("--issuer-key"),
+        issuer_key.as_os_str(),
+        OsStr::new("--signature-algorithm"),
+        OsStr::new("ecdsa-with-SHA256"),
+        OsStr::new("--next-update-seconds"),
+        OsStr::new("3600"),
+        OsStr::new("--revoked"),
+        revoked.as_os_str(),
+        OsStr::new("--out"),
+        crl.as_os_str(),
+    ];
+    // Negative CRL numbers are rejected by the argument parser
+    let output = upkit_x509(&[create_args.as_slice(), &[OsStr::new("--crl-number=-1")]].concat());
+    assert_eq!(output.status.code(), Some(2));
+    // CRL numbers larger than u64::MAX are allowed
+    let output = upkit_x509(
+        &[
+            create_args.as_slice(),
+            &[OsStr::new("--crl-number=18446744073709551616")],
+        ]
+        .concat(),
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let output = upkit_x509(&[
+        OsStr::new("crl"),
+        OsStr::new("list"),
+        OsStr::new("--issuer-cert"),
+        issuer_cert.as_os_str(),
+        crl.as_os_str(),
+    ]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let listing: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(listing["crl_number"], "18446744073709551616");
+    assert_eq!(listing["signature_valid"], true);
+    assert!(listing["issuer"]
+        .as_str()
+        .unwrap()
+        .contains("CN=Test EC CA"));
+    let revoked_certificates = listing["revoked_certificates"].as_array().unwrap();
+    assert_eq!(revoked_certificates.len(), 1);
+    assert_eq!(revoked_certificates[0]["reason"], "key_compromise");
+    assert_eq!(
+        listing["next_update"].as_u64().unwrap() - listing["this_update"].as_u64().unwrap(),
+        3600
+    );
+    // Verification against a certificate that did not issue the CRL fails
+    let output = upkit_x509(&[
+        OsStr::new("crl"),
+        OsStr::new("list"),
+        OsStr::new("--issuer-cert"),
+        other_cert.as_os_str(),
+        crl.as_os_str(),
+    ]);
+    assert!(!output.status.success());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn crl_list_external_crl() {
+    let dir = test_dir("crl-external");
+    let issuer_cert = write_test_certificate(&dir, "ec_ca.b64");
+    let crl = write_test_certificate(&dir, "ec_ca_crl.b64");
+    let output = upkit_x509(&[
+        OsStr::new("crl"),
+        OsStr::new("list"),
+        OsStr::new("--issuer-cert"),
+        issuer_cert.as_os_str(),
+        crl.as_os_str(),
+    ]);
+    assert_success(&output);
+    let listing: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(listing["crl_number"], "4096");
+    assert_eq!(listing["signature_valid"], true);
+    let revoked_certificates = listing["revoked_certificates"].as_array().unwrap();
+    assert_eq!(revoked_certificates.len(), 1);
+    assert_eq!(revoked_certificates[0]["reason"], "key_compromise");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
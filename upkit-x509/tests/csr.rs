@@ -0,0 +1,173 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Integration tests of the `csr`, `issue`, `inspect`, `convert` and
+//! `validate` subcommands.
+
+mod common;
+
+use self::common::*;
+use std::ffi::OsStr;
+
+const PROFILE: &str = r#"{
+    "validity_seconds": 86400,
+    "signature_algorithm": "ecdsa-with-SHA256",
+    "basic_constraints": { "ca": false },
+    "key_usages": ["digital_signature"],
+    "extended_key_usages": ["pkix_server_auth"]
+}"#;
+
+#[test]
+fn csr_issue_inspect_convert_and_validate() {
+    let dir = test_dir("csr");
+    let issuer_cert = write_test_certificate(&dir, "ec_ca.b64");
+    let issuer_key = test_resource("ec_ca_key.pem");
+    let other_trust = write_test_certificate(&dir, "h1_root_ca.b64");
+    let public_key = test_resource("ec_csr_public_key.pem");
+    let key = test_resource("ec_csr_key.pem");
+    let profile = dir.join("profile.json");
+    std::fs::write(&profile, PROFILE).unwrap();
+    // Certification request
+    let csr = dir.join("request.pem");
+    let output = upkit_x509(&[
+        OsStr::new("csr"),
+        OsStr::new("--subject"),
+        OsStr::new("organization_name=Example,common_name=www.example.com"),
+        OsStr::new("--san"),
+        OsStr::new("dns_name=www.example.com"),
+        OsStr::new("--public-key"),
+        public_key.as_os_str(),
+        OsStr::new("--key"),
+        key.as_os_str(),
+        OsStr::new("--signature-algorithm"),
+        OsStr::new("ecdsa-with-SHA256"),
+        OsStr::new("--out"),
+        csr.as_os_str(),
+    ]);
+    assert_success(&output);
+    assert!(std::fs::read_to_string(&csr)
+        .unwrap()
+        .starts_with("-----BEGIN CERTIFICATE REQUEST-----"));
+    // A request signed with a key that doesn't match the public key is refused
+    let bad_csr = dir.join("bad_request.pem");
+    let output = upkit_x509(&[
+        OsStr::new("csr"),
+        OsStr::new("--subject"),
+        OsStr::new("common_name=www.example.com"),
+        OsStr::new("--public-key"),
+        public_key.as_os_str(),
+        OsStr::new("--key"),
+        issuer_key.as_os_str(),
+        OsStr::new("--signature-algorithm"),
+        OsStr::new("ecdsa-with-SHA256"),
+        OsStr::new("--out"),
+        bad_csr.as_os_str(),
+    ]);
+    assert_success(&output);
+    let output = upkit_x509(&[
+        OsStr::new("issue"),
+        OsStr::new("--profile"),
+        profile.as_os_str(),
+        OsStr::new("--issuer-key"),
+        issuer_key.as_os_str(),
+        OsStr::new("--issuer-cert"),
+        issuer_cert.as_os_str(),
+        OsStr::new("--csr"),
+        bad_csr.as_os_str(),
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    // Issuance from the request
+    let leaf = dir.join("leaf.pem");
+    let output = upkit_x509(&[
+        OsStr::new("issue"),
+        OsStr::new("--profile"),
+        profile.as_os_str(),
+        OsStr::new("--issuer-key"),
+        issuer_key.as_os_str(),
+        OsStr::new("--issuer-cert"),
+        issuer_cert.as_os_str(),
+        OsStr::new("--csr"),
+        csr.as_os_str(),
+        OsStr::new("--out"),
+        leaf.as_os_str(),
+    ]);
+    assert_success(&output);
+    // Inspection
+    let output = upkit_x509(&[
+        OsStr::new("inspect"),
+        OsStr::new("--format"),
+        OsStr::new("json"),
+        leaf.as_os_str(),
+    ]);
+    assert_success(&output);
+    let summaries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let summaries = summaries.as_array().unwrap();
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0]["version"], 3);
+    assert!(summaries[0]["issuer"]
+        .as_str()
+        .unwrap()
+        .contains("CN=Test EC CA"));
+    assert!(summaries[0]["subject"]
+        .as_str()
+        .unwrap()
+        .contains("CN=www.example.com"));
+    assert_eq!(
+        summaries[0]["signature_algorithm_oid"],
+        "1.2.840.10045.4.3.2"
+    );
+    // Conversion to DER and back
+    let leaf_der = dir.join("leaf.der");
+    let output = upkit_x509(&[
+        OsStr::new("convert"),
+        OsStr::new("--to"),
+        OsStr::new("der"),
+        OsStr::new("--out"),
+        leaf_der.as_os_str(),
+        leaf.as_os_str(),
+    ]);
+    assert_success(&output);
+    let output = upkit_x509(&[
+        OsStr::new("convert"),
+        OsStr::new("--to"),
+        OsStr::new("pem"),
+        leaf_der.as_os_str(),
+    ]);
+    assert_success(&output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        std::fs::read_to_string(&leaf).unwrap().trim()
+    );
+    // Validation
+    let output = upkit_x509(&[
+        OsStr::new("validate"),
+        OsStr::new("--trust"),
+        issuer_cert.as_os_str(),
+        OsStr::new("--eku"),
+        OsStr::new("pkix_server_auth"),
+        leaf_der.as_os_str(),
+    ]);
+    assert_success(&output);
+    let output = upkit_x509(&[
+        OsStr::new("validate"),
+        OsStr::new("--trust"),
+        other_trust.as_os_str(),
+        leaf_der.as_os_str(),
+    ]);
+    assert_eq!(output.status.code(), Some(15));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
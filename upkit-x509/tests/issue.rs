@@ -0,0 +1,82 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Integration tests of linting during `issue`.
+
+mod common;
+
+use self::common::*;
+use std::ffi::OsStr;
+
+/// TLS server profile without Subject Alternative Name or revocation
+/// information.
+const PROFILE: &str = r#"{
+    "validity_seconds": 86400,
+    "signature_algorithm": "ecdsa-with-SHA256",
+    "basic_constraints": { "ca": false },
+    "key_usages": ["digital_signature"],
+    "extended_key_usages": ["pkix_server_auth"]
+}"#;
+
+#[test]
+fn issue_with_lint_errors() {
+    let dir = test_dir("issue");
+    let profile = dir.join("profile.json");
+    std::fs::write(&profile, PROFILE).unwrap();
+    let issuer_cert = write_test_certificate(&dir, "ec_ca.b64");
+    let issuer_key = test_resource("ec_ca_key.pem");
+    let public_key = test_resource("ec_csr_public_key.pem");
+    let cert = dir.join("cert.pem");
+    let issue_args = [
+        OsStr::new("issue"),
+        OsStr::new("--profile"),
+        profile.as_os_str(),
+        OsStr::new("--issuer-key"),
+        issuer_key.as_os_str(),
+        OsStr::new("--issuer-cert"),
+        issuer_cert.as_os_str(),
+        OsStr::new("--subject"),
+        OsStr::new("common_name=lint.example.com"),
+        OsStr::new("--public-key"),
+        public_key.as_os_str(),
+        OsStr::new("--out"),
+        cert.as_os_str(),
+    ];
+    // Findings are reported, but the certificate is still issued
+    let output = upkit_x509(&issue_args);
+    assert_success(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Error e_tls_server_missing_subject_alternative_name:"),
+        "{stderr}"
+    );
+    assert!(std::fs::read_to_string(&cert)
+        .unwrap()
+        .starts_with("-----BEGIN CERTIFICATE-----"));
+    std::fs::remove_file(&cert).unwrap();
+    // Denied lint errors prevent issuance
+    let output = upkit_x509(&[issue_args.as_slice(), &[OsStr::new("--deny-lint-errors")]].concat());
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Error e_tls_server_missing_subject_alternative_name:"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("Refusing to sign."), "{stderr}");
+    assert!(!cert.exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}